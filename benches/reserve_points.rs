@@ -0,0 +1,75 @@
+//! Frame-time jitter of `TracesCollection::update` under a high-rate stream,
+//! with and without `reserve_points`.
+//!
+//! Run with `cargo bench --bench reserve_points`.  Each frame ingests a batch
+//! of samples per trace; the per-frame `update` times are summarised as mean,
+//! 99th percentile and maximum.  Growing the live buffers reallocates (and
+//! copies) them a few times on the way to `max_points`, which shows up as
+//! outliers in the tail that pre-allocation removes.
+
+use std::time::{Duration, Instant};
+
+use liveplot::data::traces::TracesCollection;
+use liveplot::sink::{PlotCommand, PlotPoint};
+
+const TRACES: u32 = 8;
+const MAX_POINTS: usize = 500_000;
+const SAMPLES_PER_FRAME: usize = 5_000;
+const FRAMES: usize = 150;
+const RUNS: usize = 5;
+
+fn run(reserve: bool) -> Vec<Duration> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    col.max_points = MAX_POINTS;
+    col.reserve_points = reserve;
+    for id in 0..TRACES {
+        tx.send(PlotCommand::RegisterTrace {
+            id,
+            name: format!("t{id}"),
+            info: None,
+        })
+        .unwrap();
+    }
+    col.update();
+
+    let mut t = 0.0;
+    let mut times = Vec::with_capacity(FRAMES);
+    for _ in 0..FRAMES {
+        let points: Vec<PlotPoint> = (0..SAMPLES_PER_FRAME)
+            .map(|i| PlotPoint {
+                x: t + i as f64 * 1e-6,
+                y: (i as f64).sin(),
+            })
+            .collect();
+        t += SAMPLES_PER_FRAME as f64 * 1e-6;
+        for id in 0..TRACES {
+            tx.send(PlotCommand::Points {
+                trace_id: id,
+                points: points.clone(),
+            })
+            .unwrap();
+        }
+        let start = Instant::now();
+        col.update();
+        times.push(start.elapsed());
+    }
+    times
+}
+
+fn report(label: &str, mut times: Vec<Duration>) {
+    times.sort();
+    let mean = times.iter().sum::<Duration>() / times.len() as u32;
+    let p99 = times[(times.len() * 99 / 100).min(times.len() - 1)];
+    let max = times[times.len() - 1];
+    println!("{label:<18} mean {mean:>10.2?}  p99 {p99:>10.2?}  max {max:>10.2?}");
+}
+
+fn main() {
+    // Warm up the allocator and caches once.
+    run(false);
+    for (label, reserve) in [("growing buffers", false), ("reserve_points", true)] {
+        let times = (0..RUNS).flat_map(|_| run(reserve)).collect();
+        report(label, times);
+    }
+}
@@ -13,14 +13,30 @@
 //!   adds/removes threshold definitions from the thresholds panel.
 //! * [`publish_threshold_events`](LivePlotPanel::publish_threshold_events) –
 //!   forwards newly generated threshold crossing events to listeners.
+//! * [`apply_measurement_controller_requests`](LivePlotPanel::apply_measurement_controller_requests) /
+//!   [`publish_measurement_result`](LivePlotPanel::publish_measurement_result) /
+//!   [`publish_measurement_snapshot`](LivePlotPanel::publish_measurement_snapshot) –
+//!   places measurement points and publishes the active measurement and the
+//!   automatic measurements per trace.
+//! * [`apply_trigger_controller_requests`](LivePlotPanel::apply_trigger_controller_requests) /
+//!   [`publish_trigger_captures`](LivePlotPanel::publish_trigger_captures) /
+//!   [`publish_trigger_state`](LivePlotPanel::publish_trigger_state) –
+//!   adds/removes/arms/forces/disarms triggers and publishes their captures
+//!   and state.
+//! * [`apply_math_controller_requests`](LivePlotPanel::apply_math_controller_requests) –
+//!   resets the runtime state of math traces.
 
 use std::collections::HashMap;
 
 use eframe::egui;
 
+use crate::controllers::{MeasurementSnapshot, TraceMeasurements, TriggerOp};
 use crate::data::data::LivePlotData;
 use crate::data::export;
+use crate::data::measurement::SignalStats;
 use crate::data::traces::TraceRef;
+use crate::panels::export_ui::trace_units;
+use crate::panels::MeasurementPanel;
 
 use super::LivePlotPanel;
 
@@ -38,6 +54,14 @@ impl LivePlotPanel {
     /// * **FFTController** – publishes FFT panel info.
     /// * **ThresholdController** – threshold add/remove and event publishing
     ///   (via [`apply_threshold_controller_requests`] and [`publish_threshold_events`]).
+    /// * **MeasurementController** – handled per frame in `update_data`
+    ///   (via [`apply_measurement_controller_requests`], [`publish_measurement_result`]
+    ///   and [`publish_measurement_snapshot`]).
+    /// * **TriggerController** – handled per frame in `update_data`
+    ///   (via [`apply_trigger_controller_requests`], [`publish_trigger_captures`]
+    ///   and [`publish_trigger_state`]).
+    /// * **MathController** – handled per frame in `update_data`
+    ///   (via [`apply_math_controller_requests`]).
     pub fn apply_controllers_embedded(&mut self, ctx: &egui::Context) {
         // ── WindowController ─────────────────────────────────────────────────
         if let Some(ctrl) = &self.window_ctrl {
@@ -68,6 +92,9 @@ impl LivePlotPanel {
         self.publish_threshold_events();
 
         // ── UiActionController ───────────────────────────────────────────────
+        if let Some(ctrl) = self.ui_ctrl.clone() {
+            self.apply_panel_requests(&ctrl);
+        }
         if let Some(ctrl) = &self.ui_ctrl {
             let mut take_actions = {
                 let mut inner = ctrl.inner.lock().unwrap();
@@ -82,6 +109,7 @@ impl LivePlotPanel {
                     inner.request_save_raw.take(),
                     inner.request_save_raw_to.take(),
                     inner.fft_request.take(),
+                    std::mem::take(&mut inner.request_copy_visible),
                 )
             };
 
@@ -93,6 +121,12 @@ impl LivePlotPanel {
                 }
             }
 
+            let export_range = if take_actions.4.is_some() {
+                self.export_range()
+            } else {
+                None
+            };
+            let settings = self.export_settings();
             let data = LivePlotData {
                 scope_data: self.liveplot_panel.get_data_mut(),
                 traces: &mut self.traces_data,
@@ -113,27 +147,62 @@ impl LivePlotPanel {
                     path: Some(path),
                 });
             }
-            if let Some((_fmt, path)) = take_actions.4.take() {
+            if let Some((fmt, path)) = take_actions.4.take() {
                 if let Some(scope_id) = primary_scope_id {
-                    let tol = 1e-9;
-                    let order = data
+                    let tol = settings.join_tolerance;
+                    let order: Vec<_> = data
                         .primary_scope()
                         .map(|s| s.trace_order.clone())
-                        .unwrap_or_default();
-                    let series = order
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|name| settings.include_math || !data.traces.is_math(name))
+                        .collect();
+                    let path = if path.is_dir() {
+                        let scope = data.primary_scope().map_or("", |s| s.name.as_str());
+                        let stem = export::expand_filename_template(
+                            &settings.filename_template,
+                            scope,
+                            order.len(),
+                            chrono::Local::now(),
+                        );
+                        path.join(format!("{stem}.{}", fmt.extension()))
+                    } else {
+                        path
+                    };
+                    let mut series = order
                         .iter()
                         .filter_map(|name| {
                             data.get_drawn_points(name, scope_id)
                                 .map(|v| (name.clone(), v.into_iter().collect()))
                         })
                         .collect();
-                    let _ = if path.extension().and_then(|s| s.to_str()) == Some("csv") {
-                        export::write_csv_aligned_path(&path, &order, &series, tol)
-                    } else {
-                        export::write_parquet_aligned_path(&path, &order, &series, tol)
+                    if let Some(range) = export_range {
+                        export::clip_series(&mut series, range);
+                    }
+                    let _ = match path.extension().and_then(|s| s.to_str()) {
+                        Some("csv") => export::write_csv_aligned_path(
+                            &path,
+                            &order,
+                            &series,
+                            tol,
+                            settings.csv_timestamps,
+                        ),
+                        Some("json") => {
+                            let scope = data.scope_by_id(scope_id);
+                            let units = trace_units(scope.as_slice(), data.traces, &order);
+                            export::write_json_aligned_path(&path, &order, &units, &series, tol)
+                        }
+                        _ => export::write_parquet_aligned_path(&path, &order, &series, tol),
                     };
                 }
             }
+            if take_actions.6 {
+                if let Some(scope) = data.primary_scope() {
+                    let (order, series) = scope.visible_series(data.traces);
+                    let rows = export::align_series(&order, &series, settings.join_tolerance);
+                    ctx.copy_text(export::aligned_rows_tsv(&order, &rows));
+                }
+            }
             if let Some(_req) = take_actions.5.take() {
                 // Placeholder for FFT data requests in embedded mode
             }
@@ -159,7 +228,8 @@ impl LivePlotPanel {
                 for (name, rgb) in inner.color_requests.drain(..) {
                     let tref = TraceRef(name.clone());
                     if let Some(tr) = data.traces.get_trace_mut(&tref) {
-                        tr.look.color = egui::Color32::from_rgb(rgb[0], rgb[1], rgb[2]);
+                        tr.look
+                            .set_custom_color(egui::Color32::from_rgb(rgb[0], rgb[1], rgb[2]));
                         // Emit COLOR_CHANGED event
                         if let Some(evt_ctrl) = &data.event_ctrl {
                             let mut evt = crate::events::PlotEvent::new(
@@ -251,6 +321,8 @@ impl LivePlotPanel {
                     }
                 }
 
+                inner.last_ingest = Some(data.traces.ingest_stats());
+
                 // Build and publish trace info snapshot.
                 let mut infos: Vec<crate::controllers::TraceInfo> = Vec::new();
                 if let Some(scope) = data.primary_scope() {
@@ -277,6 +349,37 @@ impl LivePlotPanel {
                     };
                     inner.last_snapshot = Some(snapshot.clone());
                     inner.listeners.retain(|s| s.send(snapshot.clone()).is_ok());
+
+                    if let Some((x, mode)) = inner.sample_x {
+                        let mut values: Vec<(String, Option<f64>)> = Vec::new();
+                        for name in scope.trace_order.iter() {
+                            let Some(tr) = data.traces.get_trace(name) else {
+                                continue;
+                            };
+                            if !tr.look.visible {
+                                continue;
+                            }
+                            let value = data
+                                .traces
+                                .get_points_ref(name, scope.shows_snapshot())
+                                .and_then(|pts| {
+                                    // Only the two samples around `x` are needed.
+                                    let i = pts.partition_point(|p| p[0] < x);
+                                    let around: Vec<[f64; 2]> = pts
+                                        .range(i.saturating_sub(1)..(i + 1).min(pts.len()))
+                                        .copied()
+                                        .collect();
+                                    crate::data::export::sample_at(&around, x, mode)
+                                })
+                                .map(|v| tr.display_y(v));
+                            values.push((name.0.clone(), value));
+                        }
+                        let samples = crate::controllers::TraceSamples { x, values };
+                        inner.last_samples = Some(samples.clone());
+                        inner
+                            .sample_listeners
+                            .retain(|s| s.send(samples.clone()).is_ok());
+                    }
                 }
 
                 (show_request, detached_request)
@@ -344,9 +447,15 @@ impl LivePlotPanel {
             if requests.add_scope {
                 self.liveplot_panel.add_scope();
             }
+            if let Some(target) = requests.set_auto_attach {
+                self.auto_attach = target;
+            }
             if let Some(id) = requests.remove_scope {
                 let _ = self.liveplot_panel.remove_scope_by_id(id);
             }
+            if let Some(order) = requests.reorder {
+                self.liveplot_panel.reorder_scopes(&order);
+            }
             if requests.save_screenshot {
                 self.pending_requests.screenshot = Some(crate::data::data::ScreenshotRequest {
                     target: crate::data::data::ScreenshotTarget::CenterPanel,
@@ -369,11 +478,13 @@ impl LivePlotPanel {
                         scope.scope_type = scope_req.scope_type;
                         scope.trace_order = scope_req.trace_order.clone();
                         scope.trace_order.retain(|t| traces.contains_key(t));
+                        scope.max_points = scope_req.max_points;
                     }
                 }
             }
 
             let scopes_state = {
+                let global_max_points = self.traces_data.max_points;
                 let scopes = self.liveplot_panel.get_data_mut();
                 let mut scopes_info: Vec<crate::controllers::ScopeControlState> = Vec::new();
                 for scope in scopes {
@@ -389,6 +500,12 @@ impl LivePlotPanel {
                         legend_position: scope.legend_position,
                         trace_order: scope.trace_order.clone(),
                         scope_type: scope.scope_type,
+                        max_points: scope.max_points,
+                        effective_max_points: if scope.max_points > 0 {
+                            scope.max_points
+                        } else {
+                            global_max_points
+                        },
                     });
                 }
                 crate::controllers::ScopesState {
@@ -441,8 +558,9 @@ impl LivePlotPanel {
                 if let Some(id) = requests.remove_scope {
                     let _ = self.liveplot_panel.remove_scope_by_id(id);
                 }
-                // Reorder not yet supported; consume request.
-                let _ = requests.reorder_scopes;
+                if let Some(order) = requests.reorder_scopes {
+                    self.liveplot_panel.reorder_scopes(&order);
+                }
             }
 
             if let Some(size) = requests.set_window_size {
@@ -507,9 +625,15 @@ impl LivePlotPanel {
             if requests.add_scope {
                 self.liveplot_panel.add_scope();
             }
+            if let Some(target) = requests.set_auto_attach {
+                self.auto_attach = target;
+            }
             if let Some(id) = requests.remove_scope {
                 let _ = self.liveplot_panel.remove_scope_by_id(id);
             }
+            if let Some(order) = requests.reorder {
+                self.liveplot_panel.reorder_scopes(&order);
+            }
             if requests.save_screenshot {
                 self.pending_requests.screenshot = Some(crate::data::data::ScreenshotRequest {
                     target: crate::data::data::ScreenshotTarget::CenterPanel,
@@ -532,11 +656,13 @@ impl LivePlotPanel {
                         scope.scope_type = scope_req.scope_type;
                         scope.trace_order = scope_req.trace_order.clone();
                         scope.trace_order.retain(|t| traces.contains_key(t));
+                        scope.max_points = scope_req.max_points;
                     }
                 }
             }
 
             let scopes_state = {
+                let global_max_points = self.traces_data.max_points;
                 let scopes = self.liveplot_panel.get_data_mut();
                 let mut scopes_info: Vec<crate::controllers::ScopeControlState> = Vec::new();
                 for scope in scopes {
@@ -552,6 +678,12 @@ impl LivePlotPanel {
                         legend_position: scope.legend_position,
                         trace_order: scope.trace_order.clone(),
                         scope_type: scope.scope_type,
+                        max_points: scope.max_points,
+                        effective_max_points: if scope.max_points > 0 {
+                            scope.max_points
+                        } else {
+                            global_max_points
+                        },
                     });
                 }
                 crate::controllers::ScopesState {
@@ -603,8 +735,9 @@ impl LivePlotPanel {
                 if let Some(id) = requests.remove_scope {
                     let _ = self.liveplot_panel.remove_scope_by_id(id);
                 }
-                // Reorder not yet supported in liveplot panel; consume request.
-                let _ = requests.reorder_scopes;
+                if let Some(order) = requests.reorder_scopes {
+                    self.liveplot_panel.reorder_scopes(&order);
+                }
             }
 
             if let Some(size) = requests.set_window_size {
@@ -655,15 +788,16 @@ impl LivePlotPanel {
             return;
         };
 
-        let (adds, removes) = {
+        let (adds, removes, clears) = {
             let mut inner = ctrl.inner.lock().unwrap();
             (
                 inner.add_requests.drain(..).collect::<Vec<_>>(),
                 inner.remove_requests.drain(..).collect::<Vec<_>>(),
+                inner.clear_event_requests.drain(..).collect::<Vec<_>>(),
             )
         };
 
-        if adds.is_empty() && removes.is_empty() {
+        if adds.is_empty() && removes.is_empty() && clears.is_empty() {
             return;
         }
         if let Some(tp) = self.thresholds_panel_mut() {
@@ -675,6 +809,10 @@ impl LivePlotPanel {
                 added_names.push(def.name.clone());
                 tp.thresholds.insert(def.name.clone(), def);
             }
+            let mut cleared: Vec<String> = Vec::new();
+            for name in &clears {
+                cleared.extend(tp.clear_events(name.as_deref()));
+            }
 
             for name in removes {
                 self.threshold_event_cursors.remove(&name);
@@ -682,6 +820,11 @@ impl LivePlotPanel {
             for name in added_names {
                 self.threshold_event_cursors.entry(name).or_insert(0);
             }
+            // Cleared buffers restart at 0, so the next events are forwarded
+            // instead of waiting for the buffer to pass the old cursor.
+            for name in cleared {
+                self.threshold_event_cursors.insert(name, 0);
+            }
         }
     }
 
@@ -689,20 +832,25 @@ impl LivePlotPanel {
     ///
     /// Each threshold definition accumulates events in its runtime state.  This
     /// method tracks a per-threshold cursor so that only events generated *since
-    /// the last call* are forwarded.
+    /// the last call* are forwarded.  Events of thresholds with `notify` set are
+    /// additionally raised as `THRESHOLD_ALARM` events.
     pub(crate) fn publish_threshold_events(&mut self) {
-        let Some(ctrl) = self.threshold_ctrl.clone() else {
+        if self.threshold_ctrl.is_none() && self.event_ctrl.is_none() {
             return;
-        };
+        }
 
         let mut pending: Vec<crate::data::thresholds::ThresholdEvent> = Vec::new();
         let mut collected: Vec<(String, Vec<crate::data::thresholds::ThresholdEvent>)> = Vec::new();
+        let mut notify: Vec<String> = Vec::new();
 
         if let Some(tp) = self.thresholds_panel_mut() {
             for (name, def) in tp.thresholds.iter() {
                 let events: Vec<crate::data::thresholds::ThresholdEvent> =
                     def.get_runtime_state().events.iter().cloned().collect();
                 collected.push((name.clone(), events));
+                if def.notify {
+                    notify.push(name.clone());
+                }
             }
         }
 
@@ -739,20 +887,30 @@ impl LivePlotPanel {
         // ── Also emit THRESHOLD_EXCEEDED events via the event system ──────
         if let Some(evt_ctrl) = &self.event_ctrl {
             for ev in &pending {
-                let mut plot_evt =
-                    crate::events::PlotEvent::new(crate::events::EventKind::THRESHOLD_EXCEEDED);
-                plot_evt.threshold = Some(crate::events::ThresholdMeta {
+                let meta = crate::events::ThresholdMeta {
                     threshold_name: ev.threshold.clone(),
                     trace: Some(ev.trace.clone()),
                     start_t: Some(ev.start_t),
                     end_t: Some(ev.end_t),
                     duration: Some(ev.duration),
                     area: Some(ev.area),
-                });
+                };
+                if notify.contains(&ev.threshold) {
+                    let mut alarm =
+                        crate::events::PlotEvent::new(crate::events::EventKind::THRESHOLD_ALARM);
+                    alarm.threshold = Some(meta.clone());
+                    evt_ctrl.emit_filtered(alarm);
+                }
+                let mut plot_evt =
+                    crate::events::PlotEvent::new(crate::events::EventKind::THRESHOLD_EXCEEDED);
+                plot_evt.threshold = Some(meta);
                 evt_ctrl.emit_filtered(plot_evt);
             }
         }
 
+        let Some(ctrl) = self.threshold_ctrl.clone() else {
+            return;
+        };
         let mut inner = ctrl.inner.lock().unwrap();
         inner.listeners.retain(|s| {
             for ev in &pending {
@@ -763,4 +921,165 @@ impl LivePlotPanel {
             true
         });
     }
+
+    /// Queue a point placement from the [`MeasurementController`] for the
+    /// measurement panel's next data update.
+    pub(crate) fn apply_measurement_controller_requests(&mut self) {
+        let Some(ctrl) = &self.measurement_ctrl else {
+            return;
+        };
+        if let Some(points) = ctrl.inner.lock().unwrap().set_points.take() {
+            self.pending_requests.set_measurement_points = Some(points);
+        }
+    }
+
+    /// Publish the active measurement result to controller listeners when it changed.
+    pub(crate) fn publish_measurement_result(&mut self) {
+        let Some(ctrl) = self.measurement_ctrl.clone() else {
+            return;
+        };
+        let Some(result) = self
+            .measurement_panel_mut()
+            .and_then(|mp| mp.current_result().cloned())
+        else {
+            return;
+        };
+        let mut inner = ctrl.inner.lock().unwrap();
+        if inner.last_result.as_ref() != Some(&result) {
+            inner.listeners.retain(|s| s.send(result.clone()).is_ok());
+            inner.last_result = Some(result);
+        }
+    }
+
+    /// Publish the automatic measurements of the selected traces to
+    /// [`MeasurementController`] snapshot listeners when they changed.  Each
+    /// trace is measured over the visible window of the first scope showing
+    /// it, with the hysteresis of a measurement following it.
+    pub(crate) fn publish_measurement_snapshot(&mut self) {
+        let Some(ctrl) = self.measurement_ctrl.clone() else {
+            return;
+        };
+        let (selected, kinds) = {
+            let inner = ctrl.inner.lock().unwrap();
+            (inner.snapshot_traces.clone(), inner.snapshot_kinds.clone())
+        };
+        let mut targets: Vec<(TraceRef, usize)> = Vec::new();
+        for scope in self.liveplot_panel.get_data() {
+            for name in &scope.trace_order {
+                let wanted = selected.as_ref().is_none_or(|sel| sel.contains(name));
+                if wanted && !targets.iter().any(|(n, _)| n == name) {
+                    targets.push((name.clone(), scope.id));
+                }
+            }
+        }
+        let hysteresis: Vec<f64> = match self.measurement_panel_mut() {
+            Some(mp) => targets.iter().map(|(n, _)| mp.hysteresis_for(n)).collect(),
+            None => vec![0.0; targets.len()],
+        };
+        let scopes = self.liveplot_panel.get_data();
+        let per_trace = targets
+            .into_iter()
+            .zip(hysteresis)
+            .filter_map(|((trace, scope_id), hysteresis)| {
+                let scope = scopes.iter().find(|s| s.id == scope_id)?;
+                let visible = MeasurementPanel::visible_points(&self.traces_data, scope, &trace);
+                let stats = SignalStats::compute(visible.iter().copied(), hysteresis);
+                Some(TraceMeasurements {
+                    trace,
+                    scope_id,
+                    samples: visible.len(),
+                    values: kinds
+                        .iter()
+                        .map(|k| (*k, stats.as_ref().and_then(|s| k.value(s))))
+                        .collect(),
+                })
+            })
+            .collect();
+        let snapshot = MeasurementSnapshot { per_trace };
+        let mut inner = ctrl.inner.lock().unwrap();
+        if inner.last_snapshot.as_ref() != Some(&snapshot) {
+            inner
+                .snapshot_listeners
+                .retain(|s| s.send(snapshot.clone()).is_ok());
+            inner.last_snapshot = Some(snapshot);
+        }
+    }
+
+    /// Add and remove triggers requested through the [`TriggerController`]
+    /// in request order, then queue its arm/force/disarm requests for the
+    /// triggers panel's next data update.  Additions and removals wait in the
+    /// controller while there is no triggers panel.
+    pub(crate) fn apply_trigger_controller_requests(&mut self) {
+        let Some(ctrl) = self.trigger_ctrl.clone() else {
+            return;
+        };
+        if let Some(tp) = self.triggers_panel_mut() {
+            let ops = std::mem::take(&mut ctrl.inner.lock().unwrap().ops);
+            for op in ops {
+                match op {
+                    TriggerOp::Add(trigger) => {
+                        tp.triggers.insert(trigger.name.clone(), trigger);
+                    }
+                    TriggerOp::Remove(name) => tp.remove_trigger(&name),
+                }
+            }
+        }
+        let commands = std::mem::take(&mut ctrl.inner.lock().unwrap().commands);
+        self.pending_requests.trigger_commands.extend(commands);
+    }
+
+    /// Queue math state resets from the [`MathController`] for the math
+    /// panel's next data update.
+    pub(crate) fn apply_math_controller_requests(&mut self) {
+        let Some(ctrl) = &self.math_ctrl else {
+            return;
+        };
+        let names = std::mem::take(&mut ctrl.inner.lock().unwrap().reset_state);
+        self.pending_requests
+            .reset_math_state
+            .extend(names.into_iter().map(TraceRef::from));
+    }
+
+    /// Publish the trigger state to [`TriggerController`] state listeners
+    /// when it changed since the last frame.
+    pub(crate) fn publish_trigger_state(&mut self) {
+        if self.trigger_ctrl.is_none() {
+            return;
+        }
+        let Some(state) = self.triggers_panel_mut().map(|tp| tp.triggers_state()) else {
+            return;
+        };
+        let Some(ctrl) = &self.trigger_ctrl else {
+            return;
+        };
+        let mut inner = ctrl.inner.lock().unwrap();
+        if inner.last_state.as_ref() != Some(&state) {
+            inner
+                .state_listeners
+                .retain(|s| s.send(state.clone()).is_ok());
+            inner.last_state = Some(state);
+        }
+    }
+
+    /// Forward captures completed this frame to trigger controller listeners.
+    pub(crate) fn publish_trigger_captures(&mut self) {
+        let Some(captures) = self.triggers_panel_mut().map(|tp| tp.take_captures()) else {
+            return;
+        };
+        let Some(ctrl) = &self.trigger_ctrl else {
+            return;
+        };
+        if captures.is_empty() {
+            return;
+        }
+        let mut inner = ctrl.inner.lock().unwrap();
+        inner.listeners.retain(|s| {
+            for capture in &captures {
+                if s.send(capture.clone()).is_err() {
+                    return false;
+                }
+            }
+            true
+        });
+    }
 }
@@ -11,6 +11,8 @@
 //!   strip, left/right/bottom sidebars, and detached panel windows.
 //! * **[`render_tabs`](LivePlotPanel::render_tabs)** – shared tab-strip + panel-body
 //!   renderer used by the left, right, and bottom sidebars.
+//! * **[`render_maximized`](LivePlotPanel::render_maximized)** – draws a single
+//!   maximized panel in place of the whole layout.
 
 use eframe::egui;
 use eframe::egui::scroll_area::{ScrollBarVisibility, ScrollSource};
@@ -19,6 +21,7 @@ use egui_phosphor_icons::icons::{
 };
 
 use crate::config::ScopeButton;
+use crate::controllers::{StateIoEvent, StateIoOp};
 use crate::data::data::LivePlotData;
 use crate::data::hotkeys::{format_button_tooltip, get_hotkey_for_name, should_collapse_topbar};
 use crate::data::traces::TraceRef;
@@ -170,8 +173,6 @@ impl LivePlotPanel {
             }
 
             let (
-                save_req,
-                load_req,
                 add_scope_req,
                 remove_scope_req,
                 should_trigger_pause,
@@ -416,8 +417,6 @@ impl LivePlotPanel {
                 }
 
                 (
-                    data.pending_requests.save_state.take(),
-                    data.pending_requests.load_state.take(),
                     std::mem::take(&mut data.pending_requests.add_scope),
                     data.pending_requests.remove_scope.take(),
                     should_trigger_pause,
@@ -444,15 +443,6 @@ impl LivePlotPanel {
             if let Some(scope_id) = remove_scope_req {
                 let _ = self.liveplot_panel.remove_scope_by_id(scope_id);
             }
-
-            // ── State persistence (save / load) ─────────────────────────────
-            if let Some(path) = save_req {
-                self.handle_save_state(ui, &path);
-            }
-
-            if let Some(path) = load_req {
-                self.handle_load_state(ui, &path);
-            }
         });
     }
 
@@ -494,24 +484,18 @@ impl LivePlotPanel {
         let trace_styles: Vec<crate::persistence::TraceStyleSerde> = {
             let scopes = self.liveplot_panel.get_data();
             let mut seen = std::collections::HashSet::new();
-            let mut snapshot: Vec<(String, crate::data::trace_look::TraceLook, f64)> = Vec::new();
+            let mut snapshot: Vec<crate::persistence::TraceStyleSerde> = Vec::new();
             for scope in scopes.iter() {
                 for name in scope.trace_order.iter() {
                     if seen.insert(name.0.clone()) {
                         if let Some(tr) = self.traces_data.get_trace(name) {
-                            snapshot.push((name.0.clone(), tr.look.clone(), tr.offset));
+                            snapshot
+                                .push(crate::persistence::TraceStyleSerde::from_trace(name, tr));
                         }
                     }
                 }
             }
             snapshot
-                .into_iter()
-                .map(|(n, look, off)| crate::persistence::TraceStyleSerde {
-                    name: n,
-                    look: crate::persistence::TraceLookSerde::from(&look),
-                    offset: off,
-                })
-                .collect()
         };
 
         // Math traces: extract from MathPanel.
@@ -710,8 +694,9 @@ impl LivePlotPanel {
         }
 
         // Apply traces styles (uses pending_styles for traces not yet created).
-        crate::persistence::apply_trace_styles(&loaded.traces_style, |name, look, off| {
+        crate::persistence::apply_trace_styles(&loaded.traces_style, |name, look, off, display| {
             self.traces_data.set_pending_style(name, look, off);
+            self.traces_data.set_pending_display(name, display);
         });
 
         // Apply math traces (always, even when empty, so undo can remove them).
@@ -866,12 +851,46 @@ impl LivePlotPanel {
         self.traces_data.max_age_secs = loaded.max_age_secs;
     }
 
-    /// Serialize the current application state and write it to `path`.
+    /// Process pending state save/load requests.
     ///
-    /// Called from [`render_menu`](Self::render_menu) when the user (or a
-    /// controller) requests a state save.
-    fn handle_save_state(&mut self, ui: &mut egui::Ui, path: &std::path::Path) {
-        let ctx = ui.ctx();
+    /// Runs every frame independently of the top bar, so requests from the
+    /// export panel and from [`crate::LiveplotController`] are handled even when the
+    /// menu is hidden or the panel is compact.  Each completed request is
+    /// reported to the controller's state-I/O subscribers.
+    pub(crate) fn handle_state_requests(&mut self, ctx: &egui::Context) {
+        if let Some(path) = self.pending_requests.save_state.take() {
+            let result = self.handle_save_state(ctx, &path);
+            self.publish_state_io(StateIoEvent {
+                op: StateIoOp::Save,
+                path,
+                result,
+            });
+        }
+        if let Some(path) = self.pending_requests.load_state.take() {
+            let result = self.handle_load_state(ctx, &path);
+            self.publish_state_io(StateIoEvent {
+                op: StateIoOp::Load,
+                path,
+                result,
+            });
+        }
+    }
+
+    fn publish_state_io(&self, evt: StateIoEvent) {
+        if let Some(ctrl) = &self.liveplot_ctrl {
+            let mut inner = ctrl.inner.lock().unwrap();
+            inner
+                .state_io_listeners
+                .retain(|s| s.send(evt.clone()).is_ok());
+        }
+    }
+
+    /// Serialize the current application state and write it to `path`.
+    fn handle_save_state(
+        &mut self,
+        ctx: &egui::Context,
+        path: &std::path::Path,
+    ) -> Result<(), String> {
         let rect = ctx.input(|i| i.content_rect());
         let win_size = Some([rect.width(), rect.height()]);
         let win_pos = Some([rect.left(), rect.top()]);
@@ -880,27 +899,26 @@ impl LivePlotPanel {
         state.window_size = win_size;
         state.window_pos = win_pos;
 
-        let _ = crate::persistence::save_state_to_path(&state, path);
+        crate::persistence::save_state_to_path(&state, path)
     }
 
     /// Load application state from `path` and apply it to the panel.
-    ///
-    /// Called from [`render_menu`](Self::render_menu) when the user (or a
-    /// controller) requests a state load.
-    fn handle_load_state(&mut self, ui: &mut egui::Ui, path: &std::path::Path) {
-        let Ok(loaded) = crate::persistence::load_state_from_path(path) else {
-            return;
-        };
+    fn handle_load_state(
+        &mut self,
+        ctx: &egui::Context,
+        path: &std::path::Path,
+    ) -> Result<(), String> {
+        let loaded = crate::persistence::load_state_from_path(path)?;
 
         // Window: attempt to request size/pos via ctx.
         if let Some(sz) = loaded.window_size {
-            ui.ctx()
-                .send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::Vec2::new(
-                    sz[0], sz[1],
-                )));
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::Vec2::new(
+                sz[0], sz[1],
+            )));
         }
 
         self.apply_state_snapshot(&loaded);
+        Ok(())
     }
 
     // ─────────────────────────────────────────────────────────────────────────
@@ -1006,7 +1024,7 @@ impl LivePlotPanel {
                         }
                     }
                     ScopeButton::ClearAll => {
-                        self.traces_data.clear_all();
+                        self.traces_data.request_clear();
                         for s in self.liveplot_panel.get_data_mut() {
                             s.clicked_point = None;
                         }
@@ -1048,6 +1066,51 @@ impl LivePlotPanel {
         self.render_detached_windows(ui);
     }
 
+    /// Render the [`maximized_panel`](LivePlotPanel::maximized_panel) over the
+    /// whole widget instead of the regular layout.
+    ///
+    /// Returns `false` (and clears the setting) when no docked, visible panel
+    /// has that title, in which case the regular layout is rendered.
+    pub(crate) fn render_maximized(&mut self, ui: &mut egui::Ui) -> bool {
+        let Some(title) = self.maximized_panel.clone() else {
+            return false;
+        };
+        let is_target =
+            |p: &dyn Panel| p.title() == title && p.state().visible && !p.state().detached;
+        let found = [
+            &mut self.left_side_panels,
+            &mut self.right_side_panels,
+            &mut self.bottom_panels,
+        ]
+        .into_iter()
+        .enumerate()
+        .find_map(|(dock, list)| {
+            list.iter()
+                .position(|p| is_target(p.as_ref()))
+                .map(|i| (dock, i))
+        });
+        let Some((dock, idx)) = found else {
+            self.maximized_panel = None;
+            return false;
+        };
+
+        fn dock_list(this: &mut LivePlotPanel, dock: usize) -> &mut Vec<Box<dyn Panel>> {
+            match dock {
+                0 => &mut this.left_side_panels,
+                1 => &mut this.right_side_panels,
+                _ => &mut this.bottom_panels,
+            }
+        }
+        let mut single = vec![dock_list(self, dock).remove(idx)];
+        egui::CentralPanel::default().show(ui, |ui| {
+            self.render_tabs(ui, &mut single);
+        });
+        dock_list(self, dock).insert(idx, single.remove(0));
+
+        self.render_detached_windows(ui);
+        true
+    }
+
     /// Render the left sidebar (panel dock or collapsed icon strip).
     fn render_left_sidebar(&mut self, ui: &mut egui::Ui) {
         let show_left = !self.left_side_panels.is_empty()
@@ -1333,6 +1396,8 @@ impl LivePlotPanel {
         let count = list.len();
 
         let mut clicked: Option<usize> = None;
+        // Some(None) = restore, Some(Some(title)) = maximize that panel.
+        let mut toggle_maximize: Option<Option<String>> = None;
 
         let hk_rc_tabs = self.hotkeys.clone();
 
@@ -1380,7 +1445,14 @@ impl LivePlotPanel {
                 };
                 let pad = ui.spacing().button_padding.x * 2.0 + ui.spacing().item_spacing.x;
 
-                let actions_w = txt_width("Pop out", ui) + pad + txt_width("Hide", ui) + pad;
+                let maximized = self.maximized_panel.is_some();
+                let maximize_label = if maximized { "Restore" } else { "Maximize" };
+                let actions_w = txt_width("Pop out", ui)
+                    + pad
+                    + txt_width("Hide", ui)
+                    + pad
+                    + txt_width(maximize_label, ui)
+                    + pad;
 
                 let full_tabs_w: f32 = match count {
                     0 => 0.0,
@@ -1448,6 +1520,24 @@ impl LivePlotPanel {
                             }
                         }
                     }
+                    let maximize_tip = if maximized {
+                        "Restore the regular layout"
+                    } else {
+                        "Show only this panel, filling the whole area"
+                    };
+                    if ui
+                        .button(maximize_label)
+                        .on_hover_text(maximize_tip)
+                        .clicked()
+                    {
+                        toggle_maximize = if maximized {
+                            Some(None)
+                        } else {
+                            list.iter()
+                                .find(|p| p.state().visible && !p.state().detached)
+                                .map(|p| Some(p.title().to_string()))
+                        };
+                    }
 
                     // Left: tabs in remaining width
                     ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
@@ -1584,6 +1674,10 @@ impl LivePlotPanel {
             )
         };
 
+        if let Some(target) = toggle_maximize {
+            self.maximized_panel = target;
+        }
+
         // Apply any scope add/remove requests issued by the rendered panel(s).
         if add_scope_req {
             self.liveplot_panel.add_scope();
@@ -15,6 +15,7 @@ use crate::data::data::LivePlotData;
 use crate::data::export;
 use crate::data::hotkeys as hotkey_helpers;
 use crate::data::traces::TraceRef;
+use crate::panels::export_ui::trace_units;
 use crate::PlotCommand;
 
 use super::LivePlotPanel;
@@ -61,6 +62,11 @@ pub struct LivePlotApp {
     pub color_scheme: Option<crate::config::ColorScheme>,
     /// Flag so we only apply the color scheme on the very first frame.
     color_scheme_applied: bool,
+
+    /// State file loaded at startup and written on exit, if configured.
+    pub auto_save_state: Option<std::path::PathBuf>,
+    /// Content area as of the last frame, saved as the window geometry on exit.
+    last_content_rect: Option<egui::Rect>,
 }
 
 impl LivePlotApp {
@@ -79,6 +85,8 @@ impl LivePlotApp {
             subheadline: None,
             color_scheme: None,
             color_scheme_applied: false,
+            auto_save_state: None,
+            last_content_rect: None,
         }
     }
 
@@ -116,6 +124,8 @@ impl LivePlotApp {
             subheadline: None,
             color_scheme: None,
             color_scheme_applied: false,
+            auto_save_state: None,
+            last_content_rect: None,
         }
     }
 
@@ -139,14 +149,26 @@ impl LivePlotApp {
                     crate::data::scope::AxisType::Time(crate::data::scope::TimeFormat::default());
                 s.show_legend = cfg.features.legend;
                 s.show_grid = cfg.features.grid;
+                s.show_cursor_readout = cfg.features.cursor_readout;
                 s.y_axis.keep_max_fit = cfg.auto_fit.keep_max_fit;
                 s.y_axis.auto_fit = cfg.auto_fit.auto_fit_to_view;
+                s.resume_behavior = cfg.resume_behavior;
+                s.initial_x = cfg.initial_x_range;
+                s.initial_y = cfg.initial_y_range;
+                s.segment_warning = cfg.segment_warning;
+                s.legend_click = cfg.legend_click;
             }
         }
+        self.main_panel.liveplot_panel.interaction_locked = cfg.interaction_locked;
 
         // Trace storage limits.
         self.main_panel.traces_data.max_points = cfg.max_points;
         self.main_panel.traces_data.max_age_secs = cfg.max_age_secs;
+        self.main_panel.traces_data.reserve_points = cfg.reserve_points;
+        self.main_panel.traces_data.out_of_order = cfg.out_of_order;
+        self.main_panel.traces_data.trace_defaults = cfg.trace_defaults.clone();
+        self.main_panel.auto_attach = cfg.auto_attach.clone();
+        self.main_panel.unfocused_behavior = cfg.unfocused;
 
         // Hotkeys: configured or fallback to default path, then defaults.
         {
@@ -158,6 +180,29 @@ impl LivePlotApp {
                 .unwrap_or_default();
         }
 
+        // Export filename template, math filter, CSV timestamps and join tolerance;
+        // controller-requested exports read them back from the Export panel.
+        if let Some(ep) = self.main_panel.export_panel_mut() {
+            ep.filename_template = cfg.export_filename_template.clone();
+            ep.include_math = cfg.export_include_math;
+            ep.csv_timestamps = cfg.export_csv_timestamps;
+            ep.join_tolerance = cfg.export_join_tolerance;
+        }
+
+        // FFT panel defaults.
+        #[cfg(feature = "fft")]
+        if let Some(fp) = self.main_panel.fft_panel_mut() {
+            fp.fft_data.fft_window = cfg.fft_default_window;
+            if crate::data::fft::is_valid_fft_size(cfg.fft_default_size) {
+                fp.fft_data.fft_size = cfg.fft_default_size;
+            } else {
+                eprintln!(
+                    "Ignoring fft_default_size {}: not a power of two between 256 and 2^20",
+                    cfg.fft_default_size
+                );
+            }
+        }
+
         // Headline / subheadline for optional top banner.
         self.headline = cfg.headline.clone();
         self.subheadline = cfg.subheadline.clone();
@@ -181,6 +226,7 @@ impl LivePlotApp {
 
         // ── Color scheme ─────────────────────────────────────────────────────
         self.color_scheme = Some(cfg.color_scheme.clone());
+        crate::color_scheme::set_palette_override(cfg.palette.colors());
         // Pass the initial scheme to the ColorSchemePanel so it starts with the
         // correct selection.  The panel will be the source of truth for scheme
         // changes going forward.
@@ -195,6 +241,43 @@ impl LivePlotApp {
         // take overlay callback out of config so ownership moves into panel
         self.main_panel.overlays = cfg.overlays.take();
         self.color_scheme_applied = false;
+
+        self.auto_save_state = cfg.auto_save_state.clone();
+    }
+
+    /// Load the [`auto_save_state`](Self::auto_save_state) file, if configured
+    /// and present, and return the window size stored in it.
+    ///
+    /// A missing file is not an error (first launch); a malformed one is
+    /// reported and ignored so the app still starts with defaults.
+    pub(crate) fn auto_load_state(&mut self) -> Option<[f32; 2]> {
+        let path = self.auto_save_state.as_ref().filter(|p| p.exists())?;
+        match crate::persistence::load_state_from_path(path) {
+            Ok(state) => {
+                self.main_panel.apply_state_snapshot(&state);
+                state.window_size
+            }
+            Err(e) => {
+                eprintln!("Failed to load state from {}: {e}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Write the current state to the [`auto_save_state`](Self::auto_save_state)
+    /// file, if configured.
+    fn auto_save(&self) {
+        let Some(path) = &self.auto_save_state else {
+            return;
+        };
+        let mut state = self.main_panel.build_full_state_snapshot();
+        if let Some(rect) = self.last_content_rect {
+            state.window_size = Some([rect.width(), rect.height()]);
+            state.window_pos = Some([rect.left(), rect.top()]);
+        }
+        if let Err(e) = crate::persistence::save_state_to_path(&state, path) {
+            eprintln!("Failed to save state to {}: {e}", path.display());
+        }
     }
 
     // ─────────────────────────────────────────────────────────────────────────
@@ -233,6 +316,9 @@ impl LivePlotApp {
         }
 
         // ── UiActionController ───────────────────────────────────────────────
+        if let Some(ctrl) = &self.ui_ctrl {
+            self.main_panel.apply_panel_requests(ctrl);
+        }
         if let Some(ctrl) = &self.ui_ctrl {
             let mut take_actions = {
                 let mut inner = ctrl.inner.lock().unwrap();
@@ -247,16 +333,33 @@ impl LivePlotApp {
                     inner.request_save_raw.take(),
                     inner.request_save_raw_to.take(),
                     inner.fft_request.take(),
+                    std::mem::take(&mut inner.request_copy_visible),
                 )
             };
 
+            let settings = self.main_panel.export_settings();
+            if take_actions.6 {
+                if let Some(scope) = self.main_panel.liveplot_panel.get_data().first() {
+                    let (order, series) = scope.visible_series(&self.main_panel.traces_data);
+                    let rows = export::align_series(&order, &series, settings.join_tolerance);
+                    ctx.copy_text(export::aligned_rows_tsv(&order, &rows));
+                }
+            }
+
+            let export_range = if take_actions.4.is_some() {
+                self.main_panel.export_range()
+            } else {
+                None
+            };
             let scopes = self.main_panel.liveplot_panel.get_data_mut();
 
             for scope in scopes {
                 if let Some(p) = take_actions.0 {
                     if p {
                         scope.paused = true;
-                        self.main_panel.traces_data.take_snapshot();
+                        if scope.snapshot_on_pause {
+                            self.main_panel.traces_data.take_snapshot();
+                        }
                     } else {
                         scope.paused = false;
                     }
@@ -275,10 +378,28 @@ impl LivePlotApp {
                             path: Some(path),
                         });
                 }
-                if let Some((_fmt, path)) = take_actions.4.take() {
-                    let tol = 1e-9;
-                    let order = scope.trace_order.clone();
-                    let series = order
+                if let Some((fmt, path)) = take_actions.4.take() {
+                    let tol = settings.join_tolerance;
+                    let traces = &self.main_panel.traces_data;
+                    let include_math = settings.include_math;
+                    let order: Vec<_> = scope
+                        .trace_order
+                        .iter()
+                        .filter(|name| include_math || !traces.is_math(name))
+                        .cloned()
+                        .collect();
+                    let path = if path.is_dir() {
+                        let stem = export::expand_filename_template(
+                            &settings.filename_template,
+                            &scope.name,
+                            order.len(),
+                            chrono::Local::now(),
+                        );
+                        path.join(format!("{stem}.{}", fmt.extension()))
+                    } else {
+                        path
+                    };
+                    let mut series = order
                         .iter()
                         .filter_map(|name| {
                             scope
@@ -286,10 +407,22 @@ impl LivePlotApp {
                                 .map(|v| (name.clone(), v.into_iter().collect()))
                         })
                         .collect();
-                    let _ = if path.extension().and_then(|s| s.to_str()) == Some("csv") {
-                        export::write_csv_aligned_path(&path, &order, &series, tol)
-                    } else {
-                        export::write_parquet_aligned_path(&path, &order, &series, tol)
+                    if let Some(range) = export_range {
+                        export::clip_series(&mut series, range);
+                    }
+                    let _ = match path.extension().and_then(|s| s.to_str()) {
+                        Some("csv") => export::write_csv_aligned_path(
+                            &path,
+                            &order,
+                            &series,
+                            tol,
+                            settings.csv_timestamps,
+                        ),
+                        Some("json") => {
+                            let units = trace_units(&[&*scope], traces, &order);
+                            export::write_json_aligned_path(&path, &order, &units, &series, tol)
+                        }
+                        _ => export::write_parquet_aligned_path(&path, &order, &series, tol),
                     };
                 }
                 if let Some(_req) = take_actions.5.take() {
@@ -317,7 +450,8 @@ impl LivePlotApp {
                 for (name, rgb) in inner.color_requests.drain(..) {
                     let tref = TraceRef(name.clone());
                     if let Some(tr) = data.traces.get_trace_mut(&tref) {
-                        tr.look.color = egui::Color32::from_rgb(rgb[0], rgb[1], rgb[2]);
+                        tr.look
+                            .set_custom_color(egui::Color32::from_rgb(rgb[0], rgb[1], rgb[2]));
                     }
                 }
                 for (name, vis) in inner.visible_requests.drain(..) {
@@ -459,9 +593,15 @@ impl LivePlotApp {
             if requests.add_scope {
                 self.main_panel.liveplot_panel.add_scope();
             }
+            if let Some(target) = requests.set_auto_attach {
+                self.main_panel.auto_attach = target;
+            }
             if let Some(id) = requests.remove_scope {
                 let _ = self.main_panel.liveplot_panel.remove_scope_by_id(id);
             }
+            if let Some(order) = requests.reorder {
+                self.main_panel.liveplot_panel.reorder_scopes(&order);
+            }
             if requests.save_screenshot {
                 self.main_panel.pending_requests.screenshot =
                     Some(crate::data::data::ScreenshotRequest {
@@ -485,11 +625,13 @@ impl LivePlotApp {
                         scope.scope_type = scope_req.scope_type;
                         scope.trace_order = scope_req.trace_order.clone();
                         scope.trace_order.retain(|t| traces.contains_key(t));
+                        scope.max_points = scope_req.max_points;
                     }
                 }
             }
 
             let scopes_state = {
+                let global_max_points = self.main_panel.traces_data.max_points;
                 let scopes = self.main_panel.liveplot_panel.get_data_mut();
                 let mut scopes_info: Vec<crate::controllers::ScopeControlState> = Vec::new();
                 for scope in scopes {
@@ -505,6 +647,12 @@ impl LivePlotApp {
                         legend_position: scope.legend_position,
                         trace_order: scope.trace_order.clone(),
                         scope_type: scope.scope_type,
+                        max_points: scope.max_points,
+                        effective_max_points: if scope.max_points > 0 {
+                            scope.max_points
+                        } else {
+                            global_max_points
+                        },
                     });
                 }
                 crate::controllers::ScopesState {
@@ -557,8 +705,9 @@ impl LivePlotApp {
                 if let Some(id) = requests.remove_scope {
                     let _ = self.main_panel.liveplot_panel.remove_scope_by_id(id);
                 }
-                // Reorder not yet supported; consume request.
-                let _ = requests.reorder_scopes;
+                if let Some(order) = requests.reorder_scopes {
+                    self.main_panel.liveplot_panel.reorder_scopes(&order);
+                }
             }
 
             if let Some(size) = requests.set_window_size {
@@ -659,7 +808,16 @@ impl eframe::App for LivePlotApp {
         // Apply and publish controller requests after the main panel has updated.
         self.apply_controllers(&ctx, frame);
 
-        // Request continuous repainting (~60 fps).
-        ui.request_repaint_after(std::time::Duration::from_millis(16));
+        // Request continuous repainting (~60 fps), slowed down in the
+        // background as configured.
+        ui.request_repaint_after(self.main_panel.repaint_after(&ctx));
+
+        if self.auto_save_state.is_some() {
+            self.last_content_rect = Some(ctx.input(|i| i.content_rect()));
+        }
+    }
+
+    fn on_exit(&mut self) {
+        self.auto_save();
     }
 }
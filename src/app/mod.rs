@@ -41,11 +41,12 @@ use eframe::egui;
 
 use crate::config::ScopeButton;
 use crate::controllers::{
-    FFTController, LiveplotController, ScopesController, ThresholdController, TracesController,
-    UiActionController, WindowController,
+    FFTController, LiveplotController, MathController, MeasurementController, ScopesController,
+    ThresholdController, TracesController, TriggerController, UiActionController, WindowController,
 };
 use crate::data::data::LivePlotRequests;
 use crate::data::hotkeys::Hotkeys;
+use crate::data::scope::AutoAttach;
 use crate::data::traces::TracesCollection;
 use crate::events::EventController;
 use crate::panels::liveplot_ui::LiveplotPanel;
@@ -55,9 +56,10 @@ use crate::PlotCommand;
 #[cfg(feature = "fft")]
 use crate::panels::fft_ui::FftPanel;
 use crate::panels::{
-    color_scheme_ui::ColorSchemePanel, export_ui::ExportPanel, hotkeys_ui::HotkeysPanel,
-    math_ui::MathPanel, measurment_ui::MeasurementPanel, thresholds_ui::ThresholdsPanel,
-    traces_ui::TracesPanel, triggers_ui::TriggersPanel,
+    color_scheme_ui::ColorSchemePanel, data_inspector_ui::DataInspectorPanel,
+    export_ui::ExportPanel, hotkeys_ui::HotkeysPanel, math_ui::MathPanel,
+    measurment_ui::MeasurementPanel, thresholds_ui::ThresholdsPanel, traces_ui::TracesPanel,
+    triggers_ui::TriggersPanel,
 };
 
 /// Global monotonic counter that assigns unique IDs to [`LivePlotPanel`] instances.
@@ -182,6 +184,15 @@ pub struct LivePlotPanel {
     /// Event controller for dispatching UI/data events to subscribers.
     pub(crate) event_ctrl: Option<EventController>,
 
+    /// Active measurement publishing and point placement.
+    pub(crate) measurement_ctrl: Option<MeasurementController>,
+
+    /// Trigger arming and capture publishing.
+    pub(crate) trigger_ctrl: Option<TriggerController>,
+
+    /// Math trace state resets.
+    pub(crate) math_ctrl: Option<MathController>,
+
     /// Tracks the pause state from the previous frame to detect changes.
     pub(crate) last_frame_paused: bool,
 
@@ -220,6 +231,10 @@ pub struct LivePlotPanel {
     /// Buttons placed in the right sidebar icon strip.  `None` = empty (standard behaviour).
     pub sidebar_buttons: Option<Vec<ScopeButton>>,
 
+    /// Repaint behaviour while the window is unfocused or hidden; see
+    /// [`repaint_after`](Self::repaint_after).
+    pub unfocused_behavior: crate::config::UnfocusedBehavior,
+
     /// Minimum plot-area height (px) before the top bar is hidden and its buttons move to sidebar.
     pub min_height_for_top_bar: f32,
 
@@ -243,10 +258,19 @@ pub struct LivePlotPanel {
     /// fills every pixel of the allocated space.  Useful for dense embedded grid layouts.
     pub compact: bool,
 
+    /// Title of a docked panel expanded to fill the whole widget, hiding the
+    /// plot, the menu bar and every other dock.  Set by the tab header's
+    /// "Maximize" button; cleared by "Restore" or when the panel is hidden
+    /// or popped out.
+    pub maximized_panel: Option<String>,
+
     /// Set when `update_background` (external) registers new traces that
     /// haven't been synced to any scope's `trace_order` yet.
     pub traces_dirty: bool,
 
+    /// Scope that newly discovered traces are added to.
+    pub auto_attach: AutoAttach,
+
     // ── Undo/redo (standalone mode) ────────────────────────────────────────
     /// Undo/redo stack storing state snapshots.  Active in standalone mode;
     /// in embedded mode the host application's undo stack is used instead.
@@ -275,7 +299,8 @@ impl LivePlotPanel {
     ///
     /// The panel is pre-populated with the default set of sub-panels:
     ///
-    /// * **Right:** Traces, Math, Hotkeys, Thresholds, Triggers, Measurement
+    /// * **Right:** Traces, Math, Hotkeys, Thresholds, Triggers, Measurement, Color Scheme,
+    ///   Data Inspector
     /// * **Bottom:** FFT (when the `fft` feature is enabled)
     /// * **Hidden:** Export
     pub fn new(rx: std::sync::mpsc::Receiver<PlotCommand>) -> Self {
@@ -293,6 +318,7 @@ impl LivePlotPanel {
                 Box::new(TriggersPanel::default()),
                 Box::new(MeasurementPanel::default()),
                 Box::new(ColorSchemePanel::default()),
+                Box::new(DataInspectorPanel::default()),
             ],
             left_side_panels: vec![],
             #[cfg(feature = "fft")]
@@ -309,6 +335,9 @@ impl LivePlotPanel {
             fft_ctrl: None,
             threshold_ctrl: None,
             event_ctrl: None,
+            measurement_ctrl: None,
+            trigger_ctrl: None,
+            math_ctrl: None,
             last_frame_paused: false,
             suppress_next_pause_emit: false,
             pending_explicit_pause: None,
@@ -319,6 +348,7 @@ impl LivePlotPanel {
             pending_screenshot_capture: None,
             top_bar_buttons: None,
             sidebar_buttons: None,
+            unfocused_behavior: crate::config::UnfocusedBehavior::default(),
             min_height_for_top_bar: 200.0,
             min_width_for_sidebar: 550.0,
             min_height_for_sidebar: 280.0,
@@ -327,7 +357,9 @@ impl LivePlotPanel {
             last_plot_size: egui::Vec2::new(10_000.0, 10_000.0),
             panel_id: PANEL_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             compact: false,
+            maximized_panel: None,
             traces_dirty: false,
+            auto_attach: AutoAttach::default(),
             undo_stack: crate::undo::LivePlotUndoStack::new(),
             suppress_undo: false,
             pending_undo: false,
@@ -366,13 +398,32 @@ impl LivePlotPanel {
         self.event_ctrl = event_ctrl;
     }
 
-    /// Pause all scopes and take a trace snapshot.
+    /// Attach a measurement controller.
+    pub fn set_measurement_controller(&mut self, measurement_ctrl: Option<MeasurementController>) {
+        self.measurement_ctrl = measurement_ctrl;
+    }
+
+    /// Attach a trigger controller.
+    pub fn set_trigger_controller(&mut self, trigger_ctrl: Option<TriggerController>) {
+        self.trigger_ctrl = trigger_ctrl;
+    }
+
+    /// Attach a math controller.
+    pub fn set_math_controller(&mut self, math_ctrl: Option<MathController>) {
+        self.math_ctrl = math_ctrl;
+    }
+
+    /// Pause all scopes and take a trace snapshot if any scope uses one.
     pub fn pause_all(&mut self) {
         self.suppress_next_pause_emit = true;
+        let mut wants_snapshot = false;
         for scope in self.liveplot_panel.get_data_mut() {
             scope.paused = true;
+            wants_snapshot |= scope.snapshot_on_pause;
+        }
+        if wants_snapshot {
+            self.traces_data.take_snapshot();
         }
-        self.traces_data.take_snapshot();
     }
 
     /// Resume all scopes and clear the trace snapshot.
@@ -389,8 +440,11 @@ impl LivePlotPanel {
     /// Returns `Some(paused)` when the state flipped, `None` otherwise.
     /// Calling this updates the internal tracking field.
     pub fn pause_state_changed(&mut self) -> Option<bool> {
-        let currently_paused = self.liveplot_panel.get_data_mut().iter().all(|s| s.paused)
-            && self.traces_data.has_snapshot();
+        let currently_paused = self
+            .liveplot_panel
+            .get_data()
+            .iter()
+            .all(|s| s.is_frozen(&self.traces_data));
         if self.suppress_next_pause_emit {
             self.suppress_next_pause_emit = false;
             self.last_frame_paused = currently_paused;
@@ -418,8 +472,11 @@ impl LivePlotPanel {
 
     /// Toggle pause/resume on all scopes and mark the action as explicit.
     pub fn trigger_toggle_pause(&mut self) {
-        let currently_paused = self.liveplot_panel.get_data().iter().all(|s| s.paused)
-            && self.traces_data.has_snapshot();
+        let currently_paused = self
+            .liveplot_panel
+            .get_data()
+            .iter()
+            .all(|s| s.is_frozen(&self.traces_data));
         if currently_paused {
             self.trigger_resume_all();
         } else {
@@ -460,6 +517,47 @@ impl LivePlotPanel {
         self.pending_view_change.take()
     }
 
+    /// Whether the window hosting `ctx` is unfocused, minimized or occluded.
+    pub fn is_backgrounded(ctx: &egui::Context) -> bool {
+        ctx.input(|i| {
+            let vp = i.viewport();
+            !i.focused || vp.minimized == Some(true) || vp.occluded == Some(true)
+        })
+    }
+
+    /// Whether the window hosting `ctx` is minimized or occluded, i.e. nothing
+    /// drawn would be seen.
+    pub fn is_hidden(ctx: &egui::Context) -> bool {
+        ctx.input(|i| {
+            let vp = i.viewport();
+            vp.minimized == Some(true) || vp.occluded == Some(true)
+        })
+    }
+
+    /// Delay before the next frame should be requested, following
+    /// [`unfocused_behavior`](Self::unfocused_behavior).
+    ///
+    /// The standalone app paces itself with this; embedded hosts that drive
+    /// repaints themselves can use it the same way.
+    pub fn repaint_after(&self, ctx: &egui::Context) -> std::time::Duration {
+        let normal = std::time::Duration::from_millis(16);
+        if !Self::is_backgrounded(ctx) {
+            return normal;
+        }
+        match self.unfocused_behavior {
+            crate::config::UnfocusedBehavior::KeepRunning => normal,
+            crate::config::UnfocusedBehavior::Throttle(interval) => interval.max(normal),
+            crate::config::UnfocusedBehavior::Freeze => crate::config::FROZEN_DRAIN_INTERVAL,
+        }
+    }
+
+    /// Whether this frame should only ingest data and skip drawing: the
+    /// behaviour is [`Freeze`](crate::config::UnfocusedBehavior::Freeze) and
+    /// the window is [hidden](Self::is_hidden).
+    pub fn skips_drawing(&self, ctx: &egui::Context) -> bool {
+        self.unfocused_behavior == crate::config::UnfocusedBehavior::Freeze && Self::is_hidden(ctx)
+    }
+
     /// Set the X-axis range on all TimeScope scopes and suppress the next
     /// view-change emission to avoid feedback loops when syncing across tabs.
     ///
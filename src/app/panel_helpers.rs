@@ -4,10 +4,16 @@
 //! empty) to locate a panel by its concrete type and modify its visibility state.
 //! They are used by the controller modules and the layout/rendering code.
 
+use crate::controllers::{PanelInfo, UiActionController};
+use crate::panels::export_ui::{ExportPanel, ExportSettings};
+#[cfg(feature = "fft")]
+use crate::panels::fft_ui::FftPanel;
 use crate::panels::hotkeys_ui::HotkeysPanel;
+use crate::panels::measurment_ui::MeasurementPanel;
 use crate::panels::panel_trait::Panel;
 use crate::panels::thresholds_ui::ThresholdsPanel;
 use crate::panels::traces_ui::TracesPanel;
+use crate::panels::triggers_ui::TriggersPanel;
 
 use super::LivePlotPanel;
 
@@ -31,6 +37,25 @@ impl LivePlotPanel {
         None
     }
 
+    /// Return a mutable reference to the [`TriggersPanel`], if one exists in any panel list.
+    ///
+    /// Searches left → right → bottom → detached → empty panels in order.
+    pub(crate) fn triggers_panel_mut(&mut self) -> Option<&mut TriggersPanel> {
+        for p in self
+            .left_side_panels
+            .iter_mut()
+            .chain(self.right_side_panels.iter_mut())
+            .chain(self.bottom_panels.iter_mut())
+            .chain(self.detached_panels.iter_mut())
+            .chain(self.empty_panels.iter_mut())
+        {
+            if let Some(tp) = p.downcast_mut::<TriggersPanel>() {
+                return Some(tp);
+            }
+        }
+        None
+    }
+
     /// Return a mutable reference to the [`TracesPanel`], if one exists in any panel list.
     ///
     /// Searches left → right → bottom → detached → empty panels in order.
@@ -50,6 +75,83 @@ impl LivePlotPanel {
         None
     }
 
+    /// Return a mutable reference to the [`MeasurementPanel`], if one exists in any panel list.
+    ///
+    /// Searches left → right → bottom → detached → empty panels in order.
+    pub(crate) fn measurement_panel_mut(&mut self) -> Option<&mut MeasurementPanel> {
+        for p in self
+            .left_side_panels
+            .iter_mut()
+            .chain(self.right_side_panels.iter_mut())
+            .chain(self.bottom_panels.iter_mut())
+            .chain(self.detached_panels.iter_mut())
+            .chain(self.empty_panels.iter_mut())
+        {
+            if let Some(mp) = p.downcast_mut::<MeasurementPanel>() {
+                return Some(mp);
+            }
+        }
+        None
+    }
+
+    /// Return a mutable reference to the [`ExportPanel`], if one exists in any panel list.
+    ///
+    /// Searches left → right → bottom → detached → empty panels in order.
+    pub(crate) fn export_panel_mut(&mut self) -> Option<&mut ExportPanel> {
+        for p in self
+            .left_side_panels
+            .iter_mut()
+            .chain(self.right_side_panels.iter_mut())
+            .chain(self.bottom_panels.iter_mut())
+            .chain(self.detached_panels.iter_mut())
+            .chain(self.empty_panels.iter_mut())
+        {
+            if let Some(ep) = p.downcast_mut::<ExportPanel>() {
+                return Some(ep);
+            }
+        }
+        None
+    }
+
+    /// Time range the Export panel's region options select for data exports,
+    /// `None` for the full buffer.
+    pub(crate) fn export_range(&mut self) -> Option<(f64, f64)> {
+        let view = self
+            .liveplot_panel
+            .get_data()
+            .first()
+            .map(|s| s.x_axis.bounds);
+        self.export_panel_mut()?.export_range(view)
+    }
+
+    /// Export options set in the [`ExportPanel`], or its defaults if there is
+    /// none, for exports requested through the window controller.
+    pub(crate) fn export_settings(&mut self) -> ExportSettings {
+        self.export_panel_mut()
+            .map(|ep| ep.settings())
+            .unwrap_or_else(|| ExportPanel::default().settings())
+    }
+
+    /// Return a mutable reference to the [`FftPanel`], if one exists in any panel list.
+    ///
+    /// Searches left → right → bottom → detached → empty panels in order.
+    #[cfg(feature = "fft")]
+    pub(crate) fn fft_panel_mut(&mut self) -> Option<&mut FftPanel> {
+        for p in self
+            .left_side_panels
+            .iter_mut()
+            .chain(self.right_side_panels.iter_mut())
+            .chain(self.bottom_panels.iter_mut())
+            .chain(self.detached_panels.iter_mut())
+            .chain(self.empty_panels.iter_mut())
+        {
+            if let Some(fp) = p.downcast_mut::<FftPanel>() {
+                return Some(fp);
+            }
+        }
+        None
+    }
+
     /// Toggle the visibility of the first panel of type `T` found in any list.
     ///
     /// If the panel is currently visible and attached (not detached), it becomes hidden.
@@ -77,6 +179,56 @@ impl LivePlotPanel {
         false
     }
 
+    /// Apply pending panel show/hide/detach requests from a
+    /// [`UiActionController`] and publish the resulting visibility list when it
+    /// changed.  Panels are matched by title, case-insensitively.
+    pub(crate) fn apply_panel_requests(&mut self, ctrl: &UiActionController) {
+        let (visible_reqs, detach_reqs) = {
+            let mut inner = ctrl.inner.lock().unwrap();
+            (
+                std::mem::take(&mut inner.panel_visible_requests),
+                std::mem::take(&mut inner.panel_detach_requests),
+            )
+        };
+
+        let mut panels: Vec<PanelInfo> = Vec::new();
+        for p in self
+            .left_side_panels
+            .iter_mut()
+            .chain(self.right_side_panels.iter_mut())
+            .chain(self.bottom_panels.iter_mut())
+            .chain(self.detached_panels.iter_mut())
+            .chain(self.empty_panels.iter_mut())
+        {
+            let st = p.state_mut();
+            for (title, visible) in visible_reqs.iter() {
+                if st.title.eq_ignore_ascii_case(title) {
+                    st.visible = *visible;
+                    st.request_focus |= *visible;
+                }
+            }
+            for (title, detached) in detach_reqs.iter() {
+                if st.title.eq_ignore_ascii_case(title) {
+                    st.detached = *detached;
+                    st.visible = true;
+                }
+            }
+            panels.push(PanelInfo {
+                title: st.title.to_string(),
+                visible: st.visible,
+                detached: st.detached,
+            });
+        }
+
+        let mut inner = ctrl.inner.lock().unwrap();
+        if inner.last_panels.as_ref() != Some(&panels) {
+            inner
+                .panel_listeners
+                .retain(|s| s.send(panels.clone()).is_ok());
+            inner.last_panels = Some(panels);
+        }
+    }
+
     /// Hide the Hotkeys panel (useful when focus switches away via hotkeys).
     pub fn hide_hotkeys_panel(&mut self) {
         for p in self
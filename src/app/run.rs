@@ -32,6 +32,9 @@ pub fn run_liveplot(
     let fft_ctrl = cfg.controllers.fft.take();
     let threshold_ctrl = cfg.controllers.threshold.take();
     let event_ctrl = cfg.controllers.event.take();
+    let measurement_ctrl = cfg.controllers.measurement.take();
+    let trigger_ctrl = cfg.controllers.trigger.take();
+    let math_ctrl = cfg.controllers.math.take();
     let mut app = LivePlotApp::with_controllers(
         rx,
         window_ctrl,
@@ -43,7 +46,11 @@ pub fn run_liveplot(
         threshold_ctrl,
     );
     app.main_panel.set_event_controller(event_ctrl);
+    app.main_panel.set_measurement_controller(measurement_ctrl);
+    app.main_panel.set_trigger_controller(trigger_ctrl);
+    app.main_panel.set_math_controller(math_ctrl);
     app.apply_config(&mut cfg);
+    let restored_size = app.auto_load_state();
 
     let title = cfg.title.clone();
     let mut opts = cfg
@@ -58,12 +65,11 @@ pub fn run_liveplot(
         }
     }
 
-    // Set a bigger default window size if one is not provided by config.
+    // Set a bigger default window size if one is not provided by config,
+    // preferring the size saved with an auto-loaded state.
     if opts.viewport.inner_size.is_none() {
-        opts.viewport = opts
-            .viewport
-            .clone()
-            .with_inner_size(egui::vec2(1400.0, 900.0));
+        let [w, h] = restored_size.unwrap_or([1400.0, 900.0]);
+        opts.viewport = opts.viewport.clone().with_inner_size(egui::vec2(w, h));
     }
 
     eframe::run_native(
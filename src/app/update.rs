@@ -329,6 +329,12 @@ impl LivePlotPanel {
 
             self.update_data();
             self.handle_completed_screenshot(ui.ctx());
+            self.handle_state_requests(ui.ctx());
+            // Frozen in the background: keep ingesting and serving requests,
+            // but draw nothing.
+            if self.skips_drawing(ui.ctx()) {
+                return;
+            }
 
             // Propagate the event controller to scope panels (handles new scopes too).
             self.liveplot_panel
@@ -400,63 +406,67 @@ impl LivePlotPanel {
             // In compact mode, skip all chrome (menu bar, sidebars, bottom panels)
             // so the plot fills the entire allocated area.  This avoids collapsed
             // panel stubs stealing space from very small embedded cells.
-            if !self.compact {
+            // A maximized panel replaces the whole layout, plot included.
+            let maximized = !self.compact && self.render_maximized(ui);
+            if !self.compact && !maximized {
                 self.render_menu(ui);
                 self.render_panels(ui);
             }
 
-            // Render the central plot area with overlay support from sub-panels.
-            let central_panel = egui::CentralPanel::default();
-            let central_panel = if self.compact {
-                central_panel.frame(egui::Frame::NONE)
-            } else {
-                central_panel
-            };
-            central_panel.show(ui, |ui| {
-                use std::cell::RefCell;
-                // Temporarily take panel lists to build a local overlay drawer
-                // without borrowing `self` mutably (needed because the liveplot
-                // render callback borrows traces_data through self).
-                let left = RefCell::new(std::mem::take(&mut self.left_side_panels));
-                let right = RefCell::new(std::mem::take(&mut self.right_side_panels));
-                let bottom = RefCell::new(std::mem::take(&mut self.bottom_panels));
-                let detached = RefCell::new(std::mem::take(&mut self.detached_panels));
-                let empty = RefCell::new(std::mem::take(&mut self.empty_panels));
-
-                let mut draw_overlays =
-                    |plot_ui: &mut egui_plot::PlotUi,
-                     scope: &crate::data::scope::ScopeData,
-                     traces: &crate::data::traces::TracesCollection| {
-                        for p in right
-                            .borrow_mut()
-                            .iter_mut()
-                            .chain(left.borrow_mut().iter_mut())
-                            .chain(bottom.borrow_mut().iter_mut())
-                            .chain(detached.borrow_mut().iter_mut())
-                            .chain(empty.borrow_mut().iter_mut())
-                        {
-                            p.draw(plot_ui, scope, traces);
-                        }
-                        // invoke optional user overlay callback after panel overlays
-                        if let Some(cb) = &mut self.overlays {
-                            cb(plot_ui, scope, traces);
-                        }
-                    };
+            if !maximized {
+                // Render the central plot area with overlay support from sub-panels.
+                let central_panel = egui::CentralPanel::default();
+                let central_panel = if self.compact {
+                    central_panel.frame(egui::Frame::NONE)
+                } else {
+                    central_panel
+                };
+                central_panel.show(ui, |ui| {
+                    use std::cell::RefCell;
+                    // Temporarily take panel lists to build a local overlay drawer
+                    // without borrowing `self` mutably (needed because the liveplot
+                    // render callback borrows traces_data through self).
+                    let left = RefCell::new(std::mem::take(&mut self.left_side_panels));
+                    let right = RefCell::new(std::mem::take(&mut self.right_side_panels));
+                    let bottom = RefCell::new(std::mem::take(&mut self.bottom_panels));
+                    let detached = RefCell::new(std::mem::take(&mut self.detached_panels));
+                    let empty = RefCell::new(std::mem::take(&mut self.empty_panels));
+
+                    let mut draw_overlays =
+                        |plot_ui: &mut egui_plot::PlotUi,
+                         scope: &crate::data::scope::ScopeData,
+                         traces: &crate::data::traces::TracesCollection| {
+                            for p in right
+                                .borrow_mut()
+                                .iter_mut()
+                                .chain(left.borrow_mut().iter_mut())
+                                .chain(bottom.borrow_mut().iter_mut())
+                                .chain(detached.borrow_mut().iter_mut())
+                                .chain(empty.borrow_mut().iter_mut())
+                            {
+                                p.draw(plot_ui, scope, traces);
+                            }
+                            // invoke optional user overlay callback after panel overlays
+                            if let Some(cb) = &mut self.overlays {
+                                cb(plot_ui, scope, traces);
+                            }
+                        };
 
-                // Render the liveplot panel; `draw_overlays` supplies per-panel overlays.
-                self.liveplot_panel.clear_rendered_flags();
-                self.liveplot_panel
-                    .render_panel(ui, &mut draw_overlays, &mut self.traces_data);
+                    // Render the liveplot panel; `draw_overlays` supplies per-panel overlays.
+                    self.liveplot_panel.clear_rendered_flags();
+                    self.liveplot_panel
+                        .render_panel(ui, &mut draw_overlays, &mut self.traces_data);
 
-                // Return panel lists back to self.
-                self.left_side_panels = left.into_inner();
-                self.right_side_panels = right.into_inner();
-                self.bottom_panels = bottom.into_inner();
-                self.detached_panels = detached.into_inner();
-                self.empty_panels = empty.into_inner();
+                    // Return panel lists back to self.
+                    self.left_side_panels = left.into_inner();
+                    self.right_side_panels = right.into_inner();
+                    self.bottom_panels = bottom.into_inner();
+                    self.detached_panels = detached.into_inner();
+                    self.empty_panels = empty.into_inner();
 
-                self.traces_data.hover_trace = None;
-            });
+                    self.traces_data.hover_trace = None;
+                });
+            }
 
             // Collect any pending view changes from scope panels (zoom/pan/slider/fit).
             if let Some(vc) = self.liveplot_panel.collect_view_changes() {
@@ -543,6 +553,12 @@ impl LivePlotPanel {
     ///
     /// Called at the start of every frame before any rendering.
     pub(crate) fn update_data(&mut self) {
+        // Scope point caps become per-trace caps before new samples are trimmed.
+        self.traces_data.point_caps = crate::data::scope::trace_point_caps(
+            self.liveplot_panel.get_data(),
+            self.traces_data.max_points,
+        );
+
         // Process incoming plot commands; collect any newly created traces.
         let new_traces = self.traces_data.update();
 
@@ -561,6 +577,9 @@ impl LivePlotPanel {
         // Apply any queued threshold add/remove requests before processing data so new defs
         // participate in this frame's evaluation.
         self.apply_threshold_controller_requests();
+        self.apply_measurement_controller_requests();
+        self.apply_trigger_controller_requests();
+        self.apply_math_controller_requests();
 
         // Collect existing trace names only when traces were registered
         // externally (via update_background) since the last update_data call.
@@ -580,8 +599,12 @@ impl LivePlotPanel {
             settings_changed: false,
         };
 
-        // Attach newly created traces to the primary (first) scope only.
-        if let Some(scope) = data.primary_scope_mut() {
+        for name in data.traces.take_removed() {
+            data.remove_trace(&name);
+        }
+
+        // Attach newly created traces to the auto-attach scope only.
+        if let Some(scope) = data.auto_attach_scope_mut(&self.auto_attach) {
             for name in new_traces.into_iter().chain(all_trace_names) {
                 if !scope.trace_order.iter().any(|n| n == &name) {
                     scope.trace_order.push(name);
@@ -613,5 +636,24 @@ impl LivePlotPanel {
 
         // After threshold processing, forward freshly generated events to controller listeners.
         self.publish_threshold_events();
+        self.publish_measurement_result();
+        self.publish_measurement_snapshot();
+        self.publish_trigger_captures();
+        self.publish_trigger_state();
+
+        // Share the P1–P2 span with the Export panel's region option, and its
+        // join tolerance with the measurement cursor values.
+        let selection = self
+            .measurement_panel_mut()
+            .and_then(|mp| mp.current_result())
+            .and_then(|r| Some((r.p1?[0], r.p2?[0])))
+            .map(|(a, b)| (a.min(b), a.max(b)));
+        let join_tolerance = self.export_panel_mut().map(|ep| {
+            ep.set_selection(selection);
+            ep.join_tolerance
+        });
+        if let (Some(tol), Some(mp)) = (join_tolerance, self.measurement_panel_mut()) {
+            mp.join_tolerance = tol;
+        }
     }
 }
@@ -29,6 +29,76 @@ pub fn set_global_palette(new: Vec<Color32>) {
     *guard = new;
 }
 
+// Palette that takes precedence over the scheme's own trace colours when a
+// scheme is applied.  Set from `LivePlotConfig::palette`.
+static PALETTE_OVERRIDE: Lazy<Mutex<Option<Vec<Color32>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Set (or clear with `None`) the palette used for automatic trace colours
+/// regardless of the active [`ColorScheme`].  Also updates the global palette
+/// immediately so traces created before the next scheme change use it.
+pub fn set_palette_override(palette: Option<Vec<Color32>>) {
+    if let Some(p) = &palette {
+        set_global_palette(p.clone());
+    }
+    *PALETTE_OVERRIDE.lock().unwrap() = palette;
+}
+
+/// Palette for automatic trace colour assignment.
+///
+/// Drives the colours given to new traces and the recolouring on scheme
+/// changes.  A colour set explicitly on a trace (look editor, controller)
+/// replaces its palette colour.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum TracePalette {
+    /// Use the trace colours of the active [`ColorScheme`].
+    #[default]
+    Scheme,
+    /// Okabe–Ito: 8 colours distinguishable with common colour-vision deficiencies.
+    OkabeIto,
+    /// Tableau 10: the default categorical palette of Tableau / matplotlib.
+    Tableau10,
+    /// User-supplied RGB colours, e.g. brand colours.
+    Custom(Vec<[u8; 3]>),
+}
+
+impl TracePalette {
+    /// The palette colours, or `None` when the scheme's palette should be used.
+    pub fn colors(&self) -> Option<Vec<Color32>> {
+        let rgb: &[[u8; 3]] = match self {
+            TracePalette::Scheme => return None,
+            TracePalette::OkabeIto => &[
+                [230, 159, 0],
+                [86, 180, 233],
+                [0, 158, 115],
+                [240, 228, 66],
+                [0, 114, 178],
+                [213, 94, 0],
+                [204, 121, 167],
+                [153, 153, 153],
+            ],
+            TracePalette::Tableau10 => &[
+                [78, 121, 167],
+                [242, 142, 43],
+                [225, 87, 89],
+                [118, 183, 178],
+                [89, 161, 79],
+                [237, 201, 72],
+                [176, 122, 161],
+                [255, 157, 167],
+                [156, 117, 95],
+                [186, 176, 172],
+            ],
+            TracePalette::Custom(c) if c.is_empty() => return None,
+            TracePalette::Custom(c) => c,
+        };
+        Some(
+            rgb.iter()
+                .map(|c| Color32::from_rgb(c[0], c[1], c[2]))
+                .collect(),
+        )
+    }
+}
+
 /// Visual theme for the plot UI, including user-defined custom schemes.
 #[derive(Clone, Debug, PartialEq)]
 pub enum ColorScheme {
@@ -249,7 +319,8 @@ impl ColorScheme {
         // colours appropriate for the newly-applied scheme.  This is also
         // important for unit tests and for programmes that query the palette
         // directly.
-        set_global_palette(self.trace_colors());
+        let over = PALETTE_OVERRIDE.lock().unwrap().clone();
+        set_global_palette(over.unwrap_or_else(|| self.trace_colors()));
     }
 
     /// Default trace colour palette for this scheme (up to 8 colours).
@@ -1,9 +1,17 @@
 //! Configuration types shared across the live plot UIs.
 
+use crate::controllers::MathController;
+use crate::controllers::MeasurementController;
 use crate::controllers::ThresholdController;
 use crate::controllers::TracesController;
+use crate::controllers::TriggerController;
 use crate::controllers::{FFTController, UiActionController, WindowController};
 use crate::data::hotkeys::Hotkeys;
+use crate::data::scope::{
+    AutoAttach, InitialXRange, LegendClick, ResumeBehavior, DEFAULT_SEGMENT_WARNING,
+};
+use crate::data::trace_look::TraceDefaults;
+use crate::data::traces::OutOfOrderPolicy;
 use crate::events::EventController;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -23,6 +31,7 @@ pub enum ScopeButton {
     Fft,
     Export,
     ColorScheme,
+    DataInspector,
     PauseResume,
     ClearAll,
     /// Any panel with a custom title string.
@@ -42,6 +51,7 @@ impl ScopeButton {
             ScopeButton::Fft => title == "FFT",
             ScopeButton::Export => title == "Export",
             ScopeButton::ColorScheme => title == "Color Scheme",
+            ScopeButton::DataInspector => title == "Data Inspector",
             ScopeButton::Custom(t) => t.as_str() == title,
             ScopeButton::Scopes | ScopeButton::PauseResume | ScopeButton::ClearAll => false,
         }
@@ -60,6 +70,7 @@ impl ScopeButton {
             ScopeButton::Fft,
             ScopeButton::Export,
             ScopeButton::ColorScheme,
+            ScopeButton::DataInspector,
             ScopeButton::PauseResume,
             ScopeButton::ClearAll,
         ]
@@ -70,7 +81,7 @@ impl ScopeButton {
 // Color scheme
 // ─────────────────────────────────────────────────────────────────────────────
 
-pub use crate::{ColorScheme, CustomColorScheme};
+pub use crate::{ColorScheme, CustomColorScheme, TracePalette};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Feature flags
@@ -116,6 +127,8 @@ pub struct FeatureFlags {
     pub pause_resume: bool,
     /// Show the clear-all button.
     pub clear_all: bool,
+    /// Show the cursor coordinate strip below each scope.
+    pub cursor_readout: bool,
 }
 
 impl Default for FeatureFlags {
@@ -138,6 +151,7 @@ impl Default for FeatureFlags {
             scopes: true,
             pause_resume: true,
             clear_all: true,
+            cursor_readout: false,
         }
     }
 }
@@ -210,6 +224,30 @@ impl Default for AutoFitConfig {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Background behaviour
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// What the plot does while its window is unfocused, minimized or occluded.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum UnfocusedBehavior {
+    /// Keep repainting and ingesting at the normal rate, so background
+    /// monitoring (thresholds, triggers, controllers) stays live.  Default.
+    #[default]
+    KeepRunning,
+    /// Repaint, and thereby ingest, at most once per interval.
+    Throttle(std::time::Duration),
+    /// Stop drawing while the window is minimized or occluded, and only
+    /// wake up every [`FROZEN_DRAIN_INTERVAL`] to drain the channel and
+    /// evaluate thresholds and triggers.  While merely unfocused the plot is
+    /// still drawn, at that same low rate.
+    Freeze,
+}
+
+/// Repaint interval used by [`UnfocusedBehavior::Freeze`] in the background,
+/// so queued samples are ingested instead of piling up in the channel.
+pub const FROZEN_DRAIN_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Controllers sub-config
 // ─────────────────────────────────────────────────────────────────────────────
@@ -223,6 +261,9 @@ pub struct Controllers {
     pub threshold: Option<ThresholdController>,
     pub traces: Option<TracesController>,
     pub event: Option<EventController>,
+    pub measurement: Option<MeasurementController>,
+    pub trigger: Option<TriggerController>,
+    pub math: Option<MathController>,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -238,7 +279,9 @@ pub struct Controllers {
 /// | `features`       | Toggle individual UI features on/off |
 /// | `layout`         | Responsive hide/show thresholds |
 /// | `color_scheme`   | Predefined visual theme |
+/// | `palette`        | Trace colour palette |
 /// | `auto_fit`       | Automatic axis fitting behaviour |
+/// | `unfocused`      | Repaint rate while the window is in the background |
 /// | `controllers`    | Programmatic interaction handles |
 pub struct LivePlotConfig {
     // ── Scope / data ─────────────────────────────────────────────────────────
@@ -248,10 +291,41 @@ pub struct LivePlotConfig {
     pub max_points: usize,
     /// Maximum age in seconds for retained points.  0.0 disables time-based pruning.
     pub max_age_secs: f64,
+    /// Pre-allocate every trace buffer for `max_points` samples to avoid
+    /// reallocation hitches under high-rate streams.  Default: `false`.
+    pub reserve_points: bool,
+    /// What to do with a sample older than the last sample of its trace.
+    /// Default: [`OutOfOrderPolicy::Reject`].
+    pub out_of_order: OutOfOrderPolicy,
+    /// Visibility, line width and line style of newly created traces.
+    /// Styles restored from a saved state take precedence.
+    pub trace_defaults: TraceDefaults,
     /// Optional unit label for the Y axis (e.g. "V", "°C").
     pub y_unit: Option<String>,
     /// Show Y axis in log10 scale.
     pub y_log: bool,
+    /// What the x-window does when a paused scope is resumed.
+    pub resume_behavior: ResumeBehavior,
+    /// X-window shown before any data arrives, e.g.
+    /// [`InitialXRange::WallClock`] for sources stamped with wall-clock time.
+    /// Default: [`InitialXRange::FromZero`].
+    pub initial_x_range: InitialXRange,
+    /// Y bounds shown before any data arrives.  Default: `None` (unchanged).
+    pub initial_y_range: Option<(f64, f64)>,
+    /// Scope that newly discovered traces are added to, or
+    /// [`AutoAttach::Disabled`] to leave them unassigned.
+    /// Default: [`AutoAttach::Primary`].
+    pub auto_attach: AutoAttach,
+    /// Warn on a scope when one frame draws more line segments than this
+    /// (`0` = never).  Default: [`DEFAULT_SEGMENT_WARNING`].
+    pub segment_warning: usize,
+    /// What a left click on a legend entry does.
+    /// Default: [`LegendClick::ToggleVisible`].
+    pub legend_click: LegendClick,
+    /// Lock every scope against pan, zoom and click selection, and hide the
+    /// lock toggle so viewers cannot undo it (kiosk/dashboard displays).
+    /// Default: `false`.
+    pub interaction_locked: bool,
 
     // ── Window / chrome ──────────────────────────────────────────────────────
     /// Native window title.
@@ -274,6 +348,8 @@ pub struct LivePlotConfig {
     // ── Appearance ───────────────────────────────────────────────────────────
     /// Color scheme / visual theme.
     pub color_scheme: ColorScheme,
+    /// Palette for automatic trace colours; overrides the scheme's palette.
+    pub palette: TracePalette,
     /// Optional per-plot overlay callback.  The closure is invoked inside the
     /// plot rendering callback and can draw custom graphics using the
     /// [`egui_plot::PlotUi`] API.  Useful for example code that wants to add
@@ -292,6 +368,43 @@ pub struct LivePlotConfig {
     /// Automatic axis fitting configuration.
     pub auto_fit: AutoFitConfig,
 
+    // ── FFT ──────────────────────────────────────────────────────────────────
+    /// Window function the FFT panel starts with.  Default: Hann.
+    #[cfg(feature = "fft")]
+    pub fft_default_window: crate::data::fft::FFTWindow,
+    /// FFT size the panel starts with.  Must be a power of two between 256
+    /// and 2^20; other values are ignored with a warning.  Default: 1024.
+    #[cfg(feature = "fft")]
+    pub fft_default_size: usize,
+
+    // ── Background ───────────────────────────────────────────────────────────
+    /// Repaint behaviour while the window is unfocused or hidden.
+    pub unfocused: UnfocusedBehavior,
+
+    // ── Export ───────────────────────────────────────────────────────────────
+    /// Filename (without extension) suggested for data exports.  `{timestamp}`,
+    /// `{scope}` and `{trace_count}` are expanded when the name is built.
+    /// Also used for raw exports requested to a directory.  Default: `"snapshot"`.
+    pub export_filename_template: String,
+    /// Include math (derived) traces in data exports.  Applies to the Export
+    /// panel and to raw exports requested via the window controller.
+    /// Default: `true`.
+    pub export_include_math: bool,
+    /// Timestamp column(s) in CSV exports: float seconds, integer
+    /// microseconds or both.  Default: [`CsvTimestamp::Seconds`](crate::data::export::CsvTimestamp).
+    pub export_csv_timestamps: crate::data::export::CsvTimestamp,
+    /// Samples of different traces whose timestamps differ by at most this
+    /// many seconds share one row in CSV/Parquet exports.  Raise it for
+    /// jittery multi-rate data.  Default: [`DEFAULT_JOIN_TOLERANCE`](crate::data::export::DEFAULT_JOIN_TOLERANCE).
+    pub export_join_tolerance: f64,
+
+    // ── Persistence ──────────────────────────────────────────────────────────
+    /// State file for automatic persistence in the standalone app.  When set,
+    /// the state is loaded from this file at startup (if it exists) and
+    /// written back when the window closes, in the same format as the
+    /// Export panel's "Save state".  Default: `None` (nothing is persisted).
+    pub auto_save_state: Option<std::path::PathBuf>,
+
     // ── Hotkeys ──────────────────────────────────────────────────────────────
     /// Optional hotkeys configuration.
     pub hotkeys: Option<Hotkeys>,
@@ -307,8 +420,18 @@ impl Clone for LivePlotConfig {
             time_window_secs: self.time_window_secs,
             max_points: self.max_points,
             max_age_secs: self.max_age_secs,
+            reserve_points: self.reserve_points,
+            out_of_order: self.out_of_order,
+            trace_defaults: self.trace_defaults.clone(),
             y_unit: self.y_unit.clone(),
             y_log: self.y_log,
+            resume_behavior: self.resume_behavior,
+            initial_x_range: self.initial_x_range,
+            initial_y_range: self.initial_y_range,
+            auto_attach: self.auto_attach.clone(),
+            segment_warning: self.segment_warning,
+            legend_click: self.legend_click,
+            interaction_locked: self.interaction_locked,
             title: self.title.clone(),
             headline: self.headline.clone(),
             subheadline: self.subheadline.clone(),
@@ -316,8 +439,19 @@ impl Clone for LivePlotConfig {
             features: self.features.clone(),
             layout: self.layout.clone(),
             color_scheme: self.color_scheme.clone(),
+            palette: self.palette.clone(),
             overlays: None, // cannot clone closure
             auto_fit: self.auto_fit.clone(),
+            #[cfg(feature = "fft")]
+            fft_default_window: self.fft_default_window,
+            #[cfg(feature = "fft")]
+            fft_default_size: self.fft_default_size,
+            unfocused: self.unfocused,
+            export_filename_template: self.export_filename_template.clone(),
+            export_include_math: self.export_include_math,
+            export_csv_timestamps: self.export_csv_timestamps,
+            export_join_tolerance: self.export_join_tolerance,
+            auto_save_state: self.auto_save_state.clone(),
             hotkeys: self.hotkeys.clone(),
             controllers: self.controllers.clone(),
         }
@@ -330,8 +464,18 @@ impl Default for LivePlotConfig {
             time_window_secs: 10.0,
             max_points: 10_000,
             max_age_secs: 0.0,
+            reserve_points: false,
+            out_of_order: OutOfOrderPolicy::default(),
+            trace_defaults: TraceDefaults::default(),
             y_unit: None,
             y_log: false,
+            resume_behavior: ResumeBehavior::default(),
+            initial_x_range: InitialXRange::default(),
+            initial_y_range: None,
+            auto_attach: AutoAttach::default(),
+            segment_warning: DEFAULT_SEGMENT_WARNING,
+            legend_click: LegendClick::default(),
+            interaction_locked: false,
 
             title: "LivePlot".to_string(),
             headline: None,
@@ -341,8 +485,21 @@ impl Default for LivePlotConfig {
             features: FeatureFlags::default(),
             layout: ResponsiveLayout::default(),
             color_scheme: ColorScheme::default(),
+            palette: TracePalette::default(),
             overlays: None,
             auto_fit: AutoFitConfig::default(),
+            #[cfg(feature = "fft")]
+            fft_default_window: crate::data::fft::FFTWindow::default(),
+            #[cfg(feature = "fft")]
+            fft_default_size: 1024,
+            unfocused: UnfocusedBehavior::default(),
+
+            export_filename_template: crate::data::export::DEFAULT_FILENAME_TEMPLATE.to_string(),
+            export_include_math: true,
+            export_csv_timestamps: Default::default(),
+            export_join_tolerance: crate::data::export::DEFAULT_JOIN_TOLERANCE,
+
+            auto_save_state: None,
 
             hotkeys: None,
             controllers: Controllers::default(),
@@ -8,10 +8,11 @@ use std::path::PathBuf;
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
 
+use crate::data::scope::AutoAttach;
 use crate::data::scope::AxisSettings;
 use crate::data::scope::LegendPosition;
 use crate::data::scope::ScopeType;
-use crate::data::traces::TraceRef;
+use crate::data::traces::{IngestStats, TraceRef};
 use egui_plot::LineStyle;
 
 /// Current window information (physical pixels).
@@ -124,6 +125,21 @@ pub(crate) struct UiActionInner {
     pub(crate) fft_listeners: Vec<Sender<FFTRawData>>,
     pub(crate) request_screenshot_to: Option<std::path::PathBuf>,
     pub(crate) request_save_raw_to: Option<(RawExportFormat, std::path::PathBuf)>,
+    pub(crate) request_copy_visible: bool,
+    /// Pending `(panel title, visible)` requests, applied in order.
+    pub(crate) panel_visible_requests: Vec<(String, bool)>,
+    /// Pending `(panel title, detached)` requests, applied in order.
+    pub(crate) panel_detach_requests: Vec<(String, bool)>,
+    pub(crate) last_panels: Option<Vec<PanelInfo>>,
+    pub(crate) panel_listeners: Vec<Sender<Vec<PanelInfo>>>,
+}
+
+/// Visibility state of one sub-panel (Traces, Math, FFT, …), keyed by title.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanelInfo {
+    pub title: String,
+    pub visible: bool,
+    pub detached: bool,
 }
 
 impl UiActionController {
@@ -136,8 +152,13 @@ impl UiActionController {
                 request_screenshot_to: None,
                 request_save_raw: None,
                 request_save_raw_to: None,
+                request_copy_visible: false,
                 fft_request: None,
                 fft_listeners: Vec::new(),
+                panel_visible_requests: Vec::new(),
+                panel_detach_requests: Vec::new(),
+                last_panels: None,
+                panel_listeners: Vec::new(),
             })),
         }
     }
@@ -173,6 +194,9 @@ impl UiActionController {
     }
 
     /// Request saving raw data directly to the given path (non-interactive).
+    ///
+    /// If `path` is an existing directory, the file is created inside it,
+    /// named from the configured export filename template.
     pub fn request_save_raw_to_path<P: Into<std::path::PathBuf>>(
         &self,
         fmt: RawExportFormat,
@@ -182,6 +206,14 @@ impl UiActionController {
         inner.request_save_raw_to = Some((fmt, path.into()));
     }
 
+    /// Request copying the visible traces of the primary scope to the
+    /// clipboard as a tab-separated table of their drawn points (the
+    /// snapshot while paused), one row per aligned timestamp.
+    pub fn copy_visible_to_clipboard(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.request_copy_visible = true;
+    }
+
     /// Subscribe to receive the current raw FFT input data (time-domain) for a trace.
     pub fn subscribe_fft_data(&self) -> std::sync::mpsc::Receiver<FFTRawData> {
         let (tx, rx) = std::sync::mpsc::channel();
@@ -201,15 +233,58 @@ impl UiActionController {
         let mut inner = self.inner.lock().unwrap();
         inner.fft_request = Some(FFTDataRequest::NamedTrace(name.into()));
     }
+
+    /// Show or hide the sub-panel with the given title (e.g. "Math", "FFT",
+    /// "Thresholds"; matched case-insensitively).  Unknown titles are ignored.
+    pub fn set_panel_visible<S: Into<String>>(&self, title: S, visible: bool) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.panel_visible_requests.push((title.into(), visible));
+    }
+
+    /// Detach the sub-panel with the given title into its own window (`true`)
+    /// or dock it again (`false`).  Detaching also makes the panel visible.
+    pub fn detach_panel<S: Into<String>>(&self, title: S, detached: bool) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.panel_detach_requests.push((title.into(), detached));
+    }
+
+    /// Get the last published panel visibility list, if any.
+    pub fn get_panels(&self) -> Option<Vec<PanelInfo>> {
+        self.inner.lock().unwrap().last_panels.clone()
+    }
+
+    /// Subscribe to the panel visibility list.  A new list is sent whenever
+    /// any panel is shown, hidden, detached or docked.
+    pub fn subscribe_panels(&self) -> std::sync::mpsc::Receiver<Vec<PanelInfo>> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(last) = inner.last_panels.clone() {
+            let _ = tx.send(last);
+        }
+        inner.panel_listeners.push(tx);
+        rx
+    }
 }
 
 /// Raw export format for saving captured data.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RawExportFormat {
     Csv,
+    Json,
     Parquet,
 }
 
+impl RawExportFormat {
+    /// File extension for this format, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RawExportFormat::Csv => "csv",
+            RawExportFormat::Json => "json",
+            RawExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
 /// Request for FFT raw input data.
 #[derive(Debug, Clone)]
 pub enum FFTDataRequest {
@@ -335,6 +410,16 @@ pub struct TraceControlState {
     pub offset: f64,
 }
 
+/// Values of the visible traces at one x position, published every frame
+/// while [`TracesController::sample_traces_at`] is active.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceSamples {
+    pub x: f64,
+    /// `(trace, value)` in scope order.  Values are as drawn (offset applied);
+    /// `None` when `x` lies outside the trace's data.
+    pub values: Vec<(String, Option<f64>)>,
+}
+
 /// Controller to observe and modify traces UI state (color/visibility/marker selection).
 #[derive(Clone)]
 pub struct TracesController {
@@ -356,10 +441,14 @@ pub(crate) struct TracesCtrlInner {
     pub(crate) detached_request: Option<bool>,
     pub(crate) width_requests: Vec<(String, f32)>,
     pub(crate) style_requests: Vec<(String, LineStyle)>,
+    pub(crate) sample_x: Option<(f64, crate::data::export::SampleMode)>,
     pub(crate) listeners: Vec<Sender<TracesInfo>>,
     pub(crate) panel_listeners: Vec<Sender<TracesPanelState>>,
+    pub(crate) sample_listeners: Vec<Sender<TraceSamples>>,
     pub(crate) last_snapshot: Option<TracesInfo>,
     pub(crate) last_panel_state: Option<TracesPanelState>,
+    pub(crate) last_samples: Option<TraceSamples>,
+    pub(crate) last_ingest: Option<IngestStats>,
 }
 
 impl TracesController {
@@ -380,10 +469,14 @@ impl TracesController {
                 detached_request: None,
                 width_requests: Vec::new(),
                 style_requests: Vec::new(),
+                sample_x: None,
                 listeners: Vec::new(),
                 panel_listeners: Vec::new(),
+                sample_listeners: Vec::new(),
                 last_snapshot: None,
                 last_panel_state: None,
+                last_samples: None,
+                last_ingest: None,
             })),
         }
     }
@@ -507,6 +600,37 @@ impl TracesController {
     pub fn get_last_panel_state(&self) -> Option<TracesPanelState> {
         self.inner.lock().unwrap().last_panel_state.clone()
     }
+
+    /// Sample every visible trace at `x` on each frame until
+    /// [`stop_sampling`](Self::stop_sampling) is called.  Results are
+    /// published as [`TraceSamples`]; calling again moves the position.
+    pub fn sample_traces_at(&self, x: f64, mode: crate::data::export::SampleMode) {
+        self.inner.lock().unwrap().sample_x = Some((x, mode));
+    }
+
+    /// Stop the per-frame sampling started by [`sample_traces_at`](Self::sample_traces_at).
+    pub fn stop_sampling(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.sample_x = None;
+        inner.last_samples = None;
+    }
+
+    /// Subscribe to the per-frame trace samples.
+    pub fn subscribe_samples(&self) -> std::sync::mpsc::Receiver<TraceSamples> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.inner.lock().unwrap().sample_listeners.push(tx);
+        rx
+    }
+
+    /// Get the last published trace samples, if sampling is active.
+    pub fn get_last_samples(&self) -> Option<TraceSamples> {
+        self.inner.lock().unwrap().last_samples.clone()
+    }
+
+    /// Received / dropped / evicted sample counters, updated every frame.
+    pub fn get_ingest_stats(&self) -> Option<IngestStats> {
+        self.inner.lock().unwrap().last_ingest
+    }
 }
 
 /// Controller to manage threshold definitions and subscribe to threshold events.
@@ -518,6 +642,8 @@ pub struct ThresholdController {
 pub(crate) struct ThresholdCtrlInner {
     pub(crate) add_requests: Vec<crate::data::thresholds::ThresholdDef>,
     pub(crate) remove_requests: Vec<String>,
+    /// Pending event-history clears; `None` clears every threshold.
+    pub(crate) clear_event_requests: Vec<Option<String>>,
     pub(crate) listeners: Vec<Sender<crate::data::thresholds::ThresholdEvent>>, // name + events
 }
 
@@ -535,6 +661,12 @@ pub struct ScopeControlState {
     pub legend_position: LegendPosition,
     pub trace_order: Vec<TraceRef>,
     pub scope_type: ScopeType,
+    /// Live samples kept per trace of this scope (`0` = the global
+    /// `max_points`).
+    pub max_points: usize,
+    /// Cap actually in effect for this scope: `max_points`, or the global
+    /// `max_points` when that is `0`.  Published only; ignored in requests.
+    pub effective_max_points: usize,
 }
 
 #[derive(Clone, Debug)]
@@ -553,6 +685,7 @@ pub struct ScopeRequests {
     pub remove_scope: Option<usize>,
     pub reorder: Option<Vec<usize>>, // new order by scope id
     pub save_screenshot: bool,
+    pub set_auto_attach: Option<AutoAttach>,
 }
 
 #[derive(Clone)]
@@ -619,6 +752,11 @@ impl ScopesController {
     pub fn request_replace_scopes(&self, scopes: Vec<ScopeControlState>) {
         self.inner.lock().unwrap().requests.set_scopes = scopes;
     }
+
+    /// Choose the scope that traces appearing from now on are added to.
+    pub fn request_set_auto_attach(&self, target: AutoAttach) {
+        self.inner.lock().unwrap().requests.set_auto_attach = Some(target);
+    }
 }
 
 /// Global liveplot controller (window/frame + high-level actions).
@@ -652,10 +790,28 @@ pub struct LiveplotController {
     pub(crate) inner: Arc<Mutex<LiveplotCtrlInner>>, // crate-visible for UI
 }
 
+/// Which state operation a [`StateIoEvent`] reports on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateIoOp {
+    Save,
+    Load,
+}
+
+/// Completion notice for a state save/load, published to
+/// [`LiveplotController::subscribe_state_io`] subscribers.
+#[derive(Clone, Debug)]
+pub struct StateIoEvent {
+    pub op: StateIoOp,
+    pub path: PathBuf,
+    /// `Err` carries a human-readable reason (I/O or parse error).
+    pub result: Result<(), String>,
+}
+
 pub(crate) struct LiveplotCtrlInner {
     pub(crate) requests: LiveplotRequests,
     pub(crate) last_state: Option<LiveplotState>,
     pub(crate) listeners: Vec<Sender<LiveplotState>>,
+    pub(crate) state_io_listeners: Vec<Sender<StateIoEvent>>,
 }
 
 impl LiveplotController {
@@ -665,6 +821,7 @@ impl LiveplotController {
                 requests: LiveplotRequests::default(),
                 last_state: None,
                 listeners: Vec::new(),
+                state_io_listeners: Vec::new(),
             })),
         }
     }
@@ -700,6 +857,28 @@ impl LiveplotController {
         self.inner.lock().unwrap().requests.load_state = Some(path.into());
     }
 
+    /// Save the full UI state (scopes, panels, trace styles) to `path` on the
+    /// next frame, without any file dialog.  Works with the top bar hidden.
+    /// The outcome is published to [`subscribe_state_io`](Self::subscribe_state_io).
+    pub fn save_state_to<P: Into<PathBuf>>(&self, path: P) {
+        self.request_save_state(path);
+    }
+
+    /// Load and apply UI state from `path` on the next frame, without any file
+    /// dialog.  The outcome is published to
+    /// [`subscribe_state_io`](Self::subscribe_state_io).
+    pub fn load_state_from<P: Into<PathBuf>>(&self, path: P) {
+        self.request_load_state(path);
+    }
+
+    /// Subscribe to completion events of state saves and loads, whether
+    /// requested through this controller or from the UI.
+    pub fn subscribe_state_io(&self) -> std::sync::mpsc::Receiver<StateIoEvent> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.inner.lock().unwrap().state_io_listeners.push(tx);
+        rx
+    }
+
     pub fn request_set_window_size(&self, size: [f32; 2]) {
         self.inner.lock().unwrap().requests.set_window_size = Some(size);
     }
@@ -735,6 +914,7 @@ impl ThresholdController {
             inner: Arc::new(Mutex::new(ThresholdCtrlInner {
                 add_requests: Vec::new(),
                 remove_requests: Vec::new(),
+                clear_event_requests: Vec::new(),
                 listeners: Vec::new(),
             })),
         }
@@ -752,6 +932,15 @@ impl ThresholdController {
         inner.remove_requests.push(name.into());
     }
 
+    /// Request clearing the recorded event history of one threshold, or of all
+    /// thresholds when `name` is `None`.
+    ///
+    /// The definitions themselves are kept; only past events are dropped.
+    pub fn clear_events<S: Into<String>>(&self, name: Option<S>) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.clear_event_requests.push(name.map(Into::into));
+    }
+
     /// Subscribe to threshold events fired by the UI.
     pub fn subscribe(&self) -> std::sync::mpsc::Receiver<crate::data::thresholds::ThresholdEvent> {
         let (tx, rx) = std::sync::mpsc::channel();
@@ -760,3 +949,295 @@ impl ThresholdController {
         rx
     }
 }
+
+/// Automatic measurements of one trace over the visible window of the first
+/// scope showing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceMeasurements {
+    pub trace: TraceRef,
+    pub scope_id: usize,
+    /// Number of samples in the window.
+    pub samples: usize,
+    /// Selected measurements, in the requested order; `None` when the window
+    /// is empty or, for the frequency, holds fewer than two crossings.
+    pub values: Vec<(crate::data::measurement::MeasurementKind, Option<f64>)>,
+}
+
+impl TraceMeasurements {
+    /// Value of one measurement, if selected and available.
+    pub fn get(&self, kind: crate::data::measurement::MeasurementKind) -> Option<f64> {
+        self.values
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .and_then(|(_, v)| *v)
+    }
+}
+
+/// Automatic measurements of all selected traces.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MeasurementSnapshot {
+    pub per_trace: Vec<TraceMeasurements>,
+}
+
+impl MeasurementSnapshot {
+    /// Measurements of the named trace, if it was measured.
+    pub fn trace(&self, name: &str) -> Option<&TraceMeasurements> {
+        self.per_trace.iter().find(|t| t.trace.0 == name)
+    }
+}
+
+/// Controller to observe and place the active two-point measurement and to
+/// read back automatic measurements (Vpp, mean, frequency, ...) per trace.
+///
+/// Publishes a [`MeasurementResult`](crate::data::measurement::MeasurementResult)
+/// for the selected (or first) measurement whenever its points change, and a
+/// [`MeasurementSnapshot`] whenever the automatic measurements change.
+#[derive(Clone)]
+pub struct MeasurementController {
+    pub(crate) inner: Arc<Mutex<MeasurementCtrlInner>>, // crate-visible for UI
+}
+
+pub(crate) struct MeasurementCtrlInner {
+    pub(crate) set_points: Option<([f64; 2], [f64; 2])>,
+    pub(crate) last_result: Option<crate::data::measurement::MeasurementResult>,
+    pub(crate) listeners: Vec<Sender<crate::data::measurement::MeasurementResult>>,
+    pub(crate) snapshot_traces: Option<Vec<TraceRef>>,
+    pub(crate) snapshot_kinds: Vec<crate::data::measurement::MeasurementKind>,
+    pub(crate) last_snapshot: Option<MeasurementSnapshot>,
+    pub(crate) snapshot_listeners: Vec<Sender<MeasurementSnapshot>>,
+}
+
+impl MeasurementController {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(MeasurementCtrlInner {
+                set_points: None,
+                last_result: None,
+                listeners: Vec::new(),
+                snapshot_traces: None,
+                snapshot_kinds: crate::data::measurement::MeasurementKind::ALL.to_vec(),
+                last_snapshot: None,
+                snapshot_listeners: Vec::new(),
+            })),
+        }
+    }
+
+    /// Subscribe to automatic measurement snapshots. The last snapshot, if
+    /// any, is sent immediately.
+    pub fn subscribe_snapshots(&self) -> std::sync::mpsc::Receiver<MeasurementSnapshot> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(last) = inner.last_snapshot.clone() {
+            let _ = tx.send(last);
+        }
+        inner.snapshot_listeners.push(tx);
+        rx
+    }
+
+    /// Get the last published measurement snapshot, if any.
+    pub fn get_last_snapshot(&self) -> Option<MeasurementSnapshot> {
+        self.inner.lock().unwrap().last_snapshot.clone()
+    }
+
+    /// Measure only these traces; `None` (the default) measures every trace
+    /// shown in a scope.
+    pub fn request_set_traces(&self, traces: Option<Vec<TraceRef>>) {
+        self.inner.lock().unwrap().snapshot_traces = traces;
+    }
+
+    /// Choose which measurements the snapshots contain (all by default).
+    pub fn request_set_kinds(&self, kinds: Vec<crate::data::measurement::MeasurementKind>) {
+        self.inner.lock().unwrap().snapshot_kinds = kinds;
+    }
+
+    /// Subscribe to measurement changes. The last result, if any, is sent immediately.
+    pub fn subscribe(
+        &self,
+    ) -> std::sync::mpsc::Receiver<crate::data::measurement::MeasurementResult> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(last) = inner.last_result.clone() {
+            let _ = tx.send(last);
+        }
+        inner.listeners.push(tx);
+        rx
+    }
+
+    /// Get the last published measurement result, if any.
+    pub fn get_last_result(&self) -> Option<crate::data::measurement::MeasurementResult> {
+        self.inner.lock().unwrap().last_result.clone()
+    }
+
+    /// Place both points of the active measurement, in axis units. A
+    /// measurement is created if none exists.
+    pub fn request_set_points(&self, p1: [f64; 2], p2: [f64; 2]) {
+        self.inner.lock().unwrap().set_points = Some((p1, p2));
+    }
+}
+
+impl Default for MeasurementController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Snapshot of one trigger, as published by [`TriggerController`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerInfo {
+    pub name: String,
+    pub target: TraceRef,
+    pub enabled: bool,
+    pub level: f64,
+    pub slope: crate::data::triggers::TriggerSlope,
+    pub single_shot: bool,
+    /// Armed and looking for crossings.
+    pub armed: bool,
+    /// A crossing was seen and the capture is still being filled.
+    pub pending: bool,
+    /// Fired since it was last armed.
+    pub triggered: bool,
+    pub last_trigger_time: Option<f64>,
+}
+
+/// All triggers, sorted by name.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TriggersState {
+    pub triggers: Vec<TriggerInfo>,
+}
+
+/// Controller to add, remove, arm, force and disarm triggers by name and to
+/// observe captures and trigger state, e.g. for synchronised captures in
+/// automated test rigs.
+#[derive(Clone)]
+pub struct TriggerController {
+    pub(crate) inner: Arc<Mutex<TriggerCtrlInner>>, // crate-visible for UI
+}
+
+/// Trigger addition or removal requested through a [`TriggerController`].
+pub(crate) enum TriggerOp {
+    Add(crate::data::triggers::Trigger),
+    Remove(String),
+}
+
+pub(crate) struct TriggerCtrlInner {
+    /// Additions and removals in request order, kept until a triggers panel
+    /// applies them.
+    pub(crate) ops: Vec<TriggerOp>,
+    pub(crate) commands: Vec<(String, crate::data::triggers::TriggerCommand)>,
+    pub(crate) listeners: Vec<Sender<crate::data::triggers::TriggerCapture>>,
+    pub(crate) last_state: Option<TriggersState>,
+    pub(crate) state_listeners: Vec<Sender<TriggersState>>,
+}
+
+impl TriggerController {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(TriggerCtrlInner {
+                ops: Vec::new(),
+                commands: Vec::new(),
+                listeners: Vec::new(),
+                last_state: None,
+                state_listeners: Vec::new(),
+            })),
+        }
+    }
+
+    /// Add a trigger, replacing any trigger of the same name.  Additions and
+    /// removals are applied before arm/force/disarm requests of the same
+    /// frame, so a trigger can be added and armed right away.
+    pub fn add_trigger(&self, trigger: crate::data::triggers::Trigger) {
+        self.inner.lock().unwrap().ops.push(TriggerOp::Add(trigger));
+    }
+
+    /// Remove the named trigger.
+    pub fn remove_trigger<S: Into<String>>(&self, name: S) {
+        self.inner
+            .lock()
+            .unwrap()
+            .ops
+            .push(TriggerOp::Remove(name.into()));
+    }
+
+    fn push(&self, name: impl Into<String>, cmd: crate::data::triggers::TriggerCommand) {
+        self.inner.lock().unwrap().commands.push((name.into(), cmd));
+    }
+
+    /// Enable the named trigger and start looking for crossings, resuming a
+    /// stream paused by its previous capture.
+    pub fn arm<S: Into<String>>(&self, name: S) {
+        self.push(name, crate::data::triggers::TriggerCommand::Arm);
+    }
+
+    /// Fire the named trigger at the newest sample of its target trace.
+    pub fn force<S: Into<String>>(&self, name: S) {
+        self.push(name, crate::data::triggers::TriggerCommand::Force);
+    }
+
+    /// Stop the named trigger from looking for crossings.
+    pub fn disarm<S: Into<String>>(&self, name: S) {
+        self.push(name, crate::data::triggers::TriggerCommand::Disarm);
+    }
+
+    /// Subscribe to completed captures.
+    pub fn subscribe(&self) -> std::sync::mpsc::Receiver<crate::data::triggers::TriggerCapture> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.inner.lock().unwrap().listeners.push(tx);
+        rx
+    }
+
+    /// Subscribe to trigger state changes.  The last state, if any, is sent
+    /// immediately.
+    pub fn subscribe_state(&self) -> std::sync::mpsc::Receiver<TriggersState> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(last) = inner.last_state.clone() {
+            let _ = tx.send(last);
+        }
+        inner.state_listeners.push(tx);
+        rx
+    }
+
+    /// Get the last published trigger state, if any.
+    pub fn get_last_state(&self) -> Option<TriggersState> {
+        self.inner.lock().unwrap().last_state.clone()
+    }
+}
+
+impl Default for TriggerController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Controller to manage math traces from code.
+#[derive(Clone)]
+pub struct MathController {
+    pub(crate) inner: Arc<Mutex<MathCtrlInner>>, // crate-visible for UI
+}
+
+pub(crate) struct MathCtrlInner {
+    pub(crate) reset_state: Vec<String>,
+}
+
+impl MathController {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(MathCtrlInner {
+                reset_state: Vec::new(),
+            })),
+        }
+    }
+
+    /// Discard the runtime state (integrator sum, filter history, min/max,
+    /// moving-average window) of the named math trace and recompute it from
+    /// the current input data.  The definition is left unchanged.
+    pub fn reset_state<S: Into<String>>(&self, name: S) {
+        self.inner.lock().unwrap().reset_state.push(name.into());
+    }
+}
+
+impl Default for MathController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
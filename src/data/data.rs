@@ -1,6 +1,6 @@
 //! LivePlotData: a view struct combining scope data and traces.
 
-use crate::data::scope::ScopeData;
+use crate::data::scope::{AutoAttach, ScopeData};
 use crate::data::traces::{TraceData, TraceRef, TracesCollection};
 use crate::events::EventController;
 use std::collections::HashMap;
@@ -36,6 +36,15 @@ pub struct LivePlotRequests {
     pub clear_measurements: bool,
     pub clear_thresholds: bool,
     pub clear_triggers: bool,
+    /// Step the snapping trace of the active measurement through the scope's
+    /// trace order (`1` = next, `-1` = previous, `0` = no request).
+    pub cycle_measurement_trace: isize,
+    /// Place both points of the active measurement (axis units).
+    pub set_measurement_points: Option<([f64; 2], [f64; 2])>,
+    /// Arm/force/disarm requests by trigger name, in arrival order.
+    pub trigger_commands: Vec<(String, crate::data::triggers::TriggerCommand)>,
+    /// Math traces whose runtime state should be reset, by name.
+    pub reset_math_state: Vec<TraceRef>,
 }
 
 impl Default for LivePlotRequests {
@@ -50,6 +59,10 @@ impl Default for LivePlotRequests {
             clear_measurements: false,
             clear_thresholds: false,
             clear_triggers: false,
+            cycle_measurement_trace: 0,
+            set_measurement_points: None,
+            trigger_commands: Vec::new(),
+            reset_math_state: Vec::new(),
         }
     }
 }
@@ -72,7 +85,9 @@ impl<'a> LivePlotData<'a> {
             let scope = &mut **scope;
             scope.paused = true;
         }
-        self.traces.take_snapshot();
+        if self.scope_data.iter().any(|scope| scope.snapshot_on_pause) {
+            self.traces.take_snapshot();
+        }
     }
 
     pub fn resume_all(&mut self) {
@@ -83,8 +98,11 @@ impl<'a> LivePlotData<'a> {
         self.traces.clear_snapshot();
     }
 
+    /// Clear all trace data, measurements, thresholds, triggers and math
+    /// state.  Trace buffers are emptied at the start of the next frame, before
+    /// anything is drawn (see [`TracesCollection::request_clear`]).
     pub fn request_clear_all(&mut self) {
-        self.traces.clear_all();
+        self.traces.request_clear();
         for scope in self.scope_data.iter_mut() {
             let scope = &mut **scope;
             scope.clicked_point = None;
@@ -104,14 +122,16 @@ impl<'a> LivePlotData<'a> {
     }
 
     pub fn pause(&mut self, scope_id: usize) {
+        let mut wants_snapshot = false;
         for scope in self.scope_data.iter_mut() {
             let scope = &mut **scope;
             if scope.id == scope_id {
                 scope.paused = true;
+                wants_snapshot = scope.snapshot_on_pause;
                 break;
             }
         }
-        if !self.traces.has_snapshot() {
+        if wants_snapshot && !self.traces.has_snapshot() {
             self.traces.take_snapshot();
         }
     }
@@ -130,7 +150,9 @@ impl<'a> LivePlotData<'a> {
     }
 
     pub fn are_all_paused(&self) -> bool {
-        self.scope_data.iter().all(|scope| (**scope).paused) && self.traces.has_snapshot()
+        self.scope_data
+            .iter()
+            .all(|scope| scope.is_frozen(self.traces))
     }
 
     pub fn get_trace_or_new(&mut self, name: &TraceRef) -> &mut TraceData {
@@ -151,14 +173,36 @@ impl<'a> LivePlotData<'a> {
         traces
     }
 
+    /// Delete a trace and drop it from every scope.  Math traces using it as
+    /// an input and thresholds targeting it react on their next update.
     pub fn remove_trace(&mut self, name: &TraceRef) {
         self.traces.remove_trace(name);
         for scope in self.scope_data.iter_mut() {
-            let scope = &mut **scope;
-            scope.trace_order.retain(|n| n != name);
+            scope.remove_trace(name);
+        }
+        if self
+            .traces
+            .hover_trace
+            .as_ref()
+            .is_some_and(|h| h.contains(name))
+        {
+            self.traces.hover_trace = None;
         }
     }
 
+    /// Freeze the current data of `name` into a new static trace (see
+    /// [`TracesCollection::duplicate_as_static`]) and show it, right after the
+    /// original, in every scope that shows the original.
+    pub fn duplicate_as_static(&mut self, name: &TraceRef) -> Option<TraceRef> {
+        let copy = self.traces.duplicate_as_static(name)?;
+        for scope in self.scope_data.iter_mut() {
+            if let Some(pos) = scope.trace_order.iter().position(|t| t == name) {
+                scope.trace_order.insert(pos + 1, copy.clone());
+            }
+        }
+        Some(copy)
+    }
+
     pub fn get_drawn_points(&self, name: &TraceRef, scope_id: usize) -> Option<Vec<[f64; 2]>> {
         self.scope_data.iter().find_map(|scope| {
             let scope = &**scope;
@@ -267,6 +311,13 @@ impl<'a> LivePlotData<'a> {
         }
     }
 
+    /// Ask every scope to fit all of its data once on the next draw.
+    pub fn request_fit_all_data(&mut self) {
+        for scope in self.scope_data.iter_mut() {
+            scope.fit_all_requested = true;
+        }
+    }
+
     pub fn fit_all_y_bounds(&mut self) {
         for scope in self.scope_data.iter_mut() {
             let scope = &mut **scope;
@@ -281,4 +332,21 @@ impl<'a> LivePlotData<'a> {
     pub fn primary_scope_mut(&mut self) -> Option<&mut ScopeData> {
         self.scope_data.first_mut().map(|scope| &mut **scope)
     }
+
+    /// Scope that new traces are attached to under `target`.
+    ///
+    /// Falls back to the primary scope when the requested scope does not
+    /// exist (e.g. it was removed or renamed), so new traces are never lost
+    /// silently; `None` only for [`AutoAttach::Disabled`] or no scopes.
+    pub fn auto_attach_scope_mut(&mut self, target: &AutoAttach) -> Option<&mut ScopeData> {
+        let idx = match target {
+            AutoAttach::Disabled => return None,
+            AutoAttach::Primary => None,
+            AutoAttach::ScopeId(id) => self.scope_data.iter().position(|s| s.id == *id),
+            AutoAttach::ScopeName(name) => self.scope_data.iter().position(|s| &s.name == name),
+        };
+        self.scope_data
+            .get_mut(idx.unwrap_or(0))
+            .map(|scope| &mut **scope)
+    }
 }
@@ -1,4 +1,5 @@
-//! Data export utilities: align multi-trace time series by timestamp tolerance and write CSV.
+//! Data export utilities: align multi-trace time series by timestamp tolerance and write CSV,
+//! JSON or Parquet.
 
 use std::collections::HashMap;
 use std::io::{self, Write};
@@ -6,6 +7,8 @@ use std::path::Path;
 #[cfg(feature = "parquet")]
 use std::sync::Arc;
 
+#[cfg(feature = "fft")]
+use crate::data::fft::SpectrumMeta;
 use crate::data::traces::TraceRef;
 
 /// A single aligned row: timestamp in seconds and one value per trace (None if missing).
@@ -64,14 +67,54 @@ pub fn align_series(
     out
 }
 
+/// Timestamp column(s) written at the start of each CSV row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CsvTimestamp {
+    /// `timestamp_seconds` as a float with 9 decimal places.
+    #[default]
+    Seconds,
+    /// `timestamp_micros` as an integer, matching the ingest format and
+    /// avoiding f64 formatting round-off in downstream tools.
+    Micros,
+    /// Both columns: `timestamp_seconds,timestamp_micros`.
+    Both,
+}
+
+impl CsvTimestamp {
+    pub const ALL: [CsvTimestamp; 3] = [Self::Seconds, Self::Micros, Self::Both];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Seconds => "Seconds",
+            Self::Micros => "Microseconds",
+            Self::Both => "Both",
+        }
+    }
+}
+
 /// Write aligned rows to CSV with the header: `timestamp_seconds,<trace1>,<trace2>,...`.
 pub fn write_aligned_rows_csv<W: Write>(
+    w: W,
+    trace_order: &[TraceRef],
+    rows: &[AlignedRow],
+) -> io::Result<()> {
+    write_aligned_rows_csv_with(w, trace_order, rows, CsvTimestamp::Seconds)
+}
+
+/// Like [`write_aligned_rows_csv`], with a choice of timestamp column(s).
+/// Microseconds are rounded to the nearest integer.
+pub fn write_aligned_rows_csv_with<W: Write>(
     mut w: W,
     trace_order: &[TraceRef],
     rows: &[AlignedRow],
+    timestamps: CsvTimestamp,
 ) -> io::Result<()> {
     // Header
-    write!(w, "timestamp_seconds")?;
+    match timestamps {
+        CsvTimestamp::Seconds => write!(w, "timestamp_seconds")?,
+        CsvTimestamp::Micros => write!(w, "timestamp_micros")?,
+        CsvTimestamp::Both => write!(w, "timestamp_seconds,timestamp_micros")?,
+    }
     for name in trace_order {
         write!(w, ",{}", name.0)?;
     }
@@ -79,8 +122,13 @@ pub fn write_aligned_rows_csv<W: Write>(
 
     // Rows
     for (t, vals) in rows.iter() {
+        let us = (*t * 1e6).round() as i64;
         // 9 decimal places as in previous CSV
-        write!(w, "{:.9}", *t)?;
+        match timestamps {
+            CsvTimestamp::Seconds => write!(w, "{:.9}", *t)?,
+            CsvTimestamp::Micros => write!(w, "{}", us)?,
+            CsvTimestamp::Both => write!(w, "{:.9},{}", *t, us)?,
+        }
         for v in vals.iter() {
             if let Some(y) = v {
                 write!(w, ",{}", y)?;
@@ -93,16 +141,479 @@ pub fn write_aligned_rows_csv<W: Write>(
     Ok(())
 }
 
+/// Aligned rows as tab-separated text with the header
+/// `timestamp_seconds<TAB><trace1><TAB>...`, e.g. for pasting into a
+/// spreadsheet.  Missing values are left empty.
+pub fn aligned_rows_tsv(trace_order: &[TraceRef], rows: &[AlignedRow]) -> String {
+    let mut out = String::from("timestamp_seconds");
+    for name in trace_order {
+        out.push('\t');
+        out.push_str(&name.0);
+    }
+    out.push('\n');
+    for (t, vals) in rows {
+        out.push_str(&format!("{:.9}", t));
+        for v in vals {
+            out.push('\t');
+            if let Some(y) = v {
+                out.push_str(&y.to_string());
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Read a CSV written by [`write_aligned_rows_csv`] back into one
+/// `(name, [timestamp_sec, value])` series per column.  Empty or non-numeric
+/// cells are skipped; rows without a numeric timestamp are ignored.  A
+/// leading `timestamp_micros` column is converted to seconds; when both
+/// timestamp columns are present the seconds column is used.
+pub fn read_aligned_csv<R: io::BufRead>(r: R) -> io::Result<Vec<(String, Vec<[f64; 2]>)>> {
+    let mut lines = r.lines();
+    let header = lines
+        .next()
+        .transpose()?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "empty CSV"))?;
+    let columns: Vec<&str> = header.trim_end().split(',').map(str::trim).collect();
+    let t_scale = if columns.first() == Some(&"timestamp_micros") {
+        1e-6
+    } else {
+        1.0
+    };
+    let skip = if columns.get(1) == Some(&"timestamp_micros") {
+        2
+    } else {
+        1
+    };
+    let mut series: Vec<(String, Vec<[f64; 2]>)> = columns
+        .iter()
+        .skip(skip)
+        .map(|name| (name.trim().to_string(), Vec::new()))
+        .collect();
+    if series.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "CSV has no value columns",
+        ));
+    }
+    for line in lines {
+        let line = line?;
+        let mut cells = line.trim_end().split(',');
+        let Some(Ok(t)) = cells.next().map(|c| c.trim().parse::<f64>()) else {
+            continue;
+        };
+        let t = t * t_scale;
+        if skip == 2 {
+            cells.next();
+        }
+        for ((_, points), cell) in series.iter_mut().zip(cells) {
+            if let Ok(v) = cell.trim().parse::<f64>() {
+                points.push([t, v]);
+            }
+        }
+    }
+    Ok(series)
+}
+
+/// How a trace is sampled at a timestamp that need not coincide with a sample.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SampleMode {
+    /// Value of the sample closest in time.
+    Nearest,
+    /// Linear interpolation between the two neighbouring samples.
+    #[default]
+    Linear,
+}
+
+/// Sample a sorted `[timestamp_sec, value]` series at `t`.
+///
+/// Returns `None` when `t` lies outside the series' time span (no
+/// extrapolation) or the series is empty.
+pub fn sample_at(data: &[[f64; 2]], t: f64, mode: SampleMode) -> Option<f64> {
+    let (first, last) = (data.first()?, data.last()?);
+    if !t.is_finite() || t < first[0] || t > last[0] {
+        return None;
+    }
+    let i = data.partition_point(|p| p[0] < t);
+    if i < data.len() && data[i][0] == t {
+        return Some(data[i][1]);
+    }
+    // first[0] < t <= last[0], so both neighbours exist.
+    let (a, b) = (data[i - 1], data[i]);
+    Some(match mode {
+        SampleMode::Nearest => {
+            if t - a[0] <= b[0] - t {
+                a[1]
+            } else {
+                b[1]
+            }
+        }
+        SampleMode::Linear => a[1] + (b[1] - a[1]) * (t - a[0]) / (b[0] - a[0]),
+    })
+}
+
+/// Per-trace values at two cursor timestamps: `(trace, value_at_p1, value_at_p2)`.
+pub type CursorValueRow = (TraceRef, Option<f64>, Option<f64>);
+
+/// Sample every trace in `trace_order` at `t1` and `t2`.
+///
+/// [`SampleMode::Nearest`] reads the row of [`align_series`] (joined with
+/// `tol`) closest to each cursor, so the values match an aligned export; a
+/// trace without a sample in that row reads `None`.  [`SampleMode::Linear`]
+/// interpolates each trace on its own.
+pub fn sample_series_at(
+    trace_order: &[TraceRef],
+    series: &HashMap<TraceRef, Vec<[f64; 2]>>,
+    t1: f64,
+    t2: f64,
+    mode: SampleMode,
+    tol: f64,
+) -> Vec<CursorValueRow> {
+    let rows = match mode {
+        SampleMode::Nearest => align_series(trace_order, series, tol),
+        SampleMode::Linear => Vec::new(),
+    };
+    let nearest_row = |t: f64| -> Option<&AlignedRow> {
+        let (first, last) = (rows.first()?, rows.last()?);
+        if !t.is_finite() || t < first.0 - tol || t > last.0 + tol {
+            return None;
+        }
+        let i = rows.partition_point(|r| r.0 < t);
+        match (i.checked_sub(1).map(|j| &rows[j]), rows.get(i)) {
+            (Some(a), Some(b)) if t - a.0 <= b.0 - t => Some(a),
+            (_, Some(b)) => Some(b),
+            (a, None) => a,
+        }
+    };
+    let (row1, row2) = (nearest_row(t1), nearest_row(t2));
+    trace_order
+        .iter()
+        .enumerate()
+        .map(|(i, name)| match mode {
+            SampleMode::Nearest => (
+                name.clone(),
+                row1.and_then(|r| r.1[i]),
+                row2.and_then(|r| r.1[i]),
+            ),
+            SampleMode::Linear => {
+                let data = series.get(name).map(|v| v.as_slice()).unwrap_or(&[][..]);
+                (
+                    name.clone(),
+                    sample_at(data, t1, mode),
+                    sample_at(data, t2, mode),
+                )
+            }
+        })
+        .collect()
+}
+
+/// Write cursor values to CSV with the header `trace,value_at_p1,value_at_p2,delta`.
+///
+/// Missing values (cursor outside a trace's data) are written as empty fields.
+pub fn write_cursor_values_csv<W: Write>(mut w: W, rows: &[CursorValueRow]) -> io::Result<()> {
+    writeln!(w, "trace,value_at_p1,value_at_p2,delta")?;
+    let fmt = |v: Option<f64>| v.map(|y| y.to_string()).unwrap_or_default();
+    for (name, v1, v2) in rows {
+        let delta = v1.zip(*v2).map(|(a, b)| b - a);
+        writeln!(w, "{},{},{},{}", name.0, fmt(*v1), fmt(*v2), fmt(delta))?;
+    }
+    Ok(())
+}
+
+/// One spectrum for [`write_spectrum_csv`]: trace, computation parameters (if
+/// known) and `[frequency_hz, value]` points.
+#[cfg(feature = "fft")]
+pub type SpectrumSeries = (TraceRef, Option<SpectrumMeta>, Vec<[f64; 2]>);
+
+/// Write spectra side by side as CSV, one `<trace>_hz,<trace>` column pair per
+/// trace.  Shorter spectra leave their cells empty.
+///
+/// The file starts with `#` comment lines naming the value quantity and, per
+/// trace, the window, FFT size, zero-padding and sample rate used.
+#[cfg(feature = "fft")]
+pub fn write_spectrum_csv<W: Write>(
+    mut w: W,
+    value_label: &str,
+    spectra: &[SpectrumSeries],
+) -> io::Result<()> {
+    writeln!(w, "# FFT spectrum, values: {}", value_label)?;
+    for (name, meta, _) in spectra {
+        match meta {
+            Some(m) => writeln!(
+                w,
+                "# {}: window={}, N={}, padded={}, sample_rate_hz={}, scale={}",
+                name.0,
+                m.window.description(),
+                m.fft_size,
+                m.padded_size,
+                m.sample_rate,
+                m.scale.label()
+            )?,
+            None => writeln!(w, "# {}: parameters unknown", name.0)?,
+        }
+    }
+
+    let header: Vec<String> = spectra
+        .iter()
+        .map(|(name, _, _)| format!("{0}_hz,{0}", name.0))
+        .collect();
+    writeln!(w, "{}", header.join(","))?;
+
+    let rows = spectra.iter().map(|(_, _, s)| s.len()).max().unwrap_or(0);
+    for i in 0..rows {
+        let cells: Vec<String> = spectra
+            .iter()
+            .map(|(_, _, s)| match s.get(i) {
+                Some([f, v]) => format!("{},{}", f, v),
+                None => ",".to_string(),
+            })
+            .collect();
+        writeln!(w, "{}", cells.join(","))?;
+    }
+    Ok(())
+}
+
 /// Convenience: align series by tolerance and write to a CSV file at `path`.
 pub fn write_csv_aligned_path(
     path: &Path,
     trace_order: &[TraceRef],
     series: &HashMap<TraceRef, Vec<[f64; 2]>>,
     tol: f64,
+    timestamps: CsvTimestamp,
 ) -> io::Result<()> {
     let rows = align_series(trace_order, series, tol);
     let mut f = std::fs::File::create(path)?;
-    write_aligned_rows_csv(&mut f, trace_order, &rows)
+    write_aligned_rows_csv_with(&mut f, trace_order, &rows, timestamps)
+}
+
+/// Write aligned rows as a JSON object:
+///
+/// ```json
+/// {
+///   "metadata": {
+///     "traces": ["a", "b"],
+///     "units": ["V", null],
+///     "exported_at": "2024-01-01T12:00:00+01:00",
+///     "columns": ["timestamp_seconds", "a", "b"]
+///   },
+///   "rows": [[0.0, 1.0, null], ...]
+/// }
+/// ```
+///
+/// `units` holds one entry per trace in `trace_order`; missing values and
+/// non-finite numbers are written as `null`.
+pub fn write_aligned_rows_json<W: Write>(
+    w: W,
+    trace_order: &[TraceRef],
+    units: &[Option<String>],
+    rows: &[AlignedRow],
+    exported_at: chrono::DateTime<chrono::Local>,
+) -> io::Result<()> {
+    let traces: Vec<&str> = trace_order.iter().map(|t| t.0.as_str()).collect();
+    let units: Vec<Option<&str>> = (0..trace_order.len())
+        .map(|i| units.get(i).and_then(|u| u.as_deref()))
+        .collect();
+    let columns: Vec<&str> = std::iter::once("timestamp_seconds")
+        .chain(traces.iter().copied())
+        .collect();
+    let rows: Vec<serde_json::Value> = rows
+        .iter()
+        .map(|(t, vals)| {
+            std::iter::once(serde_json::Value::from(*t))
+                .chain(vals.iter().map(|v| serde_json::Value::from(*v)))
+                .collect()
+        })
+        .collect();
+    let doc = serde_json::json!({
+        "metadata": {
+            "traces": traces,
+            "units": units,
+            "exported_at": exported_at.to_rfc3339(),
+            "columns": columns,
+        },
+        "rows": rows,
+    });
+    let mut w = io::BufWriter::new(w);
+    serde_json::to_writer(&mut w, &doc)?;
+    w.flush()
+}
+
+/// Convenience: align series by tolerance and write to a JSON file at `path`
+/// (see [`write_aligned_rows_json`]), stamped with the current local time.
+pub fn write_json_aligned_path(
+    path: &Path,
+    trace_order: &[TraceRef],
+    units: &[Option<String>],
+    series: &HashMap<TraceRef, Vec<[f64; 2]>>,
+    tol: f64,
+) -> io::Result<()> {
+    let rows = align_series(trace_order, series, tol);
+    let f = std::fs::File::create(path)?;
+    write_aligned_rows_json(f, trace_order, units, &rows, chrono::Local::now())
+}
+
+/// Drop samples outside `[t_min, t_max]` from every series, to export only a
+/// time region.
+pub fn clip_series(series: &mut HashMap<TraceRef, Vec<[f64; 2]>>, (t_min, t_max): (f64, f64)) {
+    for pts in series.values_mut() {
+        pts.retain(|p| p[0] >= t_min && p[0] <= t_max);
+    }
+}
+
+/// Default tolerance (seconds) within which samples of different traces are
+/// joined into one row by [`align_series`].
+pub const DEFAULT_JOIN_TOLERANCE: f64 = 1e-9;
+
+/// Default export filename template (without extension).
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "snapshot";
+
+/// Expand an export filename template.  Recognised tokens are `{timestamp}`
+/// (local time, `YYYYmmdd-HHMMSS`), `{scope}` and `{trace_count}`; unknown
+/// tokens are kept verbatim.  Characters that are unsafe in file names are
+/// replaced by `_` in the substituted values.
+pub fn expand_filename_template(
+    template: &str,
+    scope: &str,
+    trace_count: usize,
+    now: chrono::DateTime<chrono::Local>,
+) -> String {
+    let safe = |s: &str| -> String {
+        s.chars()
+            .map(|c| {
+                if c.is_control() || "/\\:*?\"<>|".contains(c) {
+                    '_'
+                } else {
+                    c
+                }
+            })
+            .collect()
+    };
+    template
+        .replace("{timestamp}", &now.format("%Y%m%d-%H%M%S").to_string())
+        .replace("{scope}", &safe(scope))
+        .replace("{trace_count}", &trace_count.to_string())
+}
+
+/// Write `points` as a NumPy `.npy` (format 1.0) `float64` array of shape
+/// `(2, N)`: row 0 holds the timestamps, row 1 the values.
+#[cfg(feature = "npz")]
+pub fn write_npy<W: Write>(mut w: W, points: &[[f64; 2]]) -> io::Result<()> {
+    let dict = format!(
+        "{{'descr': '<f8', 'fortran_order': False, 'shape': (2, {}), }}",
+        points.len()
+    );
+    // magic (6) + version (2) + header length (2) + dict + padding + '\n'
+    // must be a multiple of 64.
+    let unpadded = 10 + dict.len() + 1;
+    let header_len = dict.len() + 1 + (64 - unpadded % 64) % 64;
+    let header_len_u16 = u16::try_from(header_len)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "npy header too long"))?;
+    w.write_all(b"\x93NUMPY\x01\x00")?;
+    w.write_all(&header_len_u16.to_le_bytes())?;
+    write!(w, "{:<width$}", dict, width = header_len - 1)?;
+    w.write_all(b"\n")?;
+    for p in points {
+        w.write_all(&p[0].to_le_bytes())?;
+    }
+    for p in points {
+        w.write_all(&p[1].to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// Write every trace in `trace_order` as a `(2, N)` array (see [`write_npy`])
+/// into an uncompressed `.npz` archive keyed by trace name, readable with
+/// `numpy.load`.  Traces missing from `series` are written as empty arrays.
+#[cfg(feature = "npz")]
+pub fn write_npz<W: Write>(
+    mut w: W,
+    trace_order: &[TraceRef],
+    series: &HashMap<TraceRef, Vec<[f64; 2]>>,
+) -> io::Result<()> {
+    let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "npz archive exceeds 4 GiB");
+    // Local headers and file data first, then the central directory.
+    let mut offset: u32 = 0;
+    let mut central: Vec<u8> = Vec::new();
+    for name in trace_order {
+        let mut npy = Vec::new();
+        write_npy(&mut npy, series.get(name).map_or(&[][..], |v| v.as_slice()))?;
+        let file_name = format!("{}.npy", name.0);
+        let name_len = u16::try_from(file_name.len()).map_err(|_| too_large())?;
+        let size = u32::try_from(npy.len()).map_err(|_| too_large())?;
+        let crc = crc32(&npy);
+
+        // Shared fields: version, flags, method (stored), DOS time/date
+        // (1980-01-01), CRC, compressed and uncompressed size, name length.
+        let mut common = Vec::with_capacity(26);
+        common.extend_from_slice(&20u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0x21u16.to_le_bytes());
+        common.extend_from_slice(&crc.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&name_len.to_le_bytes());
+
+        w.write_all(&0x0403_4b50u32.to_le_bytes())?;
+        w.write_all(&common)?;
+        w.write_all(&0u16.to_le_bytes())?; // extra field length
+        w.write_all(file_name.as_bytes())?;
+        w.write_all(&npy)?;
+
+        central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central.extend_from_slice(&common);
+        central.extend_from_slice(&[0u8; 12]); // extra, comment, disk, attributes
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(file_name.as_bytes());
+
+        offset = (30 + u32::from(name_len))
+            .checked_add(size)
+            .and_then(|n| offset.checked_add(n))
+            .ok_or_else(too_large)?;
+    }
+    let entries = u16::try_from(trace_order.len()).map_err(|_| too_large())?;
+    let central_len = u32::try_from(central.len()).map_err(|_| too_large())?;
+    w.write_all(&central)?;
+    w.write_all(&0x0605_4b50u32.to_le_bytes())?;
+    w.write_all(&[0u8; 4])?; // disk numbers
+    w.write_all(&entries.to_le_bytes())?;
+    w.write_all(&entries.to_le_bytes())?;
+    w.write_all(&central_len.to_le_bytes())?;
+    w.write_all(&offset.to_le_bytes())?;
+    w.write_all(&0u16.to_le_bytes())?; // comment length
+    Ok(())
+}
+
+/// Convenience: write the traces as a NumPy `.npz` archive at `path` (see [`write_npz`]).
+#[cfg(feature = "npz")]
+pub fn write_npz_path(
+    path: &Path,
+    trace_order: &[TraceRef],
+    series: &HashMap<TraceRef, Vec<[f64; 2]>>,
+) -> io::Result<()> {
+    let mut w = io::BufWriter::new(std::fs::File::create(path)?);
+    write_npz(&mut w, trace_order, series)?;
+    w.flush()
+}
+
+/// CRC-32 (IEEE) as required by the zip container.
+#[cfg(feature = "npz")]
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in data {
+        crc ^= u32::from(b);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
 }
 
 /// Convenience: align series by tolerance and write to a Parquet file at `path` (feature-gated).
@@ -0,0 +1,358 @@
+//! Small formula language for [`MathKind::Expr`](crate::data::math::MathKind::Expr).
+//!
+//! An expression combines trace values with numbers, `+ - * / % ^`,
+//! parentheses, the constant `pi` and the functions in [`FUNCTIONS`], e.g.
+//! `(a + 2*b) / c` or `sqrt(i^2 + q^2)`.  Every other identifier names a
+//! trace.  Trace names that are not plain identifiers (such as `trace-1` or
+//! `left sensor`) are written in double quotes: `"trace-1" * 0.5`.
+//!
+//! Parsing happens once per evaluation pass; [`ParsedExpr::eval`] is then
+//! called per sample with the input values in [`ParsedExpr::variables`] order.
+
+use std::fmt;
+
+/// Functions callable from an expression, with their number of arguments.
+pub const FUNCTIONS: &[(&str, usize)] = &[
+    ("abs", 1),
+    ("sqrt", 1),
+    ("exp", 1),
+    ("ln", 1),
+    ("log", 1),
+    ("log10", 1),
+    ("sin", 1),
+    ("cos", 1),
+    ("tan", 1),
+    ("asin", 1),
+    ("acos", 1),
+    ("atan", 1),
+    ("floor", 1),
+    ("ceil", 1),
+    ("round", 1),
+    ("atan2", 2),
+    ("min", 2),
+    ("max", 2),
+    ("pow", 2),
+];
+
+/// Parse failure with the byte offset where it was detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExprError {
+    pub pos: usize,
+    pub message: String,
+}
+
+impl ExprError {
+    fn new(pos: usize, message: impl Into<String>) -> Self {
+        Self {
+            pos,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at column {}", self.message, self.pos + 1)
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Num(f64),
+    /// Index into [`ParsedExpr::variables`].
+    Var(usize),
+    Neg(Box<Node>),
+    Bin(char, Box<Node>, Box<Node>),
+    Call(&'static str, Vec<Node>),
+}
+
+/// A parsed expression ready for per-sample evaluation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedExpr {
+    root: Node,
+    vars: Vec<String>,
+}
+
+impl ParsedExpr {
+    pub fn parse(src: &str) -> Result<Self, ExprError> {
+        let mut parser = Parser {
+            toks: lex(src)?,
+            i: 0,
+            vars: Vec::new(),
+        };
+        if parser.peek() == &Tok::End {
+            return Err(ExprError::new(0, "empty expression"));
+        }
+        let root = parser.expr()?;
+        if parser.peek() != &Tok::End {
+            return Err(ExprError::new(parser.pos(), "unexpected input"));
+        }
+        Ok(Self {
+            root,
+            vars: parser.vars,
+        })
+    }
+
+    /// Trace names referenced by the expression, in first-use order.
+    pub fn variables(&self) -> &[String] {
+        &self.vars
+    }
+
+    /// Evaluate with `values[i]` bound to `variables()[i]`.
+    pub fn eval(&self, values: &[f64]) -> f64 {
+        eval(&self.root, values)
+    }
+}
+
+fn eval(node: &Node, values: &[f64]) -> f64 {
+    match node {
+        Node::Num(v) => *v,
+        Node::Var(i) => values.get(*i).copied().unwrap_or(f64::NAN),
+        Node::Neg(a) => -eval(a, values),
+        Node::Bin(op, a, b) => {
+            let (a, b) = (eval(a, values), eval(b, values));
+            match op {
+                '+' => a + b,
+                '-' => a - b,
+                '*' => a * b,
+                '/' => a / b,
+                '%' => a % b,
+                _ => a.powf(b),
+            }
+        }
+        Node::Call(name, args) => {
+            let x = eval(&args[0], values);
+            let y = || eval(&args[1], values);
+            match *name {
+                "abs" => x.abs(),
+                "sqrt" => x.sqrt(),
+                "exp" => x.exp(),
+                "ln" | "log" => x.ln(),
+                "log10" => x.log10(),
+                "sin" => x.sin(),
+                "cos" => x.cos(),
+                "tan" => x.tan(),
+                "asin" => x.asin(),
+                "acos" => x.acos(),
+                "atan" => x.atan(),
+                "floor" => x.floor(),
+                "ceil" => x.ceil(),
+                "round" => x.round(),
+                "atan2" => x.atan2(y()),
+                "min" => x.min(y()),
+                "max" => x.max(y()),
+                _ => x.powf(y()),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Num(f64),
+    Ident(String),
+    Quoted(String),
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+    End,
+}
+
+fn lex(src: &str) -> Result<Vec<(Tok, usize)>, ExprError> {
+    let mut toks = Vec::new();
+    let mut chars = src.char_indices().peekable();
+    while let Some(&(pos, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '0'..='9' | '.' => {
+                let mut end = pos;
+                let mut prev = ' ';
+                while let Some(&(i, d)) = chars.peek() {
+                    let exp_sign = (d == '+' || d == '-') && matches!(prev, 'e' | 'E');
+                    if d.is_ascii_digit() || d == '.' || d == 'e' || d == 'E' || exp_sign {
+                        end = i + d.len_utf8();
+                        prev = d;
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let text = &src[pos..end];
+                let v = text
+                    .parse::<f64>()
+                    .map_err(|_| ExprError::new(pos, format!("invalid number '{text}'")))?;
+                toks.push((Tok::Num(v), pos));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = pos;
+                while let Some(&(i, d)) = chars.peek() {
+                    if d.is_alphanumeric() || d == '_' || d == '.' {
+                        end = i + d.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                toks.push((Tok::Ident(src[pos..end].to_string()), pos));
+            }
+            '"' => {
+                chars.next();
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, d)) => name.push(d),
+                        None => return Err(ExprError::new(pos, "unterminated quoted name")),
+                    }
+                }
+                toks.push((Tok::Quoted(name), pos));
+            }
+            '+' | '-' | '*' | '/' | '%' | '^' => {
+                chars.next();
+                toks.push((Tok::Op(c), pos));
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                let tok = match c {
+                    '(' => Tok::LParen,
+                    ')' => Tok::RParen,
+                    _ => Tok::Comma,
+                };
+                toks.push((tok, pos));
+            }
+            _ => return Err(ExprError::new(pos, format!("unexpected character '{c}'"))),
+        }
+    }
+    toks.push((Tok::End, src.len()));
+    Ok(toks)
+}
+
+struct Parser {
+    toks: Vec<(Tok, usize)>,
+    i: usize,
+    vars: Vec<String>,
+}
+
+impl Parser {
+    fn peek(&self) -> &Tok {
+        &self.toks[self.i].0
+    }
+
+    fn pos(&self) -> usize {
+        self.toks[self.i].1
+    }
+
+    fn next(&mut self) -> Tok {
+        let tok = self.toks[self.i].0.clone();
+        if tok != Tok::End {
+            self.i += 1;
+        }
+        tok
+    }
+
+    fn expect(&mut self, tok: Tok, what: &str) -> Result<(), ExprError> {
+        if *self.peek() == tok {
+            self.next();
+            Ok(())
+        } else {
+            Err(ExprError::new(self.pos(), format!("expected {what}")))
+        }
+    }
+
+    /// `term (('+' | '-') term)*`
+    fn expr(&mut self) -> Result<Node, ExprError> {
+        let mut lhs = self.term()?;
+        while let Tok::Op(op @ ('+' | '-')) = *self.peek() {
+            self.next();
+            lhs = Node::Bin(op, Box::new(lhs), Box::new(self.term()?));
+        }
+        Ok(lhs)
+    }
+
+    /// `unary (('*' | '/' | '%') unary)*`
+    fn term(&mut self) -> Result<Node, ExprError> {
+        let mut lhs = self.unary()?;
+        while let Tok::Op(op @ ('*' | '/' | '%')) = *self.peek() {
+            self.next();
+            lhs = Node::Bin(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    /// `('-' | '+') unary | power`
+    fn unary(&mut self) -> Result<Node, ExprError> {
+        match *self.peek() {
+            Tok::Op('-') => {
+                self.next();
+                Ok(Node::Neg(Box::new(self.unary()?)))
+            }
+            Tok::Op('+') => {
+                self.next();
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    /// `primary ('^' unary)?` — right-associative, so `-a^2` is `-(a^2)`.
+    fn power(&mut self) -> Result<Node, ExprError> {
+        let base = self.primary()?;
+        if *self.peek() == Tok::Op('^') {
+            self.next();
+            return Ok(Node::Bin('^', Box::new(base), Box::new(self.unary()?)));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Node, ExprError> {
+        let pos = self.pos();
+        match self.next() {
+            Tok::Num(v) => Ok(Node::Num(v)),
+            Tok::LParen => {
+                let inner = self.expr()?;
+                self.expect(Tok::RParen, "')'")?;
+                Ok(inner)
+            }
+            Tok::Quoted(name) => Ok(self.var(name)),
+            Tok::Ident(name) if *self.peek() == Tok::LParen => {
+                let Some(&(func, arity)) = FUNCTIONS.iter().find(|(f, _)| *f == name) else {
+                    return Err(ExprError::new(pos, format!("unknown function '{name}'")));
+                };
+                self.next();
+                let mut args = vec![self.expr()?];
+                while *self.peek() == Tok::Comma {
+                    self.next();
+                    args.push(self.expr()?);
+                }
+                self.expect(Tok::RParen, "')'")?;
+                if args.len() != arity {
+                    return Err(ExprError::new(
+                        pos,
+                        format!("{func} takes {arity} argument(s), got {}", args.len()),
+                    ));
+                }
+                Ok(Node::Call(func, args))
+            }
+            Tok::Ident(name) if name == "pi" => Ok(Node::Num(std::f64::consts::PI)),
+            Tok::Ident(name) => Ok(self.var(name)),
+            Tok::End => Err(ExprError::new(pos, "unexpected end of expression")),
+            _ => Err(ExprError::new(pos, "expected a value")),
+        }
+    }
+
+    fn var(&mut self, name: String) -> Node {
+        let idx = match self.vars.iter().position(|v| *v == name) {
+            Some(i) => i,
+            None => {
+                self.vars.push(name);
+                self.vars.len() - 1
+            }
+        };
+        Node::Var(idx)
+    }
+}
@@ -7,8 +7,294 @@ use std::collections::VecDeque;
 
 use crate::data::traces::{TraceData, TraceRef};
 
+/// Smallest FFT size used when adapting to a buffer shorter than `fft_size`.
+pub const MIN_ADAPTIVE_FFT_SIZE: usize = 64;
+
+/// FFT sizes selectable in the panel: powers of two from 2^8 to 2^20.
+pub const FFT_SIZE_RANGE: std::ops::RangeInclusive<usize> = (1 << 8)..=(1 << 20);
+
+/// Whether `size` is a power of two within [`FFT_SIZE_RANGE`].
+pub fn is_valid_fft_size(size: usize) -> bool {
+    size.is_power_of_two() && FFT_SIZE_RANGE.contains(&size)
+}
+
+/// Choose the number of samples to transform for a buffer holding `available`.
+///
+/// Returns `requested` once the buffer is long enough.  Before that, with
+/// `adaptive` set, the largest power of two not exceeding `available` is used
+/// (but never less than [`MIN_ADAPTIVE_FFT_SIZE`]), so a spectrum appears on
+/// slow data without waiting for the buffer to fill.
+///
+/// Trade-off: bin spacing is `sample_rate / N`, so a 256-point FFT resolves
+/// frequencies 4× more coarsely than a 1024-point one.  The spectrum sharpens
+/// automatically as samples accumulate until `requested` is reached.
+pub fn effective_fft_size(requested: usize, available: usize, adaptive: bool) -> Option<usize> {
+    if available >= requested {
+        return Some(requested);
+    }
+    if !adaptive || available < MIN_ADAPTIVE_FFT_SIZE {
+        return None;
+    }
+    // Largest power of two <= available.
+    Some(1usize << (usize::BITS - 1 - available.leading_zeros()))
+}
+
+/// Coefficient of variation of the sample intervals above which a spectrum
+/// is flagged as irregularly sampled (see [`interval_cv`]).
+pub const IRREGULAR_SAMPLING_CV: f64 = 0.1;
+
+/// Coefficient of variation (standard deviation / mean) of the intervals
+/// between consecutive timestamps.
+///
+/// The FFT assumes uniform sampling at the mean rate; jittered or gappy
+/// timestamps smear the spectrum, which a large value here reveals.
+/// Returns `None` for fewer than three timestamps or a non-positive mean.
+pub fn interval_cv(times: impl IntoIterator<Item = f64>) -> Option<f64> {
+    let mut times = times.into_iter();
+    let mut prev = times.next()?;
+    let (mut n, mut sum, mut sum_sq) = (0usize, 0.0, 0.0);
+    for t in times {
+        let dt = t - prev;
+        prev = t;
+        n += 1;
+        sum += dt;
+        sum_sq += dt * dt;
+    }
+    if n < 2 {
+        return None;
+    }
+    let mean = sum / n as f64;
+    if mean <= 0.0 {
+        return None;
+    }
+    let var = (sum_sq / n as f64 - mean * mean).max(0.0);
+    Some(var.sqrt() / mean)
+}
+
+/// Normalisation of the FFT magnitude axis.
+///
+/// All modes are corrected for the window so that values do not depend on
+/// the window type or zero-padding: `Σw` (coherent gain) for amplitudes and
+/// `Σw²` (window power) together with the sample rate for densities.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FftScale {
+    /// Unnormalised bin magnitude `|X|`, which grows with the FFT size and
+    /// depends on the window.
+    Raw,
+    /// Peak amplitude of a sinusoid per bin: `2·|X|/Σw`.
+    #[default]
+    Linear,
+    /// Power spectral density in units²/Hz: `2·|X|²/(fs·Σw²)`.
+    Psd,
+    /// Amplitude spectral density in units/√Hz (square root of the PSD).
+    Asd,
+    /// Amplitude in dB relative to a full-scale reference: `20·log10(A/ref)`.
+    Dbfs,
+}
+
+impl FftScale {
+    /// All scaling modes (for UI selection)
+    pub const ALL: &'static [FftScale] = &[
+        FftScale::Raw,
+        FftScale::Linear,
+        FftScale::Psd,
+        FftScale::Asd,
+        FftScale::Dbfs,
+    ];
+
+    /// Human-readable label for each mode
+    pub fn label(&self) -> &'static str {
+        match self {
+            FftScale::Raw => "Raw",
+            FftScale::Linear => "Linear",
+            FftScale::Psd => "PSD",
+            FftScale::Asd => "ASD",
+            FftScale::Dbfs => "dBFS",
+        }
+    }
+
+    /// Y-axis name and unit for this mode, optionally converted to dB.
+    /// `Dbfs` is always logarithmic and ignores `db`.
+    pub fn axis_label(&self, db: bool) -> (&'static str, Option<&'static str>) {
+        match (self, db) {
+            (FftScale::Raw, false) => ("Raw magnitude", None),
+            (FftScale::Raw, true) => ("Raw magnitude (dB)", Some("dB")),
+            (FftScale::Linear, false) => ("Magnitude", None),
+            (FftScale::Linear, true) => ("Magnitude (dB)", Some("dB")),
+            (FftScale::Psd, false) => ("PSD", Some("/Hz")),
+            (FftScale::Psd, true) => ("PSD (dB)", Some("dB/Hz")),
+            (FftScale::Asd, false) => ("ASD", Some("/√Hz")),
+            (FftScale::Asd, true) => ("ASD (dB)", Some("dB/√Hz")),
+            (FftScale::Dbfs, _) => ("Magnitude", Some("dBFS")),
+        }
+    }
+
+    /// Factor for converting a value of this mode to dB (`k·log10(v)`):
+    /// 10 for power quantities, 20 for amplitudes.
+    pub fn db_factor(&self) -> f64 {
+        match self {
+            FftScale::Psd => 10.0,
+            _ => 20.0,
+        }
+    }
+}
+
+/// Default for [`FftData::phase_mask_db`].
+pub const DEFAULT_PHASE_MASK_DB: f64 = 60.0;
+
+/// Blank the phase (set it to NaN) of bins in a `[frequency, magnitude,
+/// phase]` spectrum whose magnitude lies more than `below_db` under the
+/// strongest bin.  The phase of such bins is dominated by noise and leakage.
+///
+/// Magnitudes are in `scale` units and compared in dB (see
+/// [`FftScale::db_factor`]); `below_db <= 0` masks nothing.
+pub fn mask_phase(spectrum: &mut [[f64; 3]], scale: FftScale, below_db: f64) {
+    if below_db <= 0.0 {
+        return;
+    }
+    let to_db = |v: f64| match scale {
+        FftScale::Dbfs => v,
+        _ => scale.db_factor() * v.max(1e-300).log10(),
+    };
+    let peak = spectrum
+        .iter()
+        .map(|p| to_db(p[1]))
+        .fold(f64::NEG_INFINITY, f64::max);
+    for p in spectrum.iter_mut() {
+        if to_db(p[1]) < peak - below_db {
+            p[2] = f64::NAN;
+        }
+    }
+}
+
+/// Remove the 2π jumps from the phase column of a spectrum so it reads as a
+/// continuous curve.  NaN (masked) bins are left alone and skipped over: the
+/// next valid bin continues from the last valid one.
+pub fn unwrap_phase(spectrum: &mut [[f64; 3]]) {
+    use std::f64::consts::{PI, TAU};
+    let mut prev: Option<f64> = None;
+    let mut offset = 0.0;
+    for p in spectrum.iter_mut() {
+        if p[2].is_nan() {
+            continue;
+        }
+        let raw = p[2];
+        if let Some(prev_raw) = prev {
+            let d = raw - prev_raw;
+            if d > PI {
+                offset -= TAU * ((d + PI) / TAU).floor();
+            } else if d < -PI {
+                offset += TAU * ((-d + PI) / TAU).floor();
+            }
+        }
+        prev = Some(raw);
+        p[2] = raw + offset;
+    }
+}
+
+/// Spectrum `[frequency, value]` of an already transformed buffer,
+/// normalised according to `scale`.
+///
+/// `data` holds `padded_size` FFT bins of `fft_size` windowed samples.  A
+/// real input gives a one-sided spectrum up to Nyquist; with `two_sided` (a
+/// complex I/Q input) the bins are fft-shifted to run from −fs/2 up to +fs/2.
+#[cfg(feature = "fft")]
+fn scaled_spectrum(
+    data: &[Complex<f64>],
+    fft_size: usize,
+    sample_rate: f64,
+    window: FFTWindow,
+    scale: FftScale,
+    reference: f64,
+    two_sided: bool,
+) -> Vec<[f64; 2]> {
+    scaled_spectrum_with_phase(
+        data,
+        fft_size,
+        sample_rate,
+        window,
+        scale,
+        reference,
+        two_sided,
+    )
+    .into_iter()
+    .map(|[f, v, _]| [f, v])
+    .collect()
+}
+
+/// Like [`scaled_spectrum`], with each bin's phase in radians (wrapped to
+/// `(-π, π]`, relative to the first windowed sample) as a third column.
+#[cfg(feature = "fft")]
+fn scaled_spectrum_with_phase(
+    data: &[Complex<f64>],
+    fft_size: usize,
+    sample_rate: f64,
+    window: FFTWindow,
+    scale: FftScale,
+    reference: f64,
+    two_sided: bool,
+) -> Vec<[f64; 3]> {
+    let padded_size = data.len();
+    let (s1, s2) = window.sums(fft_size);
+    let reference = if reference > 0.0 { reference } else { 1.0 };
+    let half = padded_size / 2;
+    let (first, count) = if two_sided {
+        (half, padded_size)
+    } else {
+        (0, half)
+    };
+    let mut out: Vec<[f64; 3]> = Vec::with_capacity(count);
+    for j in 0..count {
+        let k = (first + j) % padded_size;
+        let c = data[k];
+        let bin = if two_sided && k >= half {
+            k as f64 - padded_size as f64
+        } else {
+            k as f64
+        };
+        let freq = bin * sample_rate / padded_size as f64;
+        // A one-sided spectrum folds the negative frequencies onto the
+        // positive ones; DC has no negative-frequency twin, so it is not doubled.
+        let one_sided = if two_sided || k == 0 { 1.0 } else { 2.0 };
+        let mag = (c.re * c.re + c.im * c.im).sqrt();
+        let psd = || one_sided * mag * mag / (sample_rate * s2);
+        let v = match scale {
+            FftScale::Raw => mag,
+            FftScale::Linear => one_sided * mag / s1,
+            FftScale::Psd => psd(),
+            FftScale::Asd => psd().sqrt(),
+            FftScale::Dbfs => 20.0 * (one_sided * mag / s1 / reference).max(1e-12).log10(),
+        };
+        out.push([freq, v, c.im.atan2(c.re)]);
+    }
+    out
+}
+
+/// Start index of the last `fft_size` samples of `buf` and the sample rate
+/// estimated from their timestamps, or `None` if time does not advance.
+#[cfg(feature = "fft")]
+fn sample_window(buf: &VecDeque<[f64; 2]>, fft_size: usize) -> Option<(usize, f64)> {
+    let start = buf.len().checked_sub(fft_size)?;
+    let t0 = buf.get(start)?[0];
+    let t1 = buf.back()?[0];
+    if !(t1 > t0) {
+        return None;
+    }
+    let dt_est = (t1 - t0) / (fft_size as f64 - 1.0);
+    if dt_est <= 0.0 {
+        return None;
+    }
+    Some((start, 1.0 / dt_est))
+}
+
+/// Default shape parameter of [`FFTWindow::Kaiser`].
+pub const DEFAULT_KAISER_BETA: f64 = 8.6;
+
+/// Default taper fraction of [`FFTWindow::Tukey`].
+pub const DEFAULT_TUKEY_ALPHA: f64 = 0.5;
+
 /// Supported FFT window functions for spectral analysis.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum FFTWindow {
     /// Rectangular (no windowing)
     Rect,
@@ -18,6 +304,15 @@ pub enum FFTWindow {
     Hamming,
     /// Blackman window
     Blackman,
+    /// Flat-top window: a wide main lobe whose flat peak reads a tone's
+    /// amplitude to within ~0.01 dB wherever it falls between bins.
+    FlatTop,
+    /// Kaiser window.  `beta` trades main-lobe width for sidelobe level:
+    /// 0 is rectangular, ~5 resembles Hamming, ~8.6 Blackman.
+    Kaiser { beta: f64 },
+    /// Tukey (tapered cosine) window: flat in the middle with cosine tapers
+    /// over the fraction `alpha` of its length; 0 is rectangular, 1 is Hann.
+    Tukey { alpha: f64 },
 }
 
 impl Default for FFTWindow {
@@ -33,6 +328,13 @@ impl FFTWindow {
         FFTWindow::Hann,
         FFTWindow::Hamming,
         FFTWindow::Blackman,
+        FFTWindow::FlatTop,
+        FFTWindow::Kaiser {
+            beta: DEFAULT_KAISER_BETA,
+        },
+        FFTWindow::Tukey {
+            alpha: DEFAULT_TUKEY_ALPHA,
+        },
     ];
 
     /// Human-readable label for each window type
@@ -42,9 +344,62 @@ impl FFTWindow {
             FFTWindow::Hann => "Hann",
             FFTWindow::Hamming => "Hamming",
             FFTWindow::Blackman => "Blackman",
+            FFTWindow::FlatTop => "Flat-top",
+            FFTWindow::Kaiser { .. } => "Kaiser",
+            FFTWindow::Tukey { .. } => "Tukey",
         }
     }
 
+    /// Label including the shape parameter, e.g. `Kaiser β=8.6`.
+    pub fn description(&self) -> String {
+        match self {
+            FFTWindow::Kaiser { beta } => format!("Kaiser β={}", beta),
+            FFTWindow::Tukey { alpha } => format!("Tukey α={}", alpha),
+            w => w.label().to_string(),
+        }
+    }
+
+    /// Shape parameter of Kaiser (`beta`) and Tukey (`alpha`) windows.
+    pub fn param(&self) -> Option<f64> {
+        match self {
+            FFTWindow::Kaiser { beta } => Some(*beta),
+            FFTWindow::Tukey { alpha } => Some(*alpha),
+            _ => None,
+        }
+    }
+
+    /// The same window type with its shape parameter replaced; other
+    /// windows are returned unchanged.
+    pub fn with_param(self, value: f64) -> Self {
+        match self {
+            FFTWindow::Kaiser { .. } => FFTWindow::Kaiser {
+                beta: value.max(0.0),
+            },
+            FFTWindow::Tukey { .. } => FFTWindow::Tukey {
+                alpha: value.clamp(0.0, 1.0),
+            },
+            w => w,
+        }
+    }
+
+    /// Window with the given [`label`](Self::label), with its default
+    /// shape parameter.
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|w| w.label() == label)
+    }
+
+    /// Coherent gain `Σw` and power `Σw²` of a window of `len` samples,
+    /// falling back to `len` for a degenerate window.
+    pub fn sums(&self, len: usize) -> (f64, f64) {
+        let (s1, s2) = (0..len).fold((0.0, 0.0), |(s1, s2), i| {
+            let w = self.weight(i, len);
+            (s1 + w, s2 + w * w)
+        });
+        let s1 = if s1 > 0.0 { s1 } else { len as f64 };
+        let s2 = if s2 > 0.0 { s2 } else { len as f64 };
+        (s1, s2)
+    }
+
     /// Compute the window weight for a given sample index
     pub fn weight(&self, n: usize, len: usize) -> f64 {
         match self {
@@ -62,18 +417,76 @@ impl FFTWindow {
                 0.42 - 0.5 * (2.0 * std::f64::consts::PI * n as f64 / (len as f64)).cos()
                     + 0.08 * (4.0 * std::f64::consts::PI * n as f64 / (len as f64)).cos()
             }
+            FFTWindow::FlatTop => {
+                // 5-term flat-top: w[n] = Σ (-1)^k a_k cos(2πkn/N)
+                const A: [f64; 5] = [
+                    0.21557895,
+                    0.41663158,
+                    0.277263158,
+                    0.083578947,
+                    0.006947368,
+                ];
+                let x = 2.0 * std::f64::consts::PI * n as f64 / (len as f64);
+                A.iter()
+                    .enumerate()
+                    .map(|(k, a)| {
+                        let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+                        sign * a * (k as f64 * x).cos()
+                    })
+                    .sum()
+            }
+            FFTWindow::Kaiser { beta } => {
+                // w[n] = I0(β·√(1 - (2n/N - 1)²)) / I0(β)
+                let r = 2.0 * n as f64 / len as f64 - 1.0;
+                bessel_i0(beta * (1.0 - r * r).max(0.0).sqrt()) / bessel_i0(*beta)
+            }
+            FFTWindow::Tukey { alpha } => {
+                // Cosine tapers over alpha/2 of the length at each end.
+                let x = n as f64 / len as f64;
+                let edge = x.min(1.0 - x);
+                if *alpha <= 0.0 || edge >= alpha / 2.0 {
+                    1.0
+                } else {
+                    0.5 - 0.5 * (2.0 * std::f64::consts::PI * edge / alpha).cos()
+                }
+            }
         }
     }
 }
 
+/// Modified Bessel function of the first kind, order zero (power series).
+fn bessel_i0(x: f64) -> f64 {
+    let q = x * x / 4.0;
+    let (mut sum, mut term) = (1.0, 1.0);
+    for k in 1..500 {
+        term *= q / (k * k) as f64;
+        sum += term;
+        if term < sum * 1e-16 {
+            break;
+        }
+    }
+    sum
+}
+
 #[cfg(feature = "fft")]
 struct FftJob {
     trace_ref: TraceRef,
     samples: Vec<f64>,
+    /// Quadrature samples paired with `samples`: transform `i + j·q` into a
+    /// two-sided spectrum.
+    q_samples: Option<Vec<f64>>,
     fft_size: usize,
+    /// The configured size; larger than `fft_size` when adapted to a short buffer.
+    requested_size: usize,
     padded_size: usize,
     sample_rate: f64,
     window: FFTWindow,
+    scale: FftScale,
+    reference: f64,
+    /// Produce the masked, unwrapped phase instead of the magnitude.
+    phase: bool,
+    phase_mask_db: f64,
+    interval_cv: f64,
 }
 
 #[cfg(feature = "fft")]
@@ -81,6 +494,302 @@ struct FftResult {
     trace_ref: TraceRef,
     spectrum: Vec<[f64; 2]>,
     info: String,
+    meta: SpectrumMeta,
+}
+
+/// Parameters a spectrum was computed with, kept alongside the result so
+/// exports are self-describing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectrumMeta {
+    pub window: FFTWindow,
+    /// Number of samples transformed (after adaptive sizing).
+    pub fft_size: usize,
+    /// Transform length including zero-padding.
+    pub padded_size: usize,
+    /// Sample rate estimated from the buffer's timestamps, in Hz.
+    pub sample_rate: f64,
+    pub scale: FftScale,
+    /// Coefficient of variation of the transformed samples' intervals; above
+    /// [`IRREGULAR_SAMPLING_CV`] the spectrum may be inaccurate.
+    pub interval_cv: f64,
+}
+
+impl SpectrumMeta {
+    /// Whether the source timestamps were too irregular for a reliable FFT.
+    pub fn irregular_sampling(&self) -> bool {
+        self.interval_cv > IRREGULAR_SAMPLING_CV
+    }
+}
+
+/// Power integrated over a frequency band of a spectrum (see [`band_power`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandPower {
+    /// Lower band edge in Hz.
+    pub f_lo: f64,
+    /// Upper band edge in Hz.
+    pub f_hi: f64,
+    /// Number of spectrum bins inside the band.
+    pub bins: usize,
+    /// Signal power in the band, in units² (mean square).
+    pub power: f64,
+    /// Equivalent RMS amplitude, `√power`.
+    pub rms: f64,
+}
+
+/// Integrate the power of `spectrum` between the frequencies `f1` and `f2`.
+///
+/// Each bin inside the band (edges included) is converted back to a power
+/// spectral density according to `meta.scale` and summed times the bin
+/// spacing `sample_rate / padded_size`, so the result matches the mean
+/// square of the band-limited signal regardless of window and zero-padding.
+/// A pure tone of amplitude `A` inside the band gives `A²/2`.
+/// `reference` is the full-scale value used for [`FftScale::Dbfs`].
+///
+/// Returns `None` when no bin falls inside the band.
+pub fn band_power(
+    spectrum: &[[f64; 2]],
+    meta: &SpectrumMeta,
+    reference: f64,
+    f1: f64,
+    f2: f64,
+) -> Option<BandPower> {
+    let (f_lo, f_hi) = (f1.min(f2), f1.max(f2));
+    if meta.padded_size == 0 || meta.sample_rate <= 0.0 {
+        return None;
+    }
+    let bin_width = meta.sample_rate / meta.padded_size as f64;
+    let (s1, s2) = meta.window.sums(meta.fft_size);
+    let reference = if reference > 0.0 { reference } else { 1.0 };
+    let mut bins = 0;
+    let mut power = 0.0;
+    for &[freq, v] in spectrum {
+        if freq < f_lo || freq > f_hi {
+            continue;
+        }
+        let one_sided = if freq == 0.0 { 1.0 } else { 2.0 };
+        // Amplitude and density relate through |X|: A = k·|X|/Σw and
+        // PSD = k·|X|²/(fs·Σw²), with k the one-sided factor.
+        let amp_to_psd = |a: f64| a * a * s1 * s1 / (one_sided * meta.sample_rate * s2);
+        let psd = match meta.scale {
+            FftScale::Raw => one_sided * v * v / (meta.sample_rate * s2),
+            FftScale::Linear => amp_to_psd(v),
+            FftScale::Psd => v,
+            FftScale::Asd => v * v,
+            FftScale::Dbfs => amp_to_psd(reference * 10f64.powf(v / 20.0)),
+        };
+        power += psd * bin_width;
+        bins += 1;
+    }
+    if bins == 0 {
+        return None;
+    }
+    Some(BandPower {
+        f_lo,
+        f_hi,
+        bins,
+        power,
+        rms: power.sqrt(),
+    })
+}
+
+/// Up to `count` spectral peaks, strongest first.
+///
+/// A peak is a local maximum: higher than the bin before it and at least as
+/// high as the bin after, so two neighbouring bins are never both reported.
+/// Bins at or below `floor` (in the spectrum's units) are ignored; NaN bins
+/// never qualify.
+pub fn find_peaks(spectrum: &[[f64; 2]], count: usize, floor: Option<f64>) -> Vec<[f64; 2]> {
+    let floor = floor.unwrap_or(f64::NEG_INFINITY);
+    let mut peaks: Vec<[f64; 2]> = spectrum
+        .iter()
+        .enumerate()
+        .filter(|&(i, p)| {
+            let v = p[1];
+            let left = i
+                .checked_sub(1)
+                .map_or(f64::NEG_INFINITY, |j| spectrum[j][1]);
+            let right = spectrum.get(i + 1).map_or(f64::NEG_INFINITY, |q| q[1]);
+            v > floor && v > left && v >= right
+        })
+        .map(|(_, p)| *p)
+        .collect();
+    peaks.sort_by(|a, b| b[1].total_cmp(&a[1]));
+    peaks.truncate(count);
+    peaks
+}
+
+/// Frequency with an SI prefix and four significant digits, e.g.
+/// `"1.235 kHz"` or `"50.00 mHz"`.
+pub fn format_hz(hz: f64) -> String {
+    const PREFIXES: [(f64, &str); 6] = [
+        (1e9, "GHz"),
+        (1e6, "MHz"),
+        (1e3, "kHz"),
+        (1.0, "Hz"),
+        (1e-3, "mHz"),
+        (1e-6, "µHz"),
+    ];
+    if hz == 0.0 || !hz.is_finite() {
+        return format!("{} Hz", hz);
+    }
+    let (scale, unit) = PREFIXES
+        .iter()
+        .copied()
+        .find(|(s, _)| hz.abs() >= *s)
+        .unwrap_or(PREFIXES[PREFIXES.len() - 1]);
+    let v = hz / scale;
+    let decimals = 3usize.saturating_sub(v.abs().log10().floor().max(0.0) as usize);
+    format!("{:.*} {}", decimals, v, unit)
+}
+
+/// How the FFT panel draws spectra.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FftView {
+    /// The latest spectrum of each trace as a line.
+    #[default]
+    Line,
+    /// Successive spectra of one trace as a scrolling heatmap (spectrogram).
+    Waterfall,
+}
+
+impl FftView {
+    /// All views (for UI selection)
+    pub const ALL: &'static [FftView] = &[FftView::Line, FftView::Waterfall];
+
+    /// Human-readable label for each view
+    pub fn label(&self) -> &'static str {
+        match self {
+            FftView::Line => "Line",
+            FftView::Waterfall => "Waterfall",
+        }
+    }
+}
+
+/// Spectra kept per trace by default in the waterfall view.
+pub const DEFAULT_SPECTROGRAM_FRAMES: usize = 200;
+
+/// Most frequency columns a [`SpectrogramGrid`] is reduced to; neighbouring
+/// bins of finer spectra are combined by taking their maximum.
+pub const SPECTROGRAM_MAX_COLUMNS: usize = 512;
+
+/// Range of the dB color scale below the strongest bin.
+pub const SPECTROGRAM_DB_SPAN: f64 = 100.0;
+
+/// History of one trace's spectra for the waterfall view, oldest first and
+/// capped at a number of frames.
+#[derive(Clone, Debug, Default)]
+pub struct Spectrogram {
+    frames: VecDeque<(f64, Vec<[f64; 2]>)>,
+}
+
+impl Spectrogram {
+    /// Append the spectrum computed from data ending at time `t`, dropping
+    /// the oldest frames beyond `max_frames`.
+    ///
+    /// A spectrum with a different bin layout (FFT size or padding changed)
+    /// or from earlier data (buffer cleared) starts the history over; one at
+    /// the same time replaces the newest frame.
+    pub fn push(&mut self, t: f64, spectrum: Vec<[f64; 2]>, max_frames: usize) {
+        if let Some((last_t, last)) = self.frames.back() {
+            let same_bins = last.len() == spectrum.len()
+                && last.last().map(|p| p[0]) == spectrum.last().map(|p| p[0]);
+            if !same_bins || t < *last_t {
+                self.frames.clear();
+            } else if t == *last_t {
+                self.frames.pop_back();
+            }
+        }
+        self.frames.push_back((t, spectrum));
+        while self.frames.len() > max_frames.max(1) {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Number of retained spectra.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// `true` if no spectra are retained.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Forget all retained spectra.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// The retained spectra as a grid of color levels, one row per frame.
+    ///
+    /// With `db_factor` set, magnitudes are converted to dB (`k·log10(v)`)
+    /// and the color range spans [`SPECTROGRAM_DB_SPAN`] below the maximum;
+    /// otherwise it spans the smallest to the largest value.
+    pub fn grid(&self, db_factor: Option<f64>, max_cols: usize) -> Option<SpectrogramGrid> {
+        let (t_first, first) = self.frames.front()?;
+        let t_last = self.frames.back()?.0;
+        let bins = first.len();
+        if bins < 2 {
+            return None;
+        }
+        let group = bins.div_ceil(max_cols.max(1));
+        let cols = bins.div_ceil(group);
+        let bin_width = (first[bins - 1][0] - first[0][0]) / (bins - 1) as f64;
+        let level = |v: f64| match db_factor {
+            Some(k) => k * v.max(1e-300).log10(),
+            None => v,
+        };
+        let mut values = Vec::with_capacity(cols * self.frames.len());
+        for (_, spectrum) in &self.frames {
+            for chunk in spectrum.chunks(group) {
+                let peak = chunk.iter().map(|p| p[1]).fold(f64::NEG_INFINITY, f64::max);
+                values.push(level(peak));
+            }
+        }
+        let finite = values.iter().copied().filter(|v| v.is_finite());
+        let (lo, hi) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        });
+        let lo = match db_factor {
+            Some(_) => lo.max(hi - SPECTROGRAM_DB_SPAN),
+            None => lo,
+        };
+        let rows = self.frames.len();
+        let row_height = if rows > 1 && t_last > *t_first {
+            (t_last - t_first) / (rows - 1) as f64
+        } else {
+            1.0
+        };
+        Some(SpectrogramGrid {
+            values,
+            cols,
+            rows,
+            f_start: first[0][0],
+            col_width: bin_width * group as f64,
+            t_start: t_first - t_last,
+            row_height,
+            range: (lo, hi),
+        })
+    }
+}
+
+/// A [`Spectrogram`] laid out for drawing (see [`Spectrogram::grid`]).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpectrogramGrid {
+    /// Color levels, row by row from the oldest frame.
+    pub values: Vec<f64>,
+    pub cols: usize,
+    pub rows: usize,
+    /// Frequency of the first column, in Hz.
+    pub f_start: f64,
+    /// Frequency span of one column, in Hz.
+    pub col_width: f64,
+    /// Time of the oldest row relative to the newest (≤ 0), in seconds.
+    pub t_start: f64,
+    /// Mean time between frames, in seconds.
+    pub row_height: f64,
+    /// Levels mapped to the ends of the color palette.
+    pub range: (f64, f64),
 }
 
 #[cfg(feature = "fft")]
@@ -93,11 +802,29 @@ pub struct FftData {
     pub fft_size: usize,
     pub fft_window: FFTWindow,
     /// Zero-padding factor: FFT is computed on fft_size * zero_pad_factor points.
-    /// Values > 1 interpolate between frequency bins for a smoother spectrum.
+    /// Values > 1 interpolate between frequency bins for a smoother spectrum;
+    /// they do not improve resolution, which stays `sample_rate / fft_size`.
     pub zero_pad_factor: usize,
     /// Minimum interval between FFT recomputes in milliseconds (throttle).
     pub recompute_interval_ms: u64,
+    /// Use a smaller power-of-two size while the buffer holds fewer than
+    /// `fft_size` samples (see [`effective_fft_size`]).
+    pub adaptive_size: bool,
+    /// Normalisation of the magnitude axis.
+    pub magnitude_scale: FftScale,
+    /// Full-scale amplitude that maps to 0 dBFS in [`FftScale::Dbfs`].
+    pub dbfs_reference: f64,
+    /// Compute the unwrapped phase (radians) instead of the magnitude.
+    pub phase: bool,
+    /// In phase mode, blank bins more than this many dB below the strongest
+    /// one (see [`mask_phase`]).  `0` keeps every bin.
+    pub phase_mask_db: f64,
     pub fft_traces: HashMap<TraceRef, TraceData>,
+    /// Parameters of the latest spectrum in `fft_traces`, per trace.
+    pub spectrum_meta: HashMap<TraceRef, SpectrumMeta>,
+    /// Sample interval coefficient of variation seen by the last
+    /// [`compute_fft`](Self::compute_fft) call (see [`interval_cv`]).
+    pub last_interval_cv: f64,
     /// Cached FFT plan — avoids recreating FftPlanner every frame.
     #[cfg(feature = "fft")]
     cached_fft_plan: Option<std::sync::Arc<dyn Fft<f64>>>,
@@ -114,9 +841,16 @@ pub struct FftData {
     /// Last FFT size used, to invalidate cache when size changes.
     #[cfg(feature = "fft")]
     cached_fft_size: usize,
+    /// Last zero-padding factor, to invalidate cache when it changes.
+    #[cfg(feature = "fft")]
+    cached_zero_pad: usize,
     /// Last paused state, to invalidate cache when pause state changes.
     #[cfg(feature = "fft")]
     cached_paused: bool,
+    /// Last magnitude scaling, dBFS reference and phase settings, to
+    /// invalidate cache on change.
+    #[cfg(feature = "fft")]
+    cached_scale: (FftScale, f64, bool, f64),
     /// Per-trace timestamp of the last FFT recompute, for throttling.
     #[cfg(feature = "fft")]
     last_compute_time: HashMap<TraceRef, std::time::Instant>,
@@ -132,7 +866,14 @@ impl Default for FftData {
             fft_window: FFTWindow::Hann,
             zero_pad_factor: 1,
             recompute_interval_ms: 100,
+            adaptive_size: true,
+            magnitude_scale: FftScale::Linear,
+            dbfs_reference: 1.0,
+            phase: false,
+            phase_mask_db: DEFAULT_PHASE_MASK_DB,
             fft_traces: HashMap::default(),
+            spectrum_meta: HashMap::default(),
+            last_interval_cv: 0.0,
             #[cfg(feature = "fft")]
             cached_fft_plan: None,
             #[cfg(feature = "fft")]
@@ -144,8 +885,12 @@ impl Default for FftData {
             #[cfg(feature = "fft")]
             cached_fft_size: 1024,
             #[cfg(feature = "fft")]
+            cached_zero_pad: 1,
+            #[cfg(feature = "fft")]
             cached_paused: false,
             #[cfg(feature = "fft")]
+            cached_scale: (FftScale::Linear, 1.0, false, DEFAULT_PHASE_MASK_DB),
+            #[cfg(feature = "fft")]
             last_compute_time: HashMap::default(),
             #[cfg(feature = "fft")]
             worker: None,
@@ -159,6 +904,12 @@ impl FftData {
         self.fft_size * self.zero_pad_factor.max(1)
     }
 
+    /// FFT size to use for a buffer of `available` samples, honouring
+    /// [`adaptive_size`](Self::adaptive_size).
+    pub fn effective_size(&self, available: usize) -> Option<usize> {
+        effective_fft_size(self.fft_size, available, self.adaptive_size)
+    }
+
     /// Return the cached FFT plan, creating it if needed.
     #[cfg(feature = "fft")]
     fn get_fft_plan(&mut self, fft_size: usize) -> std::sync::Arc<dyn Fft<f64>> {
@@ -176,13 +927,18 @@ impl FftData {
     /// Compute the FFT of the most recent samples in the buffer, using the selected window.
     ///
     /// Applies zero-padding when `zero_pad_factor > 1` for finer frequency bin spacing.
+    /// When the buffer is shorter than `fft_size` and `adaptive_size` is set, a
+    /// smaller power-of-two size is used (see [`effective_fft_size`]).
     ///
     /// - `buf`: The live buffer of [time, value] samples.
     /// - `paused`: Whether the app is paused (if so, use snapshot buffer).
     /// - `buffer_snapshot`: Optional snapshot buffer (used if paused).
-    /// - `fft_size`: Number of samples to use for FFT (must be <= buffer length).
+    /// - `fft_size`: Number of samples to use for FFT.
     /// - `fft_window`: Window function to apply before FFT.
     ///
+    /// The regularity of the transformed timestamps is recorded in
+    /// [`last_interval_cv`](Self::last_interval_cv).
+    ///
     /// Returns: `Some(Vec<[frequency, magnitude]>)` if enough data, else `None`.
     pub fn compute_fft(
         &mut self,
@@ -192,28 +948,35 @@ impl FftData {
         fft_size: usize,
         fft_window: FFTWindow,
     ) -> Option<Vec<[f64; 2]>> {
+        let spectrum =
+            self.compute_fft_complex(buf, paused, buffer_snapshot, fft_size, fft_window)?;
+        Some(spectrum.into_iter().map(|[f, m, _]| [f, m]).collect())
+    }
+
+    /// Like [`compute_fft`](Self::compute_fft), but keeps each bin's phase.
+    ///
+    /// Returns `Some(Vec<[frequency, magnitude, phase]>)`, the phase in
+    /// radians wrapped to `(-π, π]` and relative to the first transformed
+    /// sample.  Use [`mask_phase`] and [`unwrap_phase`] to turn it into a
+    /// continuous phase response.
+    pub fn compute_fft_complex(
+        &mut self,
+        buf: &VecDeque<[f64; 2]>,
+        paused: bool,
+        buffer_snapshot: &Option<VecDeque<[f64; 2]>>,
+        fft_size: usize,
+        fft_window: FFTWindow,
+    ) -> Option<Vec<[f64; 3]>> {
         let buf = if paused {
             buffer_snapshot.as_ref()?
         } else {
             buf
         };
-        if buf.len() < fft_size {
-            return None;
-        }
-        let len = buf.len();
-        let start = len - fft_size;
-        let t0 = buf.get(start)?[0];
-        let t1 = buf.back()?[0];
-        if !(t1 > t0) {
-            return None;
-        }
-        let dt_est = (t1 - t0) / (fft_size as f64 - 1.0);
-        if dt_est <= 0.0 {
-            return None;
-        }
-        let sample_rate = 1.0 / dt_est;
+        let fft_size = effective_fft_size(fft_size, buf.len(), self.adaptive_size)?;
+        let (start, sample_rate) = sample_window(buf, fft_size)?;
+        self.last_interval_cv = interval_cv(buf.iter().skip(start).map(|p| p[0])).unwrap_or(0.0);
 
-        let padded_size = self.padded_size();
+        let padded_size = fft_size * self.zero_pad_factor.max(1);
         let fft = self.get_fft_plan(padded_size);
 
         // Window the real input, then zero-pad to padded_size
@@ -233,16 +996,71 @@ impl FftData {
 
         fft.process(&mut data);
 
-        // Compute one-sided magnitude spectrum (up to Nyquist of padded size)
-        let half = padded_size / 2;
-        let scale = 2.0 / fft_size as f64; // normalize by actual data length, not padded
-        let mut out: Vec<[f64; 2]> = Vec::with_capacity(half);
-        for (k, c) in data.iter().take(half).enumerate() {
-            let freq = k as f64 * sample_rate / padded_size as f64;
-            let mag = (c.re * c.re + c.im * c.im).sqrt() * scale;
-            out.push([freq, mag]);
-        }
-        Some(out)
+        // One-sided spectrum up to Nyquist of the padded size, normalised by
+        // the actual data length (window sums), not the padded length.
+        Some(scaled_spectrum_with_phase(
+            &data,
+            fft_size,
+            sample_rate,
+            fft_window,
+            self.magnitude_scale,
+            self.dbfs_reference,
+            false,
+        ))
+    }
+
+    /// Two-sided spectrum of the complex signal `i + j·q` formed by the most
+    /// recent samples of an I/Q pair, with the bins fft-shifted so the
+    /// frequencies run from −fs/2 up to +fs/2.  A tone rotating clockwise in
+    /// the I/Q plane shows up at a negative frequency.
+    ///
+    /// The two buffers are paired sample by sample from their ends; the
+    /// sample rate comes from the timestamps of `i_buf`.  Pass the snapshot
+    /// buffers while paused.
+    ///
+    /// Returns `Some(Vec<[frequency, magnitude, phase]>)` like
+    /// [`compute_fft_complex`](Self::compute_fft_complex).
+    pub fn compute_fft_iq(
+        &mut self,
+        i_buf: &VecDeque<[f64; 2]>,
+        q_buf: &VecDeque<[f64; 2]>,
+        fft_size: usize,
+        fft_window: FFTWindow,
+    ) -> Option<Vec<[f64; 3]>> {
+        let available = i_buf.len().min(q_buf.len());
+        let fft_size = effective_fft_size(fft_size, available, self.adaptive_size)?;
+        let (start, sample_rate) = sample_window(i_buf, fft_size)?;
+        self.last_interval_cv = interval_cv(i_buf.iter().skip(start).map(|p| p[0])).unwrap_or(0.0);
+
+        let padded_size = fft_size * self.zero_pad_factor.max(1);
+        let fft = self.get_fft_plan(padded_size);
+
+        let mut data: Vec<Complex<f64>> = i_buf
+            .iter()
+            .skip(start)
+            .zip(q_buf.iter().skip(q_buf.len() - fft_size))
+            .enumerate()
+            .map(|(n, (i, q))| {
+                let w = fft_window.weight(n, fft_size);
+                Complex {
+                    re: i[1] * w,
+                    im: q[1] * w,
+                }
+            })
+            .collect();
+        data.resize(padded_size, Complex { re: 0.0, im: 0.0 });
+
+        fft.process(&mut data);
+
+        Some(scaled_spectrum_with_phase(
+            &data,
+            fft_size,
+            sample_rate,
+            fft_window,
+            self.magnitude_scale,
+            self.dbfs_reference,
+            true,
+        ))
     }
 
     /// Check whether the FFT for a given trace needs to be recomputed.
@@ -307,17 +1125,29 @@ impl FftData {
     pub fn check_window_pause_changed(&mut self, paused: bool) {
         let window_changed = self.cached_window != self.fft_window;
         let paused_changed = self.cached_paused != paused;
-        let fft_size_changed = self.cached_fft_size != self.fft_size;
+        let fft_size_changed =
+            self.cached_fft_size != self.fft_size || self.cached_zero_pad != self.zero_pad_factor;
+        let scale = (
+            self.magnitude_scale,
+            self.dbfs_reference,
+            self.phase,
+            self.phase_mask_db,
+        );
+        let scale_changed = self.cached_scale != scale;
+        if scale_changed {
+            self.cached_scale = scale;
+        }
         if window_changed {
             self.cached_window = self.fft_window;
         }
         if fft_size_changed {
             self.cached_fft_size = self.fft_size;
+            self.cached_zero_pad = self.zero_pad_factor;
         }
         if paused_changed {
             self.cached_paused = paused;
         }
-        if window_changed || paused_changed || fft_size_changed {
+        if window_changed || paused_changed || fft_size_changed || scale_changed {
             self.invalidate_cache();
         }
     }
@@ -351,12 +1181,6 @@ impl FftData {
         paused: bool,
         buffer_snapshot: &Option<VecDeque<[f64; 2]>>,
     ) -> bool {
-        self.ensure_worker();
-        let worker = match self.worker.as_ref() {
-            Some(w) => w,
-            None => return false,
-        };
-
         let src_buf = if paused {
             match buffer_snapshot.as_ref() {
                 Some(s) => s,
@@ -365,44 +1189,79 @@ impl FftData {
         } else {
             buf
         };
+        self.send_job(trace_ref, src_buf, None)
+    }
 
-        let fft_size = self.fft_size;
-        if src_buf.len() < fft_size {
+    /// Dispatch the two-sided spectrum of the I/Q pair `i + j·q` (see
+    /// [`compute_fft_iq`](Self::compute_fft_iq)) to the background worker,
+    /// reported under `trace_ref`.  Returns `true` if the job was sent.
+    #[cfg(feature = "fft")]
+    pub fn dispatch_fft_iq(
+        &mut self,
+        trace_ref: &TraceRef,
+        i: &TraceData,
+        q: &TraceData,
+        paused: bool,
+    ) -> bool {
+        let (i_buf, q_buf) = if paused {
+            match (i.snap.as_ref(), q.snap.as_ref()) {
+                (Some(i), Some(q)) => (i, q),
+                _ => return false,
+            }
+        } else {
+            (&i.live, &q.live)
+        };
+        self.send_job(trace_ref, i_buf, Some(q_buf))
+    }
+
+    #[cfg(feature = "fft")]
+    fn send_job(
+        &mut self,
+        trace_ref: &TraceRef,
+        src_buf: &VecDeque<[f64; 2]>,
+        q_buf: Option<&VecDeque<[f64; 2]>>,
+    ) -> bool {
+        self.ensure_worker();
+        let available = q_buf.map_or(src_buf.len(), |q| q.len().min(src_buf.len()));
+        let Some(fft_size) = self.effective_size(available) else {
             return false;
-        }
+        };
+        let Some((start, sample_rate)) = sample_window(src_buf, fft_size) else {
+            return false;
+        };
+        let worker = match self.worker.as_ref() {
+            Some(w) => w,
+            None => return false,
+        };
 
-        let len = src_buf.len();
-        let start = len - fft_size;
-        let t0 = src_buf.get(start).map(|p| p[0]);
-        let t1 = src_buf.back().map(|p| p[0]);
-        match (t0, t1) {
-            (Some(t0), Some(t1)) if t1 > t0 => {
-                let dt_est = (t1 - t0) / (fft_size as f64 - 1.0);
-                if dt_est <= 0.0 {
-                    return false;
-                }
-                let sample_rate = 1.0 / dt_est;
-                let samples: Vec<f64> = src_buf.iter().skip(start).map(|p| p[1]).collect();
-                let padded_size = self.padded_size();
-
-                worker
-                    .job_sender
-                    .send(FftJob {
-                        trace_ref: trace_ref.clone(),
-                        samples,
-                        fft_size,
-                        padded_size,
-                        sample_rate,
-                        window: self.fft_window,
-                    })
-                    .is_ok()
-            }
-            _ => false,
-        }
+        let samples: Vec<f64> = src_buf.iter().skip(start).map(|p| p[1]).collect();
+        let q_samples = q_buf.map(|q| q.iter().skip(q.len() - fft_size).map(|p| p[1]).collect());
+        let interval_cv = interval_cv(src_buf.iter().skip(start).map(|p| p[0])).unwrap_or(0.0);
+        let padded_size = fft_size * self.zero_pad_factor.max(1);
+
+        worker
+            .job_sender
+            .send(FftJob {
+                trace_ref: trace_ref.clone(),
+                samples,
+                q_samples,
+                fft_size,
+                requested_size: self.fft_size,
+                padded_size,
+                sample_rate,
+                window: self.fft_window,
+                scale: self.magnitude_scale,
+                reference: self.dbfs_reference,
+                phase: self.phase,
+                phase_mask_db: self.phase_mask_db,
+                interval_cv,
+            })
+            .is_ok()
     }
 
     /// Poll for completed FFT results from the background worker (non-blocking).
-    /// Returns `(trace_ref, spectrum, info)` tuples for all completed jobs.
+    /// Returns `(trace_ref, spectrum, info)` tuples for all completed jobs and
+    /// records each result's parameters in [`spectrum_meta`](Self::spectrum_meta).
     #[cfg(feature = "fft")]
     pub fn poll_fft_results(&mut self) -> Vec<(TraceRef, Vec<[f64; 2]>, String)> {
         let worker = match self.worker.as_ref() {
@@ -412,6 +1271,8 @@ impl FftData {
 
         let mut results = Vec::new();
         while let Ok(result) = worker.result_receiver.try_recv() {
+            self.spectrum_meta
+                .insert(result.trace_ref.clone(), result.meta);
             results.push((result.trace_ref, result.spectrum, result.info));
         }
         results
@@ -429,6 +1290,29 @@ impl FftData {
         None
     }
 
+    #[cfg(not(feature = "fft"))]
+    pub fn compute_fft_complex(
+        &mut self,
+        _buf: &VecDeque<[f64; 2]>,
+        _paused: bool,
+        _buffer_snapshot: &Option<VecDeque<[f64; 2]>>,
+        _fft_size: usize,
+        _fft_window: FFTWindow,
+    ) -> Option<Vec<[f64; 3]>> {
+        None
+    }
+
+    #[cfg(not(feature = "fft"))]
+    pub fn compute_fft_iq(
+        &mut self,
+        _i_buf: &VecDeque<[f64; 2]>,
+        _q_buf: &VecDeque<[f64; 2]>,
+        _fft_size: usize,
+        _fft_window: FFTWindow,
+    ) -> Option<Vec<[f64; 3]>> {
+        None
+    }
+
     #[cfg(not(feature = "fft"))]
     pub fn needs_recompute(
         &self,
@@ -460,6 +1344,17 @@ impl FftData {
         false
     }
 
+    #[cfg(not(feature = "fft"))]
+    pub fn dispatch_fft_iq(
+        &mut self,
+        _trace_ref: &TraceRef,
+        _i: &TraceData,
+        _q: &TraceData,
+        _paused: bool,
+    ) -> bool {
+        false
+    }
+
     #[cfg(not(feature = "fft"))]
     pub fn poll_fft_results(&mut self) -> Vec<(TraceRef, Vec<[f64; 2]>, String)> {
         Vec::new()
@@ -487,44 +1382,83 @@ fn fft_worker_loop(
         };
 
         // Apply window function and zero-pad
+        let two_sided = job.q_samples.is_some();
         let mut data: Vec<Complex<f64>> = job
             .samples
             .iter()
             .enumerate()
-            .map(|(i, &v)| Complex {
-                re: v * job.window.weight(i, job.fft_size),
-                im: 0.0,
+            .map(|(i, &v)| {
+                let w = job.window.weight(i, job.fft_size);
+                let q = job.q_samples.as_ref().map_or(0.0, |q| q[i]);
+                Complex {
+                    re: v * w,
+                    im: q * w,
+                }
             })
             .collect();
         data.resize(job.padded_size, Complex { re: 0.0, im: 0.0 });
 
         plan.process(&mut data);
 
-        let half = job.padded_size / 2;
-        let scale = 2.0 / job.fft_size as f64;
-        let mut spectrum: Vec<[f64; 2]> = Vec::with_capacity(half);
-        for (k, c) in data.iter().take(half).enumerate() {
-            let freq = k as f64 * job.sample_rate / job.padded_size as f64;
-            let mag = (c.re * c.re + c.im * c.im).sqrt() * scale;
-            spectrum.push([freq, mag]);
-        }
+        let spectrum = if job.phase {
+            let mut bins = scaled_spectrum_with_phase(
+                &data,
+                job.fft_size,
+                job.sample_rate,
+                job.window,
+                job.scale,
+                job.reference,
+                two_sided,
+            );
+            mask_phase(&mut bins, job.scale, job.phase_mask_db);
+            unwrap_phase(&mut bins);
+            bins.into_iter().map(|[f, _, ph]| [f, ph]).collect()
+        } else {
+            scaled_spectrum(
+                &data,
+                job.fft_size,
+                job.sample_rate,
+                job.window,
+                job.scale,
+                job.reference,
+                two_sided,
+            )
+        };
 
         let pad_factor = job.padded_size / job.fft_size;
-        let info = if pad_factor > 1 {
+        let size = if job.fft_size < job.requested_size {
+            format!("N={}/{}", job.fft_size, job.requested_size)
+        } else {
+            format!("N={}", job.fft_size)
+        };
+        let kind = if two_sided { "I/Q FFT" } else { "FFT" };
+        let mut info = if pad_factor > 1 {
             format!(
-                "FFT N={} ×{} {}",
-                job.fft_size,
+                "{} {} ×{} {}",
+                kind,
+                size,
                 pad_factor,
-                job.window.label()
+                job.window.description()
             )
         } else {
-            format!("FFT N={} {}", job.fft_size, job.window.label())
+            format!("{} {} {}", kind, size, job.window.description())
         };
+        if job.interval_cv > IRREGULAR_SAMPLING_CV {
+            info.push_str(" (irregular sampling)");
+        }
 
         let _ = result_sender.send(FftResult {
             trace_ref: job.trace_ref,
             spectrum,
             info,
+            meta: SpectrumMeta {
+                window: job.window,
+                fft_size: job.fft_size,
+                padded_size: job.padded_size,
+                sample_rate: job.sample_rate,
+                scale: job.scale,
+                interval_cv: job.interval_cv,
+            },
         });
     }
 }
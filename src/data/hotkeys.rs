@@ -136,9 +136,11 @@ impl Hotkey {
 #[serde(default)]
 pub struct Hotkeys {
     pub fft: Option<Hotkey>,
+    pub fft_db_toggle: Option<Hotkey>,
     pub math: Option<Hotkey>,
     pub fit_view: Option<Hotkey>,
     pub fit_view_cont: Option<Hotkey>,
+    pub fit_all_data: Option<Hotkey>,
     pub fit_y: Option<Hotkey>,
     pub traces: Option<Hotkey>,
     pub thresholds: Option<Hotkey>,
@@ -151,18 +153,22 @@ pub struct Hotkeys {
     pub reset_markers: Option<Hotkey>,
     pub clear_all: Option<Hotkey>,
     pub reset_measurements: Option<Hotkey>,
+    pub next_trace: Option<Hotkey>,
+    pub prev_trace: Option<Hotkey>,
 }
 
 impl Default for Hotkeys {
     fn default() -> Self {
         Self {
             fft: Some(Hotkey::new(Modifier::Ctrl, 'F')),
+            fft_db_toggle: Some(Hotkey::new(Modifier::Ctrl, 'D')),
             // revert math hotkey back to Ctrl+M and move measurements to 'P'.
             // (Pause also uses 'P' by default; users can reconfigure if that
             // conflict is undesirable.)
             math: Some(Hotkey::new(Modifier::Ctrl, 'M')),
             fit_view: Some(Hotkey::new(Modifier::None, 'F')),
             fit_view_cont: Some(Hotkey::new(Modifier::None, 'C')),
+            fit_all_data: Some(Hotkey::new(Modifier::Shift, 'F')),
             fit_y: Some(Hotkey::new(Modifier::None, 'Y')),
             traces: Some(Hotkey::new(Modifier::None, 'T')),
             thresholds: Some(Hotkey::new(Modifier::Ctrl, 'T')),
@@ -175,6 +181,8 @@ impl Default for Hotkeys {
             reset_markers: Some(Hotkey::new(Modifier::None, 'R')),
             clear_all: Some(Hotkey::new(Modifier::Ctrl, 'X')),
             reset_measurements: Some(Hotkey::new(Modifier::CtrlShift, 'M')),
+            next_trace: Some(Hotkey::new(Modifier::None, 'N')),
+            prev_trace: Some(Hotkey::new(Modifier::Shift, 'N')),
         }
     }
 }
@@ -216,10 +224,12 @@ impl Hotkeys {
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum HotkeyName {
     Fft,
+    FftDb,
     Math,
     FitView,
     FitY,
     FitViewCont,
+    FitAllData,
     Pause,
     Traces,
     Thresholds,
@@ -230,6 +240,8 @@ pub enum HotkeyName {
     ExportData,
     ClearAll,
     ResetMeasurements,
+    NextTrace,
+    PrevTrace,
 }
 
 fn key_from_char(c: char) -> Option<egui::Key> {
@@ -408,6 +420,9 @@ pub fn detect_hotkey_actions(cfg: &Hotkeys, ctx: &egui::Context) -> Vec<HotkeyNa
             if matches_cfg(cfg.fit_view_cont.as_ref(), &hk) {
                 push_action(&mut actions, HotkeyName::FitViewCont);
             }
+            if matches_cfg(cfg.fit_all_data.as_ref(), &hk) {
+                push_action(&mut actions, HotkeyName::FitAllData);
+            }
             if matches_cfg(cfg.reset_measurements.as_ref(), &hk) {
                 push_action(&mut actions, HotkeyName::ResetMeasurements);
             }
@@ -441,6 +456,15 @@ pub fn detect_hotkey_actions(cfg: &Hotkeys, ctx: &egui::Context) -> Vec<HotkeyNa
             if matches_cfg(cfg.clear_all.as_ref(), &hk) {
                 push_action(&mut actions, HotkeyName::ClearAll);
             }
+            if matches_cfg(cfg.fft_db_toggle.as_ref(), &hk) {
+                push_action(&mut actions, HotkeyName::FftDb);
+            }
+            if matches_cfg(cfg.next_trace.as_ref(), &hk) {
+                push_action(&mut actions, HotkeyName::NextTrace);
+            }
+            if matches_cfg(cfg.prev_trace.as_ref(), &hk) {
+                push_action(&mut actions, HotkeyName::PrevTrace);
+            }
         }
     }
 
@@ -463,6 +487,9 @@ pub fn detect_hotkey_actions(cfg: &Hotkeys, ctx: &egui::Context) -> Vec<HotkeyNa
         if is_hotkey_pressed(cfg.fit_view_cont.as_ref(), &input) {
             push_action(&mut actions, HotkeyName::FitViewCont);
         }
+        if is_hotkey_pressed(cfg.fit_all_data.as_ref(), &input) {
+            push_action(&mut actions, HotkeyName::FitAllData);
+        }
         if is_hotkey_pressed(cfg.reset_measurements.as_ref(), &input) {
             push_action(&mut actions, HotkeyName::ResetMeasurements);
         }
@@ -496,6 +523,15 @@ pub fn detect_hotkey_actions(cfg: &Hotkeys, ctx: &egui::Context) -> Vec<HotkeyNa
         if is_hotkey_pressed(cfg.clear_all.as_ref(), &input) {
             push_action(&mut actions, HotkeyName::ClearAll);
         }
+        if is_hotkey_pressed(cfg.fft_db_toggle.as_ref(), &input) {
+            push_action(&mut actions, HotkeyName::FftDb);
+        }
+        if is_hotkey_pressed(cfg.next_trace.as_ref(), &input) {
+            push_action(&mut actions, HotkeyName::NextTrace);
+        }
+        if is_hotkey_pressed(cfg.prev_trace.as_ref(), &input) {
+            push_action(&mut actions, HotkeyName::PrevTrace);
+        }
     }
 
     actions
@@ -533,9 +569,18 @@ pub fn handle_hotkeys(main_panel: &mut LivePlotPanel, ctx: &egui::Context) {
                     scope.y_axis.auto_fit = !auto_fit;
                 }
             }
+            HotkeyName::FitAllData => {
+                data.request_fit_all_data();
+            }
             HotkeyName::ResetMeasurements => {
                 data.pending_requests.clear_measurements = true;
             }
+            HotkeyName::NextTrace => {
+                data.pending_requests.cycle_measurement_trace = 1;
+            }
+            HotkeyName::PrevTrace => {
+                data.pending_requests.cycle_measurement_trace = -1;
+            }
             HotkeyName::Traces => {
                 main_panel.toggle_panel_visibility::<TracesPanel>();
                 main_panel.hide_hotkeys_panel();
@@ -570,6 +615,13 @@ pub fn handle_hotkeys(main_panel: &mut LivePlotPanel, ctx: &egui::Context) {
                     main_panel.hide_hotkeys_panel();
                 }
             }
+            HotkeyName::FftDb =>
+            {
+                #[cfg(feature = "fft")]
+                if let Some(fft) = main_panel.fft_panel_mut() {
+                    fft.toggle_db();
+                }
+            }
             HotkeyName::SavePng => {
                 data.pending_requests.screenshot = Some(crate::data::data::ScreenshotRequest {
                     target: crate::data::data::ScreenshotTarget::CenterPanel,
@@ -589,10 +641,12 @@ pub fn handle_hotkeys(main_panel: &mut LivePlotPanel, ctx: &egui::Context) {
 pub fn get_hotkey_for_name<'h>(hotkeys: &'h Hotkeys, name: HotkeyName) -> Option<&'h Hotkey> {
     match name {
         HotkeyName::Fft => hotkeys.fft.as_ref(),
+        HotkeyName::FftDb => hotkeys.fft_db_toggle.as_ref(),
         HotkeyName::Math => hotkeys.math.as_ref(),
         HotkeyName::FitView => hotkeys.fit_view.as_ref(),
         HotkeyName::FitY => hotkeys.fit_y.as_ref(),
         HotkeyName::FitViewCont => hotkeys.fit_view_cont.as_ref(),
+        HotkeyName::FitAllData => hotkeys.fit_all_data.as_ref(),
         HotkeyName::Pause => hotkeys.pause.as_ref(),
         HotkeyName::Traces => hotkeys.traces.as_ref(),
         HotkeyName::Thresholds => hotkeys.thresholds.as_ref(),
@@ -603,6 +657,8 @@ pub fn get_hotkey_for_name<'h>(hotkeys: &'h Hotkeys, name: HotkeyName) -> Option
         HotkeyName::ExportData => hotkeys.export_data.as_ref(),
         HotkeyName::ClearAll => hotkeys.clear_all.as_ref(),
         HotkeyName::ResetMeasurements => hotkeys.reset_measurements.as_ref(),
+        HotkeyName::NextTrace => hotkeys.next_trace.as_ref(),
+        HotkeyName::PrevTrace => hotkeys.prev_trace.as_ref(),
     }
 }
 
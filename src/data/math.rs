@@ -24,7 +24,7 @@
 //!
 //! See individual functions and types for more detailed documentation and per-line notes.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use serde::{Deserialize, Serialize};
 
@@ -35,6 +35,9 @@ use serde::{Deserialize, Serialize};
 /// passed to computation routines.
 use crate::data::traces::TraceRef;
 
+use crate::data::expr::{ExprError, ParsedExpr};
+use std::borrow::Cow;
+
 /// Parameters describing a biquad / low-order IIR filter in direct form I.
 ///
 /// The coefficients are stored as arrays following the conventional biquad
@@ -56,6 +59,17 @@ pub struct BiquadParams {
     pub a: [f64; 3],
 }
 
+/// Response shape of a [`FilterKind::Butterworth`] filter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FilterBand {
+    Lowpass,
+    Highpass,
+    /// Pass band from the filter's `cutoff_hz` up to `high_cut_hz`.
+    Bandpass {
+        high_cut_hz: f64,
+    },
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum MinMaxMode {
     Min,
@@ -82,6 +96,17 @@ pub enum FilterKind {
     BiquadHighpass { cutoff_hz: f64, q: f64 },
     /// Biquad bandpass (constant skirt gain, peak gain = Q)
     BiquadBandpass { center_hz: f64, q: f64 },
+    /// Biquad notch (band-stop) with center frequency and Q
+    BiquadNotch { center_hz: f64, q: f64 },
+    /// Butterworth filter of even `order`, built from `order / 2` cascaded
+    /// biquad sections with the Butterworth Q of each stage.  A bandpass runs
+    /// a highpass cascade at `cutoff_hz` into a lowpass cascade at its high
+    /// cutoff, each of `order`.
+    Butterworth {
+        kind: FilterBand,
+        cutoff_hz: f64,
+        order: u8,
+    },
     /// Raw custom biquad coefficients (advanced)
     Custom { params: BiquadParams },
 }
@@ -90,8 +115,8 @@ pub enum FilterKind {
 ///
 /// Each variant describes a different computation. Note which kinds are
 /// stateless and can be fully recomputed on the union grid (Add, Multiply,
-/// Divide, Differentiate) versus which require persistent runtime state and
-/// incremental processing (Integrate, Filter, MinMax).
+/// Divide, Differentiate, Abs) versus which require persistent runtime state and
+/// incremental processing (Integrate, Filter, MinMax, Rms, MovingAverage).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MathKind {
     /// Sum or difference of N traces: sum_i (sign_i * x_i)
@@ -102,6 +127,8 @@ pub enum MathKind {
     Divide { a: TraceRef, b: TraceRef },
     /// Numerical derivative of one trace (dy/dt)
     Differentiate { input: TraceRef },
+    /// Absolute value of one trace (full-wave rectifier): |y|
+    Abs { input: TraceRef },
     /// Numerical integral of one trace (∫ y dt), optional initial value
     Integrate { input: TraceRef, y0: f64 },
     /// IIR filter on one trace
@@ -113,6 +140,22 @@ pub enum MathKind {
         decay_per_sec: Option<f64>,
         mode: MinMaxMode,
     },
+    /// Magnitude of the complex signal `i + j·q`: sqrt(i² + q²)
+    Magnitude { i: TraceRef, q: TraceRef },
+    /// Phase of the complex signal `i + j·q` in radians: atan2(q, i)
+    Phase { i: TraceRef, q: TraceRef },
+    /// Free-form formula over trace names, e.g. `(a + 2*b) / c`; see [`crate::data::expr`].
+    /// `vars` binds short variable names to traces (`a` → `"motor current"`);
+    /// unbound identifiers name traces directly.
+    Expr {
+        expr: String,
+        #[serde(default)]
+        vars: Vec<(String, TraceRef)>,
+    },
+    /// Running RMS over the last `window_secs` seconds, weighted by sample spacing
+    Rms { input: TraceRef, window_secs: f64 },
+    /// Boxcar average of the samples within the last `window_secs` seconds
+    MovingAverage { input: TraceRef, window_secs: f64 },
 }
 
 /// Fully-defined math trace configuration.
@@ -124,65 +167,71 @@ pub enum MathKind {
 pub struct MathTrace {
     pub name: TraceRef,
     pub kind: MathKind,
-    // #[serde(skip)]
-    // runtime_state: MathRuntimeState,
+    #[serde(skip)]
+    runtime_state: MathRuntimeState,
+    #[serde(skip)]
+    expr_cache: Option<ExprCache>,
+}
+
+/// Parsed formula of a [`MathKind::Expr`], reused until its text or variable
+/// bindings change.
+#[derive(Debug, Clone)]
+struct ExprCache {
+    expr: String,
+    vars: Vec<(String, TraceRef)>,
+    parsed: Result<ParsedExpr, ExprError>,
+    /// Trace behind each of the parsed formula's variables, in order.
+    inputs: Vec<TraceRef>,
+}
+
+impl ExprCache {
+    fn new(expr: &str, vars: &[(String, TraceRef)]) -> Self {
+        let parsed = ParsedExpr::parse(expr);
+        let inputs = parsed
+            .as_ref()
+            .map(|p| p.variables().iter().map(|v| expr_input(v, vars)).collect())
+            .unwrap_or_default();
+        Self {
+            expr: expr.to_string(),
+            vars: vars.to_vec(),
+            parsed,
+            inputs,
+        }
+    }
+
+    fn for_kind(kind: &MathKind) -> Option<Self> {
+        match kind {
+            MathKind::Expr { expr, vars } => Some(Self::new(expr, vars)),
+            _ => None,
+        }
+    }
+
+    fn is_for(&self, expr: &str, vars: &[(String, TraceRef)]) -> bool {
+        self.expr == expr && self.vars == vars
+    }
 }
 
 /// Runtime state for stateful math traces.
 ///
-/// Integrators, IIR filters and min/max trackers need to persist a small amount
-/// of state between successive recomputations so that they can be updated
-/// incrementally when new samples arrive. This struct holds that state and is
-/// stored per-math-trace in `LivePlotApp::math_states`.
-// #[derive(Debug, Clone)]
-// struct MathRuntimeState {
-//     /// Timestamp of the last processed input sample (or None if no samples yet).
-//     pub last_t: Option<f64>,
-//     /// Accumulator for the integrator (running integral value).
-//     //pub accum: f64,
-//     // For biquad: previous two input samples x[n-1], x[n-2] and previous two
-//     // output samples y[n-1], y[n-2]. These are used to implement Direct Form I.
-//     pub x1: f64,
-//     pub x2: f64,
-//     pub y1: f64,
-//     pub y2: f64,
-//     // Secondary section for cascade filters (used by Bandpass implementation).
-//     pub x1b: f64,
-//     pub x2b: f64,
-//     pub y1b: f64,
-//     pub y2b: f64,
-//     // For MinMax tracker: running min and max. Initialized to infinities so the
-//     // first real sample sets them properly.
-//     // pub min_val: f64,
-//     // pub max_val: f64,
-//     // /// Timestamp where decay was last applied for the min/max exponential decay.
-//     // pub last_decay_t: Option<f64>,
-//     // Previous input sample used for incremental algorithms like integrate.
-//     pub prev_in_t: Option<f64>,
-//     pub prev_in_v: f64,
-// }
-
-// impl Default for MathRuntimeState {
-//     fn default() -> Self {
-//         Self {
-//             last_t: None,
-//             //accum: 0.0,
-//             x1: 0.0,
-//             x2: 0.0,
-//             y1: 0.0,
-//             y2: 0.0,
-//             x1b: 0.0,
-//             x2b: 0.0,
-//             y1b: 0.0,
-//             y2b: 0.0,
-//             // min_val: f64::INFINITY,
-//             // max_val: f64::NEG_INFINITY,
-//             // last_decay_t: None,
-//             prev_in_t: None,
-//             prev_in_v: 0.0,
-//         }
-//     }
-// }
+/// Most stateful kinds (integrators, IIR filters, min/max trackers) rebuild
+/// what they need from their previous output on every call.  Kinds that need
+/// more history than that keep it here; it is never persisted and is rebuilt
+/// from the sources whenever it does not continue the previous output (e.g.
+/// on the snapshot pass while paused, or after a reset).
+#[derive(Debug, Clone, Default)]
+struct MathRuntimeState {
+    /// Timestamp of the last input sample folded into the state.
+    last_t: Option<f64>,
+    /// Moving average: input samples currently inside the window.
+    window: VecDeque<[f64; 2]>,
+    /// Moving average: sum of the values in `window`.
+    window_sum: f64,
+    /// Butterworth: delay line `[x1, x2, y1, y2]` of each biquad section.
+    stages: Vec<[f64; 4]>,
+    /// Expression: timestamp of the first sample the formula evaluated to
+    /// NaN or ±∞ at (that sample is left out of the output).
+    non_finite_at: Option<f64>,
+}
 
 /// Compute a math trace given source traces. Each source trace is provided as a slice of
 /// monotonically increasing [t, y]. The result is densely sampled at the union of timestamps
@@ -214,11 +263,60 @@ impl MathTrace {
     pub fn new(name: TraceRef, kind: MathKind) -> Self {
         Self {
             name,
+            expr_cache: ExprCache::for_kind(&kind),
             kind,
-            // runtime_state: MathRuntimeState::default(),
+            runtime_state: MathRuntimeState::default(),
         }
     }
 
+    /// Re-parse an expression kind if its text or bindings changed since the
+    /// last parse.
+    fn refresh_expr_cache(&mut self) {
+        let MathKind::Expr { expr, vars } = &self.kind else {
+            self.expr_cache = None;
+            return;
+        };
+        if !self
+            .expr_cache
+            .as_ref()
+            .is_some_and(|c| c.is_for(expr, vars))
+        {
+            self.expr_cache = Some(ExprCache::new(expr, vars));
+        }
+    }
+
+    /// The cached parse of an expression kind if it is current, else a fresh
+    /// one; `None` for other kinds.
+    fn expr_parse(&self) -> Option<Cow<'_, ExprCache>> {
+        let MathKind::Expr { expr, vars } = &self.kind else {
+            return None;
+        };
+        Some(match &self.expr_cache {
+            Some(c) if c.is_for(expr, vars) => Cow::Borrowed(c),
+            _ => Cow::Owned(ExprCache::new(expr, vars)),
+        })
+    }
+
+    /// Drop all runtime state so the next [`compute_math_trace`](Self::compute_math_trace)
+    /// starts afresh.  Stateful kinds also continue from their previous
+    /// output, so clear that as well for a clean recompute.
+    pub fn reset_state(&mut self) {
+        self.runtime_state = MathRuntimeState::default();
+    }
+
+    /// Whether this kind carries state from one computation to the next
+    /// (integrators, filters, min/max trackers, RMS and moving averages).
+    pub fn is_stateful(&self) -> bool {
+        matches!(
+            self.kind,
+            MathKind::Integrate { .. }
+                | MathKind::Filter { .. }
+                | MathKind::MinMax { .. }
+                | MathKind::Rms { .. }
+                | MathKind::MovingAverage { .. }
+        )
+    }
+
     pub fn compute_math_trace(
         &mut self,
         sources: &HashMap<TraceRef, Vec<[f64; 2]>>,
@@ -238,6 +336,7 @@ impl MathTrace {
             data.partition_point(|p| p[0] <= t)
         };
 
+        self.refresh_expr_cache();
         match &self.kind {
             MathKind::Add { inputs } => {
                 let used_sources: Vec<&[[f64; 2]]> = inputs
@@ -368,6 +467,18 @@ impl MathTrace {
                     return out;
                 }
             }
+            MathKind::Abs { input } => {
+                if let Some(src) = sources.get(input) {
+                    let start = if let Some(last) = out.last() {
+                        skip_until(src.as_slice(), last[0])
+                    } else {
+                        0
+                    };
+                    out.extend(src[start..].iter().map(|p| [p[0], p[1].abs()]));
+                } else {
+                    return out;
+                }
+            }
             MathKind::Integrate { input, y0 } => {
                 if let Some(src) = sources.get(input) {
                     let start = if let Some(last) = out.last() {
@@ -409,6 +520,58 @@ impl MathTrace {
                     Some(v) => v,
                     None => return out,
                 };
+                // Coefficients of the one section (two for the first-order
+                // bandpass) of a preset at sampling interval dt.  Butterworth
+                // cascades keep their section state between calls instead.
+                type Sections = Box<dyn Fn(f64) -> (BiquadParams, Option<BiquadParams>)>;
+                let sections: Sections = match *kind {
+                    FilterKind::Lowpass { cutoff_hz } => {
+                        Box::new(move |dt| (MathTrace::first_order_lowpass(cutoff_hz, dt), None))
+                    }
+                    FilterKind::Highpass { cutoff_hz } => {
+                        Box::new(move |dt| (MathTrace::first_order_highpass(cutoff_hz, dt), None))
+                    }
+                    FilterKind::Bandpass {
+                        low_cut_hz,
+                        high_cut_hz,
+                    } => {
+                        // Implement bandpass as cascade: highpass -> lowpass.
+                        Box::new(move |dt| {
+                            (
+                                MathTrace::first_order_highpass(low_cut_hz, dt),
+                                Some(MathTrace::first_order_lowpass(high_cut_hz, dt)),
+                            )
+                        })
+                    }
+                    FilterKind::BiquadLowpass { cutoff_hz, q } => {
+                        Box::new(move |dt| (MathTrace::biquad_lowpass(cutoff_hz, q, dt), None))
+                    }
+                    FilterKind::BiquadHighpass { cutoff_hz, q } => {
+                        Box::new(move |dt| (MathTrace::biquad_highpass(cutoff_hz, q, dt), None))
+                    }
+                    FilterKind::BiquadBandpass { center_hz, q } => {
+                        Box::new(move |dt| (MathTrace::biquad_bandpass(center_hz, q, dt), None))
+                    }
+                    FilterKind::BiquadNotch { center_hz, q } => {
+                        Box::new(move |dt| (MathTrace::biquad_notch(center_hz, q, dt), None))
+                    }
+                    FilterKind::Custom { params } => Box::new(move |_| (params, None)),
+                    FilterKind::Butterworth {
+                        kind: band,
+                        cutoff_hz,
+                        order,
+                    } => {
+                        MathTrace::butterworth_cascade(
+                            &mut self.runtime_state,
+                            band,
+                            cutoff_hz,
+                            order,
+                            data,
+                            &mut out,
+                        );
+                        return out;
+                    }
+                };
                 // Reconstruct delay elements (x1,x2,y1,y2) and last processed time
                 // from existing output `out` if available.
                 let mut x1: f64 = 0.0;
@@ -483,73 +646,25 @@ impl MathTrace {
                     };
 
                     // Compute filter coefficients for the current dt and run one
-                    // step of the direct-form I biquad.
-                    let y = match kind {
-                        FilterKind::Lowpass { cutoff_hz } => {
-                            let p = MathTrace::first_order_lowpass(*cutoff_hz, dt);
-                            MathTrace::biquad_step(p, x, x1, x2, y1, y2)
-                        }
-                        FilterKind::Highpass { cutoff_hz } => {
-                            let p = MathTrace::first_order_highpass(*cutoff_hz, dt);
-                            MathTrace::biquad_step(p, x, x1, x2, y1, y2)
-                        }
-                        FilterKind::Bandpass {
-                            low_cut_hz,
-                            high_cut_hz,
-                        } => {
-                            // Implement bandpass as cascade: highpass -> lowpass.
-                            let p1 = MathTrace::first_order_highpass(*low_cut_hz, dt);
-                            let z1 = MathTrace::biquad_step(p1, x, x1, x2, y1, y2);
-                            let p2 = MathTrace::first_order_lowpass(*high_cut_hz, dt);
-                            MathTrace::biquad_step(p2, z1, x1b, x2b, y1b, y2b)
-                        }
-                        FilterKind::BiquadLowpass { cutoff_hz, q } => {
-                            let p = MathTrace::biquad_lowpass(*cutoff_hz, *q, dt);
-                            MathTrace::biquad_step(p, x, x1, x2, y1, y2)
-                        }
-                        FilterKind::BiquadHighpass { cutoff_hz, q } => {
-                            let p = MathTrace::biquad_highpass(*cutoff_hz, *q, dt);
-                            MathTrace::biquad_step(p, x, x1, x2, y1, y2)
-                        }
-                        FilterKind::BiquadBandpass { center_hz, q } => {
-                            let p = MathTrace::biquad_bandpass(*center_hz, *q, dt);
-                            MathTrace::biquad_step(p, x, x1, x2, y1, y2)
-                        }
-                        FilterKind::Custom { params } => {
-                            MathTrace::biquad_step(*params, x, x1, x2, y1, y2)
-                        }
-                    };
-
-                    // Advance delay-line state. Bandpass uses a cascade so we must
-                    // update both primary and secondary sections using the
-                    // intermediate z1 value computed above.
-                    match kind {
-                        FilterKind::Bandpass { .. } => {
-                            // Recompute the first section step to obtain the z1 used
-                            // as input to the second section when updating the
-                            // internal delay elements.
-                            let p1 = if let FilterKind::Bandpass { low_cut_hz, .. } = kind {
-                                MathTrace::first_order_highpass(*low_cut_hz, dt)
-                            } else {
-                                MathTrace::first_order_highpass(1.0, dt)
-                            };
-                            let z1 = MathTrace::biquad_step(p1, x, x1, x2, y1, y2);
-                            x2 = x1;
-                            x1 = x;
-                            y2 = y1;
-                            y1 = z1;
+                    // step of the direct-form I biquad (two in series for the
+                    // bandpass cascade), then advance the delay lines.
+                    let (p1, p2) = sections(dt);
+                    let z1 = MathTrace::biquad_step(p1, x, x1, x2, y1, y2);
+                    x2 = x1;
+                    x1 = x;
+                    y2 = y1;
+                    y1 = z1;
+                    let y = match p2 {
+                        Some(p2) => {
+                            let y = MathTrace::biquad_step(p2, z1, x1b, x2b, y1b, y2b);
                             x2b = x1b;
                             x1b = z1;
                             y2b = y1b;
                             y1b = y;
+                            y
                         }
-                        _ => {
-                            x2 = x1;
-                            x1 = x;
-                            y2 = y1;
-                            y1 = y;
-                        }
-                    }
+                        None => z1,
+                    };
 
                     last_t = Some(t);
                     out.push([t, y]);
@@ -606,6 +721,182 @@ impl MathTrace {
                     return out;
                 }
             }
+            MathKind::Magnitude { i, q } | MathKind::Phase { i, q } => {
+                let phase = matches!(self.kind, MathKind::Phase { .. });
+                if let (Some(src_i), Some(src_q)) = (sources.get(i), sources.get(q)) {
+                    let grid: Vec<f64> =
+                        MathTrace::union_times(&[src_i.as_slice(), src_q.as_slice()]);
+
+                    let start = if let Some(last) = out.last() {
+                        grid.partition_point(|&t| t <= last[0])
+                    } else {
+                        0
+                    };
+
+                    let mut idx_i = 0usize;
+                    let mut idx_q = 0usize;
+                    for &t in &grid[start..] {
+                        if let (Some(vi), Some(vq)) = (
+                            MathTrace::interpolate_value_at(t, src_i.as_slice(), &mut idx_i),
+                            MathTrace::interpolate_value_at(t, src_q.as_slice(), &mut idx_q),
+                        ) {
+                            let v = if phase { vq.atan2(vi) } else { vi.hypot(vq) };
+                            out.push([t, v]);
+                        }
+                    }
+                } else {
+                    return out;
+                }
+            }
+            MathKind::Rms { input, window_secs } => {
+                if let Some(src) = sources.get(input) {
+                    let start = if let Some(last) = out.last() {
+                        skip_until(src.as_slice(), last[0])
+                    } else {
+                        0
+                    };
+                    if start >= src.len() {
+                        return out;
+                    }
+                    let window = window_secs.max(0.0);
+                    // Energy of the segment between samples k-1 and k (trapezoidal on v²).
+                    let seg = |k: usize| -> f64 {
+                        let (a, b) = (src[k - 1], src[k]);
+                        0.5 * (a[1] * a[1] + b[1] * b[1]) * (b[0] - a[0])
+                    };
+
+                    // `j` is the first sample inside the window; `sum` holds the energy of
+                    // all whole segments from `j` to the current sample.  Older samples
+                    // only exist as far back as the source buffer has been pruned, so the
+                    // window is simply shorter until enough history is available.
+                    let t_first = src[start][0];
+                    let mut j = src.partition_point(|p| p[0] < t_first - window);
+                    let mut sum: f64 = (j + 1..=start).map(seg).sum();
+
+                    for i in start..src.len() {
+                        let t = src[i][0];
+                        if i > start {
+                            sum += seg(i);
+                        }
+                        while j < i && src[j][0] < t - window {
+                            sum -= seg(j + 1);
+                            j += 1;
+                        }
+                        // Part of the segment straddling the window start.
+                        let mut energy = sum;
+                        if j > 0 {
+                            let (a, b) = (src[j - 1], src[j]);
+                            energy +=
+                                0.5 * (a[1] * a[1] + b[1] * b[1]) * (b[0] - (t - window)).max(0.0);
+                        }
+                        let span = (t - src[0][0]).min(window);
+                        let rms = if span > 0.0 {
+                            (energy.max(0.0) / span).sqrt()
+                        } else {
+                            src[i][1].abs()
+                        };
+                        out.push([t, rms]);
+                    }
+                } else {
+                    return out;
+                }
+            }
+            MathKind::MovingAverage { input, window_secs } => {
+                let Some(src) = sources.get(input) else {
+                    return out;
+                };
+                let start = if let Some(last) = out.last() {
+                    skip_until(src.as_slice(), last[0])
+                } else {
+                    0
+                };
+                if start >= src.len() {
+                    return out;
+                }
+                let window = window_secs.max(0.0);
+                let state = &mut self.runtime_state;
+
+                // The ring buffer only carries over if it ends exactly where the
+                // previous output does; otherwise refill it from the source.
+                let last_out = out.last().map(|p| p[0]);
+                if last_out.is_none() || state.last_t != last_out {
+                    state.window.clear();
+                    let t_first = src[start][0];
+                    let from = src.partition_point(|p| p[0] <= t_first - window);
+                    state
+                        .window
+                        .extend(src[from.min(start)..start].iter().copied());
+                    state.window_sum = state.window.iter().map(|p| p[1]).sum();
+                }
+
+                for &p in &src[start..] {
+                    let t = p[0];
+                    state.window.push_back(p);
+                    state.window_sum += p[1];
+                    // Samples at least `window` old drop out, so a window shorter
+                    // than the sample spacing leaves only the newest sample.
+                    while state.window.len() > 1 && state.window[0][0] <= t - window {
+                        let old = state.window.pop_front().unwrap();
+                        state.window_sum -= old[1];
+                    }
+                    if state.window.len() == 1 {
+                        // Shed accumulated round-off whenever the window collapses.
+                        state.window_sum = p[1];
+                    }
+                    out.push([t, state.window_sum / state.window.len() as f64]);
+                }
+                state.last_t = out.last().map(|p| p[0]);
+            }
+            MathKind::Expr { .. } => {
+                let Some(ExprCache {
+                    parsed: Ok(parsed),
+                    inputs,
+                    ..
+                }) = &self.expr_cache
+                else {
+                    return out;
+                };
+                let Some(used_sources) = inputs
+                    .iter()
+                    .map(|input| sources.get(input).map(|d| d.as_slice()))
+                    .collect::<Option<Vec<&[[f64; 2]]>>>()
+                else {
+                    return out;
+                };
+                if used_sources.is_empty() {
+                    // Constant formula: nothing to align it to.
+                    return out;
+                }
+
+                let grid: Vec<f64> = MathTrace::union_times(&used_sources);
+                let start = if let Some(last) = out.last() {
+                    grid.partition_point(|&t| t <= last[0])
+                } else {
+                    0
+                };
+
+                let mut idx = vec![0usize; used_sources.len()];
+                let mut values = vec![0.0; used_sources.len()];
+                for &t in &grid[start..] {
+                    let all = used_sources
+                        .iter()
+                        .zip(idx.iter_mut())
+                        .zip(values.iter_mut())
+                        .all(|((src, last_idx), value)| {
+                            MathTrace::interpolate_value_at(t, src, last_idx)
+                                .map(|v| *value = v)
+                                .is_some()
+                        });
+                    if all {
+                        let v = parsed.eval(&values);
+                        if v.is_finite() {
+                            out.push([t, v]);
+                        } else if self.runtime_state.non_finite_at.is_none() {
+                            self.runtime_state.non_finite_at = Some(t);
+                        }
+                    }
+                }
+            }
         }
 
         out
@@ -711,18 +1002,63 @@ impl MathTrace {
     }
 
     /// Return the names of all source traces referenced by this math trace.
+    ///
+    /// For [`MathKind::Expr`] these are the traces of the formula as last
+    /// parsed, on construction or by the last
+    /// [`compute_math_trace`](Self::compute_math_trace); use
+    /// [`input_traces`](Self::input_traces) to follow edits in between.
     pub fn input_trace_names(&self) -> Vec<&TraceRef> {
         match &self.kind {
             MathKind::Add { inputs } => inputs.iter().map(|(r, _)| r).collect(),
             MathKind::Multiply { a, b } => vec![a, b],
             MathKind::Divide { a, b } => vec![a, b],
             MathKind::Differentiate { input } => vec![input],
+            MathKind::Abs { input } => vec![input],
             MathKind::Integrate { input, .. } => vec![input],
             MathKind::Filter { input, .. } => vec![input],
             MathKind::MinMax { input, .. } => vec![input],
+            MathKind::Rms { input, .. } => vec![input],
+            MathKind::MovingAverage { input, .. } => vec![input],
+            MathKind::Magnitude { i, q } => vec![i, q],
+            MathKind::Phase { i, q } => vec![i, q],
+            MathKind::Expr { .. } => self
+                .expr_cache
+                .iter()
+                .flat_map(|c| c.inputs.iter())
+                .collect(),
+        }
+    }
+
+    /// Owned list of the source traces referenced by this math trace, always
+    /// matching the current definition.  For [`MathKind::Expr`] these are the
+    /// traces named in the formula, after resolving variable bindings (none
+    /// if it does not parse).
+    pub fn input_traces(&self) -> Vec<TraceRef> {
+        match self.expr_parse() {
+            Some(cache) => cache.inputs.clone(),
+            None => self.input_trace_names().into_iter().cloned().collect(),
         }
     }
 
+    /// Why this definition cannot be computed from `sources`, if it cannot.
+    ///
+    /// Only formula kinds can fail: returns the parse error, the first
+    /// referenced trace that is not present in `sources`, or the first
+    /// timestamp at which the formula did not give a finite value (e.g. a
+    /// division by zero) since it was last edited or its state was reset.
+    pub fn math_error(&self, sources: &HashMap<TraceRef, Vec<[f64; 2]>>) -> Option<String> {
+        let cache = self.expr_parse()?;
+        let parsed = match &cache.parsed {
+            Err(e) => return Some(e.to_string()),
+            Ok(p) => p,
+        };
+        missing_input(parsed, &cache.inputs, |name| sources.contains_key(name)).or_else(|| {
+            self.runtime_state
+                .non_finite_at
+                .map(|t| format!("non-finite result at t = {t}"))
+        })
+    }
+
     /// Build a human-readable formula description for a math trace.
     pub fn math_formula_string(&self) -> String {
         match &self.kind {
@@ -747,6 +1083,7 @@ impl MathTrace {
             MathKind::Multiply { a, b } => format!("{} * {}", a.0, b.0),
             MathKind::Divide { a, b } => format!("{} / {}", a.0, b.0),
             MathKind::Differentiate { input } => format!("d({})/dt", input.0),
+            MathKind::Abs { input } => format!("|{}|", input.0),
             MathKind::Integrate { input, y0 } => format!("∫ {} dt  (y0={:.3})", input.0, y0),
             MathKind::Filter { input, kind } => {
                 let k = match kind {
@@ -765,6 +1102,24 @@ impl MathTrace {
                     FilterKind::BiquadBandpass { center_hz, q } => {
                         format!("BQ-BP f0={:.3} Q={:.3}", center_hz, q)
                     }
+                    FilterKind::BiquadNotch { center_hz, q } => {
+                        format!("BQ-Notch f0={:.3} Q={:.3}", center_hz, q)
+                    }
+                    FilterKind::Butterworth {
+                        kind,
+                        cutoff_hz,
+                        order,
+                    } => match kind {
+                        FilterBand::Lowpass => {
+                            format!("BW{}-LP fc={:.3} Hz", order, cutoff_hz)
+                        }
+                        FilterBand::Highpass => {
+                            format!("BW{}-HP fc={:.3} Hz", order, cutoff_hz)
+                        }
+                        FilterBand::Bandpass { high_cut_hz } => {
+                            format!("BW{}-BP {:.3}-{:.3} Hz", order, cutoff_hz, high_cut_hz)
+                        }
+                    },
                     FilterKind::Custom { .. } => "Custom biquad".to_string(),
                 };
                 format!("{} -> {}", input.0, k)
@@ -783,6 +1138,26 @@ impl MathTrace {
                     None => format!("{}({})", mm, input.0),
                 }
             }
+            MathKind::Magnitude { i, q } => format!("|{} + j·{}|", i.0, q.0),
+            MathKind::Phase { i, q } => format!("arg({} + j·{})", i.0, q.0),
+            MathKind::Expr { expr, vars } => {
+                let bound: Vec<String> = vars
+                    .iter()
+                    .filter(|(v, _)| !v.trim().is_empty())
+                    .map(|(v, t)| format!("{} = {}", v.trim(), t.0))
+                    .collect();
+                if bound.is_empty() {
+                    expr.trim().to_string()
+                } else {
+                    format!("{}  ({})", expr.trim(), bound.join(", "))
+                }
+            }
+            MathKind::Rms { input, window_secs } => {
+                format!("RMS({}) over {:.3} s", input.0, window_secs)
+            }
+            MathKind::MovingAverage { input, window_secs } => {
+                format!("avg({}) over {:.3} s", input.0, window_secs)
+            }
         }
     }
 
@@ -917,4 +1292,158 @@ impl MathTrace {
             a: [a0, a1, a2],
         }
     }
+
+    /// Run the Butterworth cascade over the samples of `src` not yet in
+    /// `out`, appending to `out`.  The section delay lines live in `state`.
+    fn butterworth_cascade(
+        state: &mut MathRuntimeState,
+        band: FilterBand,
+        cutoff_hz: f64,
+        order: u8,
+        src: &[[f64; 2]],
+        out: &mut Vec<[f64; 2]>,
+    ) {
+        let start = match out.last() {
+            Some(last) => src.partition_point(|p| p[0] <= last[0]),
+            None => 0,
+        };
+        if start >= src.len() {
+            return;
+        }
+        // (coefficients, cutoff, Q) of each section in signal order.
+        type Design = fn(f64, f64, f64) -> BiquadParams;
+        let cascade = |design: Design, fc: f64| {
+            MathTrace::butterworth_qs(order)
+                .into_iter()
+                .map(move |q| (design, fc, q))
+        };
+        let sections: Vec<(Design, f64, f64)> = match band {
+            FilterBand::Lowpass => cascade(MathTrace::biquad_lowpass, cutoff_hz).collect(),
+            FilterBand::Highpass => cascade(MathTrace::biquad_highpass, cutoff_hz).collect(),
+            FilterBand::Bandpass { high_cut_hz } => cascade(MathTrace::biquad_highpass, cutoff_hz)
+                .chain(cascade(MathTrace::biquad_lowpass, high_cut_hz))
+                .collect(),
+        };
+
+        // The intermediate section outputs cannot be recovered from the
+        // previous output, so unless the state continues it exactly
+        // (same order, same end time) warm it up on the earlier input.
+        let last_out = out.last().map(|p| p[0]);
+        let warm_up =
+            last_out.is_none() || state.last_t != last_out || state.stages.len() != sections.len();
+        let from = if warm_up {
+            state.stages = vec![[0.0; 4]; sections.len()];
+            state.last_t = None;
+            0
+        } else {
+            start
+        };
+
+        for (i, &[t, x]) in src.iter().enumerate().skip(from) {
+            // Same dt handling as the single-section filters.
+            let dt = state.last_t.map_or(1e-3, |t0| (t - t0).max(1e-9));
+            let mut y = x;
+            for (stage, &(design, fc, q)) in state.stages.iter_mut().zip(&sections) {
+                let p = design(fc, q, dt);
+                let [x1, x2, y1, y2] = *stage;
+                let z = MathTrace::biquad_step(p, y, x1, x2, y1, y2);
+                *stage = [y, x1, z, y1];
+                y = z;
+            }
+            state.last_t = Some(t);
+            if i >= start {
+                out.push([t, y]);
+            }
+        }
+    }
+
+    /// Q of each biquad section of an even-order Butterworth filter: the pole
+    /// pairs sit at angles (2k+1)·π/(2n) on the unit circle. Odd orders are
+    /// rounded down, with at least one section.
+    fn butterworth_qs(order: u8) -> Vec<f64> {
+        let n = (order / 2).max(1) as usize;
+        (0..n)
+            .map(|k| {
+                let theta = std::f64::consts::PI * (2 * k + 1) as f64 / (4 * n) as f64;
+                1.0 / (2.0 * theta.cos())
+            })
+            .collect()
+    }
+
+    /// RBJ biquad notch coefficient generator.
+    ///
+    /// Produces coefficients that reject a narrow band around `fc` with
+    /// bandwidth set by `q` and unity gain elsewhere. Not normalized by a0.
+    #[inline]
+    fn biquad_notch(fc: f64, q: f64, dt: f64) -> BiquadParams {
+        let fs = (1.0 / dt).max(1.0);
+        let w0 = 2.0 * std::f64::consts::PI * (fc.max(1e-9) / fs);
+        let cosw0 = w0.cos();
+        let sinw0 = w0.sin();
+        let q = q.max(1e-6);
+        let alpha = sinw0 / (2.0 * q);
+        let b0 = 1.0;
+        let b1 = -2.0 * cosw0;
+        let b2 = 1.0;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cosw0;
+        let a2 = 1.0 - alpha;
+        BiquadParams {
+            b: [b0, b1, b2],
+            a: [a0, a1, a2],
+        }
+    }
+}
+
+/// Trace an expression variable refers to: its binding in `vars`, if any,
+/// otherwise the trace of the same name.
+fn expr_input(var: &str, vars: &[(String, TraceRef)]) -> TraceRef {
+    vars.iter()
+        .find(|(v, _)| v.trim() == var)
+        .map(|(_, t)| t.clone())
+        .unwrap_or_else(|| TraceRef::new(var))
+}
+
+/// Parse error or first unresolvable variable of an expression, given a
+/// predicate telling which trace names exist.
+pub fn expr_error(
+    expr: &str,
+    vars: &[(String, TraceRef)],
+    exists: impl Fn(&TraceRef) -> bool,
+) -> Option<String> {
+    let parsed = match ParsedExpr::parse(expr) {
+        Err(e) => return Some(e.to_string()),
+        Ok(p) => p,
+    };
+    let inputs: Vec<TraceRef> = parsed
+        .variables()
+        .iter()
+        .map(|v| expr_input(v, vars))
+        .collect();
+    missing_input(&parsed, &inputs, exists)
+}
+
+/// First of `inputs` (the traces behind `parsed`'s variables) that does not
+/// exist, described for the user.
+fn missing_input(
+    parsed: &ParsedExpr,
+    inputs: &[TraceRef],
+    exists: impl Fn(&TraceRef) -> bool,
+) -> Option<String> {
+    parsed
+        .variables()
+        .iter()
+        .zip(inputs)
+        .find_map(|(v, input)| {
+            if exists(input) {
+                None
+            } else if input.0 == *v {
+                Some(format!("unknown trace '{v}'"))
+            } else {
+                Some(format!(
+                    "variable '{v}' is bound to unknown trace '{}'",
+                    input.0
+                ))
+            }
+        })
 }
@@ -8,6 +8,16 @@ pub struct Measurement {
     pub p2: Option<[f64; 2]>,
     pub catch_trace: Option<TraceRef>,
     pub scope_id: Option<usize>,
+    /// Level whose crossings drive the frequency readout.
+    #[serde(default)]
+    pub crossing_level: f64,
+    /// Width of the hysteresis band around `crossing_level`.
+    #[serde(default)]
+    pub crossing_hysteresis: f64,
+    /// Show automatic measurements (Vpp, min, max, mean, RMS, frequency) of
+    /// `catch_trace` over the visible window.
+    #[serde(default)]
+    pub show_stats: bool,
 }
 
 impl Default for Measurement {
@@ -18,6 +28,9 @@ impl Default for Measurement {
             p2: None,
             catch_trace: None,
             scope_id: None,
+            crossing_level: 0.0,
+            crossing_hysteresis: 0.0,
+            show_stats: false,
         }
     }
 }
@@ -76,3 +89,254 @@ impl Measurement {
         self.p1.is_some() && self.p2.is_some()
     }
 }
+
+/// Differences between two measurement points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointDelta {
+    pub delta_x: f64,
+    pub delta_y: f64,
+    /// `None` when both points share the same x value.
+    pub slope: Option<f64>,
+    pub distance: f64,
+}
+
+impl PointDelta {
+    /// Deltas from `p1` to `p2`.
+    pub fn between(p1: [f64; 2], p2: [f64; 2]) -> Self {
+        let delta_x = p2[0] - p1[0];
+        let delta_y = p2[1] - p1[1];
+        let slope = if delta_x.abs() > 1e-12 {
+            Some(delta_y / delta_x)
+        } else {
+            None
+        };
+        Self {
+            delta_x,
+            delta_y,
+            slope,
+            distance: (delta_x * delta_x + delta_y * delta_y).sqrt(),
+        }
+    }
+}
+
+/// Current two-point measurement as published to
+/// [`MeasurementController`](crate::controllers::MeasurementController)
+/// subscribers.  Points are in axis units (log-scale axes mapped back to
+/// linear values).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasurementResult {
+    pub measurement: String,
+    pub trace: Option<TraceRef>,
+    pub scope_id: Option<usize>,
+    pub p1: Option<[f64; 2]>,
+    pub p2: Option<[f64; 2]>,
+    /// Set once both points are placed.
+    pub delta: Option<PointDelta>,
+}
+
+/// Estimate the frequency of a periodic signal from its crossings of `level`.
+///
+/// A sample is high above `level + hysteresis / 2` and low below
+/// `level - hysteresis / 2`; samples inside the band keep the previous state,
+/// so noise around the level does not add crossings.  Every high/low change
+/// is a crossing, two per period, and the frequency is taken over the span
+/// from the first to the last crossing.  Returns `None` with fewer than two
+/// crossings.
+pub fn crossing_frequency(
+    points: impl IntoIterator<Item = [f64; 2]>,
+    level: f64,
+    hysteresis: f64,
+) -> Option<f64> {
+    let half = hysteresis.abs() / 2.0;
+    let mut high: Option<bool> = None;
+    let mut first_t: Option<f64> = None;
+    let mut last_t = 0.0;
+    let mut crossings = 0usize;
+    for [t, y] in points {
+        let state = if y > level + half {
+            true
+        } else if y < level - half {
+            false
+        } else {
+            continue;
+        };
+        if high.is_some_and(|h| h != state) {
+            crossings += 1;
+            first_t.get_or_insert(t);
+            last_t = t;
+        }
+        high = Some(state);
+    }
+    let span = last_t - first_t?;
+    (crossings >= 2 && span > 0.0).then(|| (crossings - 1) as f64 / (2.0 * span))
+}
+
+/// Automatic measurements of a trace over a window, as on a bench scope.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignalStats {
+    pub min: f64,
+    pub max: f64,
+    pub peak_to_peak: f64,
+    /// Mean of the samples (not weighted by sample spacing).
+    pub mean: f64,
+    /// Root mean square of the samples, including any DC offset.
+    pub rms: f64,
+    /// Fundamental frequency from crossings of `mean`; `None` with fewer than
+    /// two crossings.
+    pub frequency: Option<f64>,
+}
+
+impl SignalStats {
+    /// Measure `points`; returns `None` when there are none.  The frequency
+    /// is estimated with [`crossing_frequency`] at the mean, so a DC offset
+    /// does not hide the crossings, using the given `hysteresis`.
+    pub fn compute(points: impl IntoIterator<Item = [f64; 2]>, hysteresis: f64) -> Option<Self> {
+        let points: Vec<[f64; 2]> = points.into_iter().collect();
+        if points.is_empty() {
+            return None;
+        }
+        let n = points.len() as f64;
+        let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+        for &[_, y] in &points {
+            min = min.min(y);
+            max = max.max(y);
+            sum += y;
+            sum_sq += y * y;
+        }
+        let mean = sum / n;
+        Some(Self {
+            min,
+            max,
+            peak_to_peak: max - min,
+            mean,
+            rms: (sum_sq / n).sqrt(),
+            frequency: crossing_frequency(points, mean, hysteresis),
+        })
+    }
+}
+
+/// One of the automatic measurements in [`SignalStats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MeasurementKind {
+    PeakToPeak,
+    Min,
+    Max,
+    Mean,
+    Rms,
+    Frequency,
+}
+
+impl MeasurementKind {
+    pub const ALL: [MeasurementKind; 6] = [
+        MeasurementKind::PeakToPeak,
+        MeasurementKind::Min,
+        MeasurementKind::Max,
+        MeasurementKind::Mean,
+        MeasurementKind::Rms,
+        MeasurementKind::Frequency,
+    ];
+
+    /// Pick this measurement out of `stats`.
+    pub fn value(self, stats: &SignalStats) -> Option<f64> {
+        match self {
+            MeasurementKind::PeakToPeak => Some(stats.peak_to_peak),
+            MeasurementKind::Min => Some(stats.min),
+            MeasurementKind::Max => Some(stats.max),
+            MeasurementKind::Mean => Some(stats.mean),
+            MeasurementKind::Rms => Some(stats.rms),
+            MeasurementKind::Frequency => stats.frequency,
+        }
+    }
+}
+
+/// A completed measurement (both points set) recorded in the session log.
+///
+/// Point coordinates are stored in axis units (log-scale axes are mapped back
+/// to linear values).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeasurementRecord {
+    /// Wall-clock time the measurement was completed (Unix milliseconds).
+    pub timestamp_ms: i64,
+    pub measurement: String,
+    pub trace: Option<String>,
+    pub scope_id: Option<usize>,
+    pub p1: [f64; 2],
+    pub p2: [f64; 2],
+    pub delta_x: f64,
+    pub delta_y: f64,
+    /// `None` when both points share the same x value.
+    pub slope: Option<f64>,
+    pub distance: f64,
+}
+
+impl MeasurementRecord {
+    /// Build a record from two points given in axis units.
+    pub fn new(
+        timestamp_ms: i64,
+        measurement: &str,
+        trace: Option<String>,
+        scope_id: Option<usize>,
+        p1: [f64; 2],
+        p2: [f64; 2],
+    ) -> Self {
+        let delta = PointDelta::between(p1, p2);
+        Self {
+            timestamp_ms,
+            measurement: measurement.to_string(),
+            trace,
+            scope_id,
+            p1,
+            p2,
+            delta_x: delta.delta_x,
+            delta_y: delta.delta_y,
+            slope: delta.slope,
+            distance: delta.distance,
+        }
+    }
+}
+
+/// Write a measurement session log as CSV (one row per record).
+pub fn write_measurement_log_csv<W: std::io::Write>(
+    records: &[MeasurementRecord],
+    mut w: W,
+) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "timestamp,measurement,trace,scope_id,p1_x,p1_y,p2_x,p2_y,delta_x,delta_y,slope,distance"
+    )?;
+    for r in records {
+        let ts = chrono::DateTime::from_timestamp_millis(r.timestamp_ms)
+            .map(|dt| {
+                dt.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S%.3f")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        writeln!(
+            w,
+            "{},{},{},{},{:.9},{:.9},{:.9},{:.9},{:.9},{:.9},{},{:.9}",
+            ts,
+            csv_field(&r.measurement),
+            csv_field(r.trace.as_deref().unwrap_or("")),
+            r.scope_id.map(|id| id.to_string()).unwrap_or_default(),
+            r.p1[0],
+            r.p1[1],
+            r.p2[0],
+            r.p2[1],
+            r.delta_x,
+            r.delta_y,
+            r.slope.map(|s| format!("{:.9}", s)).unwrap_or_default(),
+            r.distance
+        )?;
+    }
+    Ok(())
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
@@ -1,5 +1,6 @@
 pub mod data;
 pub mod export;
+pub mod expr;
 pub mod hotkeys;
 pub mod math;
 pub mod measurement;
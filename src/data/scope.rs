@@ -1,9 +1,9 @@
 //! Scope data: axis settings, display state, and coordinate management.
 
 use crate::data::trace_look::TraceLook;
-use crate::data::traces::{TraceRef, TracesCollection};
+use crate::data::traces::{DecimationMethod, TraceData, TraceRef, TracesCollection};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Formatting options for the x-value (time) shown in point labels.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -180,7 +180,204 @@ impl Default for AxisSettings {
     }
 }
 
+/// Y axis a trace of a time scope is drawn against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum YAxisSelect {
+    /// The scope's main [`y_axis`](ScopeData::y_axis), on the left.
+    #[default]
+    Left,
+    /// The secondary [`y_axis2`](ScopeData::y_axis2), on the right.
+    Right,
+}
+
+/// Expand or replace `bounds` with the fitted range `min..max`.  A single
+/// value is widened to include zero; an empty range leaves `bounds` alone.
+fn apply_y_fit(bounds: &mut (f64, f64), min_y: f64, max_y: f64, not_shrink: bool) {
+    if min_y < max_y {
+        if not_shrink {
+            *bounds = (min_y.min(bounds.0), max_y.max(bounds.1));
+        } else {
+            *bounds = (min_y, max_y);
+        }
+    } else if min_y == max_y {
+        if min_y < 0.0 {
+            *bounds = (min_y, 0.0);
+        } else if min_y > 0.0 {
+            *bounds = (0.0, max_y);
+        } else {
+            // Both min and max are zero; set to -1.0 to 1.0
+            *bounds = (-1.0, 1.0);
+        }
+    }
+}
+
+/// Exponentially move `prev` toward `target` over `dt` seconds with time
+/// constant `tau`.  Jumps backwards or by more than `max_lag` snap straight
+/// to `target` (e.g. after clearing data or switching sources).
+pub fn smooth_toward(prev: f64, target: f64, dt: f64, tau: f64, max_lag: f64) -> f64 {
+    let lag = target - prev;
+    if tau <= 0.0 || lag < 0.0 || lag > max_lag || !lag.is_finite() {
+        return target;
+    }
+    prev + lag * (1.0 - (-dt.max(0.0) / tau).exp())
+}
+
+/// Pair samples of two time-sorted series whose timestamps lie within `tol`
+/// of each other, returning `[x_value, y_value]` for each match.  Each sample
+/// is used at most once and pairs with its nearest counterpart, so a series
+/// sampled faster than the other contributes one sample per pair.
+pub fn pair_by_time<'a, X, Y>(x_pts: X, y_pts: Y, tol: f64) -> Vec<[f64; 2]>
+where
+    X: IntoIterator<Item = &'a [f64; 2]>,
+    Y: IntoIterator<Item = &'a [f64; 2]>,
+{
+    let mut out = Vec::new();
+    let mut xs = x_pts.into_iter().peekable();
+    let mut ys = y_pts.into_iter().peekable();
+    let (mut x, mut y) = (xs.next(), ys.next());
+    while let (Some(xp), Some(yp)) = (x, y) {
+        let dt = xp[0] - yp[0];
+        if dt.abs() > tol {
+            if dt < 0.0 {
+                x = xs.next();
+            } else {
+                y = ys.next();
+            }
+            continue;
+        }
+        // The earlier sample's successor may be nearer still.
+        if dt < 0.0 && xs.peek().is_some_and(|n| (n[0] - yp[0]).abs() < -dt) {
+            x = xs.next();
+            continue;
+        }
+        if dt > 0.0 && ys.peek().is_some_and(|n| (n[0] - xp[0]).abs() < dt) {
+            y = ys.next();
+            continue;
+        }
+        out.push([xp[1], yp[1]]);
+        x = xs.next();
+        y = ys.next();
+    }
+    out
+}
+
+/// Most frames an [`Afterglow`] keeps, however long the persistence window.
+pub const AFTERGLOW_MAX_FRAMES: usize = 32;
+
+/// Recently drawn frames of one trace for the scope's persistence display
+/// (see [`ScopeData::persistence_secs`]).  Frames are sampled evenly across
+/// the window and capped at [`AFTERGLOW_MAX_FRAMES`], so memory stays bounded
+/// regardless of frame rate or window length.
+#[derive(Clone, Debug, Default)]
+pub struct Afterglow {
+    frames: VecDeque<(f64, Vec<[f64; 2]>)>,
+}
+
+impl Afterglow {
+    /// Remember `points` as drawn at time `now` (seconds), dropping frames
+    /// older than `persistence_secs`.  A frame is only kept if enough time has
+    /// passed since the previous one to spread the cap over the window.
+    pub fn record(&mut self, now: f64, points: &[[f64; 2]], persistence_secs: f64) {
+        if persistence_secs <= 0.0 {
+            self.frames.clear();
+            return;
+        }
+        while self
+            .frames
+            .front()
+            .is_some_and(|(t, _)| now - t >= persistence_secs)
+        {
+            self.frames.pop_front();
+        }
+        let spacing = persistence_secs / AFTERGLOW_MAX_FRAMES as f64;
+        if self.frames.back().is_some_and(|(t, _)| now - t < spacing) {
+            return;
+        }
+        if self.frames.len() >= AFTERGLOW_MAX_FRAMES {
+            self.frames.pop_front();
+        }
+        self.frames.push_back((now, points.to_vec()));
+    }
+
+    /// Retained frames, oldest first, with their fade in `(0, 1]`: `1` for a
+    /// frame drawn at `now`, approaching `0` at the edge of the window.
+    pub fn frames(
+        &self,
+        now: f64,
+        persistence_secs: f64,
+    ) -> impl Iterator<Item = (f32, &[[f64; 2]])> {
+        self.frames.iter().filter_map(move |(t, pts)| {
+            let fade = 1.0 - (now - t) / persistence_secs;
+            (fade > 0.0).then_some((fade.min(1.0) as f32, pts.as_slice()))
+        })
+    }
+
+    /// Number of retained frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// `true` if no frames are retained.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// A series loaded from a previous export and overlaid on a scope for
+/// comparison (see [`ScopeData::reference_traces`]).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReferenceTrace {
+    pub name: String,
+    /// Sorted `[x, y]` samples as loaded, before [`ScopeData::reference_x_offset`].
+    pub points: Vec<[f64; 2]>,
+}
+
+/// Per-division step and division count across a visible `span`.
+///
+/// `major_step` is the spacing of the labelled grid lines; when it is unknown
+/// (e.g. tick labels hidden) a 1-2-5 step giving about ten divisions is used.
+pub fn division_step(span: f64, major_step: Option<f64>) -> Option<(f64, f64)> {
+    if !span.is_finite() || span <= 0.0 {
+        return None;
+    }
+    let step = match major_step {
+        Some(s) if s.is_finite() && s > 0.0 => s,
+        _ => {
+            let raw = span / 10.0;
+            let pow = 10f64.powf(raw.log10().floor());
+            let nice = [1.0, 2.0, 5.0, 10.0]
+                .into_iter()
+                .find(|m| m * pow >= raw)
+                .unwrap_or(10.0);
+            nice * pow
+        }
+    };
+    Some((step, span / step))
+}
+
 impl AxisSettings {
+    /// Format a per-division step, e.g. `"2 ms/div"` for time axes or
+    /// `"0.5000 V/div"` for value axes.
+    pub fn format_per_div(&self, step: f64) -> String {
+        match &self.axis_type {
+            AxisType::Time(_) => {
+                let (scale, unit) = match step.abs() {
+                    s if s >= 1.0 => (1.0, "s"),
+                    s if s >= 1e-3 => (1e3, "ms"),
+                    s if s >= 1e-6 => (1e6, "µs"),
+                    _ => (1e9, "ns"),
+                };
+                format!("{} {}/div", (step * scale * 1e3).round() / 1e3, unit)
+            }
+            AxisType::Value(fmt) => {
+                format!(
+                    "{}/div",
+                    fmt.format_value(step, self.value_decimals, Some(step))
+                )
+            }
+        }
+    }
+
     pub fn new_time_axis() -> Self {
         Self {
             name: Some("Time".to_string()),
@@ -216,6 +413,48 @@ impl AxisSettings {
             AxisType::Value(fmt) => fmt.format_value(v, self.value_decimals, step),
         }
     }
+
+    /// Format `v` as a number, also on time axes (without a unit there).
+    /// Log-scaled axes use this, as their values are not timestamps.
+    pub fn format_number(&self, v: f64, decimals: usize, step: Option<f64>) -> String {
+        match &self.axis_type {
+            AxisType::Time(_) => ValueFormat::default().format_value(v, decimals, step),
+            AxisType::Value(fmt) => fmt.format_value(v, decimals, step),
+        }
+    }
+
+    /// Tick label at `log_value` (base-10 log of the value) on a log-scaled
+    /// axis.  Only decades and their 2× and 5× multiples are labelled, the
+    /// finer grid lines get an empty label.
+    pub fn format_log_tick(&self, log_value: f64) -> String {
+        if !log_value.is_finite() {
+            return String::new();
+        }
+        let exp = log_value.floor();
+        let mantissa = 10f64.powf(log_value - exp);
+        let Some(nice) = [1.0, 2.0, 5.0, 10.0]
+            .into_iter()
+            .find(|n| (mantissa - n).abs() < 0.01 * n)
+        else {
+            return String::new();
+        };
+        let v = nice * 10f64.powf(exp);
+        // Enough decimals for 0.5 or 0.02; smaller values go scientific.
+        let decimals = (-v.log10().floor()).clamp(0.0, 3.0) as usize;
+        self.format_number(v, decimals, None)
+    }
+}
+
+/// Plot range of linear axis `bounds` on a base-10 log axis.  A non-positive
+/// lower bound is clipped to three decades below the upper one; bounds
+/// without a positive part fall back to `1..10`.
+pub fn log_bounds(bounds: (f64, f64)) -> (f64, f64) {
+    let (lo, hi) = bounds;
+    if hi <= 0.0 || !hi.is_finite() {
+        return (0.0, 1.0);
+    }
+    let lo = if lo > 0.0 { lo } else { hi * 1e-3 };
+    (lo.log10(), hi.log10())
 }
 
 /// Scope type: time-based or XY mode.
@@ -236,6 +475,144 @@ pub enum LegendPosition {
     RightBottom,
 }
 
+/// What a left click on a legend entry does.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LegendClick {
+    /// Show or hide the clicked trace (the egui_plot default).
+    #[default]
+    ToggleVisible,
+    /// Show only the clicked trace; clicking the only visible one shows all.
+    Solo,
+    /// Ignore clicks; visibility is changed elsewhere (e.g. the Traces panel).
+    Nothing,
+}
+
+impl LegendClick {
+    pub const ALL: &'static [LegendClick] = &[
+        LegendClick::ToggleVisible,
+        LegendClick::Solo,
+        LegendClick::Nothing,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LegendClick::ToggleVisible => "Toggle",
+            LegendClick::Solo => "Solo",
+            LegendClick::Nothing => "Nothing",
+        }
+    }
+
+    /// Hidden legend entries after a click on `clicked`, given the entries
+    /// hidden before the click and all entries of the legend.
+    pub fn apply(
+        &self,
+        clicked: egui::Id,
+        hidden: &HashSet<egui::Id>,
+        all: &[egui::Id],
+    ) -> HashSet<egui::Id> {
+        match self {
+            LegendClick::ToggleVisible => {
+                let mut hidden = hidden.clone();
+                if !hidden.remove(&clicked) {
+                    hidden.insert(clicked);
+                }
+                hidden
+            }
+            LegendClick::Solo => {
+                let isolated = all.iter().all(|id| (*id == clicked) != hidden.contains(id));
+                if isolated {
+                    HashSet::new()
+                } else {
+                    all.iter().copied().filter(|id| *id != clicked).collect()
+                }
+            }
+            LegendClick::Nothing => hidden.clone(),
+        }
+    }
+}
+
+/// Per-trace point caps derived from the scopes' [`ScopeData::max_points`].
+///
+/// A trace gets the largest cap among the scopes showing it, where a scope
+/// without its own cap counts as `default_cap`, so no scope loses samples it
+/// asked to keep.  Traces in no capped scope are left out and use
+/// `default_cap`; the map is empty when no scope sets a cap.
+pub fn trace_point_caps<'a>(
+    scopes: impl IntoIterator<Item = &'a ScopeData> + Clone,
+    default_cap: usize,
+) -> HashMap<TraceRef, usize> {
+    let mut caps = HashMap::new();
+    if !scopes.clone().into_iter().any(|s| s.max_points > 0) {
+        return caps;
+    }
+    for scope in scopes {
+        let cap = if scope.max_points > 0 {
+            scope.max_points
+        } else {
+            default_cap
+        };
+        for name in &scope.trace_order {
+            let entry = caps.entry(name.clone()).or_insert(cap);
+            *entry = (*entry).max(cap);
+        }
+    }
+    caps.retain(|_, cap| *cap != default_cap);
+    caps
+}
+
+/// Default for [`ScopeData::segment_warning`].
+pub const DEFAULT_SEGMENT_WARNING: usize = 500_000;
+
+/// Default for [`ScopeData::y_margin`].
+pub const DEFAULT_Y_MARGIN: f64 = 0.05;
+
+/// Default for [`ScopeData::xy_tolerance`]: pair equal timestamps only.
+pub const DEFAULT_XY_TOLERANCE: f64 = 1e-9;
+
+/// What a scope's x-window does when it is resumed after a pause.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ResumeBehavior {
+    /// Keep the current window width and follow the latest data again.
+    #[default]
+    JumpToLatest,
+    /// Restore the x-window pinned at the pause instant, discarding any
+    /// pan/zoom done while paused, then continue following from there
+    /// (gliding to the latest sample when x-smoothing is enabled).
+    RestorePausedView,
+}
+
+/// X-window a time scope shows before any of its traces has data.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InitialXRange {
+    /// `[0, time_window]`.
+    #[default]
+    FromZero,
+    /// The last `time_window` seconds ending at the current wall-clock time
+    /// (Unix seconds), so data stamped with wall-clock time appears without
+    /// the axis jumping.
+    WallClock,
+    /// A fixed range.
+    Fixed { min: f64, max: f64 },
+}
+
+/// Scope that newly discovered traces are added to.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum AutoAttach {
+    /// The first scope.
+    #[default]
+    Primary,
+    /// The scope with this id.
+    ScopeId(usize),
+    /// The first scope with this name.
+    ScopeName(String),
+    /// Don't attach new traces anywhere; they stay hidden until added to a
+    /// scope by hand.
+    Disabled,
+}
+
 impl From<LegendPosition> for egui_plot::Corner {
     fn from(pos: LegendPosition) -> Self {
         match pos {
@@ -261,15 +638,113 @@ pub struct ScopeData {
     /// When `true`, the legend is unconditionally hidden regardless of `show_legend`.
     /// Useful for compact/embedded layouts where an overlay legend wastes space.
     pub force_hide_legend: bool,
+    /// When `true`, axis labels, tick labels and the legend are suppressed so
+    /// only the traces (and grid, if enabled) are drawn.  Combined with the
+    /// panel's `compact` mode this gives sparkline-style dashboards.
+    pub minimal_axes: bool,
+    /// Draw a faint horizontal reference line at [`zero_line_y`](Self::zero_line_y).
+    pub show_zero_line: bool,
+    /// Y value of the reference line, in displayed units (after trace offsets).
+    pub zero_line_y: f64,
+    /// Empty space kept above and below the Y bounds, as a fraction of their
+    /// range, so fitted traces do not touch the plot edges.  Display only:
+    /// [`y_axis.bounds`](AxisSettings::bounds) stay the fitted data range.
+    pub y_margin: f64,
+    /// Tint the plot background while any threshold on this scope is active.
+    pub threshold_backdrop: bool,
+    /// Whether a threshold targeting one of this scope's traces is currently
+    /// active.  Set by the thresholds panel each frame.
+    pub threshold_active: bool,
     pub show_info_in_legend: bool,
+    /// When positive, each legend entry of a time scope shows the mean ± σ of
+    /// its trace over the last this many seconds.  `0.0` disables the badge.
+    pub legend_stats_secs: f64,
     /// Position of the legend within the plot area.
     pub legend_position: LegendPosition,
+    /// What a left click on a legend entry does.
+    pub legend_click: LegendClick,
+    /// When `true`, the plot ignores pan, zoom and click selection, e.g. on an
+    /// unattended display.
+    pub interaction_locked: bool,
     /// When `true`, the plot background grid is visible.
     pub show_grid: bool,
+    /// When `true`, a bench-scope style "units per division" readout is drawn
+    /// over the plot.
+    pub show_div_readout: bool,
+    /// When `true`, a status strip below the plot shows the plot coordinates
+    /// under the mouse pointer, formatted like the axis ticks.
+    pub show_cursor_readout: bool,
+    /// When `true`, a running time scope stops following the latest data while
+    /// the pointer hovers the plot, and resumes once the pointer leaves.
+    pub hover_scroll_lock: bool,
+    /// Time constant (seconds) for exponentially tracking the latest sample
+    /// time that drives a running time scope's x-window.  Smooths out the
+    /// window edge stepping with irregular frame times; `0.0` (default)
+    /// follows the latest sample exactly.
+    pub x_smoothing_secs: f64,
+    /// How the x-window is restored when the scope is resumed.
+    pub resume_behavior: ResumeBehavior,
+    /// X-window of a time scope while none of its traces has data yet.
+    pub initial_x: InitialXRange,
+    /// Y bounds while none of the scope's traces has data yet.  `None` keeps
+    /// whatever bounds the scope has.
+    pub initial_y: Option<(f64, f64)>,
+    /// How time-scope traces are reduced to the per-trace draw budget.
+    pub decimation: DecimationMethod,
+    /// Line segments drawn in the last frame, summed over all traces and
+    /// reference overlays.  Updated by the draw pass; read-only diagnostics.
+    pub drawn_segments: usize,
+    /// Show a warning on the plot when [`drawn_segments`](Self::drawn_segments)
+    /// exceeds this many.  `0` disables the warning.
+    pub segment_warning: usize,
+    /// Trace whose values replace time on the x-axis of a time scope.  Every
+    /// other trace is paired with it by timestamp and drawn as y-vs-x, like
+    /// an XY scope whose pairs all share this x-source.  `None` plots
+    /// against time.  Set through [`set_x_source`](Self::set_x_source).
+    pub x_source: Option<TraceRef>,
+    /// Largest timestamp difference (seconds) at which XY pairs and the
+    /// x-source pair two samples (see [`pair_by_time`]).  Raise it for traces
+    /// sampled at different rates.
+    pub xy_tolerance: f64,
+    /// Afterglow window (seconds): recently drawn frames of each trace stay
+    /// on screen, fading out over this time, like an analog scope's phosphor.
+    /// `0` (default) draws only the current line.  See [`Afterglow`].
+    pub persistence_secs: f64,
+    /// Secondary Y axis drawn on the right of a time scope plotted against
+    /// time.  Traces whose [`axis`](TraceData::axis) is
+    /// [`YAxisSelect::Right`] are scaled against its bounds, which are mapped
+    /// onto the left axis' bounds; the right axis is always linear.  `None`
+    /// (default) draws every trace against [`y_axis`](Self::y_axis).
+    pub y_axis2: Option<AxisSettings>,
+    /// When `true` (default), pausing clones every trace buffer into a
+    /// snapshot and the scope shows that frozen copy.  When `false`, pausing
+    /// is free: the scope keeps reading the live buffers and only stops
+    /// following, with the x-window pinned.  Without a snapshot, samples that
+    /// age out of `max_points`/`max_age_secs` disappear from the paused view.
+    pub snapshot_on_pause: bool,
+    /// X-bounds pinned at the moment the scope was paused; cleared on resume.
+    pub paused_x_bounds: Option<(f64, f64)>,
 
     pub trace_order: Vec<TraceRef>,
+    /// Maximum number of traces drawn by a time scope (`0` = all).  Pinned
+    /// traces are kept first; remaining slots go to the most recently updated.
+    pub max_drawn_traces: usize,
+    /// Traces that are always drawn when [`max_drawn_traces`](Self::max_drawn_traces) applies.
+    pub pinned_traces: Vec<TraceRef>,
+    /// Live samples kept per trace of this scope (`0` = the global
+    /// [`TracesCollection::max_points`]).  See [`trace_point_caps`].
+    pub max_points: usize,
+    /// Reference series drawn faded and dashed behind the live traces.
+    pub reference_traces: Vec<ReferenceTrace>,
+    /// Shift added to reference x values, e.g. to line a recording up with
+    /// the current window.
+    pub reference_x_offset: f64,
+    /// Whether auto-fit and "Fit to View" also cover the reference series.
+    pub reference_in_autofit: bool,
     pub clicked_point: Option<[f64; 2]>,
     pub clicked_screen_pos: Option<[f32; 2]>,
+    /// One-shot "Fit all data" request, consumed by the scope's next draw.
+    pub fit_all_requested: bool,
     /// When `true`, clicking while paused sets `clicked_point` without resuming.
     /// Set by the measurement panel when measurements exist.
     pub measurement_active: bool,
@@ -290,6 +765,13 @@ pub struct ScopeData {
     cached_y_fit_key: Option<(usize, Option<f64>)>,
     /// Cache key for x-bounds auto-fit: (total_visible_points, max_timestamp).
     cached_x_fit_key: Option<(usize, Option<f64>)>,
+    /// Pause state seen by the previous `update`, used to detect transitions.
+    was_paused: bool,
+    /// Smoothed latest sample time and when it was last advanced.
+    smoothed_t_latest: Option<(f64, std::time::Instant)>,
+    /// Set when a time scope was resumed onto its pinned view; the next
+    /// `update` shows that view instead of jumping to the latest sample.
+    hold_restored_view: bool,
 }
 
 impl Default for ScopeData {
@@ -305,11 +787,43 @@ impl Default for ScopeData {
             paused: false,
             show_legend: true,
             force_hide_legend: false,
+            minimal_axes: false,
+            show_zero_line: false,
+            zero_line_y: 0.0,
+            threshold_backdrop: false,
+            threshold_active: false,
             show_info_in_legend: false,
+            legend_stats_secs: 0.0,
             legend_position: LegendPosition::default(),
+            legend_click: LegendClick::default(),
+            interaction_locked: false,
             show_grid: true,
+            show_div_readout: false,
+            show_cursor_readout: false,
+            hover_scroll_lock: false,
+            x_smoothing_secs: 0.0,
+            resume_behavior: ResumeBehavior::default(),
+            initial_x: InitialXRange::default(),
+            initial_y: None,
+            decimation: DecimationMethod::default(),
+            drawn_segments: 0,
+            segment_warning: DEFAULT_SEGMENT_WARNING,
+            y_margin: DEFAULT_Y_MARGIN,
+            x_source: None,
+            xy_tolerance: DEFAULT_XY_TOLERANCE,
+            persistence_secs: 0.0,
+            y_axis2: None,
+            snapshot_on_pause: true,
+            paused_x_bounds: None,
             trace_order: Vec::new(),
+            max_drawn_traces: 0,
+            max_points: 0,
+            pinned_traces: Vec::new(),
             clicked_point: None,
+            fit_all_requested: false,
+            reference_traces: Vec::new(),
+            reference_x_offset: 0.0,
+            reference_in_autofit: false,
             clicked_screen_pos: None,
             measurement_active: false,
             pause_on_click: false,
@@ -319,6 +833,9 @@ impl Default for ScopeData {
             rendered_this_frame: false,
             cached_y_fit_key: None,
             cached_x_fit_key: None,
+            was_paused: false,
+            smoothed_t_latest: None,
+            hold_restored_view: false,
         }
     }
 }
@@ -335,9 +852,15 @@ impl ScopeData {
             }
         }
         self.xy_pairs.retain(|(x, y, _)| x.is_some() || y.is_some());
+        self.pinned_traces.retain(|t| t != trace);
+        if self.x_source.as_ref() == Some(trace) {
+            self.set_x_source(None);
+        }
     }
 
     pub fn update(&mut self, traces: &TracesCollection) {
+        self.handle_pause_transition();
+
         // Keep trace_order in sync with current traces: drop missing, append new
         self.trace_order.retain(|n| traces.contains_key(n));
 
@@ -357,7 +880,7 @@ impl ScopeData {
             .filter_map(|name| traces.get_trace(name))
             .filter(|t| t.look.visible)
             .fold((0usize, None::<f64>), |(len, max_ts), t| {
-                let ts = if self.paused {
+                let ts = if self.shows_snapshot() {
                     t.snap
                         .as_ref()
                         .and_then(|s| s.back().map(|p| p[0]))
@@ -365,7 +888,7 @@ impl ScopeData {
                 } else {
                     t.live.back().map(|p| p[0])
                 };
-                let buf_len = if self.paused {
+                let buf_len = if self.shows_snapshot() {
                     t.snap.as_ref().map(|s| s.len()).unwrap_or(t.live.len())
                 } else {
                     t.live.len()
@@ -378,7 +901,10 @@ impl ScopeData {
                 (len + buf_len, new_max.map(|t| (t * 10.0).round() / 10.0))
             });
 
-        if self.x_axis.auto_fit {
+        // A paused scope without a snapshot still sees new samples; keep its
+        // pinned x-window instead of refitting to them.
+        let frozen_x = self.paused && !self.snapshot_on_pause;
+        if self.x_axis.auto_fit && !frozen_x && !self.hold_restored_view {
             let key = (total_pts, max_ts);
             if self.cached_x_fit_key != Some(key) {
                 self.fit_x_bounds(traces, self.x_axis.keep_max_fit);
@@ -388,18 +914,80 @@ impl ScopeData {
 
         self.live_update(traces);
 
-        if self.y_axis.auto_fit {
+        let y2_auto = self.active_y_axis2().is_some_and(|a| a.auto_fit);
+        if self.y_axis.auto_fit || y2_auto {
             let key = (total_pts, max_ts);
             if self.cached_y_fit_key != Some(key) {
-                self.fit_y_bounds(traces, self.y_axis.keep_max_fit);
+                if self.y_axis.auto_fit {
+                    self.fit_y_bounds(traces, self.y_axis.keep_max_fit);
+                }
+                if y2_auto {
+                    let keep = self.y_axis2.as_ref().is_some_and(|a| a.keep_max_fit);
+                    self.fit_y2_bounds(traces, keep);
+                }
                 self.cached_y_fit_key = Some(key);
             }
         }
+
+        if let Some(bounds) = self.initial_y.filter(|b| b.0 < b.1) {
+            if !self.has_data(traces) {
+                self.y_axis.bounds = bounds;
+            }
+        }
+    }
+
+    /// Whether any trace of this scope has at least one sample to show.
+    fn has_data(&self, traces: &TracesCollection) -> bool {
+        self.trace_order.iter().any(|name| {
+            traces
+                .get_points_ref(name, self.shows_snapshot())
+                .is_some_and(|p| !p.is_empty())
+        })
+    }
+
+    /// Whether this scope reads trace snapshots instead of the live buffers.
+    pub fn shows_snapshot(&self) -> bool {
+        self.paused && self.snapshot_on_pause
+    }
+
+    /// Whether the scope is fully paused: `paused` is set and, if it pauses
+    /// via a snapshot, the snapshot has been taken.
+    pub fn is_frozen(&self, traces: &TracesCollection) -> bool {
+        self.paused && (!self.snapshot_on_pause || traces.has_snapshot())
+    }
+
+    /// Pin the x-window when the scope is paused and apply `resume_behavior`
+    /// when it is resumed.  Pause can be toggled from many places (UI,
+    /// controllers, hotkeys), so transitions are detected here.
+    fn handle_pause_transition(&mut self) {
+        if self.paused == self.was_paused {
+            return;
+        }
+        self.was_paused = self.paused;
+        if self.paused {
+            self.paused_x_bounds = Some(self.x_axis.bounds);
+        } else if let Some(bounds) = self.paused_x_bounds.take() {
+            if self.resume_behavior == ResumeBehavior::RestorePausedView && bounds.1 > bounds.0 {
+                self.x_axis.bounds = bounds;
+                if self.scope_type == ScopeType::TimeScope {
+                    self.time_window = bounds.1 - bounds.0;
+                    self.hold_restored_view = !self.uses_x_source();
+                }
+            }
+        }
     }
 
     fn live_update(&mut self, traces: &TracesCollection) {
-        if self.scope_type == ScopeType::TimeScope {
+        if self.scope_type == ScopeType::TimeScope && !self.uses_x_source() {
             if !self.paused {
+                if self.hold_restored_view {
+                    // Show the restored view this frame and anchor the
+                    // follow there, so smoothing glides on from it.
+                    self.hold_restored_view = false;
+                    self.smoothed_t_latest =
+                        Some((self.x_axis.bounds.1, std::time::Instant::now()));
+                    return;
+                }
                 // Use only traces assigned to this scope to determine the current time
                 let now = self
                     .trace_order
@@ -408,8 +996,22 @@ impl ScopeData {
                     .filter_map(|trace| trace.live.back().map(|last| last[0]))
                     .fold(None, |acc: Option<f64>, val| {
                         Some(acc.map_or(val, |a: f64| a.max(val)))
-                    })
-                    .unwrap_or(self.time_window);
+                    });
+                let now = match (now, self.initial_x) {
+                    (Some(now), _) => now,
+                    (None, InitialXRange::FromZero) => self.time_window,
+                    (None, InitialXRange::WallClock) => {
+                        chrono::Utc::now().timestamp_micros() as f64 * 1e-6
+                    }
+                    (None, InitialXRange::Fixed { min, max }) => {
+                        if min < max {
+                            self.x_axis.bounds = (min, max);
+                            return;
+                        }
+                        self.time_window
+                    }
+                };
+                let now = self.smooth_t_latest(now);
                 let time_lower = now - self.time_window;
                 // Extend the lower bound to keep any active measurement markers
                 // visible. Without this, markers scroll off-screen after resuming
@@ -421,17 +1023,252 @@ impl ScopeData {
                 };
                 self.x_axis.bounds = (x_lower, now);
             } else {
+                self.smoothed_t_latest = None;
                 let diff = ((self.x_axis.bounds.1 - self.x_axis.bounds.0) - self.time_window) / 2.0;
                 self.x_axis.bounds = (self.x_axis.bounds.0 + diff, self.x_axis.bounds.1 - diff);
             }
         }
     }
 
+    /// Load reference series from a CSV export, replacing any existing ones.
+    pub fn load_reference_csv(&mut self, path: &std::path::Path) -> std::io::Result<()> {
+        let f = std::fs::File::open(path)?;
+        let series = crate::data::export::read_aligned_csv(std::io::BufReader::new(f))?;
+        self.reference_traces = series
+            .into_iter()
+            .filter(|(_, points)| !points.is_empty())
+            .map(|(name, mut points)| {
+                points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+                ReferenceTrace { name, points }
+            })
+            .collect();
+        self.reference_x_offset = 0.0;
+        Ok(())
+    }
+
+    /// Shift the reference so its first sample lines up with the left edge
+    /// of the current x-window.
+    pub fn align_reference_to_window(&mut self) {
+        let first = self
+            .reference_traces
+            .iter()
+            .filter_map(|r| r.points.first().map(|p| p[0]))
+            .fold(f64::INFINITY, f64::min);
+        if first.is_finite() {
+            self.reference_x_offset = self.x_axis.bounds.0 - first;
+        }
+    }
+
+    /// Reference samples (offset applied) that take part in auto-fitting.
+    fn autofit_reference_points(&self) -> impl Iterator<Item = [f64; 2]> + '_ {
+        let offset = self.reference_x_offset;
+        self.reference_traces
+            .iter()
+            .filter(|_| self.reference_in_autofit)
+            .flat_map(move |r| r.points.iter().map(move |p| [p[0] + offset, p[1]]))
+    }
+
+    /// Apply [`x_smoothing_secs`](Self::x_smoothing_secs) to the latest sample time.
+    fn smooth_t_latest(&mut self, latest: f64) -> f64 {
+        if self.x_smoothing_secs <= 0.0 {
+            self.smoothed_t_latest = None;
+            return latest;
+        }
+        let now = std::time::Instant::now();
+        let value = match self.smoothed_t_latest {
+            Some((prev, at)) => smooth_toward(
+                prev,
+                latest,
+                now.duration_since(at).as_secs_f64(),
+                self.x_smoothing_secs,
+                self.time_window,
+            ),
+            None => latest,
+        };
+        self.smoothed_t_latest = Some((value, now));
+        value
+    }
+
+    /// Whether this is a time scope plotting against [`x_source`](Self::x_source).
+    pub fn uses_x_source(&self) -> bool {
+        self.scope_type == ScopeType::TimeScope && self.x_source.is_some()
+    }
+
+    /// The secondary Y axis, if set and in effect.  XY scopes and time scopes
+    /// with an x-source draw every trace against the left axis.
+    pub fn active_y_axis2(&self) -> Option<&AxisSettings> {
+        if self.scope_type != ScopeType::TimeScope || self.uses_x_source() {
+            return None;
+        }
+        self.y_axis2.as_ref()
+    }
+
+    /// Whether `trace` is drawn against the secondary (right) Y axis.
+    pub fn on_right_axis(&self, trace: &TraceData) -> bool {
+        trace.axis == YAxisSelect::Right && self.active_y_axis2().is_some()
+    }
+
+    /// Y axis `trace`'s values are read on.
+    pub fn value_axis(&self, trace: &TraceData) -> &AxisSettings {
+        match self.active_y_axis2() {
+            Some(y2) if trace.axis == YAxisSelect::Right => y2,
+            _ => &self.y_axis,
+        }
+    }
+
+    /// Plot Y coordinate at which `trace`'s displayed value `v` is drawn:
+    /// right-axis values are mapped onto the left axis, values on a log axis
+    /// become `log10` (NaN for values that have no logarithm).
+    pub fn value_to_plot_y(&self, trace: &TraceData, v: f64) -> f64 {
+        if self.on_right_axis(trace) {
+            self.y2_to_y1(v)
+        } else if !self.y_axis.log_scale {
+            v
+        } else if v > 0.0 {
+            v.log10()
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Displayed value of `trace` drawn at plot Y coordinate `y`; inverse of
+    /// [`value_to_plot_y`](Self::value_to_plot_y).
+    pub fn plot_y_to_value(&self, trace: &TraceData, y: f64) -> f64 {
+        if self.on_right_axis(trace) {
+            self.y1_to_y2(y)
+        } else if self.y_axis.log_scale {
+            10f64.powf(y)
+        } else {
+            y
+        }
+    }
+
+    /// Unit of the Y axis `trace` is drawn against.
+    pub fn trace_unit(&self, trace: &TraceData) -> Option<String> {
+        if self.on_right_axis(trace) {
+            self.y_axis2.as_ref().and_then(|a| a.get_unit())
+        } else {
+            self.y_axis.get_unit()
+        }
+    }
+
+    /// Left-axis coordinate at which the right-axis value `v` is drawn: the
+    /// right bounds map linearly onto the left bounds.  Identity without an
+    /// active right axis.
+    pub fn y2_to_y1(&self, v: f64) -> f64 {
+        let Some(y2) = self.active_y_axis2() else {
+            return v;
+        };
+        let ((lo1, hi1), (lo2, hi2)) = (self.y_axis.bounds, y2.bounds);
+        if hi2 <= lo2 {
+            return v;
+        }
+        lo1 + (v - lo2) * (hi1 - lo1) / (hi2 - lo2)
+    }
+
+    /// Right-axis value drawn at left-axis coordinate `y`; inverse of
+    /// [`y2_to_y1`](Self::y2_to_y1).
+    pub fn y1_to_y2(&self, y: f64) -> f64 {
+        let Some(y2) = self.active_y_axis2() else {
+            return y;
+        };
+        let ((lo1, hi1), (lo2, hi2)) = (self.y_axis.bounds, y2.bounds);
+        if hi1 <= lo1 {
+            return y;
+        }
+        lo2 + (y - lo1) * (hi2 - lo2) / (hi1 - lo1)
+    }
+
+    /// After the left Y bounds changed from `old` by a pan or zoom, move the
+    /// right bounds by the same amount so right-axis traces follow the view.
+    /// Switches the right axis' auto-fit off, like a manual pan does for the
+    /// left one.
+    pub fn follow_y_bounds(&mut self, old: (f64, f64)) {
+        if self.active_y_axis2().is_none() || old.1 <= old.0 {
+            return;
+        }
+        let new = self.y_axis.bounds;
+        let Some(y2) = self.y_axis2.as_mut() else {
+            return;
+        };
+        let (lo2, hi2) = y2.bounds;
+        let scale = (hi2 - lo2) / (old.1 - old.0);
+        y2.bounds = (lo2 + (new.0 - old.0) * scale, lo2 + (new.1 - old.0) * scale);
+        y2.auto_fit = false;
+    }
+
+    /// Set or clear the x-source.  The x-axis switches to value formatting
+    /// and auto-fit while a source is set, and back to time when cleared.
+    pub fn set_x_source(&mut self, source: Option<TraceRef>) {
+        if source == self.x_source {
+            return;
+        }
+        self.x_axis.axis_type = if source.is_some() {
+            self.x_axis.auto_fit = true;
+            AxisType::Value(ValueFormat::default())
+        } else {
+            AxisType::Time(TimeFormat::default())
+        };
+        self.x_source = source;
+        self.cached_x_fit_key = None;
+        self.cached_y_fit_key = None;
+    }
+
+    /// `[x-source value, trace value]` pairs for `name`, with offsets applied
+    /// to both.  `None` without an x-source, for the source trace itself, or
+    /// when either trace is missing.
+    pub fn paired_with_x_source(
+        &self,
+        name: &TraceRef,
+        traces: &TracesCollection,
+    ) -> Option<Vec<[f64; 2]>> {
+        let source = self.x_source.as_ref().filter(|s| *s != name)?;
+        let (x_tr, y_tr) = (traces.get_trace(source)?, traces.get_trace(name)?);
+        let x_pts = traces.get_points_ref(source, self.shows_snapshot())?;
+        let y_pts = traces.get_points_ref(name, self.shows_snapshot())?;
+        Some(
+            pair_by_time(x_pts, y_pts, self.xy_tolerance)
+                .into_iter()
+                .map(|p| [x_tr.display_y(p[0]), y_tr.display_y(p[1])])
+                .collect(),
+        )
+    }
+
+    /// X and Y extent of the visible traces paired with the x-source.
+    fn x_source_extent(&self, traces: &TracesCollection) -> Option<((f64, f64), (f64, f64))> {
+        let mut x = (f64::INFINITY, f64::NEG_INFINITY);
+        let mut y = (f64::INFINITY, f64::NEG_INFINITY);
+        for name in self.trace_order.iter() {
+            if !traces.get_trace(name).is_some_and(|t| t.look.visible) {
+                continue;
+            }
+            for p in self.paired_with_x_source(name, traces).unwrap_or_default() {
+                if p[0].is_finite() && p[1].is_finite() {
+                    x = (x.0.min(p[0]), x.1.max(p[0]));
+                    y = (y.0.min(p[1]), y.1.max(p[1]));
+                }
+            }
+        }
+        (x.0 <= x.1).then_some((x, y))
+    }
+
     pub fn fit_x_bounds(&mut self, traces: &TracesCollection, not_shrink: bool) {
+        if self.uses_x_source() {
+            if let Some(((min_x, max_x), _)) = self.x_source_extent(traces) {
+                if min_x < max_x {
+                    let cur = self.x_axis.bounds;
+                    self.x_axis.bounds = if not_shrink {
+                        (min_x.min(cur.0), max_x.max(cur.1))
+                    } else {
+                        (min_x, max_x)
+                    };
+                }
+            }
+            return;
+        }
         if self.scope_type == ScopeType::XYScope && !self.xy_pairs.is_empty() {
             let mut min_x = f64::MAX;
             let mut max_x = f64::MIN;
-            let tol = 1e-9_f64;
 
             for (x_name, y_name, pair_look) in self.xy_pairs.iter() {
                 let (Some(x_name), Some(y_name)) = (x_name.as_ref(), y_name.as_ref()) else {
@@ -446,32 +1283,19 @@ impl ScopeData {
                     continue;
                 }
 
-                let x_pts = traces.get_points_ref(x_name, self.paused);
-                let y_pts = traces.get_points_ref(y_name, self.paused);
+                let x_pts = traces.get_points_ref(x_name, self.shows_snapshot());
+                let y_pts = traces.get_points_ref(y_name, self.shows_snapshot());
                 let (Some(x_pts), Some(y_pts)) = (x_pts, y_pts) else {
                     continue;
                 };
 
-                let mut i = 0usize;
-                let mut j = 0usize;
-                while i < x_pts.len() && j < y_pts.len() {
-                    let tx = x_pts[i][0];
-                    let ty = y_pts[j][0];
-                    let dt = tx - ty;
-                    if dt.abs() <= tol {
-                        let x = x_pts[i][1] + x_tr.offset;
-                        if x < min_x {
-                            min_x = x;
-                        }
-                        if x > max_x {
-                            max_x = x;
-                        }
-                        i += 1;
-                        j += 1;
-                    } else if dt < 0.0 {
-                        i += 1;
-                    } else {
-                        j += 1;
+                for [x, _] in pair_by_time(x_pts, y_pts, self.xy_tolerance) {
+                    let x = x_tr.display_y(x);
+                    if x < min_x {
+                        min_x = x;
+                    }
+                    if x > max_x {
+                        max_x = x;
                     }
                 }
             }
@@ -497,7 +1321,7 @@ impl ScopeData {
             if !trace.look.visible {
                 continue;
             }
-            let points = if self.paused {
+            let points = if self.shows_snapshot() {
                 if let Some(snap) = &trace.snap {
                     snap
                 } else {
@@ -515,6 +1339,10 @@ impl ScopeData {
                 }
             }
         }
+        for p in self.autofit_reference_points() {
+            min_x = min_x.min(p[0]);
+            max_x = max_x.max(p[0]);
+        }
         if min_x < max_x {
             if not_shrink {
                 let cur = self.x_axis.bounds;
@@ -538,11 +1366,25 @@ impl ScopeData {
         }
     }
 
+    /// Fit the (left) Y axis to the visible data.  Traces on the right axis
+    /// are left out; see [`fit_y2_bounds`](Self::fit_y2_bounds).
     pub fn fit_y_bounds(&mut self, traces: &TracesCollection, not_shrink: bool) {
+        if self.uses_x_source() {
+            if let Some((_, (min_y, max_y))) = self.x_source_extent(traces) {
+                if min_y < max_y {
+                    let cur = self.y_axis.bounds;
+                    self.y_axis.bounds = if not_shrink {
+                        (min_y.min(cur.0), max_y.max(cur.1))
+                    } else {
+                        (min_y, max_y)
+                    };
+                }
+            }
+            return;
+        }
         if self.scope_type == ScopeType::XYScope && !self.xy_pairs.is_empty() {
             let mut min_y = f64::MAX;
             let mut max_y = f64::MIN;
-            let tol = 1e-9_f64;
 
             for (x_name, y_name, pair_look) in self.xy_pairs.iter() {
                 let (Some(x_name), Some(y_name)) = (x_name.as_ref(), y_name.as_ref()) else {
@@ -557,32 +1399,19 @@ impl ScopeData {
                     continue;
                 }
 
-                let x_pts = traces.get_points_ref(x_name, self.paused);
-                let y_pts = traces.get_points_ref(y_name, self.paused);
+                let x_pts = traces.get_points_ref(x_name, self.shows_snapshot());
+                let y_pts = traces.get_points_ref(y_name, self.shows_snapshot());
                 let (Some(x_pts), Some(y_pts)) = (x_pts, y_pts) else {
                     continue;
                 };
 
-                let mut i = 0usize;
-                let mut j = 0usize;
-                while i < x_pts.len() && j < y_pts.len() {
-                    let tx = x_pts[i][0];
-                    let ty = y_pts[j][0];
-                    let dt = tx - ty;
-                    if dt.abs() <= tol {
-                        let y = y_pts[j][1] + y_tr.offset;
-                        if y < min_y {
-                            min_y = y;
-                        }
-                        if y > max_y {
-                            max_y = y;
-                        }
-                        i += 1;
-                        j += 1;
-                    } else if dt < 0.0 {
-                        i += 1;
-                    } else {
-                        j += 1;
+                for [_, y] in pair_by_time(x_pts, y_pts, self.xy_tolerance) {
+                    let y = y_tr.display_y(y);
+                    if y < min_y {
+                        min_y = y;
+                    }
+                    if y > max_y {
+                        max_y = y;
                     }
                 }
             }
@@ -598,6 +1427,32 @@ impl ScopeData {
             return;
         }
 
+        let (mut min_y, mut max_y) = self.time_y_range(traces, false);
+        let x_bounds = self.x_axis.bounds;
+        for p in self.autofit_reference_points() {
+            if p[0] >= x_bounds.0 && p[0] <= x_bounds.1 {
+                min_y = min_y.min(p[1]);
+                max_y = max_y.max(p[1]);
+            }
+        }
+        apply_y_fit(&mut self.y_axis.bounds, min_y, max_y, not_shrink);
+    }
+
+    /// Fit the secondary Y axis to its traces inside the X window.  Does
+    /// nothing unless [`active_y_axis2`](Self::active_y_axis2) is set.
+    pub fn fit_y2_bounds(&mut self, traces: &TracesCollection, not_shrink: bool) {
+        if self.active_y_axis2().is_none() {
+            return;
+        }
+        let (min_y, max_y) = self.time_y_range(traces, true);
+        if let Some(y2) = self.y_axis2.as_mut() {
+            apply_y_fit(&mut y2.bounds, min_y, max_y, not_shrink);
+        }
+    }
+
+    /// Displayed Y range of the visible traces on the left (`right == false`)
+    /// or right axis, over the samples inside the X window.
+    fn time_y_range(&self, traces: &TracesCollection, right: bool) -> (f64, f64) {
         let mut min_y = f64::MAX;
         let mut max_y = f64::MIN;
         let x_bounds = self.x_axis.bounds;
@@ -605,10 +1460,10 @@ impl ScopeData {
             let Some(trace) = traces.get_trace(name) else {
                 continue;
             };
-            if !trace.look.visible {
+            if !trace.look.visible || self.on_right_axis(trace) != right {
                 continue;
             }
-            let points = if self.paused {
+            let points = if self.shows_snapshot() {
                 if let Some(snap) = &trace.snap {
                     snap
                 } else {
@@ -624,7 +1479,7 @@ impl ScopeData {
                 if p[0] > x_bounds.1 {
                     break;
                 }
-                let y = p[1] + trace.offset;
+                let y = trace.display_y(p[1]);
                 if y < min_y {
                     min_y = y;
                 }
@@ -633,28 +1488,75 @@ impl ScopeData {
                 }
             }
         }
-        if min_y < max_y {
-            if not_shrink {
-                let cur = self.y_axis.bounds;
-                self.y_axis.bounds = (min_y.min(cur.0), max_y.max(cur.1));
-            } else {
-                self.y_axis.bounds = (min_y, max_y);
-            }
-        } else if min_y == max_y {
-            if min_y < 0.0 {
-                self.y_axis.bounds = (min_y, 0.0);
-            } else if min_y > 0.0 {
-                self.y_axis.bounds = (0.0, max_y);
-            } else {
-                // Both min and max are zero; set to -1.0 to 1.0
-                self.y_axis.bounds = (-1.0, 1.0);
-            }
-        }
+        (min_y, max_y)
     }
 
     pub fn fit_bounds(&mut self, traces: &TracesCollection, not_shrink: bool) {
         self.fit_x_bounds(traces, not_shrink);
         self.fit_y_bounds(traces, not_shrink);
+        self.fit_y2_bounds(traces, not_shrink);
+    }
+
+    /// X and Y extent of every visible trace's full buffer, with Y in display
+    /// units (offsets applied).  Right-axis traces count towards X only, so
+    /// the Y extent is empty (`min > max`) when all traces are on the right.
+    /// `None` when there is no data.
+    pub fn data_extent(&self, traces: &TracesCollection) -> Option<((f64, f64), (f64, f64))> {
+        let mut x = (f64::INFINITY, f64::NEG_INFINITY);
+        let mut y = (f64::INFINITY, f64::NEG_INFINITY);
+        for name in self.trace_order.iter() {
+            let Some(trace) = traces.get_trace(name) else {
+                continue;
+            };
+            if !trace.look.visible {
+                continue;
+            }
+            let Some(points) = traces.get_points_ref(name, self.shows_snapshot()) else {
+                continue;
+            };
+            let right = self.on_right_axis(trace);
+            for p in points.iter() {
+                let v = trace.display_y(p[1]);
+                if !p[0].is_finite() || !v.is_finite() {
+                    continue;
+                }
+                x = (x.0.min(p[0]), x.1.max(p[0]));
+                if !right {
+                    y = (y.0.min(v), y.1.max(v));
+                }
+            }
+        }
+        (x.0 <= x.1).then_some((x, y))
+    }
+
+    /// Fit both axes once to all data of the visible traces and hand the view
+    /// back to manual control (auto-fit is switched off on both axes).
+    ///
+    /// Unlike [`fit_bounds`](Self::fit_bounds), Y covers the whole buffer
+    /// rather than only the samples inside the fitted X window.
+    pub fn fit_all_data(&mut self, traces: &TracesCollection) {
+        if self.scope_type == ScopeType::XYScope || self.uses_x_source() {
+            self.fit_bounds(traces, false);
+        } else if let Some(((x_min, x_max), (y_min, y_max))) = self.data_extent(traces) {
+            let pad = |lo: f64, hi: f64| {
+                if hi > lo {
+                    (lo, hi)
+                } else {
+                    (lo - 0.5, hi + 0.5)
+                }
+            };
+            self.x_axis.bounds = pad(x_min, x_max);
+            self.time_window = self.x_axis.bounds.1 - self.x_axis.bounds.0;
+            if y_min <= y_max {
+                self.y_axis.bounds = pad(y_min, y_max);
+            }
+            self.fit_y2_bounds(traces, false);
+            if let Some(y2) = self.y_axis2.as_mut() {
+                y2.auto_fit = false;
+            }
+        }
+        self.x_axis.auto_fit = false;
+        self.y_axis.auto_fit = false;
     }
 
     pub fn get_drawn_points(
@@ -664,13 +1566,90 @@ impl ScopeData {
     ) -> Option<Vec<[f64; 2]>> {
         if self.scope_type == ScopeType::XYScope {
             traces
-                .get_points_ref(name, self.paused)
+                .get_points_ref(name, self.shows_snapshot())
                 .map(|v| v.iter().copied().collect())
         } else {
             // Decimate directly from the VecDeque without cloning all points first.
             // This avoids a 10K-point clone per trace per frame.
-            traces.get_drawn_points_decimated(name, self.paused, self.x_axis.bounds, 2000)
+            traces.get_drawn_points_decimated_with(
+                name,
+                self.shows_snapshot(),
+                self.x_axis.bounds,
+                2000,
+                self.decimation,
+            )
+        }
+    }
+
+    /// Visible traces of this scope, in scope order, and their drawn points
+    /// with [`signed`](TraceData::signed) applied as in exports.  Points come
+    /// from the snapshot while the scope shows it.
+    pub fn visible_series(
+        &self,
+        traces: &TracesCollection,
+    ) -> (Vec<TraceRef>, HashMap<TraceRef, Vec<[f64; 2]>>) {
+        let mut order = Vec::new();
+        let mut series = HashMap::new();
+        for name in self.drawn_traces(traces).0 {
+            let Some(tr) = traces.get_trace(&name).filter(|tr| tr.look.visible) else {
+                continue;
+            };
+            let Some(pts) = self.get_drawn_points(&name, traces) else {
+                continue;
+            };
+            let pts = pts.into_iter().map(|p| [p[0], tr.signed(p[1])]).collect();
+            series.insert(name.clone(), pts);
+            order.push(name);
+        }
+        (order, series)
+    }
+
+    /// Traces to draw, in creation order, and how many were left out by
+    /// [`max_drawn_traces`](Self::max_drawn_traces).  Pinned traces win, then
+    /// those whose newest sample has the largest x.  Traces idle under their
+    /// [`auto_hide`](crate::data::traces::TraceData::auto_hide) rule are left
+    /// out first and not counted.
+    pub fn drawn_traces(&self, traces: &TracesCollection) -> (Vec<TraceRef>, usize) {
+        let mut ordered: Vec<TraceRef> = self.trace_order.clone();
+        ordered.retain(|n| {
+            let Some(rule) = traces.get_trace(n).and_then(|t| t.auto_hide) else {
+                return true;
+            };
+            traces
+                .get_points_ref(n, self.shows_snapshot())
+                .is_some_and(|pts| rule.is_active(pts))
+        });
+        ordered.sort_by_key(|n| {
+            traces
+                .get_trace(n)
+                .map(|t| t.creation_index)
+                .unwrap_or(usize::MAX)
+        });
+        if self.max_drawn_traces == 0 || ordered.len() <= self.max_drawn_traces {
+            return (ordered, 0);
         }
+
+        let newest_x = |n: &TraceRef| {
+            traces
+                .get_points_ref(n, self.shows_snapshot())
+                .and_then(|pts| pts.back())
+                .map_or(f64::NEG_INFINITY, |p| p[0])
+        };
+        let mut ranked: Vec<&TraceRef> = ordered.iter().collect();
+        ranked.sort_by(|a, b| {
+            let pa = self.pinned_traces.contains(a);
+            let pb = self.pinned_traces.contains(b);
+            pb.cmp(&pa)
+                .then_with(|| newest_x(b).total_cmp(&newest_x(a)))
+        });
+        let keep: Vec<TraceRef> = ranked
+            .into_iter()
+            .take(self.max_drawn_traces)
+            .cloned()
+            .collect();
+        let hidden = ordered.len() - keep.len();
+        ordered.retain(|n| keep.contains(n));
+        (ordered, hidden)
     }
 
     pub fn get_all_drawn_points(
@@ -19,11 +19,18 @@ pub enum ThresholdKind {
     LessThan { value: f64 },
     /// Event when `low <= value <= high`
     InRange { low: f64, high: f64 },
+    /// Event when value > `high`; it only ends once value drops below `low`
+    /// (Schmitt trigger), so noise around `high` does not cause chatter.
+    Hysteresis { high: f64, low: f64 },
+    /// Event while the slope between consecutive samples exceeds
+    /// `units_per_sec` in magnitude
+    RateExceeds { units_per_sec: f64 },
 }
 
 impl ThresholdKind {
     /// Compute excess value for the given sample.
-    /// Returns 0.0 if the condition is not met.
+    /// Returns 0.0 if the condition is not met, and always for `RateExceeds`,
+    /// which needs the slope (see [`excess_at`](Self::excess_at)).
     pub fn excess(&self, v: f64) -> f64 {
         match self {
             ThresholdKind::GreaterThan { value } => (v - *value).max(0.0),
@@ -35,6 +42,19 @@ impl ThresholdKind {
                     0.0
                 }
             }
+            ThresholdKind::Hysteresis { high, .. } => (v - *high).max(0.0),
+            ThresholdKind::RateExceeds { .. } => 0.0,
+        }
+    }
+
+    /// Excess for a sample with value `v` and local `slope` (units per second,
+    /// `None` for the first sample). Level kinds ignore the slope.
+    pub fn excess_at(&self, v: f64, slope: Option<f64>) -> f64 {
+        match self {
+            ThresholdKind::RateExceeds { units_per_sec } => {
+                slope.map_or(0.0, |s| (s.abs() - *units_per_sec).max(0.0))
+            }
+            _ => self.excess(v),
         }
     }
 
@@ -42,6 +62,16 @@ impl ThresholdKind {
     pub fn is_active(&self, v: f64) -> bool {
         self.excess(v) > 0.0
     }
+
+    /// Like [`is_active`](Self::is_active), but taking into account the local
+    /// `slope` and whether an event is already in progress: a `Hysteresis`
+    /// event stays open until the value drops below its release level.
+    pub fn holds(&self, v: f64, slope: Option<f64>, was_active: bool) -> bool {
+        match self {
+            ThresholdKind::Hysteresis { low, .. } if was_active => v >= *low,
+            _ => self.excess_at(v, slope) > 0.0,
+        }
+    }
 }
 
 /// Definition of a threshold.
@@ -64,8 +94,21 @@ pub struct ThresholdDef {
     pub stop_look: TraceLook,
     /// Minimum duration (seconds) for an event to be recorded. Default 0.002 s.
     pub min_duration_s: f64,
+    /// Quiet time (seconds) after a recorded event during which new
+    /// activations are ignored, so a burst yields one event.  Default 0 (off).
+    #[serde(default)]
+    pub debounce_secs: f64,
     /// Maximum number of events to keep (oldest dropped). Default 100.
     pub max_events: usize,
+    /// Also raise each event as a [`THRESHOLD_ALARM`](crate::events::EventKind::THRESHOLD_ALARM)
+    /// so hosts can notify (bell, e-mail, ...) without consuming the bulk event stream.
+    #[serde(default)]
+    pub notify: bool,
+    /// Emit a companion [`flag_trace_name`](Self::flag_trace_name) trace that
+    /// is `1` at each target sample where an event is in progress and `0`
+    /// elsewhere.
+    #[serde(default)]
+    pub emit_flag_trace: bool,
 
     #[serde(skip)]
     pub runtime_state: ThresholdRuntimeState,
@@ -81,7 +124,10 @@ impl Default for ThresholdDef {
             start_look: TraceLook::default(),
             stop_look: TraceLook::default(),
             min_duration_s: 0.002,
+            debounce_secs: 0.0,
             max_events: 100,
+            notify: false,
+            emit_flag_trace: false,
             runtime_state: ThresholdRuntimeState::default(),
         }
     }
@@ -111,10 +157,18 @@ pub struct ThresholdRuntimeState {
     pub start_t: f64,
     pub last_t: Option<f64>,
     pub last_excess: f64,
+    /// Value of the last processed sample, for the slope of rate thresholds.
+    pub last_v: Option<f64>,
     pub accum_area: f64,
     pub prev_in_t: Option<f64>,
+    /// End time of the last recorded event, for debouncing.
+    pub last_event_end: Option<f64>,
     /// Ring buffer of recent events (cap enforced per def.max_events).
     pub events: VecDeque<ThresholdEvent>,
+    /// `[t, 0 or 1]` flag samples produced by the last
+    /// [`process_threshold`](ThresholdDef::process_threshold) call, when
+    /// [`emit_flag_trace`](ThresholdDef::emit_flag_trace) is set.
+    pub flag_points: Vec<[f64; 2]>,
 }
 
 impl ThresholdRuntimeState {
@@ -126,15 +180,23 @@ impl ThresholdRuntimeState {
         }
     }
 
+    /// Whether the condition currently holds (an event is in progress).
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
     /// Reset the runtime state.
     pub fn reset(&mut self) {
         self.active = false;
         self.start_t = 0.0;
         self.last_t = None;
         self.last_excess = 0.0;
+        self.last_v = None;
         self.accum_area = 0.0;
         self.prev_in_t = None;
+        self.last_event_end = None;
         self.events.clear();
+        self.flag_points.clear();
     }
 }
 
@@ -156,9 +218,25 @@ impl ThresholdDef {
                 let hi = axis_setting.format_value(*high, Some(diff));
                 format!("{} in [{}, {}]", self.target.0, lo, hi)
             }
+            ThresholdKind::Hysteresis { high, low } => {
+                let diff = (*high - *low).abs();
+                let hi = axis_setting.format_value(*high, Some(diff));
+                let lo = axis_setting.format_value(*low, Some(diff));
+                format!("{} > {} (release < {})", self.target.0, hi, lo)
+            }
+            ThresholdKind::RateExceeds { units_per_sec } => {
+                let v_fmt = axis_setting.format_value(*units_per_sec, None);
+                format!("|d{}/dt| > {}/s", self.target.0, v_fmt)
+            }
         }
     }
 
+    /// Name of the companion trace emitted when
+    /// [`emit_flag_trace`](Self::emit_flag_trace) is set.
+    pub fn flag_trace_name(&self) -> TraceRef {
+        TraceRef(format!("{} exceeded", self.name))
+    }
+
     /// Clear all events for this threshold.
     pub fn clear_threshold_events(&mut self) {
         self.runtime_state.events.clear();
@@ -186,6 +264,7 @@ impl ThresholdDef {
 
     /// Process new data points for this threshold, updating its runtime state and recording events.
     pub fn process_threshold(&mut self, sources: HashMap<TraceRef, Vec<[f64; 2]>>) {
+        self.runtime_state.flag_points.clear();
         let data = match sources.get(&self.target) {
             Some(d) => d,
             None => return,
@@ -207,7 +286,22 @@ impl ThresholdDef {
         for p in data.iter().skip(start_idx) {
             let t = p[0];
             let v = p[1];
-            let e = self.kind.excess(v);
+            let slope = match (self.runtime_state.last_t, self.runtime_state.last_v) {
+                (Some(t0), Some(v0)) if t > t0 => Some((v - v0) / (t - t0)),
+                _ => None,
+            };
+            let mut e = self.kind.excess_at(v, slope);
+            let mut on = self.kind.holds(v, slope, self.runtime_state.active);
+            // Within the debounce window a new activation is treated as idle.
+            if !self.runtime_state.active
+                && self
+                    .runtime_state
+                    .last_event_end
+                    .is_some_and(|end| t - end < self.debounce_secs)
+            {
+                e = 0.0;
+                on = false;
+            }
 
             // Integrate area using trapezoidal rule
             if let Some(t0) = self.runtime_state.last_t {
@@ -219,11 +313,11 @@ impl ThresholdDef {
             }
 
             // State transitions
-            if !self.runtime_state.active && e > 0.0 {
+            if !self.runtime_state.active && on {
                 // Start of new event
                 self.runtime_state.active = true;
                 self.runtime_state.start_t = t;
-            } else if self.runtime_state.active && e == 0.0 {
+            } else if self.runtime_state.active && !on {
                 // End of event
                 let end_t = t;
                 let dur = end_t - self.runtime_state.start_t;
@@ -237,13 +331,20 @@ impl ThresholdDef {
                         area: self.runtime_state.accum_area,
                     };
                     self.runtime_state.push_event_capped(evt, self.max_events);
+                    self.runtime_state.last_event_end = Some(end_t);
                 }
                 self.runtime_state.active = false;
                 self.runtime_state.accum_area = 0.0;
             }
 
+            if self.emit_flag_trace {
+                let flag = if self.runtime_state.active { 1.0 } else { 0.0 };
+                self.runtime_state.flag_points.push([t, flag]);
+            }
+
             self.runtime_state.last_t = Some(t);
             self.runtime_state.last_excess = e;
+            self.runtime_state.last_v = Some(v);
             self.runtime_state.prev_in_t = Some(t);
         }
     }
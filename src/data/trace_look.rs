@@ -2,11 +2,28 @@
 
 use eframe::egui::Color32;
 use egui_plot::{LineStyle, MarkerShape};
+use serde::{Deserialize, Serialize};
+
+/// How per-sample Y uncertainties are drawn around a trace.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorStyle {
+    /// Do not draw uncertainties.
+    Off,
+    /// Vertical bar from `y - err` to `y + err` at each sample.
+    #[default]
+    Bars,
+    /// Shaded band between `y - err` and `y + err`.
+    Band,
+}
 
 /// The visual presentation of a trace (color, visibility, line style, markers).
 #[derive(Debug, Clone)]
 pub struct TraceLook {
     pub color: Color32,
+    /// The color was picked by the user rather than taken from the palette.
+    /// Palette recoloring leaves such traces alone.
+    pub color_is_custom: bool,
     pub visible: bool,
     pub width: f32,
     pub show_points: bool,
@@ -14,12 +31,19 @@ pub struct TraceLook {
     pub style: LineStyle,
     pub point_size: f32,
     pub marker: MarkerShape,
+    /// How uncertainties are drawn, for samples sent with one.
+    pub error_style: ErrorStyle,
+    /// When set, the area between the trace and this Y value (in displayed
+    /// units, after invert and offset) is filled with a translucent trace
+    /// colour.  Time scopes only.
+    pub fill_baseline: Option<f64>,
 }
 
 impl Default for TraceLook {
     fn default() -> Self {
         Self {
             color: Color32::GRAY,
+            color_is_custom: false,
             visible: true,
             width: 1.5,
             show_points: false,
@@ -27,6 +51,42 @@ impl Default for TraceLook {
             style: LineStyle::Solid,
             point_size: 4.0,
             marker: MarkerShape::Circle,
+            error_style: ErrorStyle::default(),
+            fill_baseline: None,
+        }
+    }
+}
+
+/// Look given to a trace when it is first created, unless a loaded state
+/// already holds a style for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceDefaults {
+    /// Show new traces; `false` makes displaying a trace opt-in.
+    pub visible: bool,
+    pub width: f32,
+    pub style: LineStyle,
+}
+
+impl Default for TraceDefaults {
+    fn default() -> Self {
+        let look = TraceLook::default();
+        Self {
+            visible: look.visible,
+            width: look.width,
+            style: look.style,
+        }
+    }
+}
+
+impl TraceDefaults {
+    /// Look for the new trace with the given index (palette colour as in
+    /// [`TraceLook::new`]).
+    pub fn look(&self, index: usize) -> TraceLook {
+        TraceLook {
+            visible: self.visible,
+            width: self.width,
+            style: self.style,
+            ..TraceLook::new(index)
         }
     }
 }
@@ -40,6 +100,12 @@ impl TraceLook {
         }
     }
 
+    /// Set a user-chosen color, pinning it against palette recoloring.
+    pub fn set_custom_color(&mut self, color: Color32) {
+        self.color = color;
+        self.color_is_custom = true;
+    }
+
     /// Allocate a distinct color for the given trace index.
     pub fn alloc_color(index: usize) -> Color32 {
         // Consult the global colour palette, which is kept in sync with the
@@ -1,9 +1,12 @@
 //! TraceRef and TracesCollection: trace identity and data management.
 
-use crate::data::trace_look::TraceLook;
-use crate::sink::PlotCommand;
+use crate::data::scope::YAxisSelect;
+use crate::data::trace_look::{TraceDefaults, TraceLook};
+use crate::sink::{PlotCommand, PlotSink, TraceId};
 use serde::{Deserialize, Serialize};
-use std::collections::{hash_map::Entry, HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 /// Identifier for a trace by name.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -114,15 +117,118 @@ impl From<TraceRef> for String {
     }
 }
 
+/// Running sample counters of a [`TracesCollection`], for diagnosing data
+/// loss and tuning `max_points` / `max_age_secs`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IngestStats {
+    /// Samples received through the command channel.
+    pub received: u64,
+    /// Samples that never made it into a trace: received ones whose trace
+    /// id was unknown or belongs to a removed trace, plus samples a bounded
+    /// sink rejected with a full queue (see
+    /// [`TracesCollection::count_rejected_sends`]).
+    pub dropped: u64,
+    /// Samples pushed out of the live buffers by `max_points` or `max_age_secs`.
+    pub evicted: u64,
+    /// Received samples older than the last sample of their trace, whatever
+    /// the [`OutOfOrderPolicy`] did with them.  Rejected ones are also
+    /// counted in `dropped`.
+    pub out_of_order: u64,
+    /// Received samples per second, over roughly the last second.
+    pub received_rate: f64,
+    /// Dropped samples per second, over roughly the last second.
+    pub dropped_rate: f64,
+    /// Evicted samples per second, over roughly the last second.
+    pub evicted_rate: f64,
+}
+
+/// What [`TracesCollection::update`] does with a sample whose timestamp is
+/// earlier than the last sample of its trace.
+///
+/// Thresholds, math traces and line drawing assume non-decreasing
+/// timestamps; a late sample breaks their binary searches and draws a line
+/// back in time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum OutOfOrderPolicy {
+    /// Drop the sample and count it in [`IngestStats`], printing a warning
+    /// the first time a trace sends one.  Costs one comparison per sample.
+    #[default]
+    Reject,
+    /// Insert the sample at its sorted position.  Each late sample costs a
+    /// binary search plus shifting every newer sample in the buffer, i.e.
+    /// O(n) in the buffer length; cheap for slightly late samples, slow for
+    /// sources that are badly out of order at high rates.
+    SortInsert,
+    /// Append the sample as-is.  Only for sources known to be well-behaved,
+    /// or when the disorder itself is what you want to see.
+    Accept,
+}
+
+/// Push `p` onto `live` under `policy`.  Returns `false` if it was rejected.
+fn push_sample(
+    live: &mut VecDeque<[f64; 2]>,
+    p: [f64; 2],
+    policy: OutOfOrderPolicy,
+    ingest: &mut IngestStats,
+) -> bool {
+    let late = live.back().is_some_and(|last| p[0] < last[0]);
+    if !late {
+        live.push_back(p);
+        return true;
+    }
+    ingest.out_of_order += 1;
+    match policy {
+        OutOfOrderPolicy::Reject => {
+            ingest.dropped += 1;
+            false
+        }
+        OutOfOrderPolicy::SortInsert => {
+            let idx = live.partition_point(|q| q[0] <= p[0]);
+            live.insert(idx, p);
+            true
+        }
+        OutOfOrderPolicy::Accept => {
+            live.push_back(p);
+            true
+        }
+    }
+}
+
+/// Move every id mapped to `name` from `id_to_name` into `removed_ids`.
+fn forget_ids(
+    id_to_name: &mut HashMap<TraceId, String>,
+    removed_ids: &mut HashSet<TraceId>,
+    name: &TraceRef,
+) {
+    id_to_name.retain(|id, n| {
+        let keep = *n != name.0;
+        if !keep {
+            removed_ids.insert(*id);
+        }
+        keep
+    });
+}
+
 /// Collection of all traces with their data.
 pub struct TracesCollection {
     traces: HashMap<TraceRef, TraceData>,
     pub max_points: usize,
+    /// Per-trace overrides of `max_points`, from the scopes' own caps.  See
+    /// [`trace_point_caps`](crate::data::scope::trace_point_caps).
+    pub point_caps: HashMap<TraceRef, usize>,
     pub points_bounds: (usize, usize),
     /// Maximum age in seconds for retained points.  0.0 disables time-based pruning.
     pub max_age_secs: f64,
     /// Slider bounds for `max_age_secs`.
     pub max_age_bounds: (f64, f64),
+    /// Pre-allocate each trace's live buffer for `max_points` samples so
+    /// steady streaming never reallocates.  Trades memory for smoother frames.
+    pub reserve_points: bool,
+    /// Handling of samples older than the last sample of their trace.
+    pub out_of_order: OutOfOrderPolicy,
+    /// Look of traces created from incoming data.  A style restored from a
+    /// saved state takes precedence.
+    pub trace_defaults: TraceDefaults,
     pub hover_trace: Option<Vec<TraceRef>>,
     rx: Option<std::sync::mpsc::Receiver<PlotCommand>>,
     /// Mapping from numeric trace ID to trace name (for PlotCommand API)
@@ -131,6 +237,33 @@ pub struct TracesCollection {
     /// When a trace is loaded from a saved state, the style is stored here
     /// until the trace is created from incoming data.
     pending_styles: HashMap<String, (TraceLook, f64)>,
+    /// Pending display options for traces that haven't been created yet.
+    pending_display: HashMap<String, TraceDisplayOptions>,
+    /// Traces deleted via [`remove_trace`](Self::remove_trace) that have not
+    /// been recreated since.  Lets math traces and thresholds notice a
+    /// vanished input instead of treating it as "no data yet".
+    removed: HashSet<TraceRef>,
+    /// Traces removed by a [`PlotCommand::RemoveTrace`] whose scope
+    /// membership still needs cleaning up.
+    removed_by_command: Vec<TraceRef>,
+    /// Ids of removed traces.  Commands for them are ignored (rather than
+    /// auto-registering a `trace-<id>`) until the id is registered again.
+    removed_ids: HashSet<TraceId>,
+    /// Static copies made by [`duplicate_as_static`](Self::duplicate_as_static).
+    /// No source feeds them and they are skipped by pruning and clearing.
+    frozen: HashSet<TraceRef>,
+    ingest: IngestStats,
+    /// Traces already warned about for rejected out-of-order samples.
+    warned_out_of_order: HashSet<TraceRef>,
+    /// Start of the current rate window and the counters at that time.
+    rate_mark: Option<(std::time::Instant, IngestStats)>,
+    /// Set by [`request_clear`](Self::request_clear); applied by the next
+    /// [`update`](Self::update).
+    clear_requested: bool,
+    /// Rejection counters of the sinks passed to
+    /// [`count_rejected_sends`](Self::count_rejected_sends), with the value
+    /// already added to the stats.
+    rejected_counters: Vec<(Arc<AtomicU64>, u64)>,
 }
 
 impl Default for TracesCollection {
@@ -138,13 +271,27 @@ impl Default for TracesCollection {
         Self {
             traces: HashMap::new(),
             max_points: 10_000,
+            point_caps: HashMap::new(),
             points_bounds: (100, 200000),
             max_age_secs: 0.0,
             max_age_bounds: (0.0, 3600.0),
+            reserve_points: false,
+            out_of_order: OutOfOrderPolicy::default(),
+            trace_defaults: TraceDefaults::default(),
             hover_trace: None,
             rx: None,
             id_to_name: HashMap::new(),
             pending_styles: HashMap::new(),
+            pending_display: HashMap::new(),
+            removed: HashSet::new(),
+            removed_by_command: Vec::new(),
+            removed_ids: HashSet::new(),
+            frozen: HashSet::new(),
+            ingest: IngestStats::default(),
+            warned_out_of_order: HashSet::new(),
+            rate_mark: None,
+            clear_requested: false,
+            rejected_counters: Vec::new(),
         }
     }
 }
@@ -174,34 +321,61 @@ impl TracesCollection {
         }
     }
 
+    /// Store pending display options (display name, smoothing) for a trace
+    /// that may not exist yet.  Applied immediately if the trace exists.
+    pub fn set_pending_display(&mut self, name: &str, opts: TraceDisplayOptions) {
+        let tref = TraceRef(name.to_string());
+        if let Some(tr) = self.traces.get_mut(&tref) {
+            tr.display_name = opts.display_name;
+            tr.display_smooth_window = opts.display_smooth_window;
+            tr.invert = opts.invert;
+            tr.auto_hide = opts.auto_hide;
+            tr.axis = opts.axis;
+        } else if opts == TraceDisplayOptions::default() {
+            self.pending_display.remove(name);
+        } else {
+            self.pending_display.insert(name.to_string(), opts);
+        }
+    }
+
+    /// Label to show for a trace in legends, menus and readouts.
+    ///
+    /// Returns the user-assigned display name when set, otherwise the trace id.
+    pub fn display_name(&self, name: &TraceRef) -> String {
+        self.traces
+            .get(name)
+            .map(|tr| tr.label(name).to_string())
+            .unwrap_or_else(|| name.0.clone())
+    }
+
     fn update_rx(&mut self) -> Vec<TraceRef> {
         let mut new_traces: Vec<TraceRef> = Vec::new();
-        if let Some(rx) = &self.rx {
+        // Taken out while draining so trace creation can borrow `self`.
+        if let Some(rx) = self.rx.take() {
             while let Ok(cmd) = rx.try_recv() {
+                let samples = cmd.sample_count() as u64;
+                self.ingest.received += samples;
+                if !matches!(cmd, PlotCommand::RegisterTrace { .. })
+                    && self.removed_ids.contains(&cmd.trace_id())
+                {
+                    self.ingest.dropped += samples;
+                    continue;
+                }
+                // Only single points auto-register an unknown id.
+                if !matches!(cmd, PlotCommand::Point { .. })
+                    && !self.id_to_name.contains_key(&cmd.trace_id())
+                {
+                    self.ingest.dropped += samples;
+                }
+                let cmd_id = cmd.trace_id();
+                let late_before = self.ingest.out_of_order;
                 match cmd {
                     PlotCommand::RegisterTrace { id, name, info } => {
+                        self.removed_ids.remove(&id);
                         self.id_to_name.insert(id, name.clone());
-                        let tref = TraceRef(name.clone());
-                        let new_index = self.next_color_index();
-                        let pending = self.pending_styles.remove(name.as_str());
-                        let entry = match self.traces.entry(tref.clone()) {
-                            Entry::Occupied(entry) => entry.into_mut(),
-                            Entry::Vacant(entry) => {
-                                new_traces.push(tref.clone());
-                                let (look, offset) =
-                                    pending.unwrap_or((TraceLook::new(new_index), 0.0));
-                                entry.insert(TraceData {
-                                    look,
-                                    offset,
-                                    live: VecDeque::new(),
-                                    snap: None,
-                                    info: String::new(),
-                                    creation_index: new_index,
-                                    #[cfg(feature = "fft")]
-                                    last_fft: None,
-                                })
-                            }
-                        };
+                        let tref = TraceRef(name);
+                        self.ensure_trace(&tref, &mut new_traces);
+                        let entry = self.traces.get_mut(&tref).unwrap();
                         if let Some(inf) = info {
                             entry.info = inf;
                         }
@@ -216,114 +390,107 @@ impl TracesCollection {
                     }
                     PlotCommand::Point { trace_id, point } => {
                         if let Some(name) = self.id_to_name.get(&trace_id).cloned() {
-                            let tref = TraceRef(name.clone());
-                            let new_index = self.next_color_index();
-                            let pending = self.pending_styles.remove(name.as_str());
-                            let entry = match self.traces.entry(tref.clone()) {
-                                Entry::Occupied(entry) => entry.into_mut(),
-                                Entry::Vacant(entry) => {
-                                    new_traces.push(tref.clone());
-                                    let (look, offset) =
-                                        pending.unwrap_or((TraceLook::new(new_index), 0.0));
-                                    entry.insert(TraceData {
-                                        look,
-                                        offset,
-                                        live: VecDeque::new(),
-                                        snap: None,
-                                        info: String::new(),
-                                        creation_index: new_index,
-                                        #[cfg(feature = "fft")]
-                                        last_fft: None,
-                                    })
-                                }
-                            };
-                            entry.live.push_back([point.x, point.y]);
-                            if entry.live.len() > self.max_points {
+                            let tref = TraceRef(name);
+                            let cap = self.max_points_for(&tref);
+                            self.ensure_trace(&tref, &mut new_traces);
+                            let entry = self.traces.get_mut(&tref).unwrap();
+                            push_sample(
+                                &mut entry.live,
+                                [point.x, point.y],
+                                self.out_of_order,
+                                &mut self.ingest,
+                            );
+                            if entry.live.len() > cap {
                                 entry.live.pop_front();
+                                self.ingest.evicted += 1;
                             }
                         } else {
                             // Auto-register trace
                             let name = format!("trace-{}", trace_id);
                             self.id_to_name.insert(trace_id, name.clone());
-                            let tref = TraceRef(name.clone());
-                            let new_index = self.next_color_index();
-                            let pending = self.pending_styles.remove(name.as_str());
-                            let entry = self.traces.entry(tref.clone()).or_insert_with(|| {
-                                new_traces.push(tref.clone());
-                                let (look, offset) =
-                                    pending.unwrap_or((TraceLook::new(new_index), 0.0));
-                                TraceData {
-                                    look,
-                                    offset,
-                                    live: VecDeque::new(),
-                                    snap: None,
-                                    info: String::new(),
-                                    creation_index: new_index,
-                                    #[cfg(feature = "fft")]
-                                    last_fft: None,
-                                }
-                            });
-                            entry.live.push_back([point.x, point.y]);
+                            let tref = TraceRef(name);
+                            self.ensure_trace(&tref, &mut new_traces);
+                            let entry = self.traces.get_mut(&tref).unwrap();
+                            push_sample(
+                                &mut entry.live,
+                                [point.x, point.y],
+                                self.out_of_order,
+                                &mut self.ingest,
+                            );
                         }
                     }
-                    PlotCommand::Points { trace_id, points } => {
+                    PlotCommand::Points { trace_id, points }
+                    | PlotCommand::AppendBatch { trace_id, points } => {
                         if let Some(name) = self.id_to_name.get(&trace_id).cloned() {
-                            let tref = TraceRef(name.clone());
-                            let new_index = self.next_color_index();
-                            let pending = self.pending_styles.remove(name.as_str());
-                            let entry = match self.traces.entry(tref.clone()) {
-                                Entry::Occupied(entry) => entry.into_mut(),
-                                Entry::Vacant(entry) => {
-                                    new_traces.push(tref.clone());
-                                    let (look, offset) =
-                                        pending.unwrap_or((TraceLook::new(new_index), 0.0));
-                                    entry.insert(TraceData {
-                                        look,
-                                        offset,
-                                        live: VecDeque::new(),
-                                        snap: None,
-                                        info: String::new(),
-                                        creation_index: new_index,
-                                        #[cfg(feature = "fft")]
-                                        last_fft: None,
-                                    })
+                            let tref = TraceRef(name);
+                            let cap = self.max_points_for(&tref);
+                            self.ensure_trace(&tref, &mut new_traces);
+                            let entry = self.traces.get_mut(&tref).unwrap();
+                            let in_order = points.windows(2).all(|w| w[0].x <= w[1].x)
+                                && points.first().is_none_or(|first| {
+                                    entry.live.back().is_none_or(|last| first.x >= last[0])
+                                });
+                            if in_order {
+                                // Skip what this batch would evict anyway, then
+                                // extend in one go.
+                                let skip = points.len().saturating_sub(cap);
+                                self.ingest.evicted += skip as u64;
+                                entry.live.reserve(points.len() - skip);
+                                entry.live.extend(points[skip..].iter().map(|p| [p.x, p.y]));
+                            } else {
+                                for p in points {
+                                    push_sample(
+                                        &mut entry.live,
+                                        [p.x, p.y],
+                                        self.out_of_order,
+                                        &mut self.ingest,
+                                    );
                                 }
-                            };
-                            for p in points {
-                                entry.live.push_back([p.x, p.y]);
                             }
-                            while entry.live.len() > self.max_points {
+                            while entry.live.len() > cap {
                                 entry.live.pop_front();
+                                self.ingest.evicted += 1;
                             }
                         }
                     }
-                    PlotCommand::SetData { trace_id, points } => {
+                    PlotCommand::PointsWithError {
+                        trace_id,
+                        points,
+                        y_err,
+                    } => {
                         if let Some(name) = self.id_to_name.get(&trace_id).cloned() {
-                            let tref = TraceRef(name.clone());
-                            let new_index = self.next_color_index();
-                            let pending = self.pending_styles.remove(name.as_str());
-                            let entry = match self.traces.entry(tref.clone()) {
-                                Entry::Occupied(entry) => entry.into_mut(),
-                                Entry::Vacant(entry) => {
-                                    new_traces.push(tref.clone());
-                                    let (look, offset) =
-                                        pending.unwrap_or((TraceLook::new(new_index), 0.0));
-                                    entry.insert(TraceData {
-                                        look,
-                                        offset,
-                                        live: VecDeque::new(),
-                                        snap: None,
-                                        info: String::new(),
-                                        creation_index: new_index,
-                                        #[cfg(feature = "fft")]
-                                        last_fft: None,
-                                    })
+                            let tref = TraceRef(name);
+                            let cap = self.max_points_for(&tref);
+                            self.ensure_trace(&tref, &mut new_traces);
+                            let entry = self.traces.get_mut(&tref).unwrap();
+                            for (i, p) in points.iter().enumerate() {
+                                let kept = push_sample(
+                                    &mut entry.live,
+                                    [p.x, p.y],
+                                    self.out_of_order,
+                                    &mut self.ingest,
+                                );
+                                if !kept {
+                                    continue;
+                                }
+                                if let Some(e) = y_err.get(i).filter(|e| e.is_finite()) {
+                                    entry.y_err.insert(p.x.to_bits(), e.abs());
                                 }
-                            };
-                            entry.live.clear();
-                            for p in points {
-                                entry.live.push_back([p.x, p.y]);
                             }
+                            while entry.live.len() > cap {
+                                entry.live.pop_front();
+                                self.ingest.evicted += 1;
+                            }
+                            entry.prune_y_err();
+                        }
+                    }
+                    PlotCommand::SetData { trace_id, points } => {
+                        if let Some(name) = self.id_to_name.get(&trace_id).cloned() {
+                            let tref = TraceRef(name);
+                            self.ensure_trace(&tref, &mut new_traces);
+                            let entry = self.traces.get_mut(&tref).unwrap();
+                            entry.live = points.into_iter().map(|p| [p.x, p.y]).collect();
+                            entry.y_err.clear();
                         }
                     }
                     PlotCommand::ClearData { trace_id } => {
@@ -331,9 +498,19 @@ impl TracesCollection {
                             let tref = TraceRef(name);
                             if let Some(tr) = self.traces.get_mut(&tref) {
                                 tr.live.clear();
+                                tr.y_err.clear();
                             }
                         }
                     }
+                    PlotCommand::RemoveTrace { trace_id } => {
+                        if let Some(name) = self.id_to_name.get(&trace_id).cloned() {
+                            let tref = TraceRef(name);
+                            forget_ids(&mut self.id_to_name, &mut self.removed_ids, &tref);
+                            self.traces.remove(&tref);
+                            self.removed.insert(tref.clone());
+                            self.removed_by_command.push(tref);
+                        }
+                    }
                     PlotCommand::SetPointsY { trace_id, xs, y } => {
                         if let Some(name) = self.id_to_name.get(&trace_id).cloned() {
                             let tref = TraceRef(name);
@@ -397,24 +574,125 @@ impl TracesCollection {
                         }
                     }
                 }
+                if self.ingest.out_of_order > late_before
+                    && self.out_of_order == OutOfOrderPolicy::Reject
+                {
+                    if let Some(name) = self.id_to_name.get(&cmd_id) {
+                        let tref = TraceRef(name.clone());
+                        if self.warned_out_of_order.insert(tref) {
+                            eprintln!(
+                                "liveplot: dropping out-of-order samples for trace '{name}' \
+                                 (timestamp earlier than the last sample)"
+                            );
+                        }
+                    }
+                }
             }
+            self.rx = Some(rx);
         }
         new_traces
     }
 
     fn drain(&mut self) {
-        for (_name, trace) in self.traces.iter_mut() {
-            trace.prune_by_points(self.max_points);
+        let capacity = self.initial_capacity();
+        for (name, trace) in self.traces.iter_mut() {
+            if self.frozen.contains(name) {
+                continue;
+            }
+            let len = trace.live.len();
+            let cap = self.point_caps.get(name).copied();
+            trace.prune_by_points(cap.unwrap_or(self.max_points));
             trace.prune_by_age(self.max_age_secs);
+            self.ingest.evicted += (len - trace.live.len()) as u64;
+            // Keeps the reservation after `max_points` grows or a buffer was replaced.
+            trace
+                .live
+                .reserve(capacity.saturating_sub(trace.live.len()));
+        }
+    }
+
+    /// Live samples kept for `name`: its entry in
+    /// [`point_caps`](Self::point_caps), else [`max_points`](Self::max_points).
+    pub fn max_points_for(&self, name: &TraceRef) -> usize {
+        self.point_caps
+            .get(name)
+            .copied()
+            .unwrap_or(self.max_points)
+    }
+
+    /// Capacity to allocate for a new live buffer: `max_points + 1` (a push
+    /// precedes the trim) with [`reserve_points`](Self::reserve_points), else 0.
+    fn initial_capacity(&self) -> usize {
+        if self.reserve_points {
+            self.max_points + 1
+        } else {
+            0
         }
     }
 
     pub fn update(&mut self) -> Vec<TraceRef> {
+        // Before ingesting, so samples sent after the request survive.
+        if std::mem::take(&mut self.clear_requested) {
+            self.clear_all();
+        }
         let new_traces = self.update_rx();
         self.drain();
+        self.count_rejected();
+        self.update_rates();
         new_traces
     }
 
+    /// Count `sink`'s full-queue rejections as dropped samples in
+    /// [`ingest_stats`](Self::ingest_stats).  Clones of one sink share their
+    /// counter, so registering any of them once is enough.
+    pub fn count_rejected_sends(&mut self, sink: &PlotSink) {
+        let counter = sink.rejected_counter();
+        if !self
+            .rejected_counters
+            .iter()
+            .any(|(c, _)| Arc::ptr_eq(c, &counter))
+        {
+            let seen = counter.load(Ordering::Relaxed);
+            self.ingest.dropped += seen;
+            self.rejected_counters.push((counter, seen));
+        }
+    }
+
+    /// Add sink rejections since the last frame to the dropped count.
+    fn count_rejected(&mut self) {
+        for (counter, seen) in &mut self.rejected_counters {
+            let now = counter.load(Ordering::Relaxed);
+            self.ingest.dropped += now - *seen;
+            *seen = now;
+        }
+    }
+
+    /// Recompute the per-second rates once at least a second has passed.
+    fn update_rates(&mut self) {
+        let now = std::time::Instant::now();
+        let (since, start) = *self.rate_mark.get_or_insert((now, self.ingest));
+        let dt = now.duration_since(since).as_secs_f64();
+        if dt < 1.0 {
+            return;
+        }
+        let s = &mut self.ingest;
+        s.received_rate = (s.received - start.received) as f64 / dt;
+        s.dropped_rate = (s.dropped - start.dropped) as f64 / dt;
+        s.evicted_rate = (s.evicted - start.evicted) as f64 / dt;
+        self.rate_mark = Some((now, *s));
+    }
+
+    /// Sample counters since creation or the last
+    /// [`reset_ingest_stats`](Self::reset_ingest_stats).
+    pub fn ingest_stats(&self) -> IngestStats {
+        self.ingest
+    }
+
+    pub fn reset_ingest_stats(&mut self) {
+        self.ingest = IngestStats::default();
+        self.rate_mark = None;
+    }
+
     pub fn take_snapshot(&mut self) {
         for (_name, trace) in self.traces.iter_mut() {
             trace.take_snapshot();
@@ -437,40 +715,153 @@ impl TracesCollection {
         }
     }
 
+    /// Clear the data of every trace except the static copies.
     pub fn clear_all(&mut self) {
-        for trace in self.traces.values_mut() {
-            trace.clear_all();
+        for (name, trace) in self.traces.iter_mut() {
+            if !self.frozen.contains(name) {
+                trace.clear_all();
+            }
         }
     }
 
+    /// Clear all trace data at the start of the next [`update`](Self::update)
+    /// rather than now.
+    ///
+    /// Use this from UI code: clearing in the middle of a frame would leave
+    /// scopes drawn earlier in that frame showing the old data and later ones
+    /// the cleared buffers.  Deferred, every scope of a frame sees the same
+    /// state.
+    pub fn request_clear(&mut self) {
+        self.clear_requested = true;
+    }
+
+    /// Whether a [`request_clear`](Self::request_clear) is still pending.
+    pub fn clear_pending(&self) -> bool {
+        self.clear_requested
+    }
+
+    /// Delete a trace entirely.  Prefer [`LivePlotData::remove_trace`], which
+    /// also drops the trace from every scope.  Data still arriving for the
+    /// trace's numeric id is dropped from then on.
+    ///
+    /// [`LivePlotData::remove_trace`]: crate::data::data::LivePlotData::remove_trace
     pub fn remove_trace(&mut self, name: &TraceRef) {
-        self.traces.remove(name);
+        forget_ids(&mut self.id_to_name, &mut self.removed_ids, name);
+        self.frozen.remove(name);
+        if self.traces.remove(name).is_some() {
+            self.removed.insert(name.clone());
+        }
+    }
+
+    /// Copy the current data of `name` into a new static trace named
+    /// `<label> (frozen HH:MM:SS)` and return its id.
+    ///
+    /// The copy takes the snapshot while paused, else the live buffer, keeps
+    /// the style (with the next palette colour) and never receives data,
+    /// pruning or clears; only deleting it removes it.
+    pub fn duplicate_as_static(&mut self, name: &TraceRef) -> Option<TraceRef> {
+        let src = self.traces.get(name)?;
+        let base = format!(
+            "{} (frozen {})",
+            src.label(name),
+            chrono::Local::now().format("%H:%M:%S")
+        );
+        let mut new_name = TraceRef::new(base.clone());
+        let mut n = 2;
+        while self.traces.contains_key(&new_name) {
+            new_name = TraceRef::new(format!("{base} #{n}"));
+            n += 1;
+        }
+
+        let mut copy = self.new_trace_data(new_name.as_ref());
+        let src = &self.traces[name];
+        let points = src.snap.as_ref().unwrap_or(&src.live).clone();
+        copy.look = TraceLook {
+            color: TraceLook::alloc_color(copy.creation_index),
+            color_is_custom: false,
+            ..src.look.clone()
+        };
+        copy.offset = src.offset;
+        copy.snap = src.snap.as_ref().map(|_| points.clone());
+        copy.live = points;
+        copy.info = format!("Static copy of {}", name.as_str());
+        copy.display_smooth_window = src.display_smooth_window;
+        copy.invert = src.invert;
+        copy.auto_hide = src.auto_hide;
+        copy.axis = src.axis;
+        copy.y_err = src.y_err.clone();
+        self.removed.remove(&new_name);
+        self.traces.insert(new_name.clone(), copy);
+        self.frozen.insert(new_name.clone());
+        Some(new_name)
+    }
+
+    /// Whether `name` is a static copy made by
+    /// [`duplicate_as_static`](Self::duplicate_as_static).
+    pub fn is_frozen(&self, name: &TraceRef) -> bool {
+        self.frozen.contains(name)
+    }
+
+    /// Whether `name` is the output of a math trace.
+    pub fn is_math(&self, name: &TraceRef) -> bool {
+        self.traces.get(name).is_some_and(|tr| tr.is_math)
+    }
+
+    /// Whether `name` was removed and has not been recreated since.
+    pub fn was_removed(&self, name: &TraceRef) -> bool {
+        self.removed.contains(name)
+    }
+
+    /// Take the traces removed by [`PlotCommand::RemoveTrace`] since the last
+    /// call, so the caller can drop them from its scopes.
+    pub fn take_removed(&mut self) -> Vec<TraceRef> {
+        std::mem::take(&mut self.removed_by_command)
     }
 
     pub fn get_trace_or_new(&mut self, name: &TraceRef) -> &mut TraceData {
         if !self.traces.contains_key(name) {
-            let new_index = self.next_color_index();
-            let pending = self.pending_styles.remove(name.as_ref());
-            let (look, offset) = pending.unwrap_or((TraceLook::new(new_index), 0.0));
-            // note: later when the TraceData is created the `creation_index` is set
-            // appropriately (see above insertion sites)
-            self.traces.insert(
-                name.clone(),
-                TraceData {
-                    look,
-                    offset,
-                    live: VecDeque::new(),
-                    snap: None,
-                    info: String::new(),
-                    creation_index: new_index,
-                    #[cfg(feature = "fft")]
-                    last_fft: None,
-                },
-            );
+            self.removed.remove(name);
+            let tr = self.new_trace_data(name.as_ref());
+            self.traces.insert(name.clone(), tr);
         }
         self.traces.get_mut(name).unwrap()
     }
 
+    /// Create the trace `name` from incoming data if it does not exist yet,
+    /// recording it in `new_traces`.
+    fn ensure_trace(&mut self, name: &TraceRef, new_traces: &mut Vec<TraceRef>) {
+        if !self.traces.contains_key(name) {
+            self.removed.remove(name);
+            let tr = self.new_trace_data(name.as_ref());
+            self.traces.insert(name.clone(), tr);
+            new_traces.push(name.clone());
+        }
+    }
+
+    /// An empty trace for `name`, taking the style and display options
+    /// restored for it (see [`set_pending_style`](Self::set_pending_style))
+    /// or else the next palette colour and [`trace_defaults`](Self::trace_defaults).
+    fn new_trace_data(&mut self, name: &str) -> TraceData {
+        let new_index = self.next_color_index();
+        let (look, offset) = self
+            .pending_styles
+            .remove(name)
+            .unwrap_or_else(|| (self.trace_defaults.look(new_index), 0.0));
+        let pending_display = self.pending_display.remove(name).unwrap_or_default();
+        TraceData {
+            look,
+            offset,
+            live: VecDeque::with_capacity(self.initial_capacity()),
+            creation_index: new_index,
+            display_name: pending_display.display_name,
+            display_smooth_window: pending_display.display_smooth_window,
+            invert: pending_display.invert,
+            auto_hide: pending_display.auto_hide,
+            axis: pending_display.axis,
+            ..Default::default()
+        }
+    }
+
     pub fn get_points(&self, name: &TraceRef, snapshot: bool) -> Option<VecDeque<[f64; 2]>> {
         if let Some(trace) = self.traces.get(name) {
             if snapshot {
@@ -552,6 +943,31 @@ impl TracesCollection {
         Some(out)
     }
 
+    /// Like [`get_drawn_points_decimated`](Self::get_drawn_points_decimated),
+    /// reducing the visible range with `method`.
+    pub fn get_drawn_points_decimated_with(
+        &self,
+        name: &TraceRef,
+        snapshot: bool,
+        bounds: (f64, f64),
+        max_pts: usize,
+        method: DecimationMethod,
+    ) -> Option<Vec<[f64; 2]>> {
+        if method == DecimationMethod::Stride {
+            return self.get_drawn_points_decimated(name, snapshot, bounds, max_pts);
+        }
+        let trace = self.traces.get(name)?;
+        let source: &VecDeque<[f64; 2]> = if snapshot {
+            trace.snap.as_ref().unwrap_or(&trace.live)
+        } else {
+            &trace.live
+        };
+        let start = source.partition_point(|p| p[0] < bounds.0);
+        let end = source.partition_point(|p| p[0] <= bounds.1);
+        let visible: Vec<[f64; 2]> = source.range(start..end.max(start)).copied().collect();
+        Some(TraceData::decimate(&visible, bounds, max_pts, method))
+    }
+
     pub fn get_all_points(&self, snapshot: bool) -> HashMap<TraceRef, VecDeque<[f64; 2]>> {
         let mut result = HashMap::new();
         for (name, _) in self.traces.iter() {
@@ -594,12 +1010,16 @@ impl TracesCollection {
     ///
     /// This is called when the colour scheme changes so that existing traces
     /// (created before the scheme was applied) are recoloured appropriately.
+    /// User-picked colours ([`TraceLook::color_is_custom`]) are kept.
     pub fn recolor_using_palette(&mut self) {
         let palette = crate::color_scheme::global_palette();
         if palette.is_empty() {
             return;
         }
         for (_name, tr) in self.traces.iter_mut() {
+            if tr.look.color_is_custom {
+                continue;
+            }
             let idx = tr.creation_index;
             tr.look.color = palette[idx % palette.len()];
         }
@@ -635,7 +1055,9 @@ impl TracesCollection {
     /// Recolour traces to match their position in `order`.
     ///
     /// The Nth trace in `order` gets `palette[N % palette.len()]`.  Traces
-    /// not present in `order` are left unchanged.
+    /// not present in `order` are left unchanged, and so are user-picked
+    /// colours; those still occupy their slot so the other colours don't
+    /// shift when a colour is pinned or unpinned.
     pub fn recolor_by_order(&mut self, order: &[TraceRef]) {
         let palette = crate::color_scheme::global_palette();
         if palette.is_empty() {
@@ -643,12 +1065,36 @@ impl TracesCollection {
         }
         for (i, name) in order.iter().enumerate() {
             if let Some(tr) = self.traces.get_mut(name) {
-                tr.look.color = palette[i % palette.len()];
                 tr.creation_index = i;
+                if !tr.look.color_is_custom {
+                    tr.look.color = palette[i % palette.len()];
+                }
             }
         }
     }
 
+    /// Recolour every trace from the palette, in a deterministic order:
+    /// the traces of each scope in `scope_orders` first, in scope order,
+    /// then any remaining traces sorted by name.  See
+    /// [`recolor_by_order`](Self::recolor_by_order).
+    pub fn recolor_all(&mut self, scope_orders: &[&[TraceRef]]) {
+        let mut order: Vec<TraceRef> = Vec::new();
+        for name in scope_orders.iter().flat_map(|o| o.iter()) {
+            if !order.contains(name) {
+                order.push(name.clone());
+            }
+        }
+        let mut rest: Vec<TraceRef> = self
+            .traces
+            .keys()
+            .filter(|n| !order.contains(n))
+            .cloned()
+            .collect();
+        rest.sort();
+        order.extend(rest);
+        self.recolor_by_order(&order);
+    }
+
     pub fn len(&self) -> usize {
         self.traces.len()
     }
@@ -658,6 +1104,74 @@ impl TracesCollection {
     }
 }
 
+/// How a scope reduces the visible samples of a trace to its draw budget.
+///
+/// All methods make one pass over the visible range.  `Stride` is the
+/// cheapest (it only touches every Nth sample); `MinMax` and `Lttb` look at
+/// every visible sample.  `MinMax` keeps each bucket's extremes, so short
+/// spikes always survive but the shape between them gets blocky.  `Lttb`
+/// (Largest-Triangle-Three-Buckets) picks the sample spanning the largest
+/// triangle with its neighbours' buckets, which follows the visual shape more
+/// closely at a few floating-point operations more per sample.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DecimationMethod {
+    /// Every Nth sample.
+    #[default]
+    Stride,
+    /// Minimum and maximum of each bucket.
+    MinMax,
+    /// Largest-Triangle-Three-Buckets.
+    Lttb,
+}
+
+/// Display-only per-trace settings that live outside [`TraceLook`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TraceDisplayOptions {
+    pub display_name: Option<String>,
+    pub display_smooth_window: usize,
+    pub invert: bool,
+    pub auto_hide: Option<ActivityRule>,
+    pub axis: YAxisSelect,
+}
+
+/// Rule for drawing a trace only while it is active: its peak-to-peak value
+/// over the trailing [`window_secs`](Self::window_secs) (ending at its newest
+/// sample) must exceed [`min_peak_to_peak`](Self::min_peak_to_peak).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ActivityRule {
+    pub window_secs: f64,
+    /// Activity threshold in raw trace units.  `0.0` hides only traces that
+    /// are exactly constant.
+    pub min_peak_to_peak: f64,
+}
+
+impl Default for ActivityRule {
+    fn default() -> Self {
+        Self {
+            window_secs: 5.0,
+            min_peak_to_peak: 0.0,
+        }
+    }
+}
+
+impl ActivityRule {
+    /// Whether `pts` count as active.  An empty buffer is idle.
+    pub fn is_active(&self, pts: &VecDeque<[f64; 2]>) -> bool {
+        let Some(last) = pts.back() else {
+            return false;
+        };
+        let start = pts.partition_point(|p| p[0] < last[0] - self.window_secs);
+        let (lo, hi) = pts
+            .range(start..)
+            .filter(|p| p[1].is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+                (lo.min(p[1]), hi.max(p[1]))
+            });
+        hi - lo > self.min_peak_to_peak
+    }
+}
+
 /// Per-trace data: live buffer, optional snapshot, and styling.
 #[derive(Default)]
 pub struct TraceData {
@@ -670,6 +1184,29 @@ pub struct TraceData {
     /// colour allocation so that recolouring after a scheme change keeps the
     /// same order.
     pub creation_index: usize,
+    /// Optional human-readable label shown in legends, menus and readouts.
+    /// The trace id stays the stable key; `None` falls back to the id.
+    pub display_name: Option<String>,
+    /// Display-only moving-average window in samples (`0`/`1` = off).  Only
+    /// the drawn line is smoothed; the buffer, math inputs and exports keep
+    /// the raw data.
+    pub display_smooth_window: usize,
+    /// Negate the values for display, readouts and exports (e.g. a sensor
+    /// wired backwards).  Applied before `offset`; the buffer stays raw.
+    pub invert: bool,
+    /// When set, time scopes draw the trace only while the rule finds it
+    /// active.  `look.visible` is left alone.
+    pub auto_hide: Option<ActivityRule>,
+    /// Y axis the trace is drawn against on time scopes that have a
+    /// secondary axis ([`ScopeData::y_axis2`](crate::data::scope::ScopeData::y_axis2)).
+    pub axis: YAxisSelect,
+    /// Whether the buffer is computed by a math trace rather than fed from a
+    /// source.  Set by the math panel; lets exports leave derived data out.
+    pub is_math: bool,
+    /// Per-sample Y uncertainty keyed by the sample's x (`f64::to_bits`).
+    /// Kept beside the buffer so the sample layout is unchanged; entries older
+    /// than both the live buffer and the snapshot are pruned on insert.
+    pub y_err: HashMap<u64, f64>,
     /// Cached spectrum for the trace when the `fft` feature is enabled.
     ///
     /// The various constructors in this module previously filled this field
@@ -682,6 +1219,28 @@ pub struct TraceData {
 }
 
 impl TraceData {
+    /// Label for this trace: the display name if set and non-empty, else `id`.
+    pub fn label<'a>(&'a self, id: &'a TraceRef) -> &'a str {
+        match self.display_name.as_deref() {
+            Some(dn) if !dn.trim().is_empty() => dn,
+            _ => id.as_str(),
+        }
+    }
+
+    /// Sample value with [`invert`](Self::invert) applied (no offset), as exported.
+    pub fn signed(&self, y: f64) -> f64 {
+        if self.invert {
+            -y
+        } else {
+            y
+        }
+    }
+
+    /// Sample value as displayed: [`signed`](Self::signed) plus `offset`.
+    pub fn display_y(&self, y: f64) -> f64 {
+        self.signed(y) + self.offset
+    }
+
     pub fn prune_by_points(&mut self, max_points: usize) {
         while self.live.len() > max_points {
             self.live.pop_front();
@@ -710,6 +1269,21 @@ impl TraceData {
     pub fn clear_all(&mut self) {
         self.live.clear();
         self.snap = None;
+        self.y_err.clear();
+    }
+
+    /// Y uncertainty of the sample at `x`, if one was sent with it.
+    pub fn y_err_at(&self, x: f64) -> Option<f64> {
+        self.y_err.get(&x.to_bits()).copied()
+    }
+
+    /// Drop uncertainties for samples no longer in the live buffer or snapshot.
+    fn prune_y_err(&mut self) {
+        let oldest = |buf: &VecDeque<[f64; 2]>| buf.front().map_or(f64::INFINITY, |p| p[0]);
+        let live_min = oldest(&self.live);
+        let snap_min = self.snap.as_ref().map_or(f64::INFINITY, oldest);
+        let min_x = live_min.min(snap_min);
+        self.y_err.retain(|&k, _| f64::from_bits(k) >= min_x);
     }
 
     pub fn take_snapshot(&mut self) {
@@ -735,6 +1309,134 @@ impl TraceData {
             .collect()
     }
 
+    /// Mean and standard deviation of the y values within the last `secs`
+    /// seconds (relative to the newest sample), or `None` when empty.
+    pub fn recent_mean_std(pts: &VecDeque<[f64; 2]>, secs: f64) -> Option<(f64, f64)> {
+        let last_x = pts.back()?[0];
+        let start = pts.partition_point(|p| p[0] < last_x - secs);
+        let (mut n, mut sum, mut sum_sq) = (0.0, 0.0, 0.0);
+        for p in pts.range(start..).filter(|p| p[1].is_finite()) {
+            n += 1.0;
+            sum += p[1];
+            sum_sq += p[1] * p[1];
+        }
+        if n == 0.0 {
+            return None;
+        }
+        let mean = sum / n;
+        Some((mean, (sum_sq / n - mean * mean).max(0.0).sqrt()))
+    }
+
+    /// Trailing moving average over `window` samples (display smoothing).
+    /// The first `window - 1` outputs average over the samples seen so far.
+    pub fn moving_average(pts: &[[f64; 2]], window: usize) -> Vec<[f64; 2]> {
+        if window <= 1 {
+            return pts.to_vec();
+        }
+        let mut out = Vec::with_capacity(pts.len());
+        let mut sum = 0.0;
+        for (i, p) in pts.iter().enumerate() {
+            sum += p[1];
+            if i >= window {
+                sum -= pts[i - window][1];
+            }
+            let n = (i + 1).min(window) as f64;
+            out.push([p[0], sum / n]);
+        }
+        out
+    }
+
+    /// Reduce the points of `pts` within `bounds` to about `max_pts` using `method`.
+    pub fn decimate(
+        pts: &[[f64; 2]],
+        bounds: (f64, f64),
+        max_pts: usize,
+        method: DecimationMethod,
+    ) -> Vec<[f64; 2]> {
+        if method == DecimationMethod::Stride {
+            return Self::cap_and_decimate(pts, bounds, max_pts);
+        }
+        let visible: Vec<[f64; 2]> = pts
+            .iter()
+            .filter(|p| p[0] >= bounds.0 && p[0] <= bounds.1)
+            .copied()
+            .collect();
+        match method {
+            DecimationMethod::MinMax => Self::decimate_min_max(&visible, max_pts),
+            _ => Self::decimate_lttb(&visible, max_pts),
+        }
+    }
+
+    /// Keep the minimum and maximum (in x order) of `max_pts / 2` equal-count
+    /// buckets.  Returns `pts` unchanged when it already fits.
+    pub fn decimate_min_max(pts: &[[f64; 2]], max_pts: usize) -> Vec<[f64; 2]> {
+        let buckets = max_pts / 2;
+        if pts.len() <= max_pts || buckets == 0 {
+            return pts.to_vec();
+        }
+        let mut out = Vec::with_capacity(buckets * 2);
+        for b in 0..buckets {
+            let chunk = &pts[b * pts.len() / buckets..(b + 1) * pts.len() / buckets];
+            let (mut lo, mut hi) = (0, 0);
+            for (i, p) in chunk.iter().enumerate() {
+                if p[1] < chunk[lo][1] {
+                    lo = i;
+                }
+                if p[1] > chunk[hi][1] {
+                    hi = i;
+                }
+            }
+            out.push(chunk[lo.min(hi)]);
+            if lo != hi {
+                out.push(chunk[lo.max(hi)]);
+            }
+        }
+        out
+    }
+
+    /// Largest-Triangle-Three-Buckets down-sampling to `max_pts` points,
+    /// always keeping the first and last sample.  Returns `pts` unchanged
+    /// when it already fits.
+    pub fn decimate_lttb(pts: &[[f64; 2]], max_pts: usize) -> Vec<[f64; 2]> {
+        let n = pts.len();
+        if n <= max_pts || max_pts < 3 {
+            return pts.to_vec();
+        }
+        let buckets = max_pts - 2;
+        let bucket = |b: usize| &pts[1 + b * (n - 2) / buckets..1 + (b + 1) * (n - 2) / buckets];
+        let mut out = Vec::with_capacity(max_pts);
+        out.push(pts[0]);
+        let mut prev = pts[0];
+        for b in 0..buckets {
+            // Average of the next bucket (or the last point for the final one).
+            let next = if b + 1 < buckets {
+                let nb = bucket(b + 1);
+                let len = nb.len().max(1) as f64;
+                let (sx, sy) = nb.iter().fold((0.0, 0.0), |(x, y), p| (x + p[0], y + p[1]));
+                [sx / len, sy / len]
+            } else {
+                pts[n - 1]
+            };
+            let mut best = None;
+            let mut best_area = -1.0;
+            for p in bucket(b) {
+                let area = ((prev[0] - next[0]) * (p[1] - prev[1])
+                    - (prev[0] - p[0]) * (next[1] - prev[1]))
+                    .abs();
+                if area > best_area {
+                    best_area = area;
+                    best = Some(*p);
+                }
+            }
+            if let Some(p) = best {
+                out.push(p);
+                prev = p;
+            }
+        }
+        out.push(pts[n - 1]);
+        out
+    }
+
     /// Filter by x-bounds and decimate to at most `max_pts` points.
     /// Returns a Vec suitable for passing directly to egui_plot.
     /// When the input has fewer points than `max_pts`, all points within
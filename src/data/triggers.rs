@@ -40,6 +40,7 @@ pub struct Trigger {
     start_trigger: bool,
     last_triggered: Option<f64>,
     trigger_pending: Option<f64>,
+    force_requested: bool,
 }
 
 impl Default for Trigger {
@@ -60,10 +61,35 @@ impl Default for Trigger {
             start_trigger: false,
             last_triggered: None,
             trigger_pending: None,
+            force_requested: false,
         }
     }
 }
 
+/// Request from a [`TriggerController`](crate::controllers::TriggerController)
+/// for one trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerCommand {
+    /// Enable the trigger and start looking for crossings.
+    Arm,
+    /// Fire at the newest sample of the target trace, without a crossing.
+    Force,
+    /// Stop looking for crossings.
+    Disarm,
+}
+
+/// A completed capture, published to
+/// [`TriggerController`](crate::controllers::TriggerController) subscribers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerCapture {
+    /// Name of the trigger that captured.
+    pub trigger: String,
+    /// Trace the trigger monitors.
+    pub target: TraceRef,
+    /// Timestamp of the trigger point (crossing or forced sample).
+    pub time: f64,
+}
+
 /// Direction of signal change that triggers capture.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TriggerSlope {
@@ -102,6 +128,29 @@ impl Trigger {
     pub fn stop(&mut self) {
         self.start_trigger = false;
         self.trigger_pending = None;
+        self.force_requested = false;
+    }
+
+    /// Fire on the next [`check_trigger`](Self::check_trigger) at the newest
+    /// sample of the target, as if it crossed the level there.  Enables the
+    /// trigger; the capture then completes like a normal one.
+    pub fn force(&mut self) {
+        self.enabled = true;
+        self.last_triggered = None;
+        self.trigger_pending = None;
+        self.force_requested = true;
+    }
+
+    /// Apply a controller request.
+    pub fn apply_command(&mut self, cmd: TriggerCommand) {
+        match cmd {
+            TriggerCommand::Arm => {
+                self.enabled = true;
+                self.start();
+            }
+            TriggerCommand::Force => self.force(),
+            TriggerCommand::Disarm => self.stop(),
+        }
     }
 
     /// Short, user-facing description used in UI and legend labels.
@@ -198,8 +247,19 @@ impl Trigger {
 
         if !self.enabled {
             self.start_trigger = false;
+            self.force_requested = false;
             return false;
         }
+        if self.force_requested {
+            // Kept until the target has a sample to trigger on.
+            if let Some(last) = livedata.back() {
+                self.force_requested = false;
+                self.trigger_pending = Some(last[0]);
+                if self.single_shot {
+                    self.start_trigger = false;
+                }
+            }
+        }
         if !self.start_trigger && self.trigger_pending.is_none() {
             return false;
         }
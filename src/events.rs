@@ -119,6 +119,11 @@ impl EventKind {
     /// Y-axis unit was changed.
     pub const Y_UNIT_CHANGED: Self = Self(1 << 30);
 
+    // ── Alarms ──────────────────────────────────────────────────────────
+    /// A threshold with [`notify`](crate::data::thresholds::ThresholdDef::notify)
+    /// set fired.  Emitted as its own event, separate from `THRESHOLD_EXCEEDED`.
+    pub const THRESHOLD_ALARM: Self = Self(1 << 31);
+
     /// Wildcard: matches *every* event kind.
     pub const ALL: Self = Self(u64::MAX);
 
@@ -226,6 +231,7 @@ impl std::fmt::Display for EventKind {
             (EventKind::TRACE_OFFSET_CHANGED, "TRACE_OFFSET_CHANGED"),
             (EventKind::Y_LOG_CHANGED, "Y_LOG_CHANGED"),
             (EventKind::Y_UNIT_CHANGED, "Y_UNIT_CHANGED"),
+            (EventKind::THRESHOLD_ALARM, "THRESHOLD_ALARM"),
         ];
 
         let mut names = Vec::new();
@@ -584,6 +590,14 @@ impl EventController {
         rx
     }
 
+    /// Subscribe to threshold alarms only (see [`EventKind::THRESHOLD_ALARM`]).
+    ///
+    /// Alarms arrive on their own channel, so a notifier is never stuck
+    /// behind bulk data or view events.
+    pub fn subscribe_alarms(&self) -> Receiver<PlotEvent> {
+        self.subscribe(EventFilter::only(EventKind::THRESHOLD_ALARM))
+    }
+
     /// Subscribe to *all* events (no filtering).
     pub fn subscribe_all(&self) -> Receiver<PlotEvent> {
         self.subscribe(EventFilter::all())
@@ -1,5 +1,5 @@
 pub mod color_scheme;
-pub use color_scheme::{ColorScheme, CustomColorScheme};
+pub use color_scheme::{ColorScheme, CustomColorScheme, TracePalette};
 /// LivePlot crate root: re-exports and module wiring.
 mod app;
 pub mod data;
@@ -19,19 +19,25 @@ pub mod undo;
 pub use app::{run_liveplot, LivePlotApp, LivePlotPanel};
 pub use controllers::{
     FFTController, FFTDataRequest, FFTPanelInfo, FFTRawData, LiveplotController, LiveplotState,
-    RawExportFormat, ScopeControlState, ScopesController, ScopesState, ThresholdController,
-    TraceControlState, TraceInfo, TracesController, TracesInfo, TracesPanelState,
-    UiActionController, WindowController, WindowInfo,
+    MathController, MeasurementController, MeasurementSnapshot, PanelInfo, RawExportFormat,
+    ScopeControlState, ScopesController, ScopesState, StateIoEvent, StateIoOp, ThresholdController,
+    TraceControlState, TraceInfo, TraceMeasurements, TraceSamples, TracesController, TracesInfo,
+    TracesPanelState, TriggerController, TriggerInfo, TriggersState, UiActionController,
+    WindowController, WindowInfo,
 };
-pub use data::scope::ScopeType;
-pub use data::traces::TraceRef;
+pub use data::measurement::MeasurementKind;
+pub use data::scope::{AutoAttach, InitialXRange, LegendClick, ResumeBehavior, ScopeType};
+pub use data::traces::{IngestStats, OutOfOrderPolicy, TraceRef};
 pub use events::{
     ClickMeta, DataUpdateMeta, EventController, EventFilter, EventKind, ExportMeta, KeyModifiers,
     KeyPressMeta, MathTraceMeta, MeasurementMeta, PauseMeta, PlotEvent, PlotPos, ResizeMeta,
     ScopeManageMeta, ScreenPos, ThresholdMeta, TraceMeta, TriggerMeta, ViewChangeMeta, YAxisMeta,
 };
 pub use panels::{Panel, PanelState};
-pub use sink::{channel_plot, PlotCommand, PlotPoint, PlotSink, Trace, TraceId};
+pub use sink::{
+    channel_plot, channel_plot_bounded, merge_sinks, merge_sinks_prefixed, PlotCommand, PlotPoint,
+    PlotSink, Trace, TraceId,
+};
 // Re-export individual panel types from panels module
 pub use data::thresholds::{ThresholdDef, ThresholdKind};
 pub use panels::color_scheme_ui::{ColorSchemePanel, NamedCustomScheme};
@@ -41,10 +47,11 @@ pub use panels::{
 };
 
 // Re-exports from new modules
-pub use data::triggers::{Trigger, TriggerSlope};
+pub use data::triggers::{Trigger, TriggerCapture, TriggerCommand, TriggerSlope};
 
 // Convenience re-export for examples & embedded use
 pub use config::{
     AutoFitConfig, Controllers, FeatureFlags, LivePlotConfig, ResponsiveLayout, ScopeButton,
+    UnfocusedBehavior,
 };
 pub use undo::{LivePlotUndoEntry, LivePlotUndoStack};
@@ -0,0 +1,259 @@
+//! Data inspector panel: the most recent raw samples of one trace as a table.
+//!
+//! Complements the plot with exact numbers for debugging a stream: stored
+//! timestamps and values (before invert/offset), plus flags for duplicate or
+//! out-of-order timestamps, non-finite values and attached uncertainties.
+
+use egui::{Color32, Ui};
+use egui_phosphor_icons::icons::{COPY, TABLE};
+use egui_table::{HeaderRow as EgHeaderRow, Table, TableDelegate};
+
+use super::panel_trait::{Panel, PanelState};
+use crate::data::data::LivePlotData;
+use crate::data::traces::{TraceData, TraceRef};
+
+/// One sample as listed by the [`DataInspectorPanel`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleRow {
+    /// Position in the trace buffer (0 = oldest retained sample).
+    pub index: usize,
+    pub t: f64,
+    pub y: f64,
+    /// Space-separated notes, e.g. `dup-t`, `out-of-order`, `non-finite`, `±0.1`.
+    pub flags: String,
+}
+
+/// The last `n` samples of `trace`, oldest first.
+///
+/// Reads the frozen snapshot when `snapshot` is set and the trace has one,
+/// otherwise the live buffer.
+pub fn recent_samples(trace: &TraceData, snapshot: bool, n: usize) -> Vec<SampleRow> {
+    let pts = match (&trace.snap, snapshot) {
+        (Some(snap), true) => snap,
+        _ => &trace.live,
+    };
+    let start = pts.len().saturating_sub(n);
+    (start..pts.len())
+        .map(|i| {
+            let [t, y] = pts[i];
+            let mut flags: Vec<String> = Vec::new();
+            if let Some(prev) = i.checked_sub(1).map(|j| pts[j][0]) {
+                if t == prev {
+                    flags.push("dup-t".into());
+                } else if t < prev {
+                    flags.push("out-of-order".into());
+                }
+            }
+            if !t.is_finite() || !y.is_finite() {
+                flags.push("non-finite".into());
+            }
+            if let Some(e) = trace.y_err_at(t) {
+                flags.push(format!("±{e}"));
+            }
+            SampleRow {
+                index: i,
+                t,
+                y,
+                flags: flags.join(" "),
+            }
+        })
+        .collect()
+}
+
+/// Tab-separated rows with a header line, for pasting into a spreadsheet.
+pub fn rows_to_tsv(rows: &[SampleRow]) -> String {
+    let mut out = String::from("index\tt\tvalue\tflags\n");
+    for r in rows {
+        out.push_str(&format!("{}\t{}\t{}\t{}\n", r.index, r.t, r.y, r.flags));
+    }
+    out
+}
+
+pub struct DataInspectorPanel {
+    state: PanelState,
+    /// Trace whose samples are listed; the first trace by name when unset.
+    pub trace: Option<TraceRef>,
+    /// Number of most recent samples to list.
+    pub max_rows: usize,
+    /// List the newest sample at the top.
+    pub newest_first: bool,
+}
+
+impl Default for DataInspectorPanel {
+    fn default() -> Self {
+        Self {
+            state: PanelState::new("Data Inspector", TABLE.as_str()),
+            trace: None,
+            max_rows: 1000,
+            newest_first: true,
+        }
+    }
+}
+
+impl Panel for DataInspectorPanel {
+    fn state(&self) -> &PanelState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut PanelState {
+        &mut self.state
+    }
+
+    fn render_menu(
+        &mut self,
+        ui: &mut Ui,
+        _data: &mut LivePlotData<'_>,
+        collapsed: bool,
+        tooltip: &str,
+    ) {
+        let label = if collapsed {
+            self.icon_only()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| self.title().to_string())
+        } else {
+            self.title_and_icon()
+        };
+        let menu_cfg = egui::containers::menu::MenuConfig::new()
+            .close_behavior(egui::PopupCloseBehavior::CloseOnClickOutside);
+        let mr = egui::containers::menu::MenuButton::new(label)
+            .config(menu_cfg)
+            .ui(ui, |ui| {
+                if ui.button("Show Data Inspector").clicked() {
+                    let st = self.state_mut();
+                    st.visible = true;
+                    st.request_focus = true;
+                    ui.close();
+                }
+            });
+        if !tooltip.is_empty() {
+            mr.0.on_hover_text(tooltip);
+        }
+    }
+
+    fn update_data(&mut self, data: &mut LivePlotData<'_>) {
+        let valid = self
+            .trace
+            .as_ref()
+            .is_some_and(|t| data.traces.contains_key(t));
+        if !valid {
+            let mut names = data.traces.all_trace_names();
+            names.sort();
+            self.trace = names.into_iter().next();
+        }
+    }
+
+    fn render_panel(&mut self, ui: &mut Ui, data: &mut LivePlotData<'_>) {
+        ui.label("Most recent raw samples of one trace, as stored (before invert and offset).");
+
+        let mut names = data.traces.all_trace_names();
+        names.sort();
+        let Some(current) = self.trace.clone() else {
+            ui.weak("No traces yet.");
+            return;
+        };
+
+        let (rows, frozen) = {
+            let Some(tr) = data.traces.get_trace(&current) else {
+                return;
+            };
+            let frozen = data.are_all_paused() && tr.snap.is_some();
+            let mut rows = recent_samples(tr, frozen, self.max_rows);
+            if self.newest_first {
+                rows.reverse();
+            }
+            (rows, frozen)
+        };
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("data_inspector_trace")
+                .selected_text(data.traces.display_name(&current))
+                .show_ui(ui, |ui| {
+                    for n in &names {
+                        let label = data.traces.display_name(n);
+                        ui.selectable_value(&mut self.trace, Some(n.clone()), label);
+                    }
+                });
+            ui.label("Rows");
+            ui.add(egui::DragValue::new(&mut self.max_rows).range(1..=100_000));
+            ui.checkbox(&mut self.newest_first, "Newest first");
+        });
+
+        ui.horizontal(|ui| {
+            let source = if frozen { "snapshot" } else { "live" };
+            ui.weak(format!("{} rows ({source})", rows.len()));
+            if ui
+                .small_button(format!("{} Copy", COPY.as_str()))
+                .on_hover_text("Copy the listed rows as tab-separated text")
+                .clicked()
+            {
+                ui.ctx().copy_text(rows_to_tsv(&rows));
+            }
+        });
+        ui.separator();
+
+        struct SamplesDelegate<'a> {
+            rows: &'a [SampleRow],
+            copied: Option<String>,
+        }
+        impl TableDelegate for SamplesDelegate<'_> {
+            fn header_cell_ui(&mut self, ui: &mut egui::Ui, cell: &egui_table::HeaderCellInfo) {
+                let text = match cell.col_range.start {
+                    0 => "#",
+                    1 => "Time",
+                    2 => "Value",
+                    3 => "Flags",
+                    _ => "",
+                };
+                ui.add_space(4.0);
+                ui.strong(text);
+            }
+            fn cell_ui(&mut self, ui: &mut egui::Ui, cell: &egui_table::CellInfo) {
+                let Some(r) = self.rows.get(cell.row_nr as usize) else {
+                    return;
+                };
+                ui.add_space(4.0);
+                let text = match cell.col_nr {
+                    0 => egui::RichText::new(r.index.to_string()).weak(),
+                    1 => egui::RichText::new(r.t.to_string()).monospace(),
+                    2 => egui::RichText::new(r.y.to_string()).monospace(),
+                    _ if r.flags.is_empty() => egui::RichText::new(""),
+                    _ => egui::RichText::new(&r.flags).color(Color32::LIGHT_RED),
+                };
+                let resp = ui
+                    .add(egui::Label::new(text).sense(egui::Sense::click()))
+                    .on_hover_text("Click to copy this row");
+                if resp.clicked() {
+                    self.copied = Some(format!("{}\t{}\t{}\t{}", r.index, r.t, r.y, r.flags));
+                }
+            }
+        }
+
+        let mut delegate = SamplesDelegate {
+            rows: &rows,
+            copied: None,
+        };
+        let cols = vec![
+            egui_table::Column::new(70.0),
+            egui_table::Column::new(180.0),
+            egui_table::Column::new(180.0),
+            egui_table::Column::new(160.0),
+        ];
+        let avail_w = ui.available_width();
+        let remaining_h = ui.available_height();
+        let (rect, _resp) =
+            ui.allocate_exact_size(egui::vec2(avail_w, remaining_h), egui::Sense::hover());
+        let ui_builder = egui::UiBuilder::new()
+            .max_rect(rect)
+            .layout(egui::Layout::left_to_right(egui::Align::Min));
+        let mut table_ui = ui.new_child(ui_builder);
+        Table::new()
+            .id_salt("data_inspector_table")
+            .num_rows(rows.len() as u64)
+            .columns(cols)
+            .headers(vec![EgHeaderRow::new(24.0)])
+            .show(&mut table_ui, &mut delegate);
+        if let Some(text) = delegate.copied {
+            ui.ctx().copy_text(text);
+        }
+    }
+}
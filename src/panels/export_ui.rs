@@ -2,24 +2,106 @@ use super::panel_trait::{Panel, PanelState};
 use crate::data::data::LivePlotData;
 use crate::data::data::{ScreenshotRequest, ScreenshotTarget};
 use crate::data::export; // main crate's export module
-use crate::data::traces::TraceRef;
+use crate::data::scope::ScopeData;
+use crate::data::traces::{TraceRef, TracesCollection};
 use egui::Ui;
+#[cfg(feature = "npz")]
+use egui_phosphor_icons::icons::FILE_ZIP;
 #[cfg(feature = "parquet")]
 use egui_phosphor_icons::icons::TABLE;
-use egui_phosphor_icons::icons::{EXPORT, FILE_CSV, FOLDER_OPEN, IMAGE};
+use egui_phosphor_icons::icons::{
+    BRACKETS_CURLY, CLIPBOARD_TEXT, EXPORT, FILE_CSV, FOLDER_OPEN, IMAGE,
+};
 use std::collections::HashMap;
 
 pub struct ExportPanel {
     pub state: PanelState,
+    /// Template for the suggested filename (see
+    /// [`export::expand_filename_template`]).
+    pub filename_template: String,
+    /// Include math (derived) traces in data exports.  Default: `true`.
+    pub include_math: bool,
+    /// Timestamp column(s) in CSV exports.  Default: seconds.
+    pub csv_timestamps: export::CsvTimestamp,
+    /// Samples within this many seconds of each other share one exported row.
+    /// Default: [`export::DEFAULT_JOIN_TOLERANCE`].
+    pub join_tolerance: f64,
+    /// Export only the first scope's current X range.
+    pub region_view: bool,
+    /// Export only the X span between the active measurement's P1 and P2;
+    /// preferred over [`region_view`](Self::region_view) when both are set.
+    pub region_selection: bool,
+    /// X span of the active P1–P2 selection, refreshed every frame.
+    pub(crate) selection: Option<(f64, f64)>,
 }
 impl Default for ExportPanel {
     fn default() -> Self {
         Self {
             state: PanelState::new("Export", EXPORT.as_str()),
+            filename_template: export::DEFAULT_FILENAME_TEMPLATE.to_string(),
+            include_math: true,
+            csv_timestamps: export::CsvTimestamp::default(),
+            join_tolerance: export::DEFAULT_JOIN_TOLERANCE,
+            region_view: false,
+            region_selection: false,
+            selection: None,
         }
     }
 }
 
+/// The [`ExportPanel`] options that also apply to exports requested through
+/// the window controller.
+#[derive(Clone, Debug)]
+pub(crate) struct ExportSettings {
+    pub filename_template: String,
+    pub include_math: bool,
+    pub csv_timestamps: export::CsvTimestamp,
+    pub join_tolerance: f64,
+}
+
+impl ExportPanel {
+    /// Copy of the options shared with controller-requested exports.
+    pub(crate) fn settings(&self) -> ExportSettings {
+        ExportSettings {
+            filename_template: self.filename_template.clone(),
+            include_math: self.include_math,
+            csv_timestamps: self.csv_timestamps,
+            join_tolerance: self.join_tolerance,
+        }
+    }
+
+    /// Set the X span of the P1–P2 selection used by
+    /// [`region_selection`](Self::region_selection).  The app refreshes it
+    /// from the measurement panel every frame.
+    pub fn set_selection(&mut self, selection: Option<(f64, f64)>) {
+        self.selection = selection;
+    }
+
+    /// Time range to export given the first scope's X `view`: the P1–P2
+    /// selection if enabled and non-empty, else the view if enabled.  `None`
+    /// exports the full buffer.
+    pub fn export_range(&self, view: Option<(f64, f64)>) -> Option<(f64, f64)> {
+        export_range(
+            self.region_selection,
+            self.selection,
+            self.region_view,
+            view,
+        )
+    }
+}
+
+fn export_range(
+    use_selection: bool,
+    selection: Option<(f64, f64)>,
+    use_view: bool,
+    view: Option<(f64, f64)>,
+) -> Option<(f64, f64)> {
+    let non_empty = |r: &(f64, f64)| r.1 > r.0;
+    selection
+        .filter(|r| use_selection && non_empty(r))
+        .or_else(|| view.filter(|r| use_view && non_empty(r)))
+}
+
 impl Panel for ExportPanel {
     fn state(&self) -> &PanelState {
         &self.state
@@ -46,6 +128,13 @@ impl Panel for ExportPanel {
         } else {
             self.title_and_icon()
         };
+        let template = self.filename_template.as_str();
+        let include_math = &mut self.include_math;
+        let csv_timestamps = &mut self.csv_timestamps;
+        let join_tolerance = &mut self.join_tolerance;
+        let region_view = &mut self.region_view;
+        let region_selection = &mut self.region_selection;
+        let selection = self.selection;
         let menu_cfg = egui::containers::menu::MenuConfig::new()
             .close_behavior(egui::PopupCloseBehavior::CloseOnClickOutside);
         let mr = egui::containers::menu::MenuButton::new(label)
@@ -62,36 +151,71 @@ impl Panel for ExportPanel {
                     });
                     ui.close();
                 }
+                ui.checkbox(include_math, "Include math traces")
+                    .on_hover_text("Uncheck to export only the raw source traces");
+                let include_math = *include_math;
+                egui::ComboBox::from_label("CSV timestamps")
+                    .selected_text(csv_timestamps.label())
+                    .show_ui(ui, |ui| {
+                        for ts in export::CsvTimestamp::ALL {
+                            ui.selectable_value(csv_timestamps, ts, ts.label());
+                        }
+                    })
+                    .response
+                    .on_hover_text(
+                        "Float seconds, integer microseconds (exact, as ingested) or both columns",
+                    );
+                let csv_timestamps = *csv_timestamps;
+                ui.horizontal(|ui| {
+                    ui.label("Join tolerance (s)");
+                    ui.add(
+                        egui::DragValue::new(join_tolerance)
+                            .range(0.0..=1.0)
+                            .speed(1e-4)
+                            .custom_formatter(|v, _| format!("{v:e}"))
+                            .custom_parser(|s| s.trim().parse().ok()),
+                    )
+                    .on_hover_text(
+                        "Samples of different traces closer than this share one row; \
+                         raise it for jittery multi-rate data",
+                    );
+                });
+                let tol = *join_tolerance;
+                ui.checkbox(region_view, "Use current X view")
+                    .on_hover_text("Export only the time range shown in the first scope");
+                ui.checkbox(region_selection, "Use P1–P2 selection")
+                    .on_hover_text(
+                        "Export only the time range between the measurement points; \
+                     preferred over the X view",
+                    );
+                let range = export_range(
+                    *region_selection,
+                    selection,
+                    *region_view,
+                    data.primary_scope().map(|s| s.x_axis.bounds),
+                );
+                if (*region_view || *region_selection) && range.is_none() {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        "Empty selection: exporting the full buffer",
+                    );
+                }
                 if ui
                     .button(format!("{} Snapshot as CSV", FILE_CSV.as_str()))
                     .clicked()
                 {
                     if let Some(path) = rfd::FileDialog::new()
-                        .set_file_name("snapshot.csv")
+                        .set_file_name(default_file_name(template, data, "csv"))
                         .add_filter("CSV", &["csv"])
                         .save_file()
                     {
-                        // Build series map based on paused/snapshot state (convert TraceRef to String)
-                        let mut series: HashMap<TraceRef, Vec<[f64; 2]>> = HashMap::new();
-                        for (name, tr) in data.traces.traces_iter() {
-                            let iter: Box<dyn Iterator<Item = &[f64; 2]> + '_> =
-                                if data.are_all_paused() {
-                                    if let Some(snap) = &tr.snap {
-                                        Box::new(snap.iter())
-                                    } else {
-                                        Box::new(tr.live.iter())
-                                    }
-                                } else {
-                                    Box::new(tr.live.iter())
-                                };
-                            let vec: Vec<[f64; 2]> = iter.cloned().collect();
-                            series.insert(name.clone(), vec);
-                        }
+                        let (names, series) = snapshot_series(data, include_math, range);
                         if let Err(e) = export::write_csv_aligned_path(
                             &path,
-                            &data.traces.all_trace_names(),
+                            &names,
                             &series,
-                            1e-9,
+                            tol,
+                            csv_timestamps,
                         ) {
                             eprintln!("Failed to export snapshot CSV: {e}");
                         } else {
@@ -109,6 +233,50 @@ impl Panel for ExportPanel {
                     }
                     ui.close();
                 }
+                if ui
+                    .button(format!("{} Snapshot as JSON", BRACKETS_CURLY.as_str()))
+                    .on_hover_text("Trace names, units and export time, then the aligned rows")
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .set_file_name(default_file_name(template, data, "json"))
+                        .add_filter("JSON", &["json"])
+                        .save_file()
+                    {
+                        let (names, series) = snapshot_series(data, include_math, range);
+                        let scopes: Vec<&ScopeData> =
+                            data.scope_data.iter().map(|s| &**s).collect();
+                        let units = trace_units(&scopes, data.traces, &names);
+                        if let Err(e) =
+                            export::write_json_aligned_path(&path, &names, &units, &series, tol)
+                        {
+                            eprintln!("Failed to export snapshot JSON: {e}");
+                        } else if let Some(ctrl) = &data.event_ctrl {
+                            let mut evt =
+                                crate::events::PlotEvent::new(crate::events::EventKind::EXPORT);
+                            evt.export = Some(crate::events::ExportMeta {
+                                format: "json".to_string(),
+                                path: Some(path.to_string_lossy().to_string()),
+                            });
+                            ctrl.emit_filtered(evt);
+                        }
+                    }
+                    ui.close();
+                }
+                if ui
+                    .button(format!("{} Copy visible data", CLIPBOARD_TEXT.as_str()))
+                    .on_hover_text(
+                        "Copy the visible traces of the first scope as a tab-separated table",
+                    )
+                    .clicked()
+                {
+                    if let Some(scope) = data.primary_scope() {
+                        let (names, series) = scope.visible_series(data.traces);
+                        let rows = export::align_series(&names, &series, tol);
+                        ui.ctx().copy_text(export::aligned_rows_tsv(&names, &rows));
+                    }
+                    ui.close();
+                }
                 // Move Save/Load state into Export menu
                 ui.separator();
                 if ui
@@ -143,31 +311,13 @@ impl Panel for ExportPanel {
                         .clicked()
                     {
                         if let Some(path) = rfd::FileDialog::new()
-                            .set_file_name("snapshot.parquet")
+                            .set_file_name(default_file_name(template, data, "parquet"))
                             .add_filter("Parquet", &["parquet"])
                             .save_file()
                         {
-                            // Build series map like for CSV (convert TraceRef to String)
-                            let mut series: HashMap<TraceRef, Vec<[f64; 2]>> = HashMap::new();
-                            let names = data.traces.all_trace_names();
-                            for name in names.iter() {
-                                if let Some(tr) = data.traces.get_trace(name) {
-                                    let iter: Box<dyn Iterator<Item = &[f64; 2]> + '_> =
-                                        if data.are_all_paused() {
-                                            if let Some(snap) = &tr.snap {
-                                                Box::new(snap.iter())
-                                            } else {
-                                                Box::new(tr.live.iter())
-                                            }
-                                        } else {
-                                            Box::new(tr.live.iter())
-                                        };
-                                    let vec: Vec<[f64; 2]> = iter.cloned().collect();
-                                    series.insert(name.clone(), vec);
-                                }
-                            }
+                            let (names, series) = snapshot_series(data, include_math, range);
                             if let Err(e) =
-                                export::write_parquet_aligned_path(&path, &names, &series, 1e-9)
+                                export::write_parquet_aligned_path(&path, &names, &series, tol)
                             {
                                 eprintln!("Failed to export snapshot Parquet: {e}");
                             }
@@ -175,6 +325,37 @@ impl Panel for ExportPanel {
                         ui.close();
                     }
                 }
+                #[cfg(feature = "npz")]
+                {
+                    if ui
+                        .button(format!("{} Snapshot as NumPy (.npz)", FILE_ZIP.as_str()))
+                        .on_hover_text(
+                            "One (2, N) float64 array per trace (row 0: time, row 1: value), \
+                             keyed by trace name",
+                        )
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .set_file_name(default_file_name(template, data, "npz"))
+                            .add_filter("NumPy", &["npz"])
+                            .save_file()
+                        {
+                            let (names, series) = snapshot_series(data, include_math, range);
+                            if let Err(e) = export::write_npz_path(&path, &names, &series) {
+                                eprintln!("Failed to export snapshot NPZ: {e}");
+                            } else if let Some(ctrl) = &data.event_ctrl {
+                                let mut evt =
+                                    crate::events::PlotEvent::new(crate::events::EventKind::EXPORT);
+                                evt.export = Some(crate::events::ExportMeta {
+                                    format: "npz".to_string(),
+                                    path: Some(path.to_string_lossy().to_string()),
+                                });
+                                ctrl.emit_filtered(evt);
+                            }
+                        }
+                        ui.close();
+                    }
+                }
             });
         if !tooltip.is_empty() {
             mr.0.on_hover_text(tooltip);
@@ -182,4 +363,84 @@ impl Panel for ExportPanel {
     }
 }
 
+/// Suggested export filename: `template` expanded for the primary scope and
+/// the current trace count, plus `ext`.
+fn default_file_name(template: &str, data: &LivePlotData<'_>, ext: &str) -> String {
+    let scope = data.primary_scope().map_or("", |s| s.name.as_str());
+    let stem = export::expand_filename_template(
+        template,
+        scope,
+        data.traces.all_trace_names().len(),
+        chrono::Local::now(),
+    );
+    format!("{stem}.{ext}")
+}
+
+/// Unit of each exported column, taken from the axis the trace is drawn
+/// against in the first of `scopes` showing it.  `<trace>_err` columns share
+/// their trace's unit.
+pub(crate) fn trace_units(
+    scopes: &[&ScopeData],
+    traces: &TracesCollection,
+    names: &[TraceRef],
+) -> Vec<Option<String>> {
+    names
+        .iter()
+        .map(|name| {
+            let base = if traces.get_trace(name).is_some() {
+                name.clone()
+            } else {
+                TraceRef::new(name.0.strip_suffix("_err")?)
+            };
+            let tr = traces.get_trace(&base)?;
+            let scope = scopes.iter().find(|s| s.trace_order.contains(&base))?;
+            scope.trace_unit(tr)
+        })
+        .collect()
+}
+
+/// Column order and signed samples of every trace, from the snapshot buffers
+/// while all scopes are paused and the live buffers otherwise.  Traces with
+/// per-sample uncertainties get an extra `<name>_err` column right after them.
+/// Math traces are skipped unless `include_math` is set; with a `range`, only
+/// samples inside it are kept.
+fn snapshot_series(
+    data: &LivePlotData<'_>,
+    include_math: bool,
+    range: Option<(f64, f64)>,
+) -> (Vec<TraceRef>, HashMap<TraceRef, Vec<[f64; 2]>>) {
+    let paused = data.are_all_paused();
+    let mut names = Vec::new();
+    let mut series = HashMap::new();
+    for name in data.traces.all_trace_names() {
+        let Some(tr) = data.traces.get_trace(&name) else {
+            continue;
+        };
+        if tr.is_math && !include_math {
+            continue;
+        }
+        let buf = if paused {
+            tr.snap.as_ref().unwrap_or(&tr.live)
+        } else {
+            &tr.live
+        };
+        let vec = buf.iter().map(|p| [p[0], tr.signed(p[1])]).collect();
+        series.insert(name.clone(), vec);
+        names.push(name.clone());
+        if !tr.y_err.is_empty() {
+            let errs: Vec<[f64; 2]> = buf
+                .iter()
+                .filter_map(|p| tr.y_err_at(p[0]).map(|e| [p[0], e]))
+                .collect();
+            let err_name = TraceRef(format!("{}_err", name.0));
+            series.insert(err_name.clone(), errs);
+            names.push(err_name);
+        }
+    }
+    if let Some(range) = range {
+        export::clip_series(&mut series, range);
+    }
+    (names, series)
+}
+
 // tests moved to `tests/export_ui.rs`
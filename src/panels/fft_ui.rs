@@ -1,20 +1,46 @@
 use super::panel_trait::{Panel, PanelState};
 use crate::data::data::{LivePlotData, ScreenshotRequest, ScreenshotTarget};
-use crate::data::fft::{FFTWindow, FftData};
+use crate::data::export::{self, SpectrumSeries};
+use crate::data::fft::{
+    band_power, find_peaks, format_hz, BandPower, FFTWindow, FftData, FftScale, FftView,
+    Spectrogram, DEFAULT_SPECTROGRAM_FRAMES, IRREGULAR_SAMPLING_CV, MIN_ADAPTIVE_FFT_SIZE,
+    SPECTROGRAM_MAX_COLUMNS,
+};
+use crate::data::measurement::Measurement;
 use crate::data::scope::{AxisType, LegendPosition, ScopeType, ValueFormat};
 use crate::data::traces::TraceRef;
 use crate::data::traces::{TraceData, TracesCollection};
 use crate::panels::scope_ui::{ScopePanel, ZoomMode};
 use egui::Ui;
-use egui_phosphor_icons::icons::{CHART_BAR, WARNING};
-use egui_plot::PlotMemory;
-use std::collections::HashSet;
+use egui_phosphor_icons::icons::{CHART_BAR, CROSSHAIR, FILE_CSV, WARNING};
+use egui_plot::{Heatmap, LineStyle, Plot, PlotMemory, PlotPoint, Points, Text, VLine};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 pub struct FftPanel {
     pub state: PanelState,
     pub fft_data: FftData,
     pub scope_ui: ScopePanel,
     pub fft_db: bool,
+    /// Line plot of the latest spectra, or waterfall of one trace's history.
+    pub view: FftView,
+    /// Spectra kept per trace in the waterfall view.
+    pub spectrogram_frames: usize,
+    /// Color the waterfall by magnitude in dB instead of linearly.
+    pub spectrogram_db: bool,
+    /// Trace shown in the waterfall; the first in legend order if unset.
+    spectrogram_trace: Option<TraceRef>,
+    /// Spectrum history per trace, filled only while the waterfall is shown.
+    spectrograms: HashMap<TraceRef, Spectrogram>,
+    /// Label the strongest peaks of each visible spectrum.
+    pub mark_peaks: bool,
+    /// Peaks labeled per trace.
+    pub peak_count: usize,
+    /// Ignore peaks at or below this level (displayed units); `None` keeps all.
+    pub peak_floor: Option<f64>,
+    /// Quadrature trace paired with an in-phase trace: the I trace's
+    /// spectrum becomes the two-sided spectrum of `i + j·q`, with negative
+    /// frequencies (see [`FftData::compute_fft_iq`]).
+    pub iq_pairs: HashMap<TraceRef, TraceRef>,
     /// Trace names hidden via the plot legend (clicked to hide).
     /// These traces are neither computed nor rendered.
     hidden_in_legend: HashSet<TraceRef>,
@@ -23,11 +49,23 @@ pub struct FftPanel {
     last_pad_width: f32,
     last_window_width: f32,
     last_throttle_width: f32,
+    last_scale_width: f32,
     last_db_width: f32,
     /// Whether any visible trace had fewer points than fft_size in the last
     /// `update_data` pass.  Used to show a warning in the toolbar without
     /// re-iterating all traces in `render_panel`.
     insufficient_data: bool,
+    /// Smallest adapted FFT size in use (below `fft_size`) in the last
+    /// `update_data` pass, when some trace is still filling its buffer.
+    adapted_size: Option<usize>,
+    /// Visible traces whose latest spectrum came from irregularly spaced
+    /// samples (see [`SpectrumMeta::irregular_sampling`](crate::data::fft::SpectrumMeta::irregular_sampling)).
+    irregular_traces: Vec<TraceRef>,
+    /// Pick two frequency cursors by clicking the plot.
+    band_active: bool,
+    /// The two frequency cursors (plot coordinates) bounding the band whose
+    /// power is read out.
+    band: Measurement,
 }
 
 impl Default for FftPanel {
@@ -54,13 +92,27 @@ impl Default for FftPanel {
             fft_data: FftData::default(),
             scope_ui,
             fft_db: false,
+            view: FftView::default(),
+            spectrogram_frames: DEFAULT_SPECTROGRAM_FRAMES,
+            spectrogram_db: true,
+            spectrogram_trace: None,
+            spectrograms: HashMap::new(),
+            mark_peaks: false,
+            peak_count: 5,
+            peak_floor: None,
+            iq_pairs: HashMap::new(),
             hidden_in_legend: HashSet::default(),
             last_fft_size_width: 200.0,
             last_pad_width: 100.0,
             last_window_width: 120.0,
             last_throttle_width: 120.0,
+            last_scale_width: 100.0,
             last_db_width: 60.0,
             insufficient_data: false,
+            adapted_size: None,
+            irregular_traces: Vec::new(),
+            band_active: false,
+            band: Measurement::new("Band"),
         }
     }
 }
@@ -112,25 +164,16 @@ impl Panel for FftPanel {
                     .clicked()
                 {
                     self.fft_db = !self.fft_db;
-                    if self.fft_db {
-                        self.scope_ui.get_data_mut().y_axis.name =
-                            Some("Magnitude (dB)".to_string());
-                        self.scope_ui
-                            .get_data_mut()
-                            .y_axis
-                            .set_unit(Some("dB".to_string()));
-                    } else {
-                        self.scope_ui.get_data_mut().y_axis.name = Some("Magnitude".to_string());
-                        self.scope_ui.get_data_mut().y_axis.set_unit(None);
-                    }
+                    self.update_y_axis_label();
                 }
                 ui.menu_button("Window", |ui| {
                     // Select FFT window function
                     let mut changed = false;
                     for w in FFTWindow::ALL.iter().copied() {
-                        let sel = w == self.fft_data.fft_window;
+                        // Same type keeps its shape parameter.
+                        let sel = w.label() == self.fft_data.fft_window.label();
                         if ui.selectable_label(sel, w.label()).clicked() {
-                            if self.fft_data.fft_window != w {
+                            if !sel {
                                 self.fft_data.fft_window = w;
                             }
                             changed = true;
@@ -174,6 +217,7 @@ impl Panel for FftPanel {
         // Reset the insufficient-data flag; it will be set if any trace
         // has fewer points than fft_size during the dispatch loop below.
         self.insufficient_data = false;
+        self.adapted_size = None;
 
         // Detect window/pause/size changes and invalidate cache if needed
         self.fft_data.check_window_pause_changed(paused);
@@ -182,13 +226,39 @@ impl Panel for FftPanel {
         self.fft_data
             .fft_traces
             .retain(|name, _| data.traces.contains_key(name));
+        self.fft_data
+            .spectrum_meta
+            .retain(|name, _| data.traces.contains_key(name));
+        self.iq_pairs
+            .retain(|i, q| i != q && data.traces.contains_key(i) && data.traces.contains_key(q));
         // Clean up hidden set for traces that no longer exist
         self.hidden_in_legend
             .retain(|name| data.traces.contains_key(name));
+        // The history is only kept while it is on screen.
+        if self.view == FftView::Waterfall {
+            self.spectrograms
+                .retain(|name, _| data.traces.contains_key(name));
+        } else {
+            self.spectrograms.clear();
+        }
 
         // Poll for completed FFT results from the background worker
         let results = self.fft_data.poll_fft_results();
         for (trace_ref, spectrum, info) in results {
+            if self.view == FftView::Waterfall {
+                // Stamp each frame with the end of the data it came from.
+                let t = data
+                    .traces
+                    .get_points_ref(&trace_ref, paused)
+                    .and_then(|pts| pts.back())
+                    .map(|p| p[0]);
+                if let Some(t) = t {
+                    self.spectrograms
+                        .entry(trace_ref.clone())
+                        .or_default()
+                        .push(t, spectrum.clone(), self.spectrogram_frames);
+                }
+            }
             if let Some(entry) = self.fft_data.fft_traces.get_mut(&trace_ref) {
                 entry.live.clear();
                 entry.live.extend(spectrum.into_iter());
@@ -196,6 +266,16 @@ impl Panel for FftPanel {
                 entry.info = info;
             }
         }
+        self.irregular_traces = self
+            .fft_data
+            .spectrum_meta
+            .iter()
+            .filter(|(name, meta)| {
+                meta.irregular_sampling() && !self.hidden_in_legend.contains(*name)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        self.irregular_traces.sort_by(|a, b| a.0.cmp(&b.0));
 
         // Dispatch new FFT jobs for traces that need recomputation
         for (name, tr) in data.traces.traces_iter() {
@@ -226,13 +306,35 @@ impl Panel for FftPanel {
             } else {
                 &tr.live
             };
-            let buf_len = buf.len();
+            let q_trace = self
+                .iq_pairs
+                .get(name)
+                .and_then(|q| data.traces.get_trace(q));
+            // An I/Q pair can only use the samples both traces hold.
+            let q_len = q_trace
+                .and_then(|q| {
+                    if paused {
+                        q.snap.as_ref()
+                    } else {
+                        Some(&q.live)
+                    }
+                })
+                .map_or(0, |q| q.len());
+            let buf_len = if q_trace.is_some() {
+                buf.len().min(q_len)
+            } else {
+                buf.len()
+            };
             let last_ts = buf.back().map(|p| p[0]);
 
             // Check for insufficient data before the throttle gate so the
             // warning doesn't flicker on/off at the recompute interval.
-            if buf_len < self.fft_data.fft_size {
-                self.insufficient_data = true;
+            match self.fft_data.effective_size(buf_len) {
+                None => self.insufficient_data = true,
+                Some(n) if n < self.fft_data.fft_size => {
+                    self.adapted_size = Some(self.adapted_size.map_or(n, |m| m.min(n)));
+                }
+                Some(_) => {}
             }
 
             if !self
@@ -244,15 +346,23 @@ impl Panel for FftPanel {
 
             // Dispatch to background worker; mark as computed regardless of
             // success to prevent retrying every frame (throttle handles retry).
-            if self.fft_data.dispatch_fft(name, &tr.live, paused, &tr.snap) {
+            let sent = match q_trace {
+                Some(q) => self.fft_data.dispatch_fft_iq(name, tr, q, paused),
+                None => self.fft_data.dispatch_fft(name, &tr.live, paused, &tr.snap),
+            };
+            if sent {
                 self.fft_data.mark_computed(name, buf_len, last_ts);
-            } else if buf_len < self.fft_data.fft_size {
-                // Not enough data for the requested FFT size — update info
-                // and mark computed so we don't spin every frame.
+            } else if self.fft_data.effective_size(buf_len).is_none() {
+                // Not enough data even for the smallest usable FFT size —
+                // update info and mark computed so we don't spin every frame.
                 self.insufficient_data = true;
+                let needed = if self.fft_data.adaptive_size {
+                    self.fft_data.fft_size.min(MIN_ADAPTIVE_FFT_SIZE)
+                } else {
+                    self.fft_data.fft_size
+                };
                 if let Some(entry) = self.fft_data.fft_traces.get_mut(name) {
-                    entry.info =
-                        format!("Need {} samples (have {})", self.fft_data.fft_size, buf_len);
+                    entry.info = format!("Need {} samples (have {})", needed, buf_len);
                 }
                 self.fft_data.mark_computed(name, buf_len, last_ts);
             }
@@ -266,21 +376,13 @@ impl Panel for FftPanel {
             let out_td = tmp_traces.get_trace_or_new(name);
             out_td.look = td.look.clone();
             out_td.offset = 0.0;
-            if self.fft_db {
-                let mut v = td.live.clone();
-                for p in v.iter_mut() {
-                    let mag = p[1].max(1e-12);
-                    p[1] = 20.0 * mag.log10();
-                }
-                out_td.live = v;
-            } else {
-                out_td.live = td.live.clone();
-            }
+            out_td.live = self.displayed_spectrum(td);
             out_td.snap = None;
             out_td.info = td.info.clone();
         }
 
         // Configure scope for frequency domain
+        self.update_y_axis_label();
         let scope_data = self.scope_ui.get_data_mut();
 
         // Sync the internal scope's trace_order with whatever FFT traces are present.
@@ -319,6 +421,19 @@ impl Panel for FftPanel {
                 self.last_fft_size_width = resp.response.rect.width();
             });
 
+            if ui
+                .checkbox(&mut self.fft_data.adaptive_size, "Adaptive")
+                .on_hover_text(format!(
+                    "While a buffer holds fewer samples than the FFT size, use the largest \
+                     power of two that fits (min {}). Smaller sizes give coarser frequency \
+                     resolution (bin width = sample rate / N).",
+                    MIN_ADAPTIVE_FFT_SIZE
+                ))
+                .changed()
+            {
+                self.fft_data.invalidate_cache();
+            }
+
             // Warning when not enough datapoints for the current FFT size
             if self.insufficient_data {
                 ui.label(
@@ -328,6 +443,28 @@ impl Panel for FftPanel {
                     ))
                     .color(egui::Color32::from_rgb(220, 160, 40)),
                 );
+            } else if let Some(n) = self.adapted_size {
+                ui.label(format!("Using N={} until buffer fills", n))
+                    .on_hover_text(format!(
+                        "Bin width is {}× wider than at N={}",
+                        self.fft_data.fft_size / n,
+                        self.fft_data.fft_size
+                    ));
+            }
+            if !self.irregular_traces.is_empty() {
+                let names: Vec<&str> = self.irregular_traces.iter().map(|t| t.0.as_str()).collect();
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} Irregular sampling — results may be inaccurate; consider resampling",
+                        WARNING.as_str()
+                    ))
+                    .color(egui::Color32::from_rgb(220, 160, 40)),
+                )
+                .on_hover_text(format!(
+                    "Sample intervals vary by more than {:.0}% (coefficient of variation) in: {}",
+                    IRREGULAR_SAMPLING_CV * 100.0,
+                    names.join(", ")
+                ));
             }
 
             ui.separator();
@@ -345,13 +482,20 @@ impl Panel for FftPanel {
                         .find(|(v, _)| *v == self.fft_data.zero_pad_factor)
                         .map(|(_, l)| *l)
                         .unwrap_or("1×");
-                    let _ = egui::ComboBox::from_id_salt("fft_zero_pad")
+                    egui::ComboBox::from_id_salt("fft_zero_pad")
                         .selected_text(pad_label)
                         .show_ui(ui, |ui| {
                             for (v, label) in pad_options.iter() {
                                 ui.selectable_value(&mut self.fft_data.zero_pad_factor, *v, *label);
                             }
-                        });
+                        })
+                        .response
+                        .on_hover_text(
+                            "Zero-padding: transform fft_size × factor points for more \
+                             closely spaced bins. This only interpolates the spectrum; \
+                             resolution is still sample rate / FFT size, so two tones \
+                             closer than that stay unresolved.",
+                        );
                 });
                 self.last_pad_width = resp.response.rect.width();
             });
@@ -364,18 +508,49 @@ impl Panel for FftPanel {
             ui.scope_builder(egui::UiBuilder::new().max_rect(rect), |ui| {
                 let resp = ui.horizontal(|ui| {
                     ui.label("Window:");
+                    let current = self.fft_data.fft_window;
                     let mut w_idx = FFTWindow::ALL
                         .iter()
-                        .position(|w| *w == self.fft_data.fft_window)
+                        .position(|w| w.label() == current.label())
                         .unwrap_or(1);
                     let _ = egui::ComboBox::from_id_salt("fft_window_multi")
-                        .selected_text(self.fft_data.fft_window.label())
+                        .selected_text(current.label())
                         .show_ui(ui, |ui| {
                             for (i, w) in FFTWindow::ALL.iter().enumerate() {
                                 ui.selectable_value(&mut w_idx, i, w.label());
                             }
                         });
-                    self.fft_data.fft_window = FFTWindow::ALL[w_idx];
+                    // Same type keeps its shape parameter.
+                    if FFTWindow::ALL[w_idx].label() != current.label() {
+                        self.fft_data.fft_window = FFTWindow::ALL[w_idx];
+                    }
+                    if let Some(mut v) = self.fft_data.fft_window.param() {
+                        let (prefix, range, hint) = match self.fft_data.fft_window {
+                            FFTWindow::Kaiser { .. } => (
+                                "β ",
+                                0.0..=50.0,
+                                "Kaiser shape: larger β lowers the sidelobes and widens \
+                                 the main lobe (0 = Rect, ~5 Hamming, ~8.6 Blackman)",
+                            ),
+                            _ => (
+                                "α ",
+                                0.0..=1.0,
+                                "Fraction of the window that is tapered (0 = Rect, 1 = Hann)",
+                            ),
+                        };
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut v)
+                                    .range(range)
+                                    .speed(0.05)
+                                    .prefix(prefix),
+                            )
+                            .on_hover_text(hint)
+                            .changed()
+                        {
+                            self.fft_data.fft_window = self.fft_data.fft_window.with_param(v);
+                        }
+                    }
                 });
                 self.last_window_width = resp.response.rect.width();
             });
@@ -417,13 +592,81 @@ impl Panel for FftPanel {
 
             ui.separator();
 
+            // Magnitude scaling group
+            let desired = egui::vec2(self.last_scale_width, ui.spacing().interact_size.y);
+            let (rect, _) = ui.allocate_exact_size(desired, egui::Sense::hover());
+            ui.scope_builder(egui::UiBuilder::new().max_rect(rect), |ui| {
+                let resp = ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("fft_quantity")
+                        .selected_text(if self.fft_data.phase {
+                            "Phase"
+                        } else {
+                            "Magnitude"
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.fft_data.phase, false, "Magnitude");
+                            ui.selectable_value(&mut self.fft_data.phase, true, "Phase");
+                        })
+                        .response
+                        .on_hover_text("Plot the magnitude or the unwrapped phase (radians)");
+                    if self.fft_data.phase {
+                        ui.label("Mask:");
+                        ui.add(
+                            egui::DragValue::new(&mut self.fft_data.phase_mask_db)
+                                .range(0.0..=300.0)
+                                .speed(1.0)
+                                .suffix(" dB"),
+                        )
+                        .on_hover_text(
+                            "Hide the phase of bins this far below the strongest one, where \
+                             it is only noise (0 = show all)",
+                        );
+                    }
+                    ui.label("Scale:");
+                    egui::ComboBox::from_id_salt("fft_scale")
+                        .selected_text(self.fft_data.magnitude_scale.label())
+                        .show_ui(ui, |ui| {
+                            for s in FftScale::ALL.iter().copied() {
+                                ui.selectable_value(
+                                    &mut self.fft_data.magnitude_scale,
+                                    s,
+                                    s.label(),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text(
+                            "Raw: unnormalised bin magnitude |X| (depends on size and window)\n\
+                             Linear: window-corrected peak amplitude\n\
+                             PSD: power spectral density (unit²/Hz)\n\
+                             ASD: amplitude spectral density (unit/√Hz)\n\
+                             dBFS: amplitude in dB relative to the full-scale reference",
+                        );
+                    if self.fft_data.magnitude_scale == FftScale::Dbfs {
+                        ui.label("Ref:");
+                        ui.add(
+                            egui::DragValue::new(&mut self.fft_data.dbfs_reference)
+                                .speed(0.01)
+                                .range(1e-12..=1e12),
+                        )
+                        .on_hover_text("Amplitude that corresponds to 0 dBFS");
+                    }
+                });
+                self.last_scale_width = resp.response.rect.width();
+            });
+
+            ui.separator();
+
             // dB toggle group
             let desired = egui::vec2(self.last_db_width, ui.spacing().interact_size.y);
             let (rect, _) = ui.allocate_exact_size(desired, egui::Sense::hover());
             ui.scope_builder(egui::UiBuilder::new().max_rect(rect), |ui| {
                 let resp = ui.horizontal(|ui| {
                     if ui
-                        .button(if self.fft_db { "Linear" } else { "dB" })
+                        .add_enabled(
+                            self.fft_data.magnitude_scale != FftScale::Dbfs && !self.fft_data.phase,
+                            egui::Button::new(if self.fft_db { "Linear" } else { "dB" }),
+                        )
                         .on_hover_text("Toggle FFT magnitude scale")
                         .clicked()
                     {
@@ -435,6 +678,76 @@ impl Panel for FftPanel {
 
             ui.separator();
 
+            ui.label("View:");
+            egui::ComboBox::from_id_salt("fft_view")
+                .selected_text(self.view.label())
+                .show_ui(ui, |ui| {
+                    for v in FftView::ALL.iter().copied() {
+                        ui.selectable_value(&mut self.view, v, v.label());
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "Line: latest spectrum of each trace\n\
+                     Waterfall: how one trace's spectrum evolves over time",
+                );
+            if self.view == FftView::Waterfall {
+                self.render_waterfall_controls(ui);
+            }
+
+            ui.separator();
+
+            self.render_iq_menu(ui);
+
+            ui.separator();
+
+            if ui
+                .button(format!("{} Export spectrum", FILE_CSV.as_str()))
+                .on_hover_text("Save the displayed spectra (frequency and value per trace) as CSV")
+                .clicked()
+            {
+                self.export_spectrum_csv();
+            }
+
+            ui.separator();
+
+            if ui
+                .selectable_label(
+                    self.band_active,
+                    format!("{} Band power", CROSSHAIR.as_str()),
+                )
+                .on_hover_text(
+                    "Click the plot twice to place two frequency cursors and read out the \
+                     power (and RMS) between them",
+                )
+                .clicked()
+            {
+                self.band_active = !self.band_active;
+            }
+
+            ui.separator();
+
+            ui.add_enabled_ui(!self.fft_data.phase, |ui| {
+                ui.checkbox(&mut self.mark_peaks, "Mark peaks")
+                    .on_hover_text("Label the strongest local maxima with frequency and magnitude");
+                if self.mark_peaks {
+                    ui.add(egui::DragValue::new(&mut self.peak_count).range(1..=50))
+                        .on_hover_text("Peaks labeled per trace");
+                    let mut use_floor = self.peak_floor.is_some();
+                    ui.checkbox(&mut use_floor, "Floor")
+                        .on_hover_text("Ignore peaks at or below this level (plot units)");
+                    match (use_floor, &mut self.peak_floor) {
+                        (true, Some(floor)) => {
+                            ui.add(egui::DragValue::new(floor).speed(0.1));
+                        }
+                        (true, None) => self.peak_floor = Some(0.0),
+                        (false, _) => self.peak_floor = None,
+                    }
+                }
+            });
+
+            ui.separator();
+
             let controlls_in_toolbar = self.scope_ui.controls_in_toolbar();
             if ui
                 .selectable_label(controlls_in_toolbar, "Controls in Toolbar")
@@ -444,15 +757,72 @@ impl Panel for FftPanel {
             }
         });
 
+        if self.band_active || self.band.p1.is_some() {
+            self.render_band_readout(ui);
+        }
+
         ui.separator();
 
+        // Clicks place the band cursors while band selection is active.
+        self.scope_ui.get_data_mut().measurement_active = self.band_active;
+
+        if self.view == FftView::Waterfall {
+            self.render_waterfall(ui);
+            return;
+        }
+
         // Render using scope panel (legend is enabled via scope_data settings)
+        let band = (self.band.p1, self.band.p2);
+        let peaks = if self.mark_peaks && !self.fft_data.phase {
+            self.peak_markers(&tmp_traces)
+        } else {
+            Vec::new()
+        };
         self.scope_ui.render_panel(
             ui,
-            |_plot_ui, _scope_unused, _traces_unused| {},
+            |plot_ui, scope, _traces_unused| {
+                for p in [band.0, band.1].into_iter().flatten() {
+                    plot_ui.vline(
+                        VLine::new("", p[0])
+                            .color(egui::Color32::LIGHT_GREEN)
+                            .style(LineStyle::dashed_loose()),
+                    );
+                }
+                let to_plot = |v: f64, log: bool| if log { v.log10() } else { v };
+                for (color, [f, v]) in &peaks {
+                    let pos = PlotPoint::new(
+                        to_plot(*f, scope.x_axis.log_scale),
+                        to_plot(*v, scope.y_axis.log_scale),
+                    );
+                    // Unnamed, so the markers stay out of the legend.
+                    plot_ui.points(
+                        Points::new("", vec![[pos.x, pos.y]])
+                            .color(*color)
+                            .radius(3.0)
+                            .allow_hover(false),
+                    );
+                    let label =
+                        format!("{}\n{}", format_hz(*f), scope.y_axis.format_value(*v, None));
+                    plot_ui.text(
+                        Text::new("", pos, label)
+                            .color(*color)
+                            .anchor(egui::Align2::CENTER_BOTTOM)
+                            .allow_hover(false),
+                    );
+                }
+            },
             &mut tmp_traces,
         );
 
+        let scope = self.scope_ui.get_data_mut();
+        if let Some(point) = scope.clicked_point.take() {
+            if self.band_active {
+                self.band.set_point(point);
+                // A measurement click pauses the scope; the spectrum keeps updating.
+                scope.paused = false;
+            }
+        }
+
         // Read back which traces the user toggled in the legend
         let plot_id = ui.make_persistent_id(egui::Id::new(format!(
             "scope_plot_{}",
@@ -490,3 +860,294 @@ impl Panel for FftPanel {
         serde_json::to_string(&snap).ok()
     }
 }
+
+impl FftPanel {
+    /// Spectrum points as displayed: converted to dB when the dB toggle is on.
+    fn displayed_spectrum(&self, td: &TraceData) -> VecDeque<[f64; 2]> {
+        // dBFS spectra are already logarithmic.
+        let scale = self.fft_data.magnitude_scale;
+        if self.fft_db && scale != FftScale::Dbfs && !self.fft_data.phase {
+            let k = scale.db_factor();
+            // Same -240 dB floor for power and amplitude quantities.
+            let floor = 10f64.powf(-240.0 / k);
+            let mut v = td.live.clone();
+            for p in v.iter_mut() {
+                let mag = p[1].max(floor);
+                p[1] = k * mag.log10();
+            }
+            v
+        } else {
+            td.live.clone()
+        }
+    }
+
+    /// Trace shown in the waterfall: the chosen one while it exists, else
+    /// the first visible trace in legend order.
+    pub fn spectrogram_trace(&self) -> Option<&TraceRef> {
+        let order = &self.scope_ui.get_data().trace_order;
+        self.spectrogram_trace
+            .as_ref()
+            .filter(|t| self.fft_data.fft_traces.contains_key(*t))
+            .or_else(|| order.iter().find(|t| !self.hidden_in_legend.contains(*t)))
+    }
+
+    /// Spectrum history of `name` kept for the waterfall view.
+    pub fn spectrogram(&self, name: &TraceRef) -> Option<&Spectrogram> {
+        self.spectrograms.get(name)
+    }
+
+    /// Trace, history length and color scale selectors for the waterfall.
+    fn render_waterfall_controls(&mut self, ui: &mut Ui) {
+        let shown = self.spectrogram_trace().cloned();
+        egui::ComboBox::from_id_salt("fft_waterfall_trace")
+            .selected_text(shown.as_ref().map_or("–", |t| t.0.as_str()))
+            .show_ui(ui, |ui| {
+                for name in &self.scope_ui.get_data().trace_order {
+                    if ui
+                        .selectable_label(shown.as_ref() == Some(name), name.0.as_str())
+                        .clicked()
+                    {
+                        self.spectrogram_trace = Some(name.clone());
+                    }
+                }
+            });
+        ui.add(
+            egui::DragValue::new(&mut self.spectrogram_frames)
+                .range(2..=2000)
+                .suffix(" frames"),
+        )
+        .on_hover_text("Spectra kept per trace; older ones scroll out");
+        ui.label("Color:");
+        egui::ComboBox::from_id_salt("fft_waterfall_scale")
+            .selected_text(if self.spectrogram_db { "dB" } else { "Linear" })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.spectrogram_db, false, "Linear");
+                ui.selectable_value(&mut self.spectrogram_db, true, "dB");
+            })
+            .response
+            .on_hover_text(format!(
+                "dB shows the {:.0} dB below the strongest bin",
+                crate::data::fft::SPECTROGRAM_DB_SPAN
+            ));
+    }
+
+    /// Pick the Q trace for each I trace, turning its spectrum into the
+    /// two-sided spectrum of `i + j·q`.
+    fn render_iq_menu(&mut self, ui: &mut Ui) {
+        let names = self.scope_ui.get_data().trace_order.clone();
+        ui.menu_button("I/Q", |ui| {
+            egui::Grid::new("fft_iq_pairs").show(ui, |ui| {
+                ui.label("I");
+                ui.label("Q");
+                ui.end_row();
+                for i in &names {
+                    let current = self.iq_pairs.get(i).cloned();
+                    let mut picked = current.clone();
+                    ui.label(i.0.as_str());
+                    egui::ComboBox::from_id_salt(("fft_iq_q", i.0.as_str()))
+                        .selected_text(current.as_ref().map_or("–", |q| q.0.as_str()))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut picked, None, "–");
+                            for q in names.iter().filter(|q| *q != i) {
+                                ui.selectable_value(&mut picked, Some(q.clone()), q.0.as_str());
+                            }
+                        });
+                    ui.end_row();
+                    if picked != current {
+                        match picked {
+                            Some(q) => self.iq_pairs.insert(i.clone(), q),
+                            None => self.iq_pairs.remove(i),
+                        };
+                        self.fft_data.invalidate_cache();
+                    }
+                }
+            });
+        })
+        .response
+        .on_hover_text(
+            "Pair an I trace with its Q trace: the I trace then shows the two-sided \
+             spectrum of i + j·q, from −fs/2 to +fs/2",
+        );
+    }
+
+    /// Heatmap of the selected trace's spectra: frequency across, time up,
+    /// magnitude as color.
+    fn render_waterfall(&mut self, ui: &mut Ui) {
+        // dBFS spectra are already logarithmic; phase is colored as is.
+        let scale = self.fft_data.magnitude_scale;
+        let db = (self.spectrogram_db && scale != FftScale::Dbfs && !self.fft_data.phase)
+            .then(|| scale.db_factor());
+        let shown = self.spectrogram_trace().cloned();
+        let grid = shown
+            .as_ref()
+            .and_then(|name| self.spectrograms.get(name))
+            .and_then(|s| s.grid(db, SPECTROGRAM_MAX_COLUMNS));
+        let plot = Plot::new(format!("fft_waterfall_{}", self.scope_ui.get_data().name))
+            .x_axis_label("Frequency [Hz]")
+            .y_axis_label("Time [s]");
+        plot.show(ui, |plot_ui| {
+            let Some(g) = grid else {
+                return;
+            };
+            plot_ui.heatmap(
+                Heatmap::new(g.values, g.cols)
+                    .name(shown.map(|t| t.0).unwrap_or_default())
+                    .show_labels(false)
+                    .range(g.range.0, g.range.1)
+                    .at(PlotPoint::new(
+                        g.f_start - g.col_width / 2.0,
+                        g.t_start - g.row_height / 2.0,
+                    ))
+                    .tile_size(g.col_width as f32, g.row_height as f32),
+            );
+        });
+    }
+
+    /// Peaks of each displayed spectrum not hidden in the legend, as
+    /// `(color, [Hz, value])`.
+    fn peak_markers(&self, spectra: &TracesCollection) -> Vec<(egui::Color32, [f64; 2])> {
+        let mut out = Vec::new();
+        for (name, td) in spectra.traces_iter() {
+            if self.hidden_in_legend.contains(name) {
+                continue;
+            }
+            let pts: Vec<[f64; 2]> = td.live.iter().copied().collect();
+            for p in find_peaks(&pts, self.peak_count, self.peak_floor) {
+                out.push((td.look.color, p));
+            }
+        }
+        out
+    }
+
+    /// Band edges in Hz, once both frequency cursors are placed.
+    pub fn band_edges(&self) -> Option<(f64, f64)> {
+        let (Some(p1), Some(p2)) = self.band.get_points() else {
+            return None;
+        };
+        let to_hz = |x: f64| {
+            if self.scope_ui.get_data().x_axis.log_scale {
+                10f64.powf(x)
+            } else {
+                x
+            }
+        };
+        let (f1, f2) = (to_hz(p1[0]), to_hz(p2[0]));
+        Some((f1.min(f2), f1.max(f2)))
+    }
+
+    /// Power between the frequency cursors for each visible trace, in legend
+    /// order.  Empty while the phase is shown.
+    pub fn band_powers(&self) -> Vec<(TraceRef, BandPower)> {
+        let Some((f1, f2)) = self.band_edges() else {
+            return Vec::new();
+        };
+        if self.fft_data.phase {
+            return Vec::new();
+        }
+        let order = &self.scope_ui.get_data().trace_order;
+        order
+            .iter()
+            .filter(|name| !self.hidden_in_legend.contains(*name))
+            .filter_map(|name| {
+                let td = self.fft_data.fft_traces.get(name)?;
+                let meta = self.fft_data.spectrum_meta.get(name)?;
+                let spectrum: Vec<[f64; 2]> = td.live.iter().copied().collect();
+                let bp = band_power(&spectrum, meta, self.fft_data.dbfs_reference, f1, f2)?;
+                Some((name.clone(), bp))
+            })
+            .collect()
+    }
+
+    /// One line with the band edges and the power and RMS of each trace.
+    fn render_band_readout(&mut self, ui: &mut Ui) {
+        ui.horizontal_wrapped(|ui| {
+            let Some((f1, f2)) = self.band_edges() else {
+                let which = if self.band.p1.is_none() { 1 } else { 2 };
+                ui.label(format!("Band: click to place cursor {}", which));
+                return;
+            };
+            ui.label(format!("Band {:.3} – {:.3} Hz:", f1, f2));
+            for (name, bp) in self.band_powers() {
+                ui.label(format!(
+                    "{}: P = {:.4e}, RMS = {:.4e}",
+                    name.0, bp.power, bp.rms
+                ))
+                .on_hover_text(format!("{} bins", bp.bins));
+            }
+            if ui.small_button("Clear").clicked() {
+                self.band.clear();
+            }
+        });
+    }
+
+    /// Spectra of all non-hidden traces as displayed, in legend order.
+    pub fn spectrum_series(&self) -> Vec<SpectrumSeries> {
+        let order = &self.scope_ui.get_data().trace_order;
+        let mut names: Vec<&TraceRef> = self
+            .fft_data
+            .fft_traces
+            .keys()
+            .filter(|n| !self.hidden_in_legend.contains(*n))
+            .collect();
+        names.sort_by_key(|n| order.iter().position(|o| o == *n).unwrap_or(usize::MAX));
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let td = self.fft_data.fft_traces.get(name)?;
+                let points = self.displayed_spectrum(td).into_iter().collect();
+                let meta = self.fft_data.spectrum_meta.get(name).copied();
+                Some((name.clone(), meta, points))
+            })
+            .collect()
+    }
+
+    /// Prompt for a path and write [`spectrum_series`](Self::spectrum_series) as CSV.
+    fn export_spectrum_csv(&self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name("spectrum.csv")
+            .add_filter("CSV", &["csv"])
+            .save_file()
+        else {
+            return;
+        };
+        let (name, unit) = self.value_axis_label();
+        let label = match unit {
+            Some(u) => format!("{} [{}]", name, u),
+            None => name.to_string(),
+        };
+        let series = self.spectrum_series();
+        let res = std::fs::File::create(&path)
+            .and_then(|f| export::write_spectrum_csv(std::io::BufWriter::new(f), &label, &series));
+        if let Err(e) = res {
+            eprintln!("Failed to export spectrum: {e}");
+        }
+    }
+
+    /// Flip between dB and linear magnitude.  Has no effect while the dBFS
+    /// scale is selected, which is always logarithmic, or the phase is shown.
+    pub fn toggle_db(&mut self) {
+        if self.fft_data.magnitude_scale == FftScale::Dbfs || self.fft_data.phase {
+            return;
+        }
+        self.fft_db = !self.fft_db;
+        self.update_y_axis_label();
+    }
+
+    /// Name and unit of the plotted quantity: the phase, or the magnitude in
+    /// its scaling and dB setting.
+    fn value_axis_label(&self) -> (&'static str, Option<&'static str>) {
+        if self.fft_data.phase {
+            ("Phase", Some("rad"))
+        } else {
+            self.fft_data.magnitude_scale.axis_label(self.fft_db)
+        }
+    }
+
+    /// Set the y-axis name and unit from the magnitude scaling and dB toggle.
+    fn update_y_axis_label(&mut self) {
+        let (name, unit) = self.value_axis_label();
+        let y_axis = &mut self.scope_ui.get_data_mut().y_axis;
+        y_axis.name = Some(name.to_string());
+        y_axis.set_unit(unit.map(str::to_string));
+    }
+}
@@ -32,10 +32,12 @@ impl HotkeysPanel {
             let mut hk = self.hotkeys.borrow_mut();
             match name {
                 HotkeyName::Fft => hk.fft = value,
+                HotkeyName::FftDb => hk.fft_db_toggle = value,
                 HotkeyName::Math => hk.math = value,
                 HotkeyName::FitView => hk.fit_view = value,
                 HotkeyName::FitY => hk.fit_y = value,
                 HotkeyName::FitViewCont => hk.fit_view_cont = value,
+                HotkeyName::FitAllData => hk.fit_all_data = value,
                 HotkeyName::Pause => hk.pause = value,
                 HotkeyName::Traces => hk.traces = value,
                 HotkeyName::Thresholds => hk.thresholds = value,
@@ -46,6 +48,8 @@ impl HotkeysPanel {
                 HotkeyName::ExportData => hk.export_data = value,
                 HotkeyName::ClearAll => hk.clear_all = value,
                 HotkeyName::ResetMeasurements => hk.reset_measurements = value,
+                HotkeyName::NextTrace => hk.next_trace = value,
+                HotkeyName::PrevTrace => hk.prev_trace = value,
             }
             let _ = hk.save_to_default_path();
         }
@@ -205,10 +209,12 @@ impl Panel for HotkeysPanel {
                 ui.horizontal(|ui| {
                     let tip = match name {
                         HotkeyName::Fft => "Show / Hide FFT panel",
+                        HotkeyName::FftDb => "Toggle the FFT magnitude between dB and linear",
                         HotkeyName::Math => "Show / Hide Math panel",
                         HotkeyName::FitView => "Fit the current view to visible data",
                         HotkeyName::FitY => "Fit the Y axis to visible data",
                         HotkeyName::FitViewCont => "Toggle continuous fitting of the view",
+                        HotkeyName::FitAllData => "Fit both axes once to all buffered data",
                         HotkeyName::Pause => "Pause / resume plotting (Space also toggles)",
                         HotkeyName::Traces => "Show / Hide the Traces panel",
                         HotkeyName::Thresholds => "Show / Hide the Thresholds panel",
@@ -219,6 +225,12 @@ impl Panel for HotkeysPanel {
                         HotkeyName::ExportData => "Show / Hide the Export panel",
                         HotkeyName::ClearAll => "Clear all trace data",
                         HotkeyName::ResetMeasurements => "Clear all measurement points",
+                        HotkeyName::NextTrace => {
+                            "Snap measurement cursors to the next trace in the scope"
+                        }
+                        HotkeyName::PrevTrace => {
+                            "Snap measurement cursors to the previous trace in the scope"
+                        }
                     };
                     ui.label(label).on_hover_text(tip);
 
@@ -261,6 +273,8 @@ impl Panel for HotkeysPanel {
         let panel_rows = vec![
             #[cfg(feature = "fft")]
             ("FFT:", HotkeyName::Fft, current.fft.clone()),
+            #[cfg(feature = "fft")]
+            ("FFT dB / linear:", HotkeyName::FftDb, current.fft_db_toggle),
             ("Traces:", HotkeyName::Traces, current.traces.clone()),
             (
                 "Thresholds:",
@@ -284,6 +298,11 @@ impl Panel for HotkeysPanel {
         let view_rows = vec![
             ("Fit view:", HotkeyName::FitView, current.fit_view.clone()),
             ("Fit Y:", HotkeyName::FitY, current.fit_y.clone()),
+            (
+                "Fit all data:",
+                HotkeyName::FitAllData,
+                current.fit_all_data,
+            ),
             (
                 "Fit view continuously:",
                 HotkeyName::FitViewCont,
@@ -303,6 +322,16 @@ impl Panel for HotkeysPanel {
                 HotkeyName::ResetMeasurements,
                 current.reset_measurements.clone(),
             ),
+            (
+                "Next measurement trace:",
+                HotkeyName::NextTrace,
+                current.next_trace,
+            ),
+            (
+                "Previous measurement trace:",
+                HotkeyName::PrevTrace,
+                current.prev_trace,
+            ),
         ];
 
         let data_rows = vec![
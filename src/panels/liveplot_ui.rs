@@ -16,6 +16,12 @@ pub struct LiveplotPanel {
     event_ctrl_cache: Option<crate::events::EventController>,
     /// Set to true when a structural change occurs (add/remove scope).
     pub(crate) structure_changed: bool,
+    /// Lock every scope, including ones added later, against pan, zoom and
+    /// click selection, and hide their lock toggles.
+    pub interaction_locked: bool,
+    /// Scope ids in presentation order, set by [`reorder_scopes`](Self::reorder_scopes).
+    /// Scopes not listed follow, by ascending id.
+    scope_order: Vec<usize>,
 }
 
 impl Default for LiveplotPanel {
@@ -52,9 +58,12 @@ impl LiveplotPanel {
             next_scope_idx: 1,
             event_ctrl_cache: None,
             structure_changed: false,
+            interaction_locked: false,
+            scope_order: Vec::new(),
         }
     }
-    /// Get immutable references to all scope data, sorted by id.
+    /// Get immutable references to all scope data, in presentation order
+    /// (see [`reorder_scopes`](Self::reorder_scopes)), by default sorted by id.
     pub fn get_data(&self) -> Vec<&ScopeData> {
         let mut scopes_data: Vec<&ScopeData> = self
             .tree
@@ -65,7 +74,7 @@ impl LiveplotPanel {
                 _ => None,
             })
             .collect();
-        scopes_data.sort_by_key(|s| s.id);
+        scopes_data.sort_by_key(|s| scope_rank(&self.scope_order, s.id));
         scopes_data
     }
 
@@ -80,14 +89,57 @@ impl LiveplotPanel {
             })
             .collect();
 
-        // Ensure a stable, predictable ordering by scope id (ascending).
-        // New scopes are allocated with increasing ids, so this keeps newly
-        // added scopes at the end of the returned list.
+        // Ensure a stable, predictable ordering: the requested scope order,
+        // then ascending id.  New scopes are allocated with increasing ids,
+        // so this keeps newly added scopes at the end of the returned list.
         let mut scopes_data = scopes_data;
-        scopes_data.sort_by_key(|s| s.id);
+        scopes_data.sort_by_key(|s| scope_rank(&self.scope_order, s.id));
         scopes_data
     }
 
+    /// Put the scopes in the given order of ids.  Unknown ids are ignored and
+    /// scopes left out keep their current relative order after the listed
+    /// ones.  [`get_data`](Self::get_data), saved state and the scopes
+    /// controller follow the new order, and the panes of each tab or split
+    /// container are rearranged to match.
+    pub fn reorder_scopes(&mut self, order: &[usize]) {
+        let current: Vec<usize> = self.get_data().iter().map(|s| s.id).collect();
+        let mut new_order: Vec<usize> = Vec::with_capacity(current.len());
+        for id in order.iter().chain(current.iter()) {
+            if current.contains(id) && !new_order.contains(id) {
+                new_order.push(*id);
+            }
+        }
+        self.scope_order = new_order;
+
+        let pane_ranks: std::collections::HashMap<TileId, (usize, usize)> = self
+            .tree
+            .tiles
+            .iter()
+            .filter_map(|(tile_id, tile)| match tile {
+                Tile::Pane(pane) => Some((*tile_id, scope_rank(&self.scope_order, pane.id()))),
+                _ => None,
+            })
+            .collect();
+        for tile in self.tree.tiles.tiles_mut() {
+            let children = match tile {
+                Tile::Container(Container::Tabs(tabs)) => &mut tabs.children,
+                Tile::Container(Container::Linear(linear)) => &mut linear.children,
+                _ => continue,
+            };
+            // Permute the panes among their own slots; nested containers stay put.
+            let slots: Vec<usize> = (0..children.len())
+                .filter(|i| pane_ranks.contains_key(&children[*i]))
+                .collect();
+            let mut panes: Vec<TileId> = slots.iter().map(|i| children[*i]).collect();
+            panes.sort_by_key(|t| pane_ranks[t]);
+            for (slot, pane) in slots.into_iter().zip(panes) {
+                children[slot] = pane;
+            }
+        }
+        self.structure_changed = true;
+    }
+
     pub fn scope_states(&self) -> Vec<crate::persistence::ScopeStateSerde> {
         let mut states: Vec<(usize, crate::persistence::ScopeStateSerde)> = self
             .tree
@@ -100,7 +152,7 @@ impl LiveplotPanel {
                 _ => None,
             })
             .collect();
-        states.sort_by_key(|(id, _)| *id);
+        states.sort_by_key(|(id, _)| scope_rank(&self.scope_order, *id));
         states.into_iter().map(|(_, state)| state).collect()
     }
 
@@ -166,7 +218,7 @@ impl LiveplotPanel {
                     .on_hover_text("Clear all trace data")
                     .clicked()
                 {
-                    traces.clear_all();
+                    traces.request_clear();
                     for tile in self.tree.tiles.tiles_mut() {
                         if let Tile::Pane(pane) = tile {
                             pane.get_data_mut().clicked_point = None;
@@ -220,6 +272,15 @@ impl LiveplotPanel {
             .tiles()
             .any(|t| matches!(t, Tile::Pane(p) if p.controls_in_toolbar()));
 
+        for tile in self.tree.tiles.tiles_mut() {
+            if let Tile::Pane(pane) = tile {
+                pane.interaction_lock_fixed = self.interaction_locked;
+                if self.interaction_locked {
+                    pane.get_data_mut().interaction_locked = true;
+                }
+            }
+        }
+
         let mut behavior = ScopeBehavior {
             draw_overlays,
             traces,
@@ -512,6 +573,15 @@ impl LiveplotPanel {
         }
     }
 
+    /// Enable or disable minimal (sparkline-style) axes on every scope panel.
+    pub fn set_minimal_axes(&mut self, enabled: bool) {
+        for tile in self.tree.tiles.tiles_mut() {
+            if let Tile::Pane(pane) = tile {
+                pane.set_minimal_axes(enabled);
+            }
+        }
+    }
+
     /// Propagate the event controller to every scope panel.
     pub fn set_event_controller(&mut self, ctrl: Option<crate::events::EventController>) {
         self.event_ctrl_cache = ctrl.clone();
@@ -551,9 +621,11 @@ impl LiveplotPanel {
         // Create scope panels from the saved states
         let mut scope_panels = std::collections::HashMap::new();
         let mut max_id: usize = 0;
+        let mut order = Vec::with_capacity(scope_states.len());
         for ss in scope_states {
             let scope_id = ss.id.unwrap_or(max_id);
             max_id = max_id.max(scope_id + 1);
+            order.push(scope_id);
             let mut panel = ScopePanel::new(scope_id);
             panel.event_ctrl = self.event_ctrl_cache.clone();
             ss.apply_to_panel(&mut panel);
@@ -585,9 +657,20 @@ impl LiveplotPanel {
         self.tree = Tree::new(egui::Id::new("liveplot_scopes_restore"), root_id, tiles);
 
         self.next_scope_idx = next_idx.unwrap_or(max_id);
+        // Saved states are stored in presentation order.
+        self.scope_order = order;
     }
 }
 
+/// Sort key for scope `id` under `order`: listed ids first, in list order,
+/// then the rest by id.
+fn scope_rank(order: &[usize], id: usize) -> (usize, usize) {
+    (
+        order.iter().position(|o| *o == id).unwrap_or(usize::MAX),
+        id,
+    )
+}
+
 struct ScopeBehavior<'a, F> {
     draw_overlays: F,
     traces: &'a mut TracesCollection,
@@ -1,6 +1,6 @@
 use crate::data::data::LivePlotData;
-use crate::data::math::{FilterKind, MathKind, MathTrace, MinMaxMode};
-use crate::data::traces::TraceRef;
+use crate::data::math::{FilterBand, FilterKind, MathKind, MathTrace, MinMaxMode};
+use crate::data::traces::{TraceRef, TracesCollection};
 use eframe::egui;
 use egui::{Color32, Ui};
 use std::collections::HashMap;
@@ -21,6 +21,9 @@ pub struct MathPanel {
     creating: bool,
 
     math_traces: Vec<MathTrace>,
+    /// Serialized definitions as of the last recompute, used to notice edits
+    /// made while paused.
+    computed_defs: Option<String>,
 }
 
 impl Default for MathPanel {
@@ -34,6 +37,7 @@ impl Default for MathPanel {
             creating: false,
 
             math_traces: Vec::new(),
+            computed_defs: None,
         }
     }
 }
@@ -93,9 +97,7 @@ impl Panel for MathPanel {
                     .button(format!("{} Reset All Storage", RECYCLE.as_str()))
                     .clicked()
                 {
-                    for def in self.math_traces.iter() {
-                        data.traces.clear_trace(&def.name);
-                    }
+                    self.reset_all_states(data.traces);
                     ui.close();
                 }
                 if ui.button(format!("{} Clear All", BROOM.as_str())).clicked() {
@@ -127,23 +129,40 @@ impl Panel for MathPanel {
 
     fn update_data(&mut self, data: &mut LivePlotData<'_>) {
         if data.pending_requests.clear_math {
-            for def in self.math_traces.iter() {
-                data.traces.clear_trace(&def.name);
-            }
+            self.reset_all_states(data.traces);
             data.pending_requests.clear_math = false;
         }
+        for name in std::mem::take(&mut data.pending_requests.reset_math_state) {
+            if !self.reset_state(&name, data.traces) {
+                eprintln!("liveplot: no math trace named {:?}", name.0);
+            }
+        }
+        // Deleting a math trace's output from the traces list drops its definition.
+        self.math_traces
+            .retain(|def| !data.traces.was_removed(&def.name));
 
         if self.math_traces.is_empty() {
             return;
         }
 
+        // While every scope shows a frozen snapshot, the snapshot outputs
+        // cannot change; recompute only when a definition was edited.  Live
+        // outputs catch up incrementally on resume.
+        if data.are_all_paused() && data.traces.has_snapshot() {
+            let defs = serde_json::to_string(&self.math_traces).ok();
+            if defs == self.computed_defs {
+                return;
+            }
+            self.computed_defs = defs;
+        } else {
+            self.computed_defs = None;
+        }
+
         // Collect only the traces that are actually referenced as inputs by
         // any math trace definition, plus the math traces' own previous output.
         let mut needed: std::collections::HashSet<TraceRef> = std::collections::HashSet::new();
         for def in &self.math_traces {
-            for name in def.input_trace_names() {
-                needed.insert(name.clone());
-            }
+            needed.extend(def.input_traces());
             needed.insert(def.name.clone());
         }
 
@@ -156,11 +175,25 @@ impl Panel for MathPanel {
         }
 
         for def in self.math_traces.iter_mut() {
+            // An input that was deleted would just look like "no data"; stop
+            // computing and say so instead.
+            if let Some(missing) = removed_input(def, data.traces) {
+                let info = format!("disabled: input '{}' was removed", missing.0);
+                let tr = data.get_trace_or_new(&def.name);
+                tr.info = info;
+                tr.is_math = true;
+                continue;
+            }
+            // A malformed formula keeps its previous output.
             let out = def.compute_math_trace(&sources);
 
             let tr = data.get_trace_or_new(&def.name);
             tr.live = out.iter().copied().collect();
-            tr.info = def.math_formula_string();
+            tr.info = match def.math_error(&sources) {
+                Some(err) => format!("error: {err}"),
+                None => def.math_formula_string(),
+            };
+            tr.is_math = true;
 
             sources.insert(def.name.clone(), out);
         }
@@ -176,11 +209,14 @@ impl Panel for MathPanel {
         }
 
         for def in self.math_traces.iter_mut() {
+            if removed_input(def, data.traces).is_some() {
+                continue;
+            }
             let out = def.compute_math_trace(&sources);
 
             let tr = data.get_trace_or_new(&def.name);
             tr.snap = Some(out.iter().copied().collect());
-            tr.info = def.math_formula_string();
+            tr.is_math = true;
 
             sources.insert(def.name.clone(), out);
         }
@@ -200,10 +236,7 @@ impl Panel for MathPanel {
                 .on_hover_text("Reset integrators, filters, min/max for all math traces")
                 .clicked()
             {
-                for def in self.math_traces.iter_mut() {
-                    // def.reset_math_storage();
-                    data.traces.clear_trace(&def.name);
-                }
+                self.reset_all_states(data.traces);
             }
         });
         ui.add_space(6.0);
@@ -223,7 +256,7 @@ impl Panel for MathPanel {
                         hover_trace_intern = Some(vec![def.name.clone()]);
                     }
                     if resp.changed() {
-                        tr.look.color = c;
+                        tr.look.set_custom_color(c);
                     }
                 } else {
                     ui.label("");
@@ -252,6 +285,13 @@ impl Panel for MathPanel {
                 } else {
                     String::new()
                 };
+                let failed =
+                    removed_input(def, data.traces).is_some() || info_text.starts_with("error: ");
+                let info_text = if failed {
+                    egui::RichText::new(info_text).color(Color32::LIGHT_RED)
+                } else {
+                    egui::RichText::new(info_text)
+                };
                 let info_resp = ui.add(
                     egui::Label::new(info_text)
                         .truncate()
@@ -303,22 +343,17 @@ impl Panel for MathPanel {
                         }
                     }
                     // Show Reset for kinds that have internal storage
-                    let is_stateful = matches!(
-                        def.kind,
-                        MathKind::Integrate { .. }
-                            | MathKind::Filter { .. }
-                            | MathKind::MinMax { .. }
-                    );
-                    if is_stateful {
+                    if def.is_stateful() {
                         let reset_resp = ui
                             .button(egui_phosphor_icons::icons::ARROW_CLOCKWISE)
-                            .on_hover_text("Reset integrator/filter/min/max state for this trace");
+                            .on_hover_text(
+                                "Reset integrator/filter/min/max/average state for this trace",
+                            );
                         if reset_resp.hovered() {
                             hover_trace_intern = Some(vec![def.name.clone()]);
                         }
                         if reset_resp.clicked() {
-                            // def.reset_math_storage();
-                            data.traces.clear_trace(&def.name);
+                            self.reset_state(&def.name, data.traces);
                         }
                     }
                 });
@@ -399,6 +434,12 @@ impl Panel for MathPanel {
                 "Filter",
                 "Min",
                 "Max",
+                "Magnitude (I/Q)",
+                "Phase (I/Q)",
+                "Expression",
+                "RMS",
+                "Moving average",
+                "Abs",
             ];
             let mut kind_idx: usize = match &self.builder.kind {
                 MathKind::Add { .. } => 0,
@@ -411,6 +452,12 @@ impl Panel for MathPanel {
                     MinMaxMode::Min => 6,
                     MinMaxMode::Max => 7,
                 },
+                MathKind::Magnitude { .. } => 8,
+                MathKind::Phase { .. } => 9,
+                MathKind::Expr { .. } => 10,
+                MathKind::Rms { .. } => 11,
+                MathKind::MovingAverage { .. } => 12,
+                MathKind::Abs { .. } => 13,
             };
 
             let prev_kind_idx = kind_idx;
@@ -467,8 +514,32 @@ impl Panel for MathPanel {
                         decay_per_sec: Some(0.0),
                         mode: MinMaxMode::Max,
                     },
+                    8 => MathKind::Magnitude {
+                        i: first.clone(),
+                        q: second.clone(),
+                    },
+                    9 => MathKind::Phase {
+                        i: first.clone(),
+                        q: second.clone(),
+                    },
+                    10 => MathKind::Expr {
+                        expr: first.0.clone(),
+                        vars: Vec::new(),
+                    },
+                    11 => MathKind::Rms {
+                        input: first.clone(),
+                        window_secs: 1.0,
+                    },
+                    12 => MathKind::MovingAverage {
+                        input: first.clone(),
+                        window_secs: 1.0,
+                    },
+                    13 => MathKind::Abs {
+                        input: first.clone(),
+                    },
                     _ => MathKind::Add { inputs: vec![] },
                 };
+                self.error = None;
             }
 
             // Initialize builder look color if blank name changed to a new one (use palette color based on future index)
@@ -553,7 +624,33 @@ impl Panel for MathPanel {
                         }
                     });
                 }
-                MathKind::Differentiate { input } => {
+                MathKind::Magnitude { i, q } | MathKind::Phase { i, q } => {
+                    ui.horizontal(|ui| {
+                        let mut sel_i = i.0.clone();
+                        egui::ComboBox::from_label("I")
+                            .selected_text(sel_i.clone())
+                            .show_ui(ui, |ui| {
+                                for n in trace_names.iter() {
+                                    ui.selectable_value(&mut sel_i, n.0.clone(), n.0.clone());
+                                }
+                            });
+                        if sel_i != i.0 {
+                            i.0 = sel_i;
+                        }
+                        let mut sel_q = q.0.clone();
+                        egui::ComboBox::from_label("Q")
+                            .selected_text(sel_q.clone())
+                            .show_ui(ui, |ui| {
+                                for n in trace_names.iter() {
+                                    ui.selectable_value(&mut sel_q, n.0.clone(), n.0.clone());
+                                }
+                            });
+                        if sel_q != q.0 {
+                            q.0 = sel_q;
+                        }
+                    });
+                }
+                MathKind::Differentiate { input } | MathKind::Abs { input } => {
                     let mut sel = input.0.clone();
                     egui::ComboBox::from_label("Input")
                         .selected_text(sel.clone())
@@ -603,6 +700,10 @@ impl Panel for MathPanel {
                         "Biquad LP",
                         "Biquad HP",
                         "Biquad BP",
+                        "Biquad Notch",
+                        "Butterworth LP",
+                        "Butterworth HP",
+                        "Butterworth BP",
                     ];
                     let mut which: usize = match kind {
                         FilterKind::Lowpass { .. } => 0,
@@ -611,6 +712,12 @@ impl Panel for MathPanel {
                         FilterKind::BiquadLowpass { .. } => 3,
                         FilterKind::BiquadHighpass { .. } => 4,
                         FilterKind::BiquadBandpass { .. } => 5,
+                        FilterKind::BiquadNotch { .. } => 6,
+                        FilterKind::Butterworth { kind, .. } => match kind {
+                            FilterBand::Lowpass => 7,
+                            FilterBand::Highpass => 8,
+                            FilterBand::Bandpass { .. } => 9,
+                        },
                         FilterKind::Custom { .. } => 0,
                     };
                     let (mut f1, mut f2, mut q) = match kind {
@@ -623,8 +730,19 @@ impl Panel for MathPanel {
                         FilterKind::BiquadLowpass { cutoff_hz, q } => (*cutoff_hz, 0.0, *q),
                         FilterKind::BiquadHighpass { cutoff_hz, q } => (*cutoff_hz, 0.0, *q),
                         FilterKind::BiquadBandpass { center_hz, q } => (*center_hz, 0.0, *q),
+                        FilterKind::BiquadNotch { center_hz, q } => (*center_hz, 0.0, *q),
+                        FilterKind::Butterworth {
+                            kind: FilterBand::Bandpass { high_cut_hz },
+                            cutoff_hz,
+                            ..
+                        } => (*cutoff_hz, *high_cut_hz, 0.707),
+                        FilterKind::Butterworth { cutoff_hz, .. } => (*cutoff_hz, 0.0, 0.707),
                         FilterKind::Custom { params: _ } => (1.0, 0.0, 0.707),
                     };
+                    let mut order = match kind {
+                        FilterKind::Butterworth { order, .. } => *order,
+                        _ => 4,
+                    };
                     egui::ComboBox::from_label("Filter")
                         .selected_text(fk[which])
                         .show_ui(ui, |ui| {
@@ -649,8 +767,8 @@ impl Panel for MathPanel {
                                 ui.add(egui::DragValue::new(&mut f2).speed(0.1));
                             });
                         }
-                        3 | 4 | 5 => {
-                            let label = if which == 5 { "Center Hz" } else { "Cutoff Hz" };
+                        3..=6 => {
+                            let label = if which >= 5 { "Center Hz" } else { "Cutoff Hz" };
                             ui.horizontal(|ui| {
                                 ui.label(label);
                                 ui.add(egui::DragValue::new(&mut f1).speed(0.1));
@@ -660,6 +778,30 @@ impl Panel for MathPanel {
                                 ui.add(egui::DragValue::new(&mut q).speed(0.01));
                             });
                         }
+                        7..=9 => {
+                            if which == 9 {
+                                ui.horizontal(|ui| {
+                                    ui.label("Low cut Hz");
+                                    ui.add(egui::DragValue::new(&mut f1).speed(0.1));
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("High cut Hz");
+                                    ui.add(egui::DragValue::new(&mut f2).speed(0.1));
+                                });
+                            } else {
+                                ui.horizontal(|ui| {
+                                    ui.label("Cutoff Hz");
+                                    ui.add(egui::DragValue::new(&mut f1).speed(0.1));
+                                });
+                            }
+                            egui::ComboBox::from_label("Order")
+                                .selected_text(order.to_string())
+                                .show_ui(ui, |ui| {
+                                    for n in [2u8, 4, 6, 8] {
+                                        ui.selectable_value(&mut order, n, n.to_string());
+                                    }
+                                });
+                        }
                         _ => {}
                     }
                     // Write back updated kind
@@ -673,6 +815,16 @@ impl Panel for MathPanel {
                         3 => FilterKind::BiquadLowpass { cutoff_hz: f1, q },
                         4 => FilterKind::BiquadHighpass { cutoff_hz: f1, q },
                         5 => FilterKind::BiquadBandpass { center_hz: f1, q },
+                        6 => FilterKind::BiquadNotch { center_hz: f1, q },
+                        7..=9 => FilterKind::Butterworth {
+                            kind: match which {
+                                7 => FilterBand::Lowpass,
+                                8 => FilterBand::Highpass,
+                                _ => FilterBand::Bandpass { high_cut_hz: f2 },
+                            },
+                            cutoff_hz: f1,
+                            order,
+                        },
                         _ => FilterKind::Lowpass { cutoff_hz: f1 },
                     };
                 }
@@ -699,6 +851,95 @@ impl Panel for MathPanel {
                         *decay_per_sec = Some(decay);
                     });
                 }
+                MathKind::Rms { input, window_secs }
+                | MathKind::MovingAverage { input, window_secs } => {
+                    let mut sel = input.0.clone();
+                    egui::ComboBox::from_label("Input")
+                        .selected_text(sel.clone())
+                        .show_ui(ui, |ui| {
+                            for n in trace_names.iter() {
+                                ui.selectable_value(&mut sel, n.0.clone(), n.0.clone());
+                            }
+                        });
+                    if sel != input.0 {
+                        input.0 = sel;
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label("Window (s)");
+                        ui.add(
+                            egui::DragValue::new(window_secs)
+                                .range(1e-6..=f64::MAX)
+                                .speed(0.01),
+                        )
+                        .on_hover_text("Length of the sliding window; shorter at the start until enough samples exist");
+                    });
+                }
+                MathKind::Expr { expr, vars } => {
+                    ui.horizontal(|ui| {
+                        ui.label("Formula");
+                        ui.add(
+                            egui::TextEdit::singleline(expr)
+                                .code_editor()
+                                .hint_text("(a + 2*b) / c"),
+                        )
+                        .on_hover_text(
+                            "Trace names, numbers, + - * / % ^, parentheses, pi and \
+                             functions like sqrt(x) or atan2(y, x). Quote names that \
+                             are not plain identifiers: \"trace-1\"",
+                        );
+                    });
+                    // Variable bindings: short names for traces in the formula.
+                    let mut remove_var = None;
+                    egui::Grid::new("math_expr_vars")
+                        .num_columns(3)
+                        .show(ui, |ui| {
+                            for (idx, (var, trace_ref)) in vars.iter_mut().enumerate() {
+                                ui.add(
+                                    egui::TextEdit::singleline(var)
+                                        .desired_width(60.0)
+                                        .hint_text("name"),
+                                );
+                                egui::ComboBox::from_id_salt(("math_expr_var", idx))
+                                    .selected_text(trace_ref.0.clone())
+                                    .show_ui(ui, |ui| {
+                                        for n in trace_names.iter() {
+                                            ui.selectable_value(trace_ref, n.clone(), n.0.clone());
+                                        }
+                                    });
+                                if ui
+                                    .button(egui_phosphor_icons::icons::TRASH)
+                                    .on_hover_text("Remove variable")
+                                    .clicked()
+                                {
+                                    remove_var = Some(idx);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    if let Some(idx) = remove_var {
+                        vars.remove(idx);
+                    }
+                    if ui
+                        .button(format!("{} Variable", PLUS.as_str()))
+                        .on_hover_text("Bind a short name in the formula to a trace")
+                        .clicked()
+                    {
+                        let var = ["a", "b", "c", "d", "x", "y", "z"]
+                            .into_iter()
+                            .find(|c| !vars.iter().any(|(v, _)| v.trim() == *c))
+                            .unwrap_or("v");
+                        let first = trace_names.first().cloned().unwrap_or_default();
+                        vars.push((var.to_string(), first));
+                    }
+                    // Validate on every frame so the error (and the disabled
+                    // save) tracks the text as it is typed.
+                    self.error = crate::data::math::expr_error(expr, vars, |name| {
+                        trace_names.contains(name)
+                    })
+                    .map(|e| format!("Formula: {e}"));
+                    let names: Vec<&str> = trace_names.iter().map(|n| n.as_str()).collect();
+                    ui.weak(format!("Traces: {}", names.join(", ")));
+                }
             }
 
             // Unified Style and Save section
@@ -732,8 +973,11 @@ impl Panel for MathPanel {
                     .clicked()
                     || enter_pressed
                 {
-                    // Handle save: builder already holds the full MathTrace
-                    let tr = self.builder.clone();
+                    // Handle save: builder already holds the full MathTrace. An
+                    // edited definition (e.g. a new filter order) starts from
+                    // fresh runtime state.
+                    let mut tr = self.builder.clone();
+                    tr.reset_state();
                     if self.error.is_none() {
                         if !is_creating {
                             // Preserve look if renaming and replace in-place to keep position
@@ -840,4 +1084,32 @@ impl MathPanel {
     pub fn set_math_traces(&mut self, v: Vec<crate::data::math::MathTrace>) {
         self.math_traces = v;
     }
+
+    /// Reset the runtime state of the named math trace and clear its output so
+    /// it is recomputed from the current inputs (also while paused).  Returns
+    /// `false` if there is no such math trace.
+    fn reset_state(&mut self, name: &TraceRef, traces: &mut TracesCollection) -> bool {
+        let Some(def) = self.math_traces.iter_mut().find(|d| &d.name == name) else {
+            return false;
+        };
+        def.reset_state();
+        traces.clear_trace(name);
+        self.computed_defs = None;
+        true
+    }
+
+    fn reset_all_states(&mut self, traces: &mut TracesCollection) {
+        for def in self.math_traces.iter_mut() {
+            def.reset_state();
+            traces.clear_trace(&def.name);
+        }
+        self.computed_defs = None;
+    }
+}
+
+/// First input of `def` that was deleted from the traces collection.
+fn removed_input(def: &MathTrace, traces: &TracesCollection) -> Option<TraceRef> {
+    def.input_traces()
+        .into_iter()
+        .find(|name| traces.was_removed(name))
 }
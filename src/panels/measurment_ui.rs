@@ -1,9 +1,14 @@
 use super::panel_trait::{Panel, PanelState};
 use crate::data::data::LivePlotData;
-use crate::data::measurement::Measurement;
+use crate::data::export::{self, SampleMode};
+use crate::data::measurement::{
+    crossing_frequency, write_measurement_log_csv, Measurement, MeasurementRecord,
+    MeasurementResult, PointDelta, SignalStats,
+};
 use crate::data::scope::{AxisSettings, ScopeData};
+use crate::data::traces::{TraceData, TraceRef, TracesCollection};
 use egui::{Align2, Color32};
-use egui_phosphor_icons::icons::{BROOM, CROSSHAIR, PLUS, RULER};
+use egui_phosphor_icons::icons::{BROOM, CROSSHAIR, FILE_CSV, FILE_TEXT, PLUS, RULER};
 use egui_plot::{Line, PlotPoint, Points, Text};
 
 pub struct MeasurementPanel {
@@ -13,6 +18,15 @@ pub struct MeasurementPanel {
     selected_point_index: Option<usize>,
     last_clicked_point: Option<[f64; 2]>,
     hovered_measurement: Option<usize>,
+    /// Session log of completed measurements, oldest first.
+    history: Vec<MeasurementRecord>,
+    /// Interpolate (vs. take the nearest sample) when exporting cursor values.
+    interpolate_cursor_values: bool,
+    /// Result of the selected (or first) measurement as of the last frame.
+    last_result: Option<MeasurementResult>,
+    /// Row join tolerance (seconds) of the Export panel, so nearest cursor
+    /// values match an aligned export.
+    pub(crate) join_tolerance: f64,
 }
 
 impl Default for MeasurementPanel {
@@ -24,28 +38,185 @@ impl Default for MeasurementPanel {
             selected_point_index: None,
             last_clicked_point: None,
             hovered_measurement: None,
+            history: Vec::new(),
+            interpolate_cursor_values: true,
+            last_result: None,
+            join_tolerance: export::DEFAULT_JOIN_TOLERANCE,
         }
     }
 }
 
 impl MeasurementPanel {
+    fn plot_to_axis_value(axis: &AxisSettings, v_plot: f64) -> f64 {
+        if axis.log_scale {
+            10f64.powf(v_plot)
+        } else {
+            v_plot
+        }
+    }
+
+    /// Snapping trace of `m`, whose axis its points are read on.
+    fn catch_trace<'t>(traces: &'t TracesCollection, m: &Measurement) -> Option<&'t TraceData> {
+        m.catch_trace
+            .as_ref()
+            .and_then(|name| traces.get_trace(name))
+    }
+
+    /// Y axis the points of a measurement snapping to `catch` are read on.
+    fn value_axis<'s>(scope: &'s ScopeData, catch: Option<&TraceData>) -> &'s AxisSettings {
+        catch.map_or(&scope.y_axis, |tr| scope.value_axis(tr))
+    }
+
+    /// Y value at plot coordinate `y_plot`, on the axis of `catch`.
+    fn plot_to_value_y(scope: &ScopeData, catch: Option<&TraceData>, y_plot: f64) -> f64 {
+        match catch {
+            Some(tr) => scope.plot_y_to_value(tr, y_plot),
+            None => Self::plot_to_axis_value(&scope.y_axis, y_plot),
+        }
+    }
+
+    /// Plot coordinates of `point`, given in axis units; its Y is read on the
+    /// axis of `catch`.
     fn trace_point_to_plot_coords(
         scope: &ScopeData,
+        catch: Option<&TraceData>,
         point: [f64; 2],
-        offset: f64,
     ) -> Option<[f64; 2]> {
         let x_plot = if scope.x_axis.log_scale {
             (point[0] > 0.0).then(|| point[0].log10())?
         } else {
             point[0]
         };
-        let y_linear = point[1] + offset;
-        let y_plot = if scope.y_axis.log_scale {
-            (y_linear > 0.0).then(|| y_linear.log10())?
-        } else {
-            y_linear
+        let y_plot = match catch {
+            Some(tr) => scope.value_to_plot_y(tr, point[1]),
+            None if scope.y_axis.log_scale => (point[1] > 0.0).then(|| point[1].log10())?,
+            None => point[1],
+        };
+        y_plot.is_finite().then_some([x_plot, y_plot])
+    }
+
+    /// Move the snapping trace of the selected (or first snapping) measurement
+    /// `step` places through its scope's trace order, and re-snap both points
+    /// to the new trace's samples nearest the same x.
+    fn cycle_catch_trace(&mut self, data: &LivePlotData<'_>, step: isize) {
+        let idx = self
+            .selected_measurement
+            .filter(|i| {
+                self.measurements
+                    .get(*i)
+                    .is_some_and(|m| m.catch_trace.is_some())
+            })
+            .or_else(|| {
+                self.measurements
+                    .iter()
+                    .position(|m| m.catch_trace.is_some())
+            });
+        let Some(m) = idx.and_then(|i| self.measurements.get_mut(i)) else {
+            return;
+        };
+        let Some(current) = m.catch_trace.clone() else {
+            return;
+        };
+        let scope = m
+            .scope_id
+            .and_then(|id| data.scope_by_id(id))
+            .or_else(|| data.scope_containing_trace(&current));
+        let Some(scope) = scope else {
+            return;
+        };
+        let order = &scope.trace_order;
+        let Some(pos) = order.iter().position(|t| *t == current) else {
+            return;
+        };
+        let next = &order[(pos as isize + step).rem_euclid(order.len() as isize) as usize];
+        m.catch_trace = Some(next.clone());
+
+        let (Some(tr), Some(points)) = (
+            data.traces.get_trace(next),
+            data.traces.get_points_ref(next, scope.shows_snapshot()),
+        ) else {
+            return;
+        };
+        let resnap = |p: [f64; 2]| -> [f64; 2] {
+            let x = Self::plot_to_axis_value(&scope.x_axis, p[0]);
+            let i = points.partition_point(|q| q[0] < x);
+            let nearest = [i.checked_sub(1), Some(i)]
+                .into_iter()
+                .flatten()
+                .filter_map(|j| points.get(j))
+                .min_by(|a, b| (a[0] - x).abs().total_cmp(&(b[0] - x).abs()));
+            nearest
+                .and_then(|q| {
+                    Self::trace_point_to_plot_coords(scope, Some(tr), [q[0], tr.display_y(q[1])])
+                })
+                .unwrap_or(p)
+        };
+        m.p1 = m.p1.map(resnap);
+        m.p2 = m.p2.map(resnap);
+    }
+
+    /// Place both points of the selected (or first) measurement at the given
+    /// axis-unit positions, creating a measurement if none exists.
+    fn set_points_from_request(&mut self, data: &LivePlotData<'_>, p1: [f64; 2], p2: [f64; 2]) {
+        if self.measurements.is_empty() {
+            self.measurements.push(Measurement::new("M1"));
+        }
+        let idx = self
+            .selected_measurement
+            .filter(|i| *i < self.measurements.len())
+            .unwrap_or(0);
+        let m = &mut self.measurements[idx];
+        let Some(scope) = m
+            .scope_id
+            .and_then(|id| data.scope_by_id(id))
+            .or_else(|| data.primary_scope())
+        else {
+            return;
+        };
+        let catch = Self::catch_trace(data.traces, m);
+        let (Some(a), Some(b)) = (
+            Self::trace_point_to_plot_coords(scope, catch, p1),
+            Self::trace_point_to_plot_coords(scope, catch, p2),
+        ) else {
+            return;
+        };
+        m.set_point1(a);
+        m.set_point2(b);
+        m.scope_id = Some(scope.id);
+    }
+
+    fn compute_result(&self, data: &LivePlotData<'_>) -> Option<MeasurementResult> {
+        let idx = self
+            .selected_measurement
+            .filter(|i| *i < self.measurements.len())
+            .unwrap_or(0);
+        let m = self.measurements.get(idx)?;
+        let scope = m
+            .scope_id
+            .and_then(|id| data.scope_by_id(id))
+            .or_else(|| data.primary_scope());
+        let catch = Self::catch_trace(data.traces, m);
+        let to_axis = |p: [f64; 2]| match scope {
+            Some(scope) => [
+                Self::plot_to_axis_value(&scope.x_axis, p[0]),
+                Self::plot_to_value_y(scope, catch, p[1]),
+            ],
+            None => p,
         };
-        Some([x_plot, y_plot])
+        let p1 = m.p1.map(to_axis);
+        let p2 = m.p2.map(to_axis);
+        let delta = match (p1, p2) {
+            (Some(a), Some(b)) => Some(PointDelta::between(a, b)),
+            _ => None,
+        };
+        Some(MeasurementResult {
+            measurement: m.name.clone(),
+            trace: m.catch_trace.clone(),
+            scope_id: m.scope_id,
+            p1,
+            p2,
+            delta,
+        })
     }
 
     fn plot_point_to_screen(scope: &ScopeData, point: [f64; 2]) -> Option<[f64; 2]> {
@@ -174,6 +345,15 @@ impl Panel for MeasurementPanel {
             }
         }
 
+        let step = std::mem::take(&mut data.pending_requests.cycle_measurement_trace);
+        if step != 0 {
+            self.cycle_catch_trace(data, step);
+        }
+
+        if let Some((p1, p2)) = data.pending_requests.set_measurement_points.take() {
+            self.set_points_from_request(data, p1, p2);
+        }
+
         // Tell each scope whether a measurement is active so clicking while
         // paused sets a clicked_point instead of resuming.
         let has_measurements = !self.measurements.is_empty();
@@ -235,7 +415,7 @@ impl Panel for MeasurementPanel {
                 let sel_data_points: Option<Vec<[f64; 2]>> =
                     if let Some(name) = &measurement.catch_trace {
                         data.traces
-                            .get_points_ref(name, scope.paused)
+                            .get_points_ref(name, scope.shows_snapshot())
                             .map(|v| v.iter().copied().collect())
                     } else {
                         None
@@ -243,16 +423,18 @@ impl Panel for MeasurementPanel {
 
                 let point = match (&measurement.catch_trace, &sel_data_points) {
                     (Some(name), Some(data_points)) if !data_points.is_empty() => {
-                        let off = data.traces.get_trace(name).map(|t| t.offset).unwrap_or(0.0);
+                        let tr = data.traces.get_trace(name);
                         let clicked_screen = scope
                             .clicked_screen_pos
                             .map(|screen| [f64::from(screen[0]), f64::from(screen[1])]);
                         let mut best_point = None;
                         let mut best_d2 = f64::INFINITY;
                         for p in data_points.iter() {
-                            let Some(candidate_plot) =
-                                Self::trace_point_to_plot_coords(scope, *p, off)
-                            else {
+                            let Some(candidate_plot) = Self::trace_point_to_plot_coords(
+                                scope,
+                                tr,
+                                [p[0], tr.map_or(p[1], |t| t.display_y(p[1]))],
+                            ) else {
                                 continue;
                             };
                             let (dx, dy) = if let (Some(clicked), Some(candidate_screen)) = (
@@ -294,21 +476,34 @@ impl Panel for MeasurementPanel {
 
                 measurement.scope_id = Some(scope.id);
 
+                // Append completed measurements to the session log.
+                if let (Some(a), Some(b)) = measurement.get_points() {
+                    let catch = Self::catch_trace(data.traces, measurement);
+                    let to_axis = |p: [f64; 2]| {
+                        [
+                            Self::plot_to_axis_value(&scope.x_axis, p[0]),
+                            Self::plot_to_value_y(scope, catch, p[1]),
+                        ]
+                    };
+                    self.history.push(MeasurementRecord::new(
+                        chrono::Local::now().timestamp_millis(),
+                        &measurement.name,
+                        measurement
+                            .catch_trace
+                            .as_ref()
+                            .map(|t| data.traces.display_name(t)),
+                        Some(scope.id),
+                        to_axis(a),
+                        to_axis(b),
+                    ));
+                }
+
                 // ── Emit MEASUREMENT_POINT event ──────────────────────────
                 if let Some(ctrl) = &data.event_ctrl {
                     let (p1, p2) = measurement.get_points();
-                    let (slope, distance, delta_x, delta_y) = if let (Some(a), Some(b)) = (p1, p2) {
-                        let dx = b[0] - a[0];
-                        let dy = b[1] - a[1];
-                        let slope = if dx.abs() > 1e-12 {
-                            Some(dy / dx)
-                        } else {
-                            None
-                        };
-                        let dist = (dx * dx + dy * dy).sqrt();
-                        (slope, Some(dist), Some(dx), Some(dy))
-                    } else {
-                        (None, None, None, None)
+                    let delta = match (p1, p2) {
+                        (Some(a), Some(b)) => Some(PointDelta::between(a, b)),
+                        _ => None,
                     };
                     let kind = if p1.is_some() && p2.is_some() {
                         crate::events::EventKind::MEASUREMENT_POINT
@@ -326,23 +521,25 @@ impl Panel for MeasurementPanel {
                         measurement_name: Some(measurement.name.clone()),
                         p1,
                         p2,
-                        delta_x,
-                        delta_y,
-                        slope,
-                        distance,
+                        delta_x: delta.map(|d| d.delta_x),
+                        delta_y: delta.map(|d| d.delta_y),
+                        slope: delta.and_then(|d| d.slope),
+                        distance: delta.map(|d| d.distance),
                         trace: measurement.catch_trace.clone(),
                     });
                     ctrl.emit_filtered(evt);
                 }
             }
         }
+
+        self.last_result = self.compute_result(data);
     }
 
     fn draw(
         &mut self,
         plot_ui: &mut egui_plot::PlotUi,
         scope: &crate::data::scope::ScopeData,
-        _traces: &crate::data::traces::TracesCollection,
+        traces: &crate::data::traces::TracesCollection,
     ) {
         // Measurement overlays
         let base_body = plot_ui.ctx().global_style().text_styles[&egui::TextStyle::Body].size;
@@ -368,6 +565,9 @@ impl Panel for MeasurementPanel {
                 (Color32::YELLOW, Color32::LIGHT_BLUE, Color32::LIGHT_GREEN)
             };
 
+            // Y readouts are on the axis of the snapping trace.
+            let catch = Self::catch_trace(traces, measurement);
+            let y_axis = Self::value_axis(scope, catch);
             let (x_min_lin, x_max_lin) = scope.x_axis.bounds;
             let (y_min_lin, y_max_lin) = scope.y_axis.bounds;
             let x_min_plot = if scope.x_axis.log_scale && x_min_lin > 0.0 {
@@ -449,15 +649,11 @@ impl Panel for MeasurementPanel {
                 } else {
                     p[0]
                 };
-                let y_lin = if scope.y_axis.log_scale {
-                    10f64.powf(p[1])
-                } else {
-                    p[1]
-                };
+                let y_lin = Self::plot_to_value_y(scope, catch, p[1]);
                 let x_range = (x_max_lin - x_min_lin).abs();
-                let y_range = (y_max_lin - y_min_lin).abs();
+                let y_range = (y_axis.bounds.1 - y_axis.bounds.0).abs();
                 let x_txt = scope.x_axis.format_value(x_lin, Some(x_range));
-                let y_txt = scope.y_axis.format_value(y_lin, Some(y_range));
+                let y_txt = y_axis.format_value(y_lin, Some(y_range));
                 let txt = format!("P1\nx = {}\ny = {}", x_txt, y_txt);
                 let style = egui::Style::default();
                 let mut job = egui::text::LayoutJob::default();
@@ -475,15 +671,11 @@ impl Panel for MeasurementPanel {
                 } else {
                     p[0]
                 };
-                let y_lin = if scope.y_axis.log_scale {
-                    10f64.powf(p[1])
-                } else {
-                    p[1]
-                };
+                let y_lin = Self::plot_to_value_y(scope, catch, p[1]);
                 let x_range = (x_max_lin - x_min_lin).abs();
-                let y_range = (y_max_lin - y_min_lin).abs();
+                let y_range = (y_axis.bounds.1 - y_axis.bounds.0).abs();
                 let x_txt = scope.x_axis.format_value(x_lin, Some(x_range));
-                let y_txt = scope.y_axis.format_value(y_lin, Some(y_range));
+                let y_txt = y_axis.format_value(y_lin, Some(y_range));
                 let txt = format!("P2\nx = {}\ny = {}", x_txt, y_txt);
                 let style = egui::Style::default();
                 let mut job = egui::text::LayoutJob::default();
@@ -505,30 +697,19 @@ impl Panel for MeasurementPanel {
                 } else {
                     p2[0]
                 };
-                let y1_lin = if scope.y_axis.log_scale {
-                    10f64.powf(p1[1])
-                } else {
-                    p1[1]
-                };
-                let y2_lin = if scope.y_axis.log_scale {
-                    10f64.powf(p2[1])
-                } else {
-                    p2[1]
-                };
-                let dx_lin = x2_lin - x1_lin;
-                let dy_lin = y2_lin - y1_lin;
-                let slope = if dx_lin.abs() > 1e-12 {
-                    dy_lin / dx_lin
-                } else {
-                    f64::INFINITY
-                };
+                let y1_lin = Self::plot_to_value_y(scope, catch, p1[1]);
+                let y2_lin = Self::plot_to_value_y(scope, catch, p2[1]);
+                let delta = PointDelta::between([x1_lin, y1_lin], [x2_lin, y2_lin]);
+                let (dx_lin, dy_lin) = (delta.delta_x, delta.delta_y);
+                let slope = delta.slope.unwrap_or(f64::INFINITY);
                 let mid = [(p1[0] + p2[0]) * 0.5, (p1[1] + p2[1]) * 0.5];
-                let y_range = (y_max_lin - y_min_lin).abs();
+                let y_range = (y_axis.bounds.1 - y_axis.bounds.0).abs();
                 let txt = format!(
                     "{}:\n{}",
                     name,
                     self.format_delta_summary(
                         &scope,
+                        y_axis,
                         dx_lin,
                         dy_lin,
                         slope,
@@ -609,23 +790,26 @@ impl Panel for MeasurementPanel {
                     self.selected_measurement = Some(i);
                 }
 
-                let catch_trace_names: Vec<String> =
-                    data.traces.keys().map(|name| name.0.clone()).collect();
+                let catch_trace_names: Vec<(String, String)> = data
+                    .traces
+                    .traces_iter()
+                    .map(|(name, tr)| (name.0.clone(), tr.label(name).to_string()))
+                    .collect();
                 let mut selected_trace_name = m.catch_trace.clone();
                 let old_selected = m.catch_trace.clone();
                 let selected_text = match &selected_trace_name {
-                    Some(t) => t.0.clone(),
+                    Some(t) => data.traces.display_name(t),
                     None => "None".to_string(),
                 };
                 egui::ComboBox::from_id_salt(format!("catch_trace_{}", i))
                     .selected_text(selected_text)
                     .show_ui(ui, |ui| {
                         ui.selectable_value(&mut selected_trace_name, None, "None");
-                        for name in &catch_trace_names {
+                        for (name, label) in &catch_trace_names {
                             ui.selectable_value(
                                 &mut selected_trace_name,
                                 Some(crate::TraceRef(name.clone())),
-                                name.clone(),
+                                label.clone(),
                             );
                         }
                     });
@@ -675,8 +859,10 @@ impl Panel for MeasurementPanel {
 
             // Show values for P1/P2 and delta if available
             let (p1, p2) = self.measurements[i].get_points();
+            let catch = Self::catch_trace(data.traces, &self.measurements[i]);
+            let y_axis = Self::value_axis(scope, catch);
             let x_range = (scope.x_axis.bounds.1 - scope.x_axis.bounds.0).abs();
-            let y_range = (scope.y_axis.bounds.1 - scope.y_axis.bounds.0).abs();
+            let y_range = (y_axis.bounds.1 - y_axis.bounds.0).abs();
             let to_axis_value = |axis: &AxisSettings, v_plot: f64| -> f64 {
                 if axis.log_scale && v_plot > 0.0 {
                     10f64.powf(v_plot)
@@ -687,11 +873,11 @@ impl Panel for MeasurementPanel {
             ui.horizontal_wrapped(|ui| {
                 let mut p1_label = if let Some(p) = p1 {
                     let x_lin = to_axis_value(&scope.x_axis, p[0]);
-                    let y_lin = to_axis_value(&scope.y_axis, p[1]);
+                    let y_lin = Self::plot_to_value_y(scope, catch, p[1]);
                     let p1_text = format!(
                         "P1: x={}  y={}",
                         scope.x_axis.format_value(x_lin, Some(x_range)),
-                        scope.y_axis.format_value(y_lin, Some(y_range))
+                        y_axis.format_value(y_lin, Some(y_range))
                     );
                     let resp = ui.colored_label(Color32::YELLOW, p1_text.clone());
                     if resp.double_clicked() {
@@ -710,11 +896,11 @@ impl Panel for MeasurementPanel {
 
                 let mut p2_label = if let Some(p) = p2 {
                     let x_lin = to_axis_value(&scope.x_axis, p[0]);
-                    let y_lin = to_axis_value(&scope.y_axis, p[1]);
+                    let y_lin = Self::plot_to_value_y(scope, catch, p[1]);
                     let p2_text = format!(
                         "P2: x={}  y={}",
                         scope.x_axis.format_value(x_lin, Some(x_range)),
-                        scope.y_axis.format_value(y_lin, Some(y_range))
+                        y_axis.format_value(y_lin, Some(y_range))
                     );
                     let resp = ui.colored_label(Color32::LIGHT_BLUE, p2_text.clone());
                     if resp.double_clicked() {
@@ -735,20 +921,57 @@ impl Panel for MeasurementPanel {
                     self.hovered_measurement = Some(i);
                 };
             });
+            if let Some(name) = self.measurements[i].catch_trace.clone() {
+                let visible = Self::visible_points(data.traces, scope, &name);
+                let m = &mut self.measurements[i];
+                let freq = crossing_frequency(
+                    visible.iter().copied(),
+                    m.crossing_level,
+                    m.crossing_hysteresis,
+                );
+                ui.horizontal(|ui| {
+                    ui.label("Crossings of").on_hover_text(
+                        "Estimate frequency from level crossings in the visible window",
+                    );
+                    ui.add(egui::DragValue::new(&mut m.crossing_level).speed(0.01));
+                    ui.label("±");
+                    ui.add(
+                        egui::DragValue::new(&mut m.crossing_hysteresis)
+                            .speed(0.01)
+                            .range(0.0..=f64::INFINITY),
+                    )
+                    .on_hover_text("Hysteresis band: noise within it does not count as a crossing");
+                    match freq {
+                        Some(f) => {
+                            let txt = format!("f ≈ {} Hz", Self::format_frequency(f));
+                            if ui.label(txt.clone()).double_clicked() {
+                                ui.ctx().copy_text(txt);
+                            }
+                        }
+                        None => {
+                            ui.label("f: –");
+                        }
+                    }
+                });
+                ui.checkbox(&mut m.show_stats, "Measurements")
+                    .on_hover_text(
+                        "Vpp, min, max, mean, RMS and frequency over the visible window",
+                    );
+                if m.show_stats {
+                    self.render_stats(ui, i, y_axis, &visible, y_range);
+                }
+            }
+
             if let (Some(p1), Some(p2)) = (p1, p2) {
                 let x1_lin: f64 = to_axis_value(&scope.x_axis, p1[0]);
                 let x2_lin = to_axis_value(&scope.x_axis, p2[0]);
-                let y1_lin = to_axis_value(&scope.y_axis, p1[1]);
-                let y2_lin = to_axis_value(&scope.y_axis, p2[1]);
-                let dx_lin = x2_lin - x1_lin;
-                let dy_lin = y2_lin - y1_lin;
-                let slope_lin = if dx_lin.abs() > 1e-12 {
-                    dy_lin / dx_lin
-                } else {
-                    f64::INFINITY
-                };
+                let y1_lin = Self::plot_to_value_y(scope, catch, p1[1]);
+                let y2_lin = Self::plot_to_value_y(scope, catch, p2[1]);
+                let delta = PointDelta::between([x1_lin, y1_lin], [x2_lin, y2_lin]);
+                let (dx_lin, dy_lin) = (delta.delta_x, delta.delta_y);
+                let slope_lin = delta.slope.unwrap_or(f64::INFINITY);
                 let diff_txt = self.format_delta_summary(
-                    &scope, dx_lin, dy_lin, slope_lin, x_range, y_range, false,
+                    &scope, y_axis, dx_lin, dy_lin, slope_lin, x_range, y_range, false,
                 );
                 let mut diff_label = ui.colored_label(Color32::LIGHT_GREEN, diff_txt.clone());
                 diff_label = diff_label.on_hover_text("Delta between P1 and P2");
@@ -762,6 +985,46 @@ impl Panel for MeasurementPanel {
                 if diff_label.double_clicked() {
                     ui.ctx().copy_text(diff_txt.clone());
                 }
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(format!("{} Cursor values", FILE_CSV.as_str()))
+                        .on_hover_text(
+                            "Export every visible trace's value at P1 and P2 (and the delta) as CSV",
+                        )
+                        .clicked()
+                    {
+                        let mode = if self.interpolate_cursor_values {
+                            SampleMode::Linear
+                        } else {
+                            SampleMode::Nearest
+                        };
+                        let rows = Self::cursor_value_rows(
+                            scope,
+                            data,
+                            x1_lin,
+                            x2_lin,
+                            mode,
+                            self.join_tolerance,
+                        );
+                        if let Some(path) = rfd::FileDialog::new()
+                            .set_file_name("cursor_values.csv")
+                            .add_filter("CSV", &["csv"])
+                            .save_file()
+                        {
+                            let res = std::fs::File::create(&path).and_then(|f| {
+                                export::write_cursor_values_csv(std::io::BufWriter::new(f), &rows)
+                            });
+                            if let Err(e) = res {
+                                eprintln!("Failed to export cursor values: {e}");
+                            }
+                        }
+                    }
+                    ui.checkbox(&mut self.interpolate_cursor_values, "Interpolate")
+                        .on_hover_text(
+                            "Linearly interpolate between samples; otherwise use the nearest \
+                             exported row (Export panel join tolerance)",
+                        );
+                });
             }
 
             if remove_this {
@@ -773,6 +1036,10 @@ impl Panel for MeasurementPanel {
                 break; // restart loop due to changed indices
             }
         }
+
+        ui.add_space(6.0);
+        ui.separator();
+        self.render_history(ui);
     }
 
     fn settings_snapshot(&self, _data: &LivePlotData<'_>) -> Option<String> {
@@ -782,6 +1049,37 @@ impl Panel for MeasurementPanel {
 }
 
 impl MeasurementPanel {
+    /// Samples of `name` inside `scope`'s X window, sign applied; the input
+    /// of the crossing frequency and the automatic measurements.
+    pub(crate) fn visible_points(
+        traces: &TracesCollection,
+        scope: &ScopeData,
+        name: &TraceRef,
+    ) -> Vec<[f64; 2]> {
+        // X bounds are in data units, also on a log axis.
+        let (x0, x1) = scope.x_axis.bounds;
+        traces
+            .get_trace(name)
+            .zip(traces.get_points_ref(name, scope.shows_snapshot()))
+            .map(|(tr, pts)| {
+                pts.iter()
+                    .filter(|p| p[0] >= x0 && p[0] <= x1)
+                    .map(|p| [p[0], tr.signed(p[1])])
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Crossing hysteresis of the first measurement following `trace`, 0 if
+    /// none does.
+    pub(crate) fn hysteresis_for(&self, trace: &TraceRef) -> f64 {
+        self.measurements
+            .iter()
+            .find(|m| m.catch_trace.as_ref() == Some(trace))
+            .map(|m| m.crossing_hysteresis)
+            .unwrap_or(0.0)
+    }
+
     pub fn clear_all(&mut self) {
         for m in &mut self.measurements {
             m.clear();
@@ -796,6 +1094,126 @@ impl MeasurementPanel {
         self.selected_measurement
     }
 
+    /// Points and deltas of the selected (or first) measurement, in axis
+    /// units, as of the last data update.
+    pub fn current_result(&self) -> Option<&MeasurementResult> {
+        self.last_result.as_ref()
+    }
+
+    /// Session log of completed measurements, oldest first.
+    pub fn history(&self) -> &[MeasurementRecord] {
+        &self.history
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    pub fn save_measurement_log_csv(
+        &self,
+        path: &std::path::Path,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let f = std::fs::File::create(path)?;
+        write_measurement_log_csv(&self.history, std::io::BufWriter::new(f))?;
+        Ok(())
+    }
+
+    /// Sample the scope's visible traces at the cursor x-positions (axis units).
+    fn cursor_value_rows(
+        scope: &ScopeData,
+        data: &LivePlotData<'_>,
+        x1: f64,
+        x2: f64,
+        mode: SampleMode,
+        tol: f64,
+    ) -> Vec<export::CursorValueRow> {
+        let mut order = Vec::new();
+        let mut series = std::collections::HashMap::new();
+        for name in scope.trace_order.iter() {
+            let Some(tr) = data.traces.get_trace(name) else {
+                continue;
+            };
+            if !tr.look.visible {
+                continue;
+            }
+            if let Some(pts) = data.traces.get_points_ref(name, scope.shows_snapshot()) {
+                series.insert(
+                    name.clone(),
+                    pts.iter().map(|p| [p[0], tr.signed(p[1])]).collect(),
+                );
+                order.push(name.clone());
+            }
+        }
+        export::sample_series_at(&order, &series, x1, x2, mode, tol)
+    }
+
+    fn render_history(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(format!("History ({})", self.history.len()))
+            .id_salt("measurement_history")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !self.history.is_empty(),
+                            egui::Button::new(format!("{} Export to CSV", FILE_TEXT.as_str())),
+                        )
+                        .clicked()
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .set_file_name("measurements.csv")
+                            .add_filter("CSV", &["csv"])
+                            .save_file()
+                        {
+                            if let Err(e) = self.save_measurement_log_csv(&path) {
+                                eprintln!("Failed to export measurement log: {e}");
+                            }
+                        }
+                    }
+                    if ui
+                        .button(format!("{} Clear", BROOM.as_str()))
+                        .on_hover_text("Delete all entries from the measurement log")
+                        .clicked()
+                    {
+                        self.history.clear();
+                    }
+                });
+                if self.history.is_empty() {
+                    ui.weak("Completed measurements (P1 and P2 set) are logged here.");
+                    return;
+                }
+                egui::ScrollArea::vertical()
+                    .id_salt("measurement_history_scroll")
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for r in self.history.iter().rev() {
+                            let time = chrono::DateTime::from_timestamp_millis(r.timestamp_ms)
+                                .map(|dt| {
+                                    dt.with_timezone(&chrono::Local)
+                                        .format("%H:%M:%S")
+                                        .to_string()
+                                })
+                                .unwrap_or_default();
+                            let slope = r
+                                .slope
+                                .map(|s| format!("{:.4e}", s))
+                                .unwrap_or_else(|| "∞".to_string());
+                            ui.label(format!(
+                                "{}  {}{}  Δx={:.6}  Δy={:.6}  slope={}",
+                                time,
+                                r.measurement,
+                                r.trace
+                                    .as_ref()
+                                    .map(|t| format!(" ({})", t))
+                                    .unwrap_or_default(),
+                                r.delta_x,
+                                r.delta_y,
+                                slope
+                            ));
+                        }
+                    });
+            });
+    }
+
     pub fn restore_measurements(
         &mut self,
         measurements: Vec<Measurement>,
@@ -809,6 +1227,57 @@ impl MeasurementPanel {
         self.hovered_measurement = None;
     }
 
+    /// Readout grid of the automatic measurements of one measurement's trace.
+    fn render_stats(
+        &self,
+        ui: &mut egui::Ui,
+        i: usize,
+        y_axis: &AxisSettings,
+        visible: &[[f64; 2]],
+        y_range: f64,
+    ) {
+        let m = &self.measurements[i];
+        let stats = SignalStats::compute(visible.iter().copied(), m.crossing_hysteresis);
+        let value = |v: Option<f64>| {
+            v.map(|v| y_axis.format_value(v, Some(y_range)))
+                .unwrap_or_else(|| "—".to_string())
+        };
+        let rows = [
+            ("Vpp", value(stats.map(|s| s.peak_to_peak))),
+            ("Min", value(stats.map(|s| s.min))),
+            ("Max", value(stats.map(|s| s.max))),
+            ("Mean", value(stats.map(|s| s.mean))),
+            ("RMS", value(stats.map(|s| s.rms))),
+            (
+                "Freq",
+                stats
+                    .and_then(|s| s.frequency)
+                    .map(|f| format!("{} Hz", Self::format_frequency(f)))
+                    .unwrap_or_else(|| "—".to_string()),
+            ),
+        ];
+        egui::Grid::new(format!("measurement_stats_{}", i))
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (label, txt) in rows {
+                    ui.label(label);
+                    if ui.label(txt.clone()).double_clicked() {
+                        ui.ctx().copy_text(txt);
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Format a frequency in Hz: one decimal from 100 Hz up, four below.
+    fn format_frequency(f: f64) -> String {
+        if f >= 100.0 {
+            format!("{:.1}", f)
+        } else {
+            format!("{:.4}", f)
+        }
+    }
+
     /// Format Δx/Δy and slope consistently for UI and plot overlays.
     fn choose_time_unit_and_scale(delta_secs: f64) -> (&'static str, f64, usize) {
         // Return (unit_label, scale_multiplier, decimals)
@@ -827,6 +1296,7 @@ impl MeasurementPanel {
     fn format_delta_summary(
         &self,
         scope: &ScopeData,
+        y_axis: &AxisSettings,
         dx_lin: f64,
         dy_lin: f64,
         slope: f64,
@@ -854,7 +1324,7 @@ impl MeasurementPanel {
         };
 
         // Δy formatting
-        let (dy_txt, dy_unit_opt, y_scale) = match scope.y_axis.axis_type {
+        let (dy_txt, dy_unit_opt, y_scale) = match y_axis.axis_type {
             crate::data::scope::AxisType::Time(_) => {
                 let (u, scale, dec) = Self::choose_time_unit_and_scale(dy_lin);
                 let val = dy_lin * scale;
@@ -866,8 +1336,8 @@ impl MeasurementPanel {
                 (s + " " + u, Some(u.to_string()), scale)
             }
             _ => (
-                scope.y_axis.format_value(dy_lin, Some(y_range)),
-                scope.y_axis.get_unit(),
+                y_axis.format_value(dy_lin, Some(y_range)),
+                y_axis.get_unit(),
                 1.0,
             ),
         };
@@ -875,7 +1345,7 @@ impl MeasurementPanel {
         if slope.is_finite() {
             // Compute displayed slope adjusting for unit scales: slope_display = slope * (y_scale / x_scale)
             let slope_disp = slope * (y_scale / x_scale);
-            let num = scope.y_axis.format_value(slope_disp, None);
+            let num = y_axis.format_value(slope_disp, None);
 
             // Build unit string from chosen units
             let unit_str = match (dy_unit_opt.as_deref(), dx_unit_opt.as_deref()) {
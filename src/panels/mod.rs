@@ -1,4 +1,5 @@
 pub mod color_scheme_ui;
+pub mod data_inspector_ui;
 pub mod edge_icons;
 pub mod export_ui;
 pub mod hotkeys_ui;
@@ -17,6 +18,7 @@ pub mod triggers_ui;
 pub mod fft_ui;
 
 pub use color_scheme_ui::ColorSchemePanel;
+pub use data_inspector_ui::DataInspectorPanel;
 pub use export_ui::ExportPanel;
 pub use hotkeys_ui::HotkeysPanel;
 pub use liveplot_ui::LiveplotPanel;
@@ -140,6 +140,16 @@ impl ScopeSettingsUiPanel {
             ui.add_enabled_ui(scope.show_legend, |ui| {
                 ui.checkbox(&mut scope.show_info_in_legend, "Info")
                     .on_hover_text("Append each trace's info text to its legend label");
+                ui.add(
+                    egui::DragValue::new(&mut scope.legend_stats_secs)
+                        .range(0.0..=3600.0)
+                        .speed(0.1)
+                        .prefix("Stats ")
+                        .suffix(" s"),
+                )
+                .on_hover_text(
+                    "Show mean ± σ over the last N seconds next to each trace (0 = off)",
+                );
 
                 let positions = [
                     (LegendPosition::LeftTop, "Left Top"),
@@ -165,6 +175,24 @@ impl ScopeSettingsUiPanel {
             });
         });
 
+        ui.horizontal(|ui| {
+            ui.label("Persistence");
+            if ui
+                .add(
+                    egui::Slider::new(&mut scope.persistence_secs, 0.0..=10.0)
+                        .suffix(" s")
+                        .clamping(egui::SliderClamping::Never),
+                )
+                .on_hover_text(
+                    "Keep recently drawn lines on screen, fading out over this time (0 = off)",
+                )
+                .changed()
+            {
+                scope.persistence_secs = scope.persistence_secs.max(0.0);
+                resp.scope_changed = true;
+            }
+        });
+
         ui.horizontal(|ui| {
             if ui
                 .small_button(PALETTE.as_str())
@@ -297,6 +325,24 @@ impl ScopeSettingsUiPanel {
         scope.trace_order = ordered;
     }
 
+    /// Editor for [`ScopeData::xy_tolerance`]; returns whether it changed.
+    fn render_xy_tolerance(ui: &mut Ui, scope: &mut ScopeData) -> bool {
+        ui.horizontal(|ui| {
+            ui.label("Pairing tolerance (s)");
+            ui.add(
+                egui::DragValue::new(&mut scope.xy_tolerance)
+                    .speed(1e-4)
+                    .range(0.0..=f64::INFINITY),
+            )
+            .on_hover_text(
+                "Pair X and Y samples up to this far apart in time, each with its \
+                 nearest counterpart; raise it for traces sampled at different rates",
+            )
+            .changed()
+        })
+        .inner
+    }
+
     fn render_drop_slot(
         ui: &mut Ui,
         id_salt: impl Hash + std::fmt::Debug,
@@ -425,7 +471,7 @@ impl ScopeSettingsUiPanel {
 
                                         let resp = ui
                                             .add(
-                                                egui::Label::new(t.0.clone())
+                                                egui::Label::new(traces_collection.display_name(t))
                                                     .truncate()
                                                     .show_tooltip_when_elided(true),
                                             )
@@ -585,6 +631,10 @@ impl ScopeSettingsUiPanel {
 
         match scope.scope_type {
             ScopeType::TimeScope => {
+                // A trace used as X is paired with the others like an XY scope.
+                if scope.x_source.is_some() {
+                    scope_changed |= Self::render_xy_tolerance(ui, scope);
+                }
                 let mut tmp: Vec<Option<TraceRef>> =
                     scope.trace_order.iter().cloned().map(Some).collect();
 
@@ -645,7 +695,9 @@ impl ScopeSettingsUiPanel {
                 for t in tmp.iter().flatten() {
                     if let Some(&color) = edited_colors.get(t) {
                         if let Some(tr_state) = traces_collection.get_trace_mut(t) {
-                            tr_state.look.color = color;
+                            if tr_state.look.color != color {
+                                tr_state.look.set_custom_color(color);
+                            }
                         }
                     }
                 }
@@ -671,6 +723,7 @@ impl ScopeSettingsUiPanel {
                 }
             }
             ScopeType::XYScope => {
+                scope_changed |= Self::render_xy_tolerance(ui, scope);
                 let target_w = ui.available_width();
 
                 // Keep your per-column lists, but make them actually apply changes back.
@@ -833,7 +886,7 @@ impl ScopeSettingsUiPanel {
             }
         }
 
-        resp.scope_changed = scope_changed;
+        resp.scope_changed |= scope_changed;
         resp
     }
 }
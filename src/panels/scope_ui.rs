@@ -1,16 +1,31 @@
 use egui::{Color32, Ui};
-use egui_plot::{Legend, Line, Plot, PlotMemory, Points};
+use egui_plot::{
+    Arrows, AxisHints, FilledArea, HLine, HPlacement, Legend, Line, LineStyle, Plot, PlotMemory,
+    Points, Polygon,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+use crate::data::scope::division_step;
+use crate::data::scope::log_bounds;
+use crate::data::scope::pair_by_time;
+use crate::data::scope::Afterglow;
+use crate::data::scope::AxisSettings;
 use crate::data::scope::AxisType;
+use crate::data::scope::LegendClick;
 use crate::data::scope::LegendPosition;
+use crate::data::scope::ResumeBehavior;
 use crate::data::scope::ScopeData;
 use crate::data::scope::ScopeType;
+use crate::data::trace_look::ErrorStyle;
+use crate::data::traces::DecimationMethod;
+use crate::data::traces::TraceData;
 use crate::data::traces::TraceRef;
 use crate::data::traces::TracesCollection;
 use crate::events::EventController;
 use egui_phosphor_icons::icons::{
-    ARROWS_DOWN_UP, ARROWS_LEFT_RIGHT, IMAGE, MAGNIFYING_GLASS, PAUSE, PLAY,
+    ARROWS_DOWN_UP, ARROWS_LEFT_RIGHT, IMAGE, LOCK_SIMPLE, LOCK_SIMPLE_OPEN, MAGNIFYING_GLASS,
+    PAUSE, PLAY,
 };
 
 /// Lightweight snapshot of scope settings for change detection (undo tracking).
@@ -20,9 +35,27 @@ use egui_phosphor_icons::icons::{
 #[derive(Clone, PartialEq)]
 struct ScopeSettingsSnapshot {
     show_grid: bool,
+    show_div_readout: bool,
+    show_cursor_readout: bool,
+    hover_scroll_lock: bool,
+    x_smoothing_secs: f64,
+    resume_behavior: ResumeBehavior,
+    decimation: DecimationMethod,
+    x_source: Option<TraceRef>,
+    minimal_axes: bool,
+    show_zero_line: bool,
+    zero_line_y: f64,
+    y_margin: f64,
+    threshold_backdrop: bool,
+    snapshot_on_pause: bool,
+    max_drawn_traces: usize,
+    max_points: usize,
+    pinned_traces: Vec<TraceRef>,
     show_legend: bool,
     show_info_in_legend: bool,
+    legend_stats_secs: f64,
     legend_position: LegendPosition,
+    legend_click: LegendClick,
     x_auto_fit: bool,
     x_keep_max_fit: bool,
     x_log_scale: bool,
@@ -39,6 +72,13 @@ struct ScopeSettingsSnapshot {
     y_value_decimals: usize,
     y_axis_type: AxisType,
     y_unit: Option<String>,
+    /// Right Y axis auto-fit flag; `None` when the scope has no right axis.
+    y2_auto_fit: Option<bool>,
+    y2_show_label: bool,
+    y2_name: Option<String>,
+    y2_unit: Option<String>,
+    /// Only set when the right axis exists and its auto-fit is OFF.
+    y2_bounds: Option<(f64, f64)>,
     zoom_mode: ZoomMode,
     paused: bool,
     time_window: f64,
@@ -66,11 +106,30 @@ impl ScopePanel {
         } else {
             None
         };
+        let y2 = self.data.y_axis2.as_ref();
         ScopeSettingsSnapshot {
             show_grid: self.data.show_grid,
+            show_div_readout: self.data.show_div_readout,
+            show_cursor_readout: self.data.show_cursor_readout,
+            hover_scroll_lock: self.data.hover_scroll_lock,
+            x_smoothing_secs: self.data.x_smoothing_secs,
+            resume_behavior: self.data.resume_behavior,
+            decimation: self.data.decimation,
+            x_source: self.data.x_source.clone(),
+            minimal_axes: self.data.minimal_axes,
+            show_zero_line: self.data.show_zero_line,
+            zero_line_y: self.data.zero_line_y,
+            y_margin: self.data.y_margin,
+            threshold_backdrop: self.data.threshold_backdrop,
+            snapshot_on_pause: self.data.snapshot_on_pause,
+            max_drawn_traces: self.data.max_drawn_traces,
+            max_points: self.data.max_points,
+            pinned_traces: self.data.pinned_traces.clone(),
             show_legend: self.data.show_legend,
             show_info_in_legend: self.data.show_info_in_legend,
+            legend_stats_secs: self.data.legend_stats_secs,
             legend_position: self.data.legend_position,
+            legend_click: self.data.legend_click,
             x_auto_fit: self.data.x_axis.auto_fit,
             x_keep_max_fit: self.data.x_axis.keep_max_fit,
             x_log_scale: self.data.x_axis.log_scale,
@@ -87,6 +146,11 @@ impl ScopePanel {
             y_value_decimals: self.data.y_axis.value_decimals,
             y_axis_type: self.data.y_axis.axis_type.clone(),
             y_unit: self.data.y_axis.get_unit().map(|u| u.to_string()),
+            y2_auto_fit: y2.map(|a| a.auto_fit),
+            y2_show_label: y2.is_some_and(|a| a.show_label),
+            y2_name: y2.and_then(|a| a.name.clone()),
+            y2_unit: y2.and_then(|a| a.get_unit().map(|u| u.to_string())),
+            y2_bounds: y2.filter(|a| !a.auto_fit).map(|a| a.bounds),
             zoom_mode: self.zoom_mode,
             paused: self.data.paused,
             time_window: self.data.time_window,
@@ -150,6 +214,16 @@ pub struct ScopePanel {
 
     /// Screen-space start position for custom box zoom (right-click drag).
     box_zoom_start: Option<egui::Pos2>,
+
+    /// X-bounds pinned while the pointer hovers the plot (hover scroll lock).
+    hover_locked_x_bounds: Option<(f64, f64)>,
+
+    /// Set by the parent when the interaction lock is forced by the config;
+    /// hides the lock toggle.
+    pub(crate) interaction_lock_fixed: bool,
+
+    /// Recently drawn frames per legend label for the persistence display.
+    afterglow: HashMap<String, Afterglow>,
 }
 
 impl Default for ScopePanel {
@@ -175,6 +249,9 @@ impl Default for ScopePanel {
             pending_view_change: None,
             settings_changed: false,
             box_zoom_start: None,
+            hover_locked_x_bounds: None,
+            interaction_lock_fixed: false,
+            afterglow: HashMap::new(),
         }
     }
 }
@@ -244,9 +321,21 @@ impl ScopePanel {
         };
 
         match (self.data.scope_type, is_x) {
-            (ScopeType::TimeScope, true) => return vec!["Time".to_string()],
+            (ScopeType::TimeScope, true) => {
+                return vec![self
+                    .data
+                    .x_source
+                    .as_ref()
+                    .map_or("Time".to_string(), |s| s.0.clone())];
+            }
             (ScopeType::TimeScope, false) => {
                 for trace in &self.data.trace_order {
+                    if traces
+                        .get_trace(trace)
+                        .is_some_and(|tr| self.data.on_right_axis(tr))
+                    {
+                        continue;
+                    }
                     push_name(trace);
                 }
             }
@@ -300,6 +389,36 @@ impl ScopePanel {
         names
     }
 
+    /// Ids of the legend entries, matching the labels the plot draws.
+    fn legend_item_ids(&self, traces: &TracesCollection) -> Vec<egui::Id> {
+        if self.data.scope_type == ScopeType::XYScope && !self.data.xy_pairs.is_empty() {
+            let mut ids = Vec::new();
+            for (x_name, y_name, _pair_look) in self.data.xy_pairs.iter() {
+                let (Some(x_name), Some(y_name)) = (x_name.as_ref(), y_name.as_ref()) else {
+                    continue;
+                };
+                let (Some(x_tr), Some(y_tr)) = (traces.get_trace(x_name), traces.get_trace(y_name))
+                else {
+                    continue;
+                };
+                let label =
+                    xy_legend_label(self.data.show_info_in_legend, x_tr, x_name, y_tr, y_name);
+                ids.push(egui::Id::new(label));
+            }
+            ids
+        } else {
+            self.data
+                .trace_order
+                .iter()
+                .filter_map(|name| {
+                    let tr = traces.get_trace(name)?;
+                    let label = legend_label(self.data.show_info_in_legend, tr, name);
+                    Some(egui::Id::new(label))
+                })
+                .collect()
+        }
+    }
+
     fn axis_label_text(&self, traces: &TracesCollection, is_x: bool) -> Option<String> {
         let show_label = if is_x {
             self.data.x_axis.show_label
@@ -334,6 +453,118 @@ impl ScopePanel {
         }
     }
 
+    /// Label of the right Y axis: its custom name, else the visible traces
+    /// drawn against it.  `None` without an active right axis or when its
+    /// label is hidden.
+    fn right_axis_label_text(&self, traces: &TracesCollection) -> Option<String> {
+        let axis = self.data.active_y_axis2()?;
+        if !axis.show_label {
+            return None;
+        }
+        if axis.name.is_some() {
+            return axis.name.clone();
+        }
+        let names: Vec<&str> = self
+            .data
+            .trace_order
+            .iter()
+            .filter(|name| {
+                traces
+                    .get_trace(name)
+                    .is_some_and(|tr| tr.look.visible && self.data.on_right_axis(tr))
+            })
+            .map(|name| name.0.as_str())
+            .collect();
+        if names.is_empty() {
+            Some("Y2".to_string())
+        } else {
+            Some(names.join(", "))
+        }
+    }
+
+    /// Controls of the secondary (right) Y axis, shown in the Y-axis menu.
+    fn render_right_axis_menu(&mut self, ui: &mut Ui, traces: &TracesCollection) {
+        let mut enabled = self.data.y_axis2.is_some();
+        if ui
+            .checkbox(&mut enabled, "Show right Y axis")
+            .on_hover_text(
+                "Second Y axis with its own scale and unit. Pick the axis of each trace in the traces panel. Time scopes only.",
+            )
+            .changed()
+        {
+            self.data.y_axis2 = enabled.then(|| AxisSettings {
+                auto_fit: true,
+                show_label: self.data.y_axis.show_label,
+                ..AxisSettings::default()
+            });
+            self.data.fit_y2_bounds(traces, false);
+        }
+        let Some(axis) = self.data.y_axis2.as_mut() else {
+            return;
+        };
+        ui.separator();
+        ui.horizontal(|ui| {
+            let (mut lo, mut hi) = axis.bounds;
+            let range = hi - lo;
+            ui.label("Min:");
+            let r1 = ui.add(
+                egui::DragValue::new(&mut lo)
+                    .speed(0.1)
+                    .custom_formatter(|n, _| axis.format_value(n, Some(range))),
+            );
+            ui.label("Max:");
+            let r2 = ui.add(
+                egui::DragValue::new(&mut hi)
+                    .speed(0.1)
+                    .custom_formatter(|n, _| axis.format_value(n, Some(range))),
+            );
+            if (r1.changed() || r2.changed()) && lo < hi {
+                axis.bounds = (lo, hi);
+                axis.auto_fit = false;
+            }
+        });
+        ui.checkbox(&mut axis.auto_fit, "Auto Fit");
+        ui.add_enabled(
+            axis.auto_fit,
+            egui::Checkbox::new(&mut axis.keep_max_fit, "Only expand"),
+        );
+        ui.horizontal(|ui| {
+            ui.label("Unit:");
+            let mut unit = axis.get_unit().unwrap_or_default();
+            if ui
+                .add(egui::TextEdit::singleline(&mut unit).desired_width(80.0))
+                .changed()
+            {
+                axis.set_unit(if unit.trim().is_empty() {
+                    None
+                } else {
+                    Some(unit)
+                });
+            }
+        });
+        ui.checkbox(&mut axis.show_label, "Show Label");
+        ui.horizontal(|ui| {
+            ui.label("Label:");
+            let mut label = axis.name.clone().unwrap_or_default();
+            if ui
+                .add_enabled(
+                    axis.show_label,
+                    egui::TextEdit::singleline(&mut label)
+                        .desired_width(120.0)
+                        .hint_text("auto"),
+                )
+                .on_hover_text("Leave empty to list the traces on the right axis")
+                .changed()
+            {
+                let trimmed = label.trim();
+                axis.name = (!trimmed.is_empty()).then(|| trimmed.to_string());
+            }
+        });
+        if ui.button("Fit").clicked() {
+            self.data.fit_y2_bounds(traces, false);
+        }
+    }
+
     fn capture_clicked_plot_point(&mut self, plot_response: &egui_plot::PlotResponse<bool>) {
         let Some(screen_pos) = plot_response.response.interact_pointer_pos() else {
             return;
@@ -391,6 +622,41 @@ impl ScopePanel {
         self.data.pause_on_click = enabled;
     }
 
+    /// Returns whether axis labels, ticks and legend are suppressed.
+    pub fn minimal_axes(&self) -> bool {
+        self.data.minimal_axes
+    }
+
+    /// Suppress axis labels, ticks and legend so only the traces are drawn.
+    pub fn set_minimal_axes(&mut self, enabled: bool) {
+        self.data.minimal_axes = enabled;
+    }
+
+    /// Show a faint horizontal reference line at `y` (in displayed units), or
+    /// hide it with `None`.
+    pub fn set_reference_line(&mut self, y: Option<f64>) {
+        self.data.show_zero_line = y.is_some();
+        if let Some(y) = y {
+            self.data.zero_line_y = y;
+        }
+    }
+
+    /// Limit drawing to `max` traces (`0` = all), always keeping `pinned`.
+    pub fn set_trace_draw_limit(&mut self, max: usize, pinned: Vec<TraceRef>) {
+        self.data.max_drawn_traces = max;
+        self.data.pinned_traces = pinned;
+    }
+
+    /// Returns whether follow-latest is suspended while the plot is hovered.
+    pub fn hover_scroll_lock(&self) -> bool {
+        self.data.hover_scroll_lock
+    }
+
+    /// Enable or disable suspending follow-latest while the plot is hovered.
+    pub fn set_hover_scroll_lock(&mut self, enabled: bool) {
+        self.data.hover_scroll_lock = enabled;
+    }
+
     pub fn take_screenshot_request(&mut self) -> bool {
         std::mem::take(&mut self.request_screenshot)
     }
@@ -418,6 +684,208 @@ impl ScopePanel {
         {
             ui.close();
         };
+        ui.checkbox(&mut self.data.show_div_readout, "Show Div Readout")
+            .on_hover_text("Show the current units per grid division for both axes");
+        ui.checkbox(&mut self.data.show_cursor_readout, "Show Cursor Readout")
+            .on_hover_text("Show the plot coordinates under the mouse pointer below the plot");
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.data.show_zero_line, "Reference Line")
+                .on_hover_text("Draw a faint horizontal line at the given y value");
+            ui.add_enabled(
+                self.data.show_zero_line,
+                egui::DragValue::new(&mut self.data.zero_line_y)
+                    .speed(0.01)
+                    .prefix("y = "),
+            );
+        });
+        ui.checkbox(&mut self.data.threshold_backdrop, "Threshold Alarm Tint")
+            .on_hover_text("Tint the plot background while any threshold is active");
+        ui.checkbox(&mut self.data.snapshot_on_pause, "Snapshot on Pause")
+            .on_hover_text(
+                "Copy all traces when pausing so the view is fully frozen. \
+                 When off, pausing only stops following and pins the x-range; \
+                 no copy is made, but trimmed samples can scroll out of view.",
+            );
+        if ui
+            .checkbox(&mut self.data.minimal_axes, "Minimal Axes")
+            .on_hover_text("Hide axis labels, ticks and legend; draw only the traces")
+            .changed()
+        {
+            ui.close();
+        };
+        if ui
+            .checkbox(&mut self.data.hover_scroll_lock, "Lock Scroll on Hover")
+            .on_hover_text("Stop following the latest data while the mouse is over the plot")
+            .changed()
+        {
+            ui.close();
+        };
+        if self.data.scope_type == ScopeType::TimeScope {
+            ui.horizontal(|ui| {
+                ui.label("X Smoothing:").on_hover_text(
+                    "Time constant for smoothly tracking the latest sample (0 = exact edge)",
+                );
+                ui.add(
+                    egui::DragValue::new(&mut self.data.x_smoothing_secs)
+                        .range(0.0..=5.0)
+                        .speed(0.01)
+                        .suffix(" s"),
+                );
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.label("Max Drawn Traces:")
+                .on_hover_text("Draw only pinned and most recently updated traces (0 = all)");
+            ui.add(egui::DragValue::new(&mut self.data.max_drawn_traces).range(0..=1000));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Max Points:").on_hover_text(
+                "Live samples kept per trace of this scope (0 = the global Data Points). \
+                 A trace in several scopes keeps the largest cap.",
+            );
+            ui.add(
+                egui::DragValue::new(&mut self.data.max_points)
+                    .range(0..=10_000_000)
+                    .speed(100.0),
+            );
+        });
+        if self.data.max_drawn_traces > 0 {
+            ui.menu_button("Pinned Traces", |ui| {
+                for name in self.data.trace_order.clone() {
+                    let label = traces
+                        .get_trace(&name)
+                        .map_or(name.0.clone(), |t| t.label(&name).to_string());
+                    let mut pinned = self.data.pinned_traces.contains(&name);
+                    if ui.checkbox(&mut pinned, label).changed() {
+                        if pinned {
+                            self.data.pinned_traces.push(name);
+                        } else {
+                            self.data.pinned_traces.retain(|t| t != &name);
+                        }
+                    }
+                }
+            });
+        }
+        if self.data.scope_type == ScopeType::TimeScope {
+            ui.menu_button("Reference", |ui| {
+                if ui
+                    .button("Load CSV...")
+                    .on_hover_text(
+                        "Overlay a previous CSV export as faded, dashed reference traces",
+                    )
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("CSV", &["csv"])
+                        .pick_file()
+                    {
+                        if let Err(e) = self.data.load_reference_csv(&path) {
+                            eprintln!("Failed to load reference CSV: {e}");
+                        }
+                    }
+                    ui.close();
+                }
+                ui.add_enabled_ui(!self.data.reference_traces.is_empty(), |ui| {
+                    if ui
+                        .button("Align to Window")
+                        .on_hover_text("Move the reference start to the left edge of the view")
+                        .clicked()
+                    {
+                        self.data.align_reference_to_window();
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label("X Offset:");
+                        ui.add(egui::DragValue::new(&mut self.data.reference_x_offset).speed(0.01));
+                    });
+                    ui.checkbox(&mut self.data.reference_in_autofit, "Include in Auto-Fit")
+                        .on_hover_text("Let auto-fit and Fit to View cover the reference too");
+                    if ui.button("Clear").clicked() {
+                        self.data.reference_traces.clear();
+                        ui.close();
+                    }
+                });
+            });
+        }
+        if self.data.scope_type == ScopeType::TimeScope {
+            ui.menu_button("Decimation", |ui| {
+                let options = [
+                    (
+                        DecimationMethod::Stride,
+                        "Stride",
+                        "Every Nth sample; cheapest",
+                    ),
+                    (
+                        DecimationMethod::MinMax,
+                        "Min/Max",
+                        "Keep each bucket's extremes; preserves peaks",
+                    ),
+                    (
+                        DecimationMethod::Lttb,
+                        "LTTB",
+                        "Largest-Triangle-Three-Buckets; preserves shape",
+                    ),
+                ];
+                for (method, label, tip) in options {
+                    if ui
+                        .selectable_label(self.data.decimation == method, label)
+                        .on_hover_text(tip)
+                        .clicked()
+                    {
+                        self.data.decimation = method;
+                        ui.close();
+                    }
+                }
+                ui.separator();
+                ui.weak(format!(
+                    "{} segments drawn last frame",
+                    self.data.drawn_segments
+                ));
+                ui.horizontal(|ui| {
+                    ui.label("Warn above");
+                    ui.add(
+                        egui::DragValue::new(&mut self.data.segment_warning)
+                            .range(0..=usize::MAX)
+                            .speed(1000.0),
+                    )
+                    .on_hover_text("Show a warning on the plot above this many segments (0 = off)");
+                });
+            });
+            ui.menu_button("X Source", |ui| {
+                if ui
+                    .selectable_label(self.data.x_source.is_none(), "Time")
+                    .clicked()
+                {
+                    self.data.set_x_source(None);
+                    ui.close();
+                }
+                for name in self.data.trace_order.clone() {
+                    let selected = self.data.x_source.as_ref() == Some(&name);
+                    if ui
+                        .selectable_label(selected, name.0.as_str())
+                        .on_hover_text("Plot the other traces against this trace's values")
+                        .clicked()
+                    {
+                        self.data.set_x_source(Some(name));
+                        ui.close();
+                    }
+                }
+            });
+        }
+        ui.menu_button("On Resume", |ui| {
+            let options = [
+                (ResumeBehavior::JumpToLatest, "Jump to Latest"),
+                (ResumeBehavior::RestorePausedView, "Restore Paused View"),
+            ];
+            for (behavior, label) in options {
+                if ui
+                    .selectable_label(self.data.resume_behavior == behavior, label)
+                    .clicked()
+                {
+                    self.data.resume_behavior = behavior;
+                    ui.close();
+                }
+            }
+        });
         if ui
             .checkbox(&mut self.data.show_legend, "Show Legend")
             .on_hover_text("Show or hide the plot legend")
@@ -436,6 +904,18 @@ impl ScopePanel {
             {
                 ui.close();
             };
+            ui.horizontal(|ui| {
+                ui.label("Stats window:");
+                ui.add(
+                    egui::DragValue::new(&mut self.data.legend_stats_secs)
+                        .range(0.0..=3600.0)
+                        .speed(0.1)
+                        .suffix(" s"),
+                )
+                .on_hover_text(
+                    "Show mean ± σ over the last N seconds next to each trace (0 = off)",
+                );
+            });
 
             ui.menu_button("Legend Position", |ui| {
                 let positions = [
@@ -454,6 +934,20 @@ impl ScopePanel {
                     }
                 }
             });
+
+            ui.menu_button("Legend Click", |ui| {
+                for &click in LegendClick::ALL {
+                    if ui
+                        .selectable_label(self.data.legend_click == click, click.label())
+                        .clicked()
+                    {
+                        self.data.legend_click = click;
+                        ui.close();
+                    }
+                }
+            })
+            .response
+            .on_hover_text("What a left click on a legend entry does; right-click always solos");
         });
 
         if menu_snapshot != self.snapshot_settings() {
@@ -504,11 +998,34 @@ impl ScopePanel {
         if !self.data.paused {
             if ui.button(format!("{} Scope", PAUSE.as_str())).clicked() {
                 self.data.paused = true;
-                traces.take_snapshot();
+                if self.data.snapshot_on_pause {
+                    traces.take_snapshot();
+                }
             }
         } else if ui.button(format!("{} Scope", PLAY.as_str())).clicked() {
             self.data.paused = false;
         }
+        if !self.interaction_lock_fixed {
+            let locked = self.data.interaction_locked;
+            let (icon, tip) = if locked {
+                (
+                    LOCK_SIMPLE,
+                    "Plot locked against pan, zoom and clicks; click to unlock",
+                )
+            } else {
+                (
+                    LOCK_SIMPLE_OPEN,
+                    "Lock the plot against pan, zoom and clicks",
+                )
+            };
+            if ui
+                .selectable_label(locked, icon.as_str())
+                .on_hover_text(tip)
+                .clicked()
+            {
+                self.data.interaction_locked = !locked;
+            }
+        }
 
         ui.separator();
         // X controls
@@ -622,17 +1139,17 @@ impl ScopePanel {
         });
 
         if show_menu_only_options {
-            if self.data.scope_type == ScopeType::XYScope {
-                if ui
-                    .checkbox(&mut self.data.x_axis.log_scale, "Log scale")
-                    .on_hover_text(
-                        "Use base-10 log of (value + offset). Non-positive values are omitted.",
-                    )
-                    .changed()
-                {
-                    ui.close();
-                };
+            if ui
+                .checkbox(&mut self.data.x_axis.log_scale, "Log scale")
+                .on_hover_text(
+                    "Use base-10 log of X, e.g. for frequency data. Samples at X <= 0 are skipped and ticks show plain numbers, also on time axes.",
+                )
+                .changed()
+            {
+                ui.close();
+            };
 
+            if self.data.scope_type == ScopeType::XYScope {
                 ui.horizontal(|ui| {
                     ui.label("Unit:");
                     let mut unit = self.data.x_axis.get_unit().unwrap_or_default();
@@ -794,6 +1311,7 @@ impl ScopePanel {
                     .clicked()
                 {
                     self.data.fit_y_bounds(traces, false);
+                    self.data.fit_y2_bounds(traces, false);
                     ui.close();
                 }
 
@@ -829,6 +1347,25 @@ impl ScopePanel {
                 ui.close();
             };
 
+            ui.horizontal(|ui| {
+                ui.label("Margin:");
+                let mut percent = self.data.y_margin * 100.0;
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut percent)
+                            .range(0.0..=50.0)
+                            .speed(0.5)
+                            .suffix(" %"),
+                    )
+                    .on_hover_text(
+                        "Empty space above and below the Y range, so traces do not touch the edges",
+                    )
+                    .changed()
+                {
+                    self.data.y_margin = percent / 100.0;
+                }
+            });
+
             ui.horizontal(|ui| {
                 ui.label("Unit:");
                 let mut unit = self.data.y_axis.get_unit().unwrap_or_default();
@@ -935,6 +1472,10 @@ impl ScopePanel {
                     });
                 }
             });
+
+            if self.data.scope_type == ScopeType::TimeScope {
+                ui.menu_button("Right axis", |ui| self.render_right_axis_menu(ui, traces));
+            }
         }
 
         ui.separator();
@@ -981,6 +1522,14 @@ impl ScopePanel {
             });
         }
 
+        if ui
+            .button("Fit All Data")
+            .on_hover_text("Fit both axes once to all buffered data and switch auto-fit off")
+            .clicked()
+        {
+            self.data.fit_all_requested = true;
+        }
+
         ui.separator();
 
         // Screenshot button kept in core controls
@@ -1005,26 +1554,86 @@ impl ScopePanel {
         // Determine whether tick labels should be suppressed based on the TOTAL
         // widget size (including top bar, sidebars, etc.) so the decision is
         // stable and doesn't jump as sub-panels are toggled.
-        let hide_y_labels = self.total_widget_size.x < self.min_width_for_y_ticklabels;
-        let hide_x_labels = self.total_widget_size.y < self.min_height_for_x_ticklabels;
-        let hide_legend = self.data.force_hide_legend
+        let minimal_axes = self.data.minimal_axes;
+        let hide_y_labels =
+            minimal_axes || self.total_widget_size.x < self.min_width_for_y_ticklabels;
+        let hide_x_labels =
+            minimal_axes || self.total_widget_size.y < self.min_height_for_x_ticklabels;
+        let hide_legend = minimal_axes
+            || self.data.force_hide_legend
             || (self.min_width_for_legend > 0.0
                 && self.total_widget_size.x < self.min_width_for_legend)
             || (self.min_height_for_legend > 0.0
                 && self.total_widget_size.y < self.min_height_for_legend);
 
+        // One-shot fit to all buffered data; drop any hover lock so it does not
+        // override the new x-window.
+        if std::mem::take(&mut self.data.fit_all_requested) {
+            self.data.fit_all_data(traces);
+            self.hover_locked_x_bounds = None;
+            let view_change = crate::events::ViewChangeMeta {
+                x_range: Some(self.data.x_axis.bounds),
+                y_range: Some(self.data.y_axis.bounds),
+                scope_id: Some(self.data.id),
+                scope_type: Some(self.data.scope_type),
+            };
+            if let Some(ctrl) = &self.event_ctrl {
+                let mut evt = crate::events::PlotEvent::new(
+                    crate::events::EventKind::FIT_TO_VIEW | crate::events::EventKind::ZOOM,
+                );
+                evt.view_change = Some(view_change.clone());
+                ctrl.emit_filtered(evt);
+            }
+            self.pending_view_change = Some(view_change);
+        }
+
+        // Hover scroll lock: keep the x-window pinned while the pointer is over the plot.
+        let hover_lock_active = self.data.hover_scroll_lock
+            && self.data.scope_type == ScopeType::TimeScope
+            && !self.data.paused;
+        if !hover_lock_active {
+            self.hover_locked_x_bounds = None;
+        }
+        if let Some(bounds) = self.hover_locked_x_bounds {
+            self.data.x_axis.bounds = bounds;
+        }
+
         let y_log = self.data.y_axis.log_scale;
         let x_log = self.data.x_axis.log_scale;
         let show_grid = self.data.show_grid;
+        let locked = self.data.interaction_locked;
+        let y_margin = self.data.y_margin.max(0.0);
         let mut plot = Plot::new(format!("scope_plot_{}", self.data.name))
             .allow_scroll(false)
             .allow_zoom(false)
             .allow_boxed_zoom(false)
+            .allow_drag(!locked)
+            .allow_double_click_reset(!locked)
             .show_grid(egui::Vec2b::new(show_grid, show_grid))
             // When tick labels are hidden (thresholds set above available size), also
             // suppress the egui_plot axis space reservation so the plot fills the full
             // widget width without a black gutter on the left (Y axis) or bottom (X axis).
             .show_axes(egui::Vec2b::new(!hide_x_labels, !hide_y_labels));
+        // The right axis must be registered before the main axis is configured below.
+        let right_axis = if hide_y_labels {
+            None
+        } else {
+            self.data.active_y_axis2()
+        };
+        if let Some(y2) = right_axis {
+            let data = &self.data;
+            let mut hint =
+                AxisHints::new_y()
+                    .placement(HPlacement::Right)
+                    .formatter(move |mark, _range| {
+                        let step = (data.y1_to_y2(mark.step_size) - data.y1_to_y2(0.0)).abs();
+                        y2.format_value(data.y1_to_y2(mark.value), Some(step))
+                    });
+            if let Some(label) = self.right_axis_label_text(traces) {
+                hint = hint.label(label);
+            }
+            plot = plot.custom_y_axes(vec![AxisHints::new_y(), hint]);
+        }
         if !hide_x_labels {
             if let Some(label) = self.axis_label_text(traces, true) {
                 plot = plot.x_axis_label(label);
@@ -1038,17 +1647,25 @@ impl ScopePanel {
         if self.data.show_legend && !hide_legend {
             plot = plot.legend(Legend::default().position(self.data.legend_position.into()));
         }
-        let plot = plot
+        // Largest tick step handed to the axis formatters this frame, i.e. the
+        // spacing of the labelled grid lines, for the per-division readout.
+        let x_major_step = std::cell::Cell::new(0.0_f64);
+        let y_major_step = std::cell::Cell::new(0.0_f64);
+        let mut plot = plot
             .x_axis_formatter(|x, _range| {
+                x_major_step.set(x_major_step.get().max(x.step_size.abs()));
                 if hide_x_labels {
                     return String::new();
                 }
-                let x_value = if x_log { 10f64.powf(x.value) } else { x.value };
+                if x_log {
+                    return self.data.x_axis.format_log_tick(x.value);
+                }
                 self.data
                     .x_axis
-                    .format_value(x_value, Some(x.step_size.abs()))
+                    .format_value(x.value, Some(x.step_size.abs()))
             })
             .y_axis_formatter(|y, _range| {
+                y_major_step.set(y_major_step.get().max(y.step_size.abs()));
                 if hide_y_labels {
                     return String::new();
                 }
@@ -1072,8 +1689,17 @@ impl ScopePanel {
                 let y = if y_log { 10f64.powf(value.y) } else { value.y };
                 // For time axes this routes through TimeFormatter; for value axes numeric.
                 // For XY scopes both axes are value-typed, so both format numerically.
-                let x_str = self.data.x_axis.format_value(x, None);
-                let y_str = self.data.y_axis.format_value(y, None);
+                let x_str = if x_log {
+                    let decimals = self.data.x_axis.value_decimals;
+                    self.data.x_axis.format_number(x, decimals, None)
+                } else {
+                    self.data.x_axis.format_value(x, None)
+                };
+                let mut y_str = self.data.y_axis.format_value(y, None);
+                if let Some(y2) = self.data.active_y_axis2() {
+                    let y2_str = y2.format_value(self.data.y1_to_y2(value.y), None);
+                    y_str = format!("{y_str}\ny2 = {y2_str}");
+                }
                 if name.is_empty() {
                     Some(format!("x = {}\ny = {}", x_str, y_str))
                 } else {
@@ -1081,19 +1707,51 @@ impl ScopePanel {
                 }
             });
 
+        // Leave room below the plot for the cursor readout strip.
+        let strip_height = ui.text_style_height(&egui::TextStyle::Small) + 2.0;
+        if self.data.show_cursor_readout {
+            let plot_height = ui.available_height() - strip_height - ui.spacing().item_spacing.y;
+            plot = plot.height(plot_height.max(0.0));
+        }
+
+        // Legend entries hidden before this frame, to tell which one a click toggled.
+        let legend_hidden_before = PlotMemory::load(
+            ui.ctx(),
+            ui.make_persistent_id(egui::Id::new(format!("scope_plot_{}", self.data.name))),
+        )
+        .map(|mem| mem.hidden_items)
+        .unwrap_or_default();
+
+        let mut hidden_traces = 0usize;
+        let mut segments = 0usize;
+        let mut cursor: Option<egui_plot::PlotPoint> = None;
+        // Turning persistence off drops the history at once.
+        let persistence = self.data.persistence_secs;
+        if persistence <= 0.0 {
+            self.afterglow.clear();
+        }
+        let now = ui.input(|i| i.time);
+        let mut afterglow = std::mem::take(&mut self.afterglow);
+        let mut glowing: HashMap<String, Afterglow> = HashMap::new();
         let plot_resp = plot.show(ui, |plot_ui| {
+            cursor = plot_ui.pointer_coordinate();
             // Handle wheel zoom around hovered point
             let resp = plot_ui.response();
 
-            let is_box_zoom_dragging =
-                resp.dragged_by(egui::PointerButton::Secondary) && resp.is_pointer_button_down_on();
-            let is_box_zoom_finished = resp.drag_stopped_by(egui::PointerButton::Secondary);
-            let is_panning =
-                resp.dragged_by(egui::PointerButton::Primary) && resp.is_pointer_button_down_on();
+            // A locked plot ignores every pan/zoom gesture, so its bounds keep
+            // following the scope settings below.
+            let is_box_zoom_dragging = !locked
+                && resp.dragged_by(egui::PointerButton::Secondary)
+                && resp.is_pointer_button_down_on();
+            let is_box_zoom_finished =
+                !locked && resp.drag_stopped_by(egui::PointerButton::Secondary);
+            let is_panning = !locked
+                && resp.dragged_by(egui::PointerButton::Primary)
+                && resp.is_pointer_button_down_on();
 
             let scroll_data = resp.ctx.input(|i| i.smooth_scroll_delta);
             let is_zooming_with_wheel =
-                (scroll_data.x != 0.0 || scroll_data.y != 0.0) && resp.hovered();
+                !locked && (scroll_data.x != 0.0 || scroll_data.y != 0.0) && resp.hovered();
 
             // Capture hover_pos before mutable plot_ui calls to avoid borrow conflict
             let hover_pos = resp.hover_pos();
@@ -1156,18 +1814,60 @@ impl ScopePanel {
 
             // Apply bounds: X follows latest time using time_window; Y respects manual limits if valid
             if !bounds_changed {
-                let (x_min, x_max) = self.data.x_axis.bounds;
+                let (x_min, x_max) = if x_log {
+                    log_bounds(self.data.x_axis.bounds)
+                } else {
+                    self.data.x_axis.bounds
+                };
                 let x_space = (x_max - x_min) * 0.05;
                 plot_ui.set_plot_bounds_x(x_min - x_space..=x_max + x_space);
 
                 let (y_min, y_max) = self.data.y_axis.bounds;
-                let y_space = (y_max - y_min) * 0.05;
+                let y_space = (y_max - y_min) * y_margin;
                 plot_ui.set_plot_bounds_y(y_min - y_space..=y_max + y_space);
             }
 
+            // Alarm backdrop and reference line first so traces draw over them.
+            if self.data.threshold_backdrop && self.data.threshold_active {
+                let b = plot_ui.plot_bounds();
+                let (min, max) = (b.min(), b.max());
+                plot_ui.polygon(
+                    Polygon::new(
+                        "",
+                        vec![
+                            [min[0], min[1]],
+                            [max[0], min[1]],
+                            [max[0], max[1]],
+                            [min[0], max[1]],
+                        ],
+                    )
+                    .fill_color(Color32::from_rgba_unmultiplied(255, 0, 0, 40))
+                    .stroke(egui::Stroke::NONE)
+                    .allow_hover(false),
+                );
+            }
+            // The reference line is unnamed so it stays out of the legend.
+            if self.data.show_zero_line {
+                let y = self.data.zero_line_y;
+                let y_plot = if !self.data.y_axis.log_scale {
+                    Some(y)
+                } else if y > 0.0 {
+                    Some(y.log10())
+                } else {
+                    None
+                };
+                if let Some(y_plot) = y_plot {
+                    plot_ui.hline(
+                        HLine::new("", y_plot)
+                            .color(Color32::GRAY.gamma_multiply(0.5))
+                            .width(1.0)
+                            .allow_hover(false),
+                    );
+                }
+            }
+
             // Draw traces
             if self.data.scope_type == ScopeType::XYScope && !self.data.xy_pairs.is_empty() {
-                let tol = 1e-9_f64;
                 let mut pairs = self.data.xy_pairs.clone();
                 pairs.sort_by_key(|(_, y_name, _)| {
                     y_name
@@ -1185,48 +1885,38 @@ impl ScopePanel {
                     else {
                         continue;
                     };
-                    let x_pts = traces.get_points_ref(&x_name, self.data.paused);
-                    let y_pts = traces.get_points_ref(&y_name, self.data.paused);
+                    let x_pts = traces.get_points_ref(&x_name, self.data.shows_snapshot());
+                    let y_pts = traces.get_points_ref(&y_name, self.data.shows_snapshot());
                     let (Some(x_pts), Some(y_pts)) = (x_pts, y_pts) else {
                         continue;
                     };
 
                     let mut derived: Vec<[f64; 2]> = Vec::new();
-                    let mut i = 0usize;
-                    let mut j = 0usize;
-                    while i < x_pts.len() && j < y_pts.len() {
-                        let tx = x_pts[i][0];
-                        let ty = y_pts[j][0];
-                        let dt = tx - ty;
-                        if dt.abs() <= tol {
-                            let x_lin = x_pts[i][1] + x_tr.offset;
-                            let y_lin = y_pts[j][1] + y_tr.offset;
-                            let y = if self.data.y_axis.log_scale {
-                                if y_lin > 0.0 {
-                                    y_lin.log10()
-                                } else {
-                                    f64::NAN
-                                }
+                    for [x_raw, y_raw] in pair_by_time(x_pts, y_pts, self.data.xy_tolerance) {
+                        let x_lin = x_tr.display_y(x_raw);
+                        let y_lin = y_tr.display_y(y_raw);
+                        if x_log && x_lin <= 0.0 {
+                            continue;
+                        }
+                        let y = if self.data.y_axis.log_scale {
+                            if y_lin > 0.0 {
+                                y_lin.log10()
                             } else {
-                                y_lin
-                            };
-                            let x = if self.data.x_axis.log_scale {
-                                if x_lin > 0.0 {
-                                    x_lin.log10()
-                                } else {
-                                    f64::NAN
-                                }
+                                f64::NAN
+                            }
+                        } else {
+                            y_lin
+                        };
+                        let x = if self.data.x_axis.log_scale {
+                            if x_lin > 0.0 {
+                                x_lin.log10()
                             } else {
-                                x_lin
-                            };
-                            derived.push([x, y]);
-                            i += 1;
-                            j += 1;
-                        } else if dt < 0.0 {
-                            i += 1;
+                                f64::NAN
+                            }
                         } else {
-                            j += 1;
-                        }
+                            x_lin
+                        };
+                        derived.push([x, y]);
                     }
 
                     if derived.is_empty() {
@@ -1238,11 +1928,13 @@ impl ScopePanel {
                     let mut width: f32 = pair_look.width.max(0.1);
                     let style = pair_look.style;
 
-                    let legend_label = if self.data.show_info_in_legend && !y_tr.info.is_empty() {
-                        format!("{} vs {} — {}", y_name, x_name, y_tr.info)
-                    } else {
-                        format!("{} vs {}", y_name, x_name)
-                    };
+                    let legend_label = xy_legend_label(
+                        self.data.show_info_in_legend,
+                        x_tr,
+                        &x_name,
+                        y_tr,
+                        &y_name,
+                    );
 
                     if let Some(hov) = &traces.hover_trace {
                         // Hover on either trace highlights this pair.
@@ -1259,6 +1951,25 @@ impl ScopePanel {
                         }
                     }
 
+                    if persistence > 0.0 {
+                        let mut glow = afterglow.remove(&legend_label).unwrap_or_default();
+                        draw_afterglow(
+                            plot_ui,
+                            &mut glow,
+                            now,
+                            persistence,
+                            &derived,
+                            |frame, fade| {
+                                Line::new(legend_label.clone(), frame)
+                                    .name(legend_label.clone())
+                                    .color(color.gamma_multiply(fade * 0.6))
+                                    .width(width)
+                                    .style(style)
+                            },
+                        );
+                        glowing.insert(legend_label.clone(), glow);
+                    }
+                    segments += derived.len().saturating_sub(1);
                     plot_ui.line(
                         Line::new(legend_label.clone(), derived.clone())
                             .name(legend_label.clone())
@@ -1291,46 +2002,170 @@ impl ScopePanel {
                         );
                     }
                 }
+            } else if self.data.uses_x_source() {
+                // Shared x-source: each trace is paired with the source's value
+                // at the same timestamp and drawn as y-vs-x.
+                let (ordered, hidden) = self.data.drawn_traces(traces);
+                hidden_traces = hidden;
+                for name in ordered {
+                    let Some(tr) = traces.get_trace(&name) else {
+                        continue;
+                    };
+                    let Some(paired) = self.data.paired_with_x_source(&name, traces) else {
+                        continue;
+                    };
+                    if paired.is_empty() {
+                        continue;
+                    }
+                    let pts: Vec<[f64; 2]> = paired
+                        .into_iter()
+                        .filter(|p| !x_log || p[0] > 0.0)
+                        .map(|p| {
+                            [
+                                to_plot(p[0], self.data.x_axis.log_scale),
+                                to_plot(p[1], self.data.y_axis.log_scale),
+                            ]
+                        })
+                        .collect();
+
+                    let mut color = tr.look.color;
+                    let mut width: f32 = tr.look.width.max(0.1);
+                    if let Some(hov) = &traces.hover_trace {
+                        if !hov.contains(&name) {
+                            color = Color32::from_rgba_unmultiplied(
+                                color.r(),
+                                color.g(),
+                                color.b(),
+                                40,
+                            );
+                        } else {
+                            width = (width * 1.6).max(width + 1.0);
+                        }
+                    }
+                    let legend_label = legend_label(self.data.show_info_in_legend, tr, &name);
+                    if tr.look.show_points {
+                        plot_ui.points(
+                            Points::new(legend_label.clone(), pts.clone())
+                                .radius(tr.look.point_size.max(0.5))
+                                .shape(tr.look.marker)
+                                .color(color),
+                        );
+                    }
+                    if persistence > 0.0 {
+                        let mut glow = afterglow.remove(&legend_label).unwrap_or_default();
+                        draw_afterglow(
+                            plot_ui,
+                            &mut glow,
+                            now,
+                            persistence,
+                            &pts,
+                            |frame, fade| {
+                                Line::new(legend_label.clone(), frame)
+                                    .name(legend_label.clone())
+                                    .color(color.gamma_multiply(fade * 0.6))
+                                    .width(width)
+                                    .style(tr.look.style)
+                            },
+                        );
+                        glowing.insert(legend_label.clone(), glow);
+                    }
+                    segments += pts.len().saturating_sub(1);
+                    plot_ui.line(
+                        Line::new(legend_label.clone(), pts)
+                            .name(legend_label)
+                            .color(color)
+                            .width(width)
+                            .style(tr.look.style),
+                    );
+                }
             } else {
-                let mut ordered: Vec<TraceRef> = self.data.trace_order.clone();
-                ordered.sort_by_key(|n| {
-                    traces
-                        .get_trace(n)
-                        .map(|t| t.creation_index)
-                        .unwrap_or(usize::MAX)
-                });
+                // Reference overlay first so live traces draw over it.
+                for reference in &self.data.reference_traces {
+                    let offset = self.data.reference_x_offset;
+                    let shifted: Vec<[f64; 2]> = reference
+                        .points
+                        .iter()
+                        .map(|p| [p[0] + offset, p[1]])
+                        .collect();
+                    let pts: Vec<[f64; 2]> =
+                        TraceData::cap_and_decimate(&shifted, self.data.x_axis.bounds, 2000)
+                            .into_iter()
+                            .filter(|p| !x_log || p[0] > 0.0)
+                            .map(|p| {
+                                [
+                                    to_plot(p[0], self.data.x_axis.log_scale),
+                                    to_plot(p[1], self.data.y_axis.log_scale),
+                                ]
+                            })
+                            .collect();
+                    let color = traces
+                        .get_trace(&TraceRef::new(reference.name.as_str()))
+                        .map_or(Color32::GRAY, |t| t.look.color);
+                    segments += pts.len().saturating_sub(1);
+                    plot_ui.line(
+                        Line::new(format!("ref: {}", reference.name), pts)
+                            .color(color.gamma_multiply(0.4))
+                            .width(1.0)
+                            .style(LineStyle::dashed_loose())
+                            .allow_hover(false),
+                    );
+                }
+
+                let (ordered, hidden) = self.data.drawn_traces(traces);
+                hidden_traces = hidden;
                 let trace_count = ordered.len();
                 for idx in 0..trace_count {
                     let name = ordered[idx].clone();
                     if let Some(tr) = traces.get_trace(&name) {
-                        let shown_pts = match self.data.get_drawn_points(&name, traces) {
-                            Some(pts) => pts,
-                            None => continue,
+                        let shown_pts = if tr.display_smooth_window > 1 {
+                            // Display-only smoothing: average the raw buffer (with
+                            // enough history before the left edge) and decimate after.
+                            let Some(raw) =
+                                traces.get_points_ref(&name, self.data.shows_snapshot())
+                            else {
+                                continue;
+                            };
+                            let window = tr.display_smooth_window;
+                            let start = raw
+                                .partition_point(|p| p[0] < self.data.x_axis.bounds.0)
+                                .saturating_sub(window - 1);
+                            let raw: Vec<[f64; 2]> = raw.range(start..).copied().collect();
+                            let smoothed = TraceData::moving_average(&raw, window);
+                            TraceData::decimate(
+                                &smoothed,
+                                self.data.x_axis.bounds,
+                                2000,
+                                self.data.decimation,
+                            )
+                        } else {
+                            match self.data.get_drawn_points(&name, traces) {
+                                Some(pts) => pts,
+                                None => continue,
+                            }
                         };
+                        let log_x = self.data.x_axis.log_scale;
+                        // Right-axis values are mapped onto the left axis' range.
+                        let to_y = |v: f64| self.data.value_to_plot_y(tr, v);
+                        let value_axis = self.data.value_axis(tr);
+                        // (x, y - err, y + err) for drawn samples that carry an uncertainty.
+                        let err_rows: Vec<[f64; 3]> =
+                            if tr.look.error_style != ErrorStyle::Off && !tr.y_err.is_empty() {
+                                shown_pts
+                                    .iter()
+                                    .filter(|p| !log_x || p[0] > 0.0)
+                                    .filter_map(|p| {
+                                        let e = tr.y_err_at(p[0])?;
+                                        let y = tr.display_y(p[1]);
+                                        Some([to_plot(p[0], log_x), to_y(y - e), to_y(y + e)])
+                                    })
+                                    .collect()
+                            } else {
+                                Vec::new()
+                            };
                         let pts_vec: Vec<[f64; 2]> = shown_pts
                             .into_iter()
-                            .map(|p| {
-                                let y_lin = p[1] + tr.offset;
-                                let y = if self.data.y_axis.log_scale {
-                                    if y_lin > 0.0 {
-                                        y_lin.log10()
-                                    } else {
-                                        f64::NAN
-                                    }
-                                } else {
-                                    y_lin
-                                };
-                                let x = if self.data.x_axis.log_scale {
-                                    if p[0] > 0.0 {
-                                        p[0].log10()
-                                    } else {
-                                        f64::NAN
-                                    }
-                                } else {
-                                    p[0]
-                                };
-                                [x, y]
-                            })
+                            .filter(|p| !log_x || p[0] > 0.0)
+                            .map(|p| [to_plot(p[0], log_x), to_y(tr.display_y(p[1]))])
                             .collect();
                         let mut color = tr.look.color;
                         let mut width: f32 = tr.look.width.max(0.1);
@@ -1353,12 +2188,95 @@ impl ScopePanel {
                             .color(color)
                             .width(width)
                             .style(style);
-                        let legend_label = if self.data.show_info_in_legend && !tr.info.is_empty() {
-                            format!("{} — {}", name, tr.info)
+                        let legend_label = legend_label(self.data.show_info_in_legend, tr, &name);
+                        // Only the shown name carries the stats badge; item ids keep
+                        // the plain label so legend visibility survives value changes.
+                        let stats = if self.data.legend_stats_secs > 0.0 {
+                            traces
+                                .get_points_ref(&name, self.data.shows_snapshot())
+                                .and_then(|pts| {
+                                    TraceData::recent_mean_std(pts, self.data.legend_stats_secs)
+                                })
                         } else {
-                            name.0.clone()
+                            None
+                        };
+                        let shown_label = match stats {
+                            Some((mean, sd)) => format!(
+                                "{legend_label}  {} ± {}",
+                                value_axis.format_value(tr.display_y(mean), None),
+                                value_axis.format_value(sd, None)
+                            ),
+                            None => legend_label.clone(),
                         };
-                        line = line.name(legend_label.clone());
+                        line = line.name(shown_label.clone());
+                        if let Some(base) = tr.look.fill_baseline {
+                            let base = to_y(base);
+                            let kept: Vec<[f64; 2]> = pts_vec
+                                .iter()
+                                .copied()
+                                .filter(|p| p[0].is_finite() && p[1].is_finite())
+                                .collect();
+                            if kept.len() > 1 && base.is_finite() {
+                                let xs: Vec<f64> = kept.iter().map(|p| p[0]).collect();
+                                let lo: Vec<f64> = kept.iter().map(|p| p[1].min(base)).collect();
+                                let hi: Vec<f64> = kept.iter().map(|p| p[1].max(base)).collect();
+                                plot_ui.add(
+                                    FilledArea::new(legend_label.clone(), &xs, &lo, &hi)
+                                        .name(shown_label.clone())
+                                        .fill_color(color.gamma_multiply(0.3))
+                                        .allow_hover(false),
+                                );
+                            }
+                        }
+                        if !err_rows.is_empty() {
+                            // Same legend name as the line so hiding the trace hides these too.
+                            match tr.look.error_style {
+                                ErrorStyle::Bars => {
+                                    let lo: Vec<[f64; 2]> =
+                                        err_rows.iter().map(|r| [r[0], r[1]]).collect();
+                                    let hi: Vec<[f64; 2]> =
+                                        err_rows.iter().map(|r| [r[0], r[2]]).collect();
+                                    plot_ui.arrows(
+                                        Arrows::new(legend_label.clone(), lo, hi)
+                                            .name(shown_label.clone())
+                                            .tip_length(0.0)
+                                            .color(color)
+                                            .allow_hover(false),
+                                    );
+                                }
+                                ErrorStyle::Band => {
+                                    let xs: Vec<f64> = err_rows.iter().map(|r| r[0]).collect();
+                                    let lo: Vec<f64> = err_rows.iter().map(|r| r[1]).collect();
+                                    let hi: Vec<f64> = err_rows.iter().map(|r| r[2]).collect();
+                                    plot_ui.add(
+                                        FilledArea::new(legend_label.clone(), &xs, &lo, &hi)
+                                            .name(shown_label.clone())
+                                            .fill_color(color.gamma_multiply(0.25))
+                                            .allow_hover(false),
+                                    );
+                                }
+                                ErrorStyle::Off => {}
+                            }
+                        }
+                        if persistence > 0.0 {
+                            let mut glow = afterglow.remove(&legend_label).unwrap_or_default();
+                            draw_afterglow(
+                                plot_ui,
+                                &mut glow,
+                                now,
+                                persistence,
+                                &pts_vec,
+                                |frame, fade| {
+                                    Line::new(legend_label.clone(), frame)
+                                        .name(shown_label.clone())
+                                        .color(color.gamma_multiply(fade * 0.6))
+                                        .width(width)
+                                        .style(style)
+                                },
+                            );
+                            glowing.insert(legend_label.clone(), glow);
+                        }
+                        segments += pts_vec.len().saturating_sub(1);
                         plot_ui.line(line);
 
                         // Optional point markers for each datapoint
@@ -1371,6 +2289,7 @@ impl ScopePanel {
                                     }
                                 }
                                 let points = Points::new(legend_label, pts_vec.clone())
+                                    .name(shown_label)
                                     .radius(radius)
                                     .shape(tr.look.marker)
                                     .color(color);
@@ -1390,65 +2309,133 @@ impl ScopePanel {
 
         self.record_plot_geometry(&plot_resp);
 
-        // Handle right-click on legend items: isolate one trace or re-enable all
+        // Traces not drawn this frame lose their afterglow.
+        self.afterglow = glowing;
+        if !self.afterglow.is_empty() {
+            ui.ctx().request_repaint();
+        }
+
+        // Warn when the frame drew more line segments than is comfortable.
+        self.data.drawn_segments = segments;
+        if self.data.segment_warning > 0 && segments > self.data.segment_warning {
+            let frame = *plot_resp.transform.frame();
+            ui.painter().with_clip_rect(frame).text(
+                frame.left_top() + egui::vec2(6.0, 6.0),
+                egui::Align2::LEFT_TOP,
+                format!(
+                    "⚠ {segments} line segments this frame: enable decimation \
+                     (Decimation menu), lower Data Points or limit the drawn traces"
+                ),
+                egui::FontId::proportional(12.0),
+                Color32::from_rgb(230, 160, 40),
+            );
+        }
+
+        // Note traces left out by the per-scope draw limit.
+        if hidden_traces > 0 {
+            let frame = *plot_resp.transform.frame();
+            ui.painter().with_clip_rect(frame).text(
+                frame.right_bottom() + egui::vec2(-6.0, -6.0),
+                egui::Align2::RIGHT_BOTTOM,
+                format!("+{hidden_traces} more"),
+                egui::FontId::proportional(12.0),
+                ui.visuals().weak_text_color(),
+            );
+        }
+
+        if self.data.show_div_readout {
+            let bounds = plot_resp.transform.bounds();
+            let readout = |axis: &AxisSettings, span: f64, major: f64| {
+                if axis.log_scale {
+                    return "log".to_string();
+                }
+                match division_step(span, (major > 0.0).then_some(major)) {
+                    Some((step, divs)) => format!("{} ({divs:.1} div)", axis.format_per_div(step)),
+                    None => "-".to_string(),
+                }
+            };
+            let text = format!(
+                "X: {}   Y: {}",
+                readout(&self.data.x_axis, bounds.width(), x_major_step.get()),
+                readout(&self.data.y_axis, bounds.height(), y_major_step.get()),
+            );
+            let frame = *plot_resp.transform.frame();
+            ui.painter().with_clip_rect(frame).text(
+                frame.left_bottom() + egui::vec2(6.0, -6.0),
+                egui::Align2::LEFT_BOTTOM,
+                text,
+                egui::FontId::monospace(11.0),
+                ui.visuals().weak_text_color(),
+            );
+        }
+
+        if self.data.show_cursor_readout {
+            let unlog = |v: f64, log: bool| if log { 10f64.powf(v) } else { v };
+            let text = match cursor {
+                Some(p) => format!(
+                    "x = {}   y = {}",
+                    if self.data.x_axis.log_scale {
+                        let decimals = self.data.x_axis.value_decimals;
+                        self.data
+                            .x_axis
+                            .format_number(unlog(p.x, true), decimals, None)
+                    } else {
+                        self.data.x_axis.format_value(p.x, None)
+                    },
+                    self.data
+                        .y_axis
+                        .format_value(unlog(p.y, self.data.y_axis.log_scale), None),
+                ),
+                None => "x = –   y = –".to_string(),
+            };
+            ui.allocate_ui(egui::vec2(ui.available_width(), strip_height), |ui| {
+                ui.label(egui::RichText::new(text).small().monospace().weak());
+            });
+        }
+
+        // Apply the configured legend click behaviour, and handle right-click
+        // on legend items: isolate one trace or re-enable all.
         if self.data.show_legend && !hide_legend {
             let plot_id =
                 ui.make_persistent_id(egui::Id::new(format!("scope_plot_{}", self.data.name)));
             if let Some(mut mem) = PlotMemory::load(ui.ctx(), plot_id) {
+                let toggled: Vec<egui::Id> = mem
+                    .hidden_items
+                    .symmetric_difference(&legend_hidden_before)
+                    .copied()
+                    .collect();
+                let mut changed = false;
+                // A single changed entry is a plain click; egui_plot's own
+                // alt-click focus changes several and is left alone.
+                if let [clicked] = toggled[..] {
+                    if self.data.legend_click != LegendClick::ToggleVisible {
+                        let all_ids = self.legend_item_ids(traces);
+                        let hidden = self.data.legend_click.apply(
+                            clicked,
+                            &legend_hidden_before.iter().copied().collect(),
+                            &all_ids,
+                        );
+                        mem.hidden_items = hidden.into_iter().collect();
+                        changed = true;
+                    }
+                }
                 if let Some(hovered_id) = mem.hovered_legend_item {
                     if ui.input(|i| i.pointer.secondary_clicked())
                         && plot_resp.response.contains_pointer()
                     {
-                        // Collect all current legend item IDs
-                        let all_ids: Vec<egui::Id> = if self.data.scope_type == ScopeType::XYScope
-                            && !self.data.xy_pairs.is_empty()
-                        {
-                            let mut ids = Vec::new();
-                            for (x_name, y_name, _pair_look) in self.data.xy_pairs.clone() {
-                                let (Some(x_name), Some(y_name)) = (x_name, y_name) else {
-                                    continue;
-                                };
-                                let (Some(_x_tr), Some(y_tr)) =
-                                    (traces.get_trace(&x_name), traces.get_trace(&y_name))
-                                else {
-                                    continue;
-                                };
-                                let label =
-                                    if self.data.show_info_in_legend && !y_tr.info.is_empty() {
-                                        format!("{} vs {} — {}", y_name, x_name, y_tr.info)
-                                    } else {
-                                        format!("{} vs {}", y_name, x_name)
-                                    };
-                                ids.push(egui::Id::new(label));
-                            }
-                            ids
-                        } else {
-                            self.data
-                                .trace_order
-                                .iter()
-                                .filter_map(|name| {
-                                    traces.get_trace(name)?;
-                                    Some(egui::Id::new(name.0.clone()))
-                                })
-                                .collect()
-                        };
-
-                        // If all others are already hidden, show all; otherwise isolate
-                        let is_isolated = all_ids
-                            .iter()
-                            .all(|id| *id == hovered_id || mem.hidden_items.contains(id));
-
-                        mem.hidden_items.clear();
-                        if !is_isolated {
-                            for id in &all_ids {
-                                if *id != hovered_id {
-                                    mem.hidden_items.insert(*id);
-                                }
-                            }
-                        }
-                        mem.store(ui.ctx(), plot_id);
+                        let all_ids = self.legend_item_ids(traces);
+                        let hidden = LegendClick::Solo.apply(
+                            hovered_id,
+                            &mem.hidden_items.iter().copied().collect(),
+                            &all_ids,
+                        );
+                        mem.hidden_items = hidden.into_iter().collect();
+                        changed = true;
                     }
                 }
+                if changed {
+                    mem.store(ui.ctx(), plot_id);
+                }
             }
         }
 
@@ -1469,11 +2456,13 @@ impl ScopePanel {
                         else {
                             continue;
                         };
-                        let label = if self.data.show_info_in_legend && !y_tr.info.is_empty() {
-                            format!("{} vs {} — {}", y_name, x_name, y_tr.info)
-                        } else {
-                            format!("{} vs {}", y_name, x_name)
-                        };
+                        let label = xy_legend_label(
+                            self.data.show_info_in_legend,
+                            x_tr,
+                            x_name,
+                            y_tr,
+                            y_name,
+                        );
                         let id = egui::Id::new(label);
                         let was_visible =
                             pair_look.visible && x_tr.look.visible && y_tr.look.visible;
@@ -1487,11 +2476,7 @@ impl ScopePanel {
                         let Some(tr) = traces.get_trace_mut(name) else {
                             continue;
                         };
-                        let label = if self.data.show_info_in_legend && !tr.info.is_empty() {
-                            format!("{} — {}", name, tr.info)
-                        } else {
-                            name.0.clone()
-                        };
+                        let label = legend_label(self.data.show_info_in_legend, tr, name);
                         let id = egui::Id::new(label);
                         let now_visible = !mem.hidden_items.contains(&id);
                         if tr.look.visible != now_visible {
@@ -1550,12 +2535,19 @@ impl ScopePanel {
             let (x_min, x_max) = (xr.start(), xr.end());
             let space_x = (0.05 / 1.1) * (x_max - x_min);
             if x_min.is_finite() && x_max.is_finite() && x_max > x_min {
-                self.data.x_axis.bounds = (x_min + space_x, x_max - space_x);
-                self.data.time_window = x_max - x_min - 2.0 * space_x;
+                let (lo, hi) = (x_min + space_x, x_max - space_x);
+                // Bounds stay in data units; a log axis is only log in the plot.
+                self.data.x_axis.bounds = if x_log {
+                    (10f64.powf(lo), 10f64.powf(hi))
+                } else {
+                    (lo, hi)
+                };
+                self.data.time_window = self.data.x_axis.bounds.1 - self.data.x_axis.bounds.0;
             }
             let yr = b.range_y();
             let (y_min, y_max) = (yr.start(), yr.end());
-            let space_y = (0.05 / 1.1) * (y_max - y_min);
+            // Strip the margin added when the bounds were applied.
+            let space_y = y_margin / (1.0 + 2.0 * y_margin) * (y_max - y_min);
             if y_min.is_finite() && y_max.is_finite() && y_max > y_min {
                 self.data.y_axis.bounds = (y_min + space_y, y_max - space_y);
             }
@@ -1573,6 +2565,7 @@ impl ScopePanel {
             }
             if y_changed {
                 self.data.y_axis.auto_fit = false;
+                self.data.follow_y_bounds(old_y_bounds);
             }
 
             // Emit zoom/pan event
@@ -1596,6 +2589,14 @@ impl ScopePanel {
             });
         }
 
+        if hover_lock_active && plot_resp.response.hovered() {
+            if plot_resp.inner || self.hover_locked_x_bounds.is_none() {
+                self.hover_locked_x_bounds = Some(self.data.x_axis.bounds);
+            }
+        } else {
+            self.hover_locked_x_bounds = None;
+        }
+
         self.handle_plot_click(&plot_resp, traces);
 
         // Handle drag-drop of traces from the traces list onto the scope plot.
@@ -1659,6 +2660,9 @@ impl ScopePanel {
     ) {
         self.data.clicked_point = None;
         self.data.clicked_screen_pos = None;
+        if self.data.interaction_locked {
+            return;
+        }
         if plot_response.response.double_clicked() {
             self.data.fit_bounds(traces, false);
             let (xmin, xmax) = self.data.x_axis.bounds;
@@ -1709,7 +2713,9 @@ impl ScopePanel {
                     // pause_on_click is disabled.
                     if !self.data.paused {
                         self.data.paused = true;
-                        traces.take_snapshot();
+                        if self.data.snapshot_on_pause {
+                            traces.take_snapshot();
+                        }
                     }
                     self.capture_clicked_plot_point(plot_response);
                     if let Some(ctrl) = &self.event_ctrl {
@@ -1816,7 +2822,9 @@ impl ScopePanel {
                 }
             } else {
                 self.data.paused = true;
-                traces.take_snapshot();
+                if self.data.snapshot_on_pause {
+                    traces.take_snapshot();
+                }
 
                 if let Some(screen_pos) = plot_response.response.interact_pointer_pos() {
                     self.capture_clicked_plot_point(plot_response);
@@ -1852,3 +2860,62 @@ impl ScopePanel {
         self.data.fit_bounds(traces, false);
     }
 }
+
+/// Legend label of a time-domain trace; also the id of its plot items.
+fn legend_label(show_info: bool, tr: &TraceData, name: &TraceRef) -> String {
+    if show_info && !tr.info.is_empty() {
+        format!("{} — {}", tr.label(name), tr.info)
+    } else {
+        tr.label(name).to_string()
+    }
+}
+
+/// Legend label of an XY pair; also the id of its plot items.
+fn xy_legend_label(
+    show_info: bool,
+    x_tr: &TraceData,
+    x_name: &TraceRef,
+    y_tr: &TraceData,
+    y_name: &TraceRef,
+) -> String {
+    if show_info && !y_tr.info.is_empty() {
+        format!(
+            "{} vs {} — {}",
+            y_tr.label(y_name),
+            x_tr.label(x_name),
+            y_tr.info
+        )
+    } else {
+        format!("{} vs {}", y_tr.label(y_name), x_tr.label(x_name))
+    }
+}
+
+/// Map a data value to plot coordinates: `log10` on a log axis (NaN for
+/// values that have no logarithm), unchanged otherwise.
+fn to_plot(v: f64, log: bool) -> f64 {
+    if !log {
+        v
+    } else if v > 0.0 {
+        v.log10()
+    } else {
+        f64::NAN
+    }
+}
+
+/// Draw the retained frames of `glow` behind a trace, oldest first, then
+/// record `pts` as the newest frame.  `line` builds one faded frame from its
+/// points and fade in `(0, 1]`; giving it the trace's legend name makes
+/// hiding the trace hide its afterglow too.
+fn draw_afterglow(
+    plot_ui: &mut egui_plot::PlotUi,
+    glow: &mut Afterglow,
+    now: f64,
+    persistence_secs: f64,
+    pts: &[[f64; 2]],
+    line: impl Fn(Vec<[f64; 2]>, f32) -> Line<'static>,
+) {
+    for (fade, frame) in glow.frames(now, persistence_secs) {
+        plot_ui.line(line(frame.to_vec(), fade).allow_hover(false));
+    }
+    glow.record(now, pts, persistence_secs);
+}
@@ -3,7 +3,7 @@ use crate::data::data::LivePlotData;
 use crate::data::scope::AxisSettings;
 use crate::data::scope::ScopeData;
 use crate::data::thresholds::{ThresholdDef, ThresholdEvent, ThresholdKind};
-use crate::data::traces::TracesCollection;
+use crate::data::traces::{TraceRef, TracesCollection};
 use crate::panels::trace_look_ui::render_trace_look_editor;
 use chrono::Local;
 use egui;
@@ -12,7 +12,7 @@ use egui_phosphor_icons::icons::{BROOM, FILE_TEXT, PLUS, WARNING, X};
 use egui_plot::{HLine, LineStyle, MarkerShape, Points, VLine};
 use egui_table::{HeaderRow as EgHeaderRow, Table, TableDelegate};
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // Builder state removed; we edit a ThresholdDef directly
 
@@ -26,6 +26,8 @@ pub struct ThresholdsPanel {
     pub thresholds: HashMap<String, ThresholdDef>,
     pub events_filter: Option<String>,
     hover_threshold: Option<String>,
+    /// Companion flag traces emitted last frame, to remove ones no longer wanted.
+    flag_traces: HashSet<TraceRef>,
 }
 
 impl Default for ThresholdsPanel {
@@ -39,6 +41,7 @@ impl Default for ThresholdsPanel {
             thresholds: HashMap::new(),
             events_filter: None,
             hover_threshold: None,
+            flag_traces: HashSet::new(),
         }
     }
 }
@@ -173,16 +176,7 @@ impl Panel for ThresholdsPanel {
                     };
 
                     let mut draw_hline = |label: &str, y_world: f64| {
-                        let y_lin = y_world + tr.offset;
-                        let y_plot = if scope.y_axis.log_scale {
-                            if y_lin > 0.0 {
-                                y_lin.log10()
-                            } else {
-                                f64::NAN
-                            }
-                        } else {
-                            y_lin
-                        };
+                        let y_plot = scope.value_to_plot_y(tr, tr.display_y(y_world));
                         if y_plot.is_finite() {
                             let h = HLine::new(label, y_plot)
                                 .color(thr_color)
@@ -192,7 +186,7 @@ impl Panel for ThresholdsPanel {
                         }
                     };
 
-                    let thr_info = def.get_info(&scope.y_axis);
+                    let thr_info = def.get_info(scope.value_axis(tr));
                     let legend_label = if scope.show_info_in_legend {
                         format!("{} — {}", def.name, thr_info)
                     } else {
@@ -206,10 +200,13 @@ impl Panel for ThresholdsPanel {
                         ThresholdKind::LessThan { value } => {
                             draw_hline(&legend_label, value);
                         }
-                        ThresholdKind::InRange { low, high } => {
+                        ThresholdKind::InRange { low, high }
+                        | ThresholdKind::Hysteresis { high, low } => {
                             draw_hline(&legend_label, low);
                             draw_hline(&legend_label, high);
                         }
+                        // A slope limit has no level to draw.
+                        ThresholdKind::RateExceeds { .. } => {}
                     }
 
                     let state = def.get_runtime_state();
@@ -218,17 +215,10 @@ impl Panel for ThresholdsPanel {
                         ThresholdKind::GreaterThan { value } => value,
                         ThresholdKind::LessThan { value } => value,
                         ThresholdKind::InRange { low, high } => (low + high) * 0.5,
+                        ThresholdKind::Hysteresis { high, .. } => high,
+                        ThresholdKind::RateExceeds { .. } => 0.0,
                     };
-                    let y_lin = marker_y_world + tr.offset;
-                    let marker_y_plot = if scope.y_axis.log_scale {
-                        if y_lin > 0.0 {
-                            y_lin.log10()
-                        } else {
-                            f64::NAN
-                        }
-                    } else {
-                        y_lin
-                    };
+                    let marker_y_plot = scope.value_to_plot_y(tr, tr.display_y(marker_y_world));
                     if marker_y_plot.is_finite() {
                         for ev in state.events.iter() {
                             if ev.end_t < xmin || ev.start_t > xmax {
@@ -280,12 +270,86 @@ impl Panel for ThresholdsPanel {
             self.clear_all();
             data.pending_requests.clear_thresholds = false;
         }
+        self.thresholds.retain(|name, def| {
+            if !data.traces.was_removed(&def.target) {
+                return true;
+            }
+            if let Some(ctrl) = &data.event_ctrl {
+                let mut evt =
+                    crate::events::PlotEvent::new(crate::events::EventKind::THRESHOLD_REMOVED);
+                evt.threshold = Some(crate::events::ThresholdMeta {
+                    threshold_name: name.clone(),
+                    trace: Some(def.target.clone()),
+                    start_t: None,
+                    end_t: None,
+                    duration: None,
+                    area: None,
+                });
+                ctrl.emit_filtered(evt);
+            }
+            false
+        });
 
-        let sources = data.get_all_drawn_points();
+        let mut sources = data.get_all_drawn_points();
+        // Targets shown in no scope, e.g. a math trace that was never added to
+        // a plot, are read straight from their buffer.
+        let paused = data.are_all_paused();
+        for def in self.thresholds.values() {
+            if sources.contains_key(&def.target) {
+                continue;
+            }
+            if let Some(pts) = data.traces.get_points_ref(&def.target, paused) {
+                sources.insert(def.target.clone(), pts.iter().copied().collect());
+            }
+        }
 
         for def in self.thresholds.values_mut() {
             def.process_threshold(sources.clone());
         }
+
+        // Append this frame's flags to the companion traces, trimmed to the
+        // target's retained range.  Deleting a flag trace from the traces list
+        // turns its option off, like deleting a math trace drops its definition.
+        let mut flag_traces = HashSet::new();
+        for def in self.thresholds.values_mut() {
+            let name = def.flag_trace_name();
+            if def.emit_flag_trace
+                && self.flag_traces.contains(&name)
+                && data.traces.was_removed(&name)
+            {
+                def.emit_flag_trace = false;
+            }
+            if !def.emit_flag_trace {
+                continue;
+            }
+            let first_t = data
+                .traces
+                .get_trace(&def.target)
+                .and_then(|tr| tr.live.front())
+                .map(|p| p[0]);
+            let tr = data.get_trace_or_new(&name);
+            tr.is_math = true;
+            tr.info = format!("1 while threshold '{}' is exceeded", def.name);
+            tr.live.extend(def.runtime_state.flag_points.drain(..));
+            if let Some(t0) = first_t {
+                while tr.live.front().is_some_and(|p| p[0] < t0) {
+                    tr.live.pop_front();
+                }
+            }
+            flag_traces.insert(name);
+        }
+        for name in self.flag_traces.difference(&flag_traces) {
+            if !data.traces.was_removed(name) {
+                data.remove_trace(name);
+            }
+        }
+        self.flag_traces = flag_traces;
+
+        for scope in data.scope_data.iter_mut() {
+            scope.threshold_active = self.thresholds.values().any(|def| {
+                def.get_runtime_state().is_active() && scope.trace_order.contains(&def.target)
+            });
+        }
     }
 
     fn render_panel(&mut self, ui: &mut Ui, data: &mut LivePlotData<'_>) {
@@ -342,7 +406,8 @@ impl Panel for ThresholdsPanel {
                 let default_axis = AxisSettings::default();
                 let axis_setting = data
                     .scope_containing_trace(&def.target)
-                    .map(|scope| &scope.y_axis)
+                    .zip(data.traces.get_trace(&def.target))
+                    .map(|(scope, target)| scope.value_axis(target))
                     .unwrap_or(&default_axis);
                 let info_text = def.get_info(axis_setting);
                 let info_resp = ui.add(
@@ -409,9 +474,7 @@ impl Panel for ThresholdsPanel {
                     self.error = None;
                 }
             } else if action_clear {
-                if let Some(def) = self.thresholds.get_mut(&name) {
-                    def.clear_threshold_events();
-                }
+                self.clear_events(Some(&name));
             }
             if row.response.hovered() {
                 self.hover_threshold = Some(name.clone());
@@ -511,7 +574,17 @@ impl Panel for ThresholdsPanel {
                     let _resp = resp.on_hover_text("Enter a unique name for this threshold");
                 }
             });
-            let trace_names = data.traces.all_trace_names();
+            // Raw and math traces alike, in creation order; a threshold cannot
+            // watch its own flag trace.
+            let own_flag = self.builder.flag_trace_name();
+            let mut trace_names = data.traces.all_trace_names();
+            trace_names.retain(|n| *n != own_flag);
+            trace_names.sort_by_key(|n| {
+                data.traces
+                    .get_trace(n)
+                    .map(|t| t.creation_index)
+                    .unwrap_or(usize::MAX)
+            });
             let mut target_idx = trace_names
                 .iter()
                 .position(|n| n == &self.builder.target)
@@ -543,11 +616,13 @@ impl Panel for ThresholdsPanel {
                     }
                 }
             }
-            let kinds = [">", "<", "in range"];
+            let kinds = [">", "<", "in range", "> (hysteresis)", "|rate| >"];
             let mut kind_idx: usize = match &self.builder.kind {
                 ThresholdKind::GreaterThan { .. } => 0,
                 ThresholdKind::LessThan { .. } => 1,
                 ThresholdKind::InRange { .. } => 2,
+                ThresholdKind::Hysteresis { .. } => 3,
+                ThresholdKind::RateExceeds { .. } => 4,
             };
             egui::ComboBox::from_label("Condition")
                 .selected_text(kinds[kind_idx])
@@ -594,16 +669,53 @@ impl Panel for ThresholdsPanel {
                         *high = lo.max(hi);
                     }
                 }
+                (ThresholdKind::Hysteresis { high, low }, 3) => {
+                    let mut hi = *high;
+                    let mut lo = *low;
+                    ui.horizontal(|ui| {
+                        ui.label("Start above");
+                        ui.add(egui::DragValue::new(&mut hi).speed(0.01));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Release below");
+                        ui.add(egui::DragValue::new(&mut lo).speed(0.01));
+                    });
+                    if lo != *low || hi != *high {
+                        *low = lo.min(hi);
+                        *high = lo.max(hi);
+                    }
+                }
+                (ThresholdKind::RateExceeds { units_per_sec }, 4) => {
+                    ui.horizontal(|ui| {
+                        ui.label("Units/s");
+                        ui.add(
+                            egui::DragValue::new(units_per_sec)
+                                .speed(0.01)
+                                .range(0.0..=f64::INFINITY),
+                        );
+                    });
+                }
                 // Variant switch requested
                 (old_kind, new_idx) => {
                     let (v1, v2) = match old_kind {
                         ThresholdKind::GreaterThan { value } => (*value, *value),
                         ThresholdKind::LessThan { value } => (*value, *value),
-                        ThresholdKind::InRange { low, high } => (*low, *high),
+                        ThresholdKind::InRange { low, high }
+                        | ThresholdKind::Hysteresis { high, low } => (*low, *high),
+                        ThresholdKind::RateExceeds { units_per_sec } => {
+                            (*units_per_sec, *units_per_sec)
+                        }
                     };
                     self.builder.kind = match new_idx {
                         0 => ThresholdKind::GreaterThan { value: v1 },
                         1 => ThresholdKind::LessThan { value: v1 },
+                        3 => ThresholdKind::Hysteresis {
+                            high: v1.max(v2),
+                            low: v1.min(v2),
+                        },
+                        4 => ThresholdKind::RateExceeds {
+                            units_per_sec: v1.abs(),
+                        },
                         _ => ThresholdKind::InRange {
                             low: v1.min(v2),
                             high: v1.max(v2),
@@ -621,7 +733,8 @@ impl Panel for ThresholdsPanel {
                                 }
                             });
                         }
-                        ThresholdKind::InRange { low, high } => {
+                        ThresholdKind::InRange { low, high }
+                        | ThresholdKind::Hysteresis { high, low } => {
                             let mut lo = *low;
                             let mut hi = *high;
                             ui.horizontal(|ui| {
@@ -637,6 +750,16 @@ impl Panel for ThresholdsPanel {
                                 *high = lo.max(hi);
                             }
                         }
+                        ThresholdKind::RateExceeds { units_per_sec } => {
+                            ui.horizontal(|ui| {
+                                ui.label("Units/s");
+                                ui.add(
+                                    egui::DragValue::new(units_per_sec)
+                                        .speed(0.01)
+                                        .range(0.0..=f64::INFINITY),
+                                );
+                            });
+                        }
                     }
                 }
             }
@@ -647,10 +770,26 @@ impl Panel for ThresholdsPanel {
                     self.builder.min_duration_s = (ms / 1000.0).max(0.0);
                 }
             });
+            ui.horizontal(|ui| {
+                ui.label("Debounce (ms)");
+                let mut ms = self.builder.debounce_secs * 1000.0;
+                if ui.add(egui::DragValue::new(&mut ms).speed(1.0)).changed() {
+                    self.builder.debounce_secs = (ms / 1000.0).max(0.0);
+                }
+            })
+            .response
+            .on_hover_text("Ignore new crossings this long after the previous event ended");
             ui.horizontal(|ui| {
                 ui.label("Max events");
                 ui.add(egui::DragValue::new(&mut self.builder.max_events).speed(1));
             });
+            ui.checkbox(&mut self.builder.notify, "Notify")
+                .on_hover_text("Raise a THRESHOLD_ALARM event for each crossing");
+            ui.checkbox(&mut self.builder.emit_flag_trace, "Flag trace")
+                .on_hover_text(format!(
+                    "Add a '{}' trace that is 1 while the threshold is exceeded and 0 otherwise",
+                    self.builder.flag_trace_name().0
+                ));
 
             // Collapsible style editors (moved here, just before Save/Add)
             ui.add_space(5.0);
@@ -957,6 +1096,24 @@ impl ThresholdsPanel {
             def.clear_threshold_events();
         }
     }
+
+    /// Clear the events of the named threshold, or of all thresholds for `None`.
+    /// Returns the names whose history was cleared.
+    pub fn clear_events(&mut self, name: Option<&str>) -> Vec<String> {
+        match name {
+            Some(name) => match self.thresholds.get_mut(name) {
+                Some(def) => {
+                    def.clear_threshold_events();
+                    vec![name.to_string()]
+                }
+                None => Vec::new(),
+            },
+            None => {
+                self.clear_all_events();
+                self.thresholds.keys().cloned().collect()
+            }
+        }
+    }
 }
 // Removed unused show_thresholds_dialog helper; dialogs are shown via DockPanel::show_detached_dialog
 
@@ -1,14 +1,22 @@
 use egui::Ui;
 use egui_plot::LineStyle;
 
-use crate::data::trace_look::TraceLook;
+use crate::data::trace_look::{ErrorStyle, TraceLook};
 
 pub fn render_trace_look_editor(look: &mut TraceLook, ui: &mut Ui, allow_points: bool) {
     ui.horizontal(|ui| {
         ui.label("Color");
         let mut c = look.color;
         if ui.color_edit_button_srgba(&mut c).changed() {
-            look.color = c;
+            look.set_custom_color(c);
+        }
+        if look.color_is_custom
+            && ui
+                .small_button("Auto")
+                .on_hover_text("Use the palette color again on the next recolor")
+                .clicked()
+        {
+            look.color_is_custom = false;
         }
         ui.label("Width");
         ui.add(
@@ -59,6 +67,45 @@ pub fn render_trace_look_editor(look: &mut TraceLook, ui: &mut Ui, allow_points:
         LineStyle::Solid => {}
     }
     if allow_points {
+        egui::ComboBox::from_label("Error display")
+            .selected_text(match look.error_style {
+                ErrorStyle::Off => "Off",
+                ErrorStyle::Bars => "Bars",
+                ErrorStyle::Band => "Band",
+            })
+            .show_ui(ui, |ui| {
+                for (style, label) in [
+                    (ErrorStyle::Off, "Off"),
+                    (ErrorStyle::Bars, "Bars"),
+                    (ErrorStyle::Band, "Band"),
+                ] {
+                    if ui
+                        .selectable_label(look.error_style == style, label)
+                        .clicked()
+                    {
+                        look.error_style = style;
+                    }
+                }
+            })
+            .response
+            .on_hover_text("How per-sample uncertainties are drawn, if the source sends them");
+        ui.horizontal(|ui| {
+            let mut fill = look.fill_baseline.is_some();
+            if ui
+                .checkbox(&mut fill, "Fill to")
+                .on_hover_text("Shade the area between the trace and a baseline value")
+                .changed()
+            {
+                look.fill_baseline = fill.then_some(0.0);
+            }
+            let mut baseline = look.fill_baseline.unwrap_or(0.0);
+            if ui
+                .add_enabled(fill, egui::DragValue::new(&mut baseline).speed(0.01))
+                .changed()
+            {
+                look.fill_baseline = Some(baseline);
+            }
+        });
         ui.separator();
         ui.checkbox(&mut look.show_points, "Points");
         ui.checkbox(&mut look.highlight_newest_point, "Highlight newest point")
@@ -1,9 +1,14 @@
 use super::panel_trait::{Panel, PanelState};
-use crate::data::scope::ScopeType;
-use crate::data::{data::LivePlotData, traces::TraceRef};
+use crate::data::scope::{ScopeType, YAxisSelect};
+use crate::data::{
+    data::LivePlotData,
+    traces::{ActivityRule, TraceRef},
+};
 use eframe::egui;
-use egui::{Id, Ui};
-use egui_phosphor_icons::icons::{BROOM, CHART_LINE_UP, DOTS_SIX_VERTICAL, PLUS, X};
+use egui::{Color32, Id, Ui};
+use egui_phosphor_icons::icons::{
+    BROOM, CHART_LINE_UP, DOTS_SIX_VERTICAL, PLUS, SNOWFLAKE, TRASH, X,
+};
 use egui_table::{HeaderRow as EgHeaderRow, Table, TableDelegate};
 
 use super::scope_settings_ui::{DragPayload, ScopeSettingsUiPanel};
@@ -107,7 +112,7 @@ impl Panel for TracesPanel {
                 ui.separator();
 
                 if ui.button(format!("{} Clear All", BROOM.as_str())).clicked() {
-                    data.traces.clear_all();
+                    data.traces.request_clear();
                     ui.close();
                 }
             });
@@ -137,7 +142,7 @@ impl Panel for TracesPanel {
                         if let Some(tr) = data.traces.get_trace_mut(&tn) {
                             egui::Frame::group(ui.style()).show(ui, |ui| {
                                 ui.horizontal(|ui| {
-                                    ui.strong(format!("Style: {}", tn));
+                                    ui.strong(format!("Style: {}", tr.label(&tn)));
                                     ui.with_layout(
                                         egui::Layout::right_to_left(egui::Align::Center),
                                         |ui| {
@@ -151,6 +156,75 @@ impl Panel for TracesPanel {
                                     );
                                 });
                                 ui.separator();
+                                ui.horizontal(|ui| {
+                                    ui.label("Display name:");
+                                    let mut dn = tr.display_name.clone().unwrap_or_default();
+                                    let resp = ui.add(
+                                        egui::TextEdit::singleline(&mut dn)
+                                            .hint_text(tn.as_str())
+                                            .desired_width(160.0),
+                                    );
+                                    if resp.changed() {
+                                        tr.display_name =
+                                            if dn.trim().is_empty() { None } else { Some(dn) };
+                                    }
+                                })
+                                .response
+                                .on_hover_text(
+                                    "Label shown in legends and menus. Leave empty to use the trace id.",
+                                );
+                                ui.horizontal(|ui| {
+                                    ui.label("Smoothing:");
+                                    ui.add(
+                                        egui::DragValue::new(&mut tr.display_smooth_window)
+                                            .range(0..=1000)
+                                            .suffix(" samples"),
+                                    );
+                                })
+                                .response
+                                .on_hover_text(
+                                    "Moving-average window applied when drawing only. Stored data, exports and measurements are unaffected. 0 or 1 disables smoothing.",
+                                );
+                                ui.checkbox(&mut tr.invert, "Invert (negate values)")
+                                    .on_hover_text(
+                                        "Flip the sign for plotting, readouts and exports, e.g. for a sensor wired backwards. Applied before the offset.",
+                                    );
+                                ui.horizontal(|ui| {
+                                    ui.label("Y axis:");
+                                    ui.selectable_value(&mut tr.axis, YAxisSelect::Left, "Left");
+                                    ui.selectable_value(&mut tr.axis, YAxisSelect::Right, "Right");
+                                })
+                                .response
+                                .on_hover_text(
+                                    "Axis the trace is scaled against on time scopes with a right Y axis (enabled in the scope's Y-axis menu).",
+                                );
+                                ui.horizontal(|ui| {
+                                    let mut enabled = tr.auto_hide.is_some();
+                                    if ui
+                                        .checkbox(&mut enabled, "Hide when idle")
+                                        .on_hover_text(
+                                            "Draw the trace only while its peak-to-peak value over the window exceeds the threshold. The visibility checkbox is not changed.",
+                                        )
+                                        .changed()
+                                    {
+                                        tr.auto_hide = enabled.then(ActivityRule::default);
+                                    }
+                                    if let Some(rule) = tr.auto_hide.as_mut() {
+                                        ui.label("p-p >");
+                                        ui.add(
+                                            egui::DragValue::new(&mut rule.min_peak_to_peak)
+                                                .range(0.0..=f64::MAX)
+                                                .speed(0.01),
+                                        );
+                                        ui.label("over");
+                                        ui.add(
+                                            egui::DragValue::new(&mut rule.window_secs)
+                                                .range(0.001..=3600.0)
+                                                .speed(0.1)
+                                                .suffix(" s"),
+                                        );
+                                    }
+                                });
                                 render_trace_look_editor(&mut tr.look, ui, true);
                             });
                         } else {
@@ -213,6 +287,52 @@ impl Panel for TracesPanel {
                     .text("0 = disabled"),
                 );
 
+                let stats = data.traces.ingest_stats();
+                ui.horizontal(|ui| {
+                    let mut text = format!(
+                        "Received {} ({:.0}/s) · dropped {} ({:.0}/s) · evicted {} ({:.0}/s)",
+                        stats.received,
+                        stats.received_rate,
+                        stats.dropped,
+                        stats.dropped_rate,
+                        stats.evicted,
+                        stats.evicted_rate,
+                    );
+                    if stats.out_of_order > 0 {
+                        text.push_str(&format!(" · out of order {}", stats.out_of_order));
+                    }
+                    let color = if stats.dropped > 0 {
+                        Color32::LIGHT_RED
+                    } else {
+                        ui.visuals().weak_text_color()
+                    };
+                    ui.colored_label(color, text).on_hover_text(
+                        "Dropped: samples for unknown or removed traces, and \
+                         rejected out-of-order samples.\n\
+                         Evicted: samples pushed out by Data Points / Max Age.\n\
+                         Out of order: samples older than their trace's last sample.",
+                    );
+                    if ui.small_button("Reset").clicked() {
+                        data.traces.reset_ingest_stats();
+                    }
+                });
+
+                if ui
+                    .button("Recolor all")
+                    .on_hover_text(
+                        "Reassign palette colors in scope trace order. \
+                         Colors you picked yourself are kept.",
+                    )
+                    .clicked()
+                {
+                    let orders: Vec<&[TraceRef]> = data
+                        .scope_data
+                        .iter()
+                        .map(|s| s.trace_order.as_slice())
+                        .collect();
+                    data.traces.recolor_all(&orders);
+                }
+
                 ui.separator();
 
                 self.hover_trace = None;
@@ -235,6 +355,8 @@ impl Panel for TracesPanel {
                     hover_out: &'a mut Option<Vec<TraceRef>>,
                     look_toggle: &'a mut Option<TraceRef>,
                     drag_out: &'a mut Option<DragPayload>,
+                    delete_out: &'a mut Option<TraceRef>,
+                    duplicate_out: &'a mut Option<TraceRef>,
                     rows: Vec<Row>,
                 }
                 impl<'a> TableDelegate for TracesDelegate<'a> {
@@ -324,7 +446,7 @@ impl Panel for TracesPanel {
                                                 *self.hover_out = Some(vec![r.name.clone()]);
                                             }
                                             if resp.changed() {
-                                                tr.look.color = c;
+                                                tr.look.set_custom_color(c);
                                             }
                                         }
                                     },
@@ -337,9 +459,10 @@ impl Panel for TracesPanel {
                                     .get_trace(&r.name)
                                     .map(|tr| trace_tooltip(&r.name, &tr.info))
                                     .unwrap_or_else(|| r.name.0.clone());
+                                let label = self.traces.display_name(&r.name);
                                 let resp = ui
                                     .add(
-                                        egui::Label::new(r.name.0.clone())
+                                        egui::Label::new(label)
                                             .truncate()
                                             .show_tooltip_when_elided(true)
                                             .sense(egui::Sense::click_and_drag()),
@@ -358,6 +481,22 @@ impl Panel for TracesPanel {
                                 if resp.clicked() {
                                     *self.look_toggle = Some(r.name.clone());
                                 }
+                                resp.context_menu(|ui| {
+                                    if ui
+                                        .button(format!(
+                                            "{} Duplicate as static",
+                                            SNOWFLAKE.as_str()
+                                        ))
+                                        .on_hover_text(
+                                            "Copy the current data into a new trace that no \
+                                             longer updates",
+                                        )
+                                        .clicked()
+                                    {
+                                        *self.duplicate_out = Some(r.name.clone());
+                                        ui.close();
+                                    }
+                                });
                             }
                             3 => {
                                 // Offset DragValue centered.
@@ -404,13 +543,31 @@ impl Panel for TracesPanel {
                                     }
                                 }
                             }
+                            5 => {
+                                ui.with_layout(
+                                    egui::Layout::centered_and_justified(
+                                        egui::Direction::LeftToRight,
+                                    ),
+                                    |ui| {
+                                        let resp = ui
+                                            .small_button(TRASH)
+                                            .on_hover_text("Delete this trace and its data");
+                                        if resp.hovered() {
+                                            *self.hover_out = Some(vec![r.name.clone()]);
+                                        }
+                                        if resp.clicked() {
+                                            *self.delete_out = Some(r.name.clone());
+                                        }
+                                    },
+                                );
+                            }
                             _ => {}
                         }
                     }
                 }
 
                 // Auto-size columns, but constrain them with min/max ranges.
-                // Columns: Drag, Color, Trace, Offset, Info.
+                // Columns: Drag, Color, Trace, Offset, Info, Delete.
                 let cols = vec![
                     // Drag handle: very compact
                     egui_table::Column::new(18.0).range(egui::Rangef::new(18.0, 18.0)),
@@ -422,6 +579,8 @@ impl Panel for TracesPanel {
                     egui_table::Column::new(50.0).range(egui::Rangef::new(50.0, 50.0)),
                     // Info: flexible (large max so the table can still fill wide panels)
                     egui_table::Column::new(200.0).range(egui::Rangef::new(100.0, 360.0)),
+                    // Delete button: compact
+                    egui_table::Column::new(24.0).range(egui::Rangef::new(24.0, 24.0)),
                 ];
                 // Compute a preferred height for the table; size it relative to available height
                 let header_h = 24.0_f32;
@@ -445,6 +604,8 @@ impl Panel for TracesPanel {
                     let mut hover_tmp: Option<Vec<TraceRef>> = None;
                     let mut look_toggle_req: Option<TraceRef> = None;
                     let mut drag_from_table: Option<DragPayload> = None;
+                    let mut delete_req: Option<TraceRef> = None;
+                    let mut duplicate_req: Option<TraceRef> = None;
                     // Borrow traces mutably for the table drawing scope only.
                     let traces_ref = &mut *data.traces;
                     let mut delegate = TracesDelegate {
@@ -452,6 +613,8 @@ impl Panel for TracesPanel {
                         hover_out: &mut hover_tmp,
                         look_toggle: &mut look_toggle_req,
                         drag_out: &mut drag_from_table,
+                        delete_out: &mut delete_req,
+                        duplicate_out: &mut duplicate_req,
                         rows: rows_clone,
                     };
 
@@ -479,6 +642,16 @@ impl Panel for TracesPanel {
                     if let Some(dragged) = drag_from_table {
                         self.dragging_trace = Some(dragged);
                     }
+                    if let Some(name) = delete_req {
+                        if self.look_editor_trace.as_ref() == Some(&name) {
+                            self.look_editor_trace = None;
+                        }
+                        data.remove_trace(&name);
+                        self.hover_trace = None;
+                    }
+                    if let Some(name) = duplicate_req {
+                        data.duplicate_as_static(&name);
+                    }
                     if let Some(tn) = look_toggle_req {
                         if self.look_editor_trace.as_deref() == Some(tn.as_str()) {
                             self.look_editor_trace = None;
@@ -537,11 +710,8 @@ impl Panel for TracesPanel {
             for name in scope.trace_order.iter() {
                 if seen.insert(name.0.clone()) {
                     if let Some(tr) = data.traces.get_trace(name) {
-                        trace_styles.push(crate::persistence::TraceStyleSerde {
-                            name: name.0.clone(),
-                            look: crate::persistence::TraceLookSerde::from(&tr.look),
-                            offset: tr.offset,
-                        });
+                        trace_styles
+                            .push(crate::persistence::TraceStyleSerde::from_trace(name, tr));
                     }
                 }
             }
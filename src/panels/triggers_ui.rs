@@ -3,7 +3,7 @@ use crate::data::data::LivePlotData;
 use crate::data::scope::ScopeData;
 use crate::data::traces::TraceRef;
 use crate::data::traces::TracesCollection;
-use crate::data::triggers::{Trigger, TriggerSlope};
+use crate::data::triggers::{Trigger, TriggerCapture, TriggerSlope};
 use crate::panels::trace_look_ui::render_trace_look_editor;
 use egui::Ui;
 use egui_phosphor_icons::icons::{BELL, CROSSHAIR, PLUS};
@@ -17,6 +17,8 @@ pub struct TriggersPanel {
     pub editing: Option<String>,
     /// When `true`, the next click on the plot will set the builder's level to the Y coordinate.
     pub pick_level_pending: bool,
+    /// Captures completed since the last [`take_captures`](Self::take_captures).
+    captures: Vec<TriggerCapture>,
 }
 
 impl Default for TriggersPanel {
@@ -27,6 +29,7 @@ impl Default for TriggersPanel {
             builder: None,
             editing: None,
             pick_level_pending: false,
+            captures: Vec::new(),
         };
         // Add one default trigger on startup (disabled)
         let mut t = Trigger::default();
@@ -172,19 +175,10 @@ impl Panel for TriggersPanel {
             let style = trig.look.style;
 
             // Draw horizontal trigger level line
-            let y_lin = trig.level + tr.offset;
-            let y_plot = if scope.y_axis.log_scale {
-                if y_lin > 0.0 {
-                    y_lin.log10()
-                } else {
-                    f64::NAN
-                }
-            } else {
-                y_lin
-            };
+            let y_plot = scope.value_to_plot_y(tr, tr.display_y(trig.level));
             if y_plot.is_finite() {
                 // Legend label can include info text
-                let info = trig.get_info(&scope.y_axis);
+                let info = trig.get_info(scope.value_axis(tr));
                 let label = if scope.show_info_in_legend {
                     format!("{} — {}", trig.name, info)
                 } else {
@@ -234,12 +228,28 @@ impl Panel for TriggersPanel {
             data.pending_requests.clear_triggers = false;
         }
 
+        // Arm/force/disarm requests from a TriggerController.  Arming or
+        // forcing resumes the stream so a new capture can complete.
+        for (name, cmd) in std::mem::take(&mut data.pending_requests.trigger_commands) {
+            let Some(tr) = self.triggers.get_mut(&name) else {
+                eprintln!("liveplot: no trigger named {:?}", name);
+                continue;
+            };
+            if cmd != crate::data::triggers::TriggerCommand::Disarm && tr.is_triggered() {
+                data.resume_all();
+            }
+            tr.apply_command(cmd);
+        }
+
         // Handle "Pick Y level" from plot click
         if self.pick_level_pending {
             for scope in data.scope_data.iter_mut() {
                 if let Some(point) = scope.clicked_point.take() {
                     if let Some(builder) = &mut self.builder {
-                        builder.level = point[1];
+                        builder.level = data
+                            .traces
+                            .get_trace(&builder.target)
+                            .map_or(point[1], |target| scope.plot_y_to_value(target, point[1]));
                     }
                     self.pick_level_pending = false;
                     scope.measurement_active = false;
@@ -251,12 +261,22 @@ impl Panel for TriggersPanel {
         {
             let scope_ids: Vec<usize> = data.scope_data.iter().map(|scope| (**scope).id).collect();
             for scope_id in scope_ids {
-                for (_name, tr) in self.triggers.iter_mut() {
+                for (name, tr) in self.triggers.iter_mut() {
                     // Skip single-shot triggers that have already fired
                     if tr.single_shot && tr.is_triggered() {
                         continue;
                     }
-                    if tr.check_trigger(data) && tr.is_triggered() {
+                    let before = tr.last_trigger_time();
+                    let fired = tr.check_trigger(data);
+                    let after = tr.last_trigger_time();
+                    if let Some(time) = after.filter(|_| after != before) {
+                        self.captures.push(TriggerCapture {
+                            trigger: name.clone(),
+                            target: tr.target.clone(),
+                            time,
+                        });
+                    }
+                    if fired && tr.is_triggered() {
                         if let Some(scope) = data.scope_by_id_mut(scope_id) {
                             let tr_time = tr.last_trigger_time().unwrap();
                             let time_window = scope.x_axis.bounds.1 - scope.x_axis.bounds.0;
@@ -321,7 +341,8 @@ impl Panel for TriggersPanel {
             let mut to_remove = false;
             let scope_axes = data
                 .scope_containing_trace(&tr.target)
-                .map(|scope| (scope.x_axis.clone(), scope.y_axis.clone()));
+                .zip(data.traces.get_trace(&tr.target))
+                .map(|(scope, target)| (scope.x_axis.clone(), scope.value_axis(target).clone()));
 
             // Main row: enable toggle, name/info, remove button
             let row = ui.horizontal(|ui| {
@@ -466,14 +487,8 @@ impl Panel for TriggersPanel {
         }
 
         // Apply removals after iteration to avoid mutable borrow conflicts
-        if !removals.is_empty() {
-            for n in removals {
-                self.triggers.remove(&n);
-                if self.editing.as_deref() == Some(&n) {
-                    self.builder = None;
-                    self.editing = None;
-                }
-            }
+        for n in removals {
+            self.remove_trigger(&n);
         }
 
         // New button
@@ -730,6 +745,42 @@ impl Panel for TriggersPanel {
 }
 
 impl TriggersPanel {
+    /// Remove the named trigger, closing its editor if open.
+    pub fn remove_trigger(&mut self, name: &str) {
+        self.triggers.remove(name);
+        if self.editing.as_deref() == Some(name) {
+            self.builder = None;
+            self.editing = None;
+        }
+    }
+
+    /// Current state of every trigger, sorted by name.
+    pub fn triggers_state(&self) -> crate::controllers::TriggersState {
+        let mut triggers: Vec<crate::controllers::TriggerInfo> = self
+            .triggers
+            .values()
+            .map(|t| crate::controllers::TriggerInfo {
+                name: t.name.clone(),
+                target: t.target.clone(),
+                enabled: t.enabled,
+                level: t.level,
+                slope: t.slope,
+                single_shot: t.single_shot,
+                armed: t.is_active(),
+                pending: t.is_trigger_pending(),
+                triggered: t.is_triggered(),
+                last_trigger_time: t.last_trigger_time(),
+            })
+            .collect();
+        triggers.sort_by(|a, b| a.name.cmp(&b.name));
+        crate::controllers::TriggersState { triggers }
+    }
+
+    /// Drain the captures completed since the last call.
+    pub fn take_captures(&mut self) -> Vec<TriggerCapture> {
+        std::mem::take(&mut self.captures)
+    }
+
     pub fn reset_all(&mut self) {
         for (_name, tr) in self.triggers.iter_mut() {
             tr.reset();
@@ -8,13 +8,13 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "fft")]
-use crate::data::fft::FFTWindow;
+use crate::data::fft::{FFTWindow, FftScale, FftView};
 use crate::data::math::MathTrace;
 use crate::data::measurement::Measurement;
 use crate::data::scope::{AxisSettings, ScopeData, ScopeType};
 use crate::data::thresholds::{ThresholdDef, ThresholdKind};
-use crate::data::trace_look::TraceLook;
-use crate::data::traces::TraceRef;
+use crate::data::trace_look::{ErrorStyle, TraceLook};
+use crate::data::traces::{TraceData, TraceDisplayOptions, TraceRef};
 use crate::data::triggers::{Trigger, TriggerSlope};
 use crate::panels::color_scheme_ui::NamedCustomScheme;
 
@@ -179,6 +179,8 @@ pub enum SerMarkerShape {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraceLookSerde {
     pub color_rgba: [u8; 4],
+    #[serde(default)]
+    pub color_is_custom: bool,
     pub visible: bool,
     pub width: f32,
     pub show_points: bool,
@@ -186,6 +188,10 @@ pub struct TraceLookSerde {
     pub style: SerLineStyle,
     pub point_size: f32,
     pub marker: SerMarkerShape,
+    #[serde(default)]
+    pub error_style: ErrorStyle,
+    #[serde(default)]
+    pub fill_baseline: Option<f64>,
 }
 
 impl From<&TraceLook> for TraceLookSerde {
@@ -211,6 +217,7 @@ impl From<&TraceLook> for TraceLookSerde {
         };
         Self {
             color_rgba: [l.color.r(), l.color.g(), l.color.b(), l.color.a()],
+            color_is_custom: l.color_is_custom,
             visible: l.visible,
             width: l.width,
             show_points: l.show_points,
@@ -218,6 +225,8 @@ impl From<&TraceLook> for TraceLookSerde {
             style,
             point_size: l.point_size,
             marker,
+            error_style: l.error_style,
+            fill_baseline: l.fill_baseline,
         }
     }
 }
@@ -252,6 +261,7 @@ impl TraceLookSerde {
                 self.color_rgba[2],
                 self.color_rgba[3],
             ),
+            color_is_custom: self.color_is_custom,
             visible: self.visible,
             width: self.width,
             show_points: self.show_points,
@@ -259,6 +269,8 @@ impl TraceLookSerde {
             style,
             point_size: self.point_size,
             marker,
+            error_style: self.error_style,
+            fill_baseline: self.fill_baseline,
         }
     }
 }
@@ -269,6 +281,48 @@ pub struct TraceStyleSerde {
     pub name: String,
     pub look: TraceLookSerde,
     pub offset: f64,
+    /// Optional user-facing label; the trace id in `name` stays the key.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Display-only moving-average window (0 = off).
+    #[serde(default)]
+    pub display_smooth_window: usize,
+    /// Negate values for display and export.
+    #[serde(default)]
+    pub invert: bool,
+    /// Draw the trace only while it is active.
+    #[serde(default)]
+    pub auto_hide: Option<crate::data::traces::ActivityRule>,
+    /// Y axis the trace is drawn against.
+    #[serde(default)]
+    pub axis: crate::data::scope::YAxisSelect,
+}
+
+impl TraceStyleSerde {
+    /// Capture the persisted settings of `tr` under `name`.
+    pub fn from_trace(name: &TraceRef, tr: &TraceData) -> Self {
+        Self {
+            name: name.0.clone(),
+            look: TraceLookSerde::from(&tr.look),
+            offset: tr.offset,
+            display_name: tr.display_name.clone(),
+            display_smooth_window: tr.display_smooth_window,
+            invert: tr.invert,
+            auto_hide: tr.auto_hide,
+            axis: tr.axis,
+        }
+    }
+
+    /// The display-only settings stored in this entry.
+    pub fn display_options(&self) -> TraceDisplayOptions {
+        TraceDisplayOptions {
+            display_name: self.display_name.clone(),
+            display_smooth_window: self.display_smooth_window,
+            invert: self.invert,
+            auto_hide: self.auto_hide,
+            axis: self.axis,
+        }
+    }
 }
 
 /// Serializable trigger slope.
@@ -354,7 +408,13 @@ pub struct ThresholdSerde {
     pub target: String,
     pub kind: ThresholdKind,
     pub min_duration_s: f64,
+    #[serde(default)]
+    pub debounce_secs: f64,
     pub max_events: usize,
+    #[serde(default)]
+    pub notify: bool,
+    #[serde(default)]
+    pub emit_flag_trace: bool,
     pub look: TraceLookSerde,
     pub start_look: TraceLookSerde,
     pub stop_look: TraceLookSerde,
@@ -368,7 +428,10 @@ impl ThresholdSerde {
             target: d.target.0.clone(),
             kind: d.kind.clone(),
             min_duration_s: d.min_duration_s,
+            debounce_secs: d.debounce_secs,
             max_events: d.max_events,
+            notify: d.notify,
+            emit_flag_trace: d.emit_flag_trace,
             look: TraceLookSerde::from(&d.look),
             start_look: TraceLookSerde::from(&d.start_look),
             stop_look: TraceLookSerde::from(&d.stop_look),
@@ -382,7 +445,10 @@ impl ThresholdSerde {
         d.target = TraceRef(self.target);
         d.kind = self.kind;
         d.min_duration_s = self.min_duration_s;
+        d.debounce_secs = self.debounce_secs;
         d.max_events = self.max_events;
+        d.notify = self.notify;
+        d.emit_flag_trace = self.emit_flag_trace;
         d.look = self.look.into_look();
         d.start_look = self.start_look.into_look();
         d.stop_look = self.stop_look.into_look();
@@ -431,9 +497,15 @@ pub struct ScopeStateSerde {
     pub scope_is_xy: bool,
     pub show_legend: bool,
     pub show_info_in_legend: bool,
+    /// Legend statistics window in seconds (`0.0` = off).
+    #[serde(default)]
+    pub legend_stats_secs: f64,
     /// Position of the legend within the plot area.
     #[serde(default)]
     pub legend_position: crate::data::scope::LegendPosition,
+    /// What a left click on a legend entry does.
+    #[serde(default)]
+    pub legend_click: crate::data::scope::LegendClick,
     /// Scope id (for multi-scope layouts).
     #[serde(default)]
     pub id: Option<usize>,
@@ -456,6 +528,80 @@ pub struct ScopeStateSerde {
     /// Scroll-wheel zoom mode for this scope.
     #[serde(default)]
     pub zoom_mode: crate::panels::scope_ui::ZoomMode,
+    /// Whether following the latest data pauses while the plot is hovered.
+    #[serde(default)]
+    pub hover_scroll_lock: bool,
+    /// Time constant for smoothing the running x-window edge (`0` = off).
+    #[serde(default)]
+    pub x_smoothing_secs: f64,
+    /// How the x-window is restored on resume.
+    #[serde(default)]
+    pub resume_behavior: crate::data::scope::ResumeBehavior,
+    /// X-window shown before any data arrives.
+    #[serde(default)]
+    pub initial_x: crate::data::scope::InitialXRange,
+    /// Y bounds shown before any data arrives.
+    #[serde(default)]
+    pub initial_y: Option<(f64, f64)>,
+    /// How traces are decimated for drawing.
+    #[serde(default)]
+    pub decimation: crate::data::traces::DecimationMethod,
+    /// Trace plotted on the x-axis of a time scope instead of time.
+    #[serde(default)]
+    pub x_source: Option<String>,
+    /// Timestamp tolerance for pairing XY / x-source samples.
+    #[serde(default = "default_xy_tolerance")]
+    pub xy_tolerance: f64,
+    #[serde(default)]
+    pub persistence_secs: f64,
+    /// Secondary (right) Y axis, if any.
+    #[serde(default)]
+    pub y_axis2: Option<AxisSettingsSerde>,
+    /// Whether axis labels, ticks and legend are suppressed.
+    #[serde(default)]
+    pub minimal_axes: bool,
+    /// Whether the horizontal reference line is drawn.
+    #[serde(default)]
+    pub show_zero_line: bool,
+    /// Y value of the reference line.
+    #[serde(default)]
+    pub zero_line_y: f64,
+    /// Space above and below the Y bounds, as a fraction of their range.
+    #[serde(default = "default_y_margin")]
+    pub y_margin: f64,
+    /// Whether the plot background is tinted while a threshold is active.
+    #[serde(default)]
+    pub threshold_backdrop: bool,
+    /// Whether the units-per-division readout is drawn.
+    #[serde(default)]
+    pub show_div_readout: bool,
+    /// Whether the cursor coordinate strip is shown below the plot.
+    #[serde(default)]
+    pub show_cursor_readout: bool,
+    /// Whether pausing freezes a snapshot or only stops following.
+    #[serde(default = "default_snapshot_on_pause")]
+    pub snapshot_on_pause: bool,
+    /// Maximum number of traces drawn (`0` = all).
+    #[serde(default)]
+    pub max_drawn_traces: usize,
+    /// Live samples kept per trace of this scope (`0` = global cap).
+    #[serde(default)]
+    pub max_points: usize,
+    /// Traces always drawn when the limit applies.
+    #[serde(default)]
+    pub pinned_traces: Vec<String>,
+}
+
+fn default_snapshot_on_pause() -> bool {
+    true
+}
+
+fn default_y_margin() -> f64 {
+    crate::data::scope::DEFAULT_Y_MARGIN
+}
+
+fn default_xy_tolerance() -> f64 {
+    crate::data::scope::DEFAULT_XY_TOLERANCE
 }
 
 impl From<&ScopeData> for ScopeStateSerde {
@@ -467,7 +613,9 @@ impl From<&ScopeData> for ScopeStateSerde {
             scope_is_xy: matches!(s.scope_type, ScopeType::XYScope),
             show_legend: s.show_legend,
             show_info_in_legend: s.show_info_in_legend,
+            legend_stats_secs: s.legend_stats_secs,
             legend_position: s.legend_position,
+            legend_click: s.legend_click,
             id: Some(s.id),
             name: Some(s.name.clone()),
             trace_order: s.trace_order.iter().map(|t| t.0.clone()).collect(),
@@ -481,6 +629,27 @@ impl From<&ScopeData> for ScopeStateSerde {
                 })
                 .collect(),
             pause_on_click: s.pause_on_click,
+            hover_scroll_lock: s.hover_scroll_lock,
+            x_smoothing_secs: s.x_smoothing_secs,
+            resume_behavior: s.resume_behavior,
+            initial_x: s.initial_x,
+            initial_y: s.initial_y,
+            decimation: s.decimation,
+            x_source: s.x_source.as_ref().map(|t| t.0.clone()),
+            xy_tolerance: s.xy_tolerance,
+            persistence_secs: s.persistence_secs,
+            y_axis2: s.y_axis2.as_ref().map(AxisSettingsSerde::from),
+            minimal_axes: s.minimal_axes,
+            show_zero_line: s.show_zero_line,
+            zero_line_y: s.zero_line_y,
+            y_margin: s.y_margin,
+            threshold_backdrop: s.threshold_backdrop,
+            show_div_readout: s.show_div_readout,
+            show_cursor_readout: s.show_cursor_readout,
+            snapshot_on_pause: s.snapshot_on_pause,
+            max_drawn_traces: s.max_drawn_traces,
+            max_points: s.max_points,
+            pinned_traces: s.pinned_traces.iter().map(|t| t.0.clone()).collect(),
             controls_in_toolbar: false,
             zoom_mode: crate::panels::scope_ui::ZoomMode::default(),
         }
@@ -509,7 +678,9 @@ impl ScopeStateSerde {
         };
         scope.show_legend = self.show_legend;
         scope.show_info_in_legend = self.show_info_in_legend;
+        scope.legend_stats_secs = self.legend_stats_secs;
         scope.legend_position = self.legend_position;
+        scope.legend_click = self.legend_click;
         if let Some(name) = self.name {
             scope.name = name;
         }
@@ -524,6 +695,31 @@ impl ScopeStateSerde {
                 .collect();
         }
         scope.pause_on_click = self.pause_on_click;
+        scope.hover_scroll_lock = self.hover_scroll_lock;
+        scope.x_smoothing_secs = self.x_smoothing_secs;
+        scope.resume_behavior = self.resume_behavior;
+        scope.initial_x = self.initial_x;
+        scope.initial_y = self.initial_y;
+        scope.decimation = self.decimation;
+        scope.x_source = self.x_source.map(TraceRef);
+        scope.xy_tolerance = self.xy_tolerance;
+        scope.persistence_secs = self.persistence_secs.max(0.0);
+        scope.y_axis2 = self.y_axis2.map(|a| {
+            let mut axis = AxisSettings::default();
+            a.apply_to(&mut axis);
+            axis
+        });
+        scope.minimal_axes = self.minimal_axes;
+        scope.show_zero_line = self.show_zero_line;
+        scope.zero_line_y = self.zero_line_y;
+        scope.y_margin = self.y_margin;
+        scope.threshold_backdrop = self.threshold_backdrop;
+        scope.show_div_readout = self.show_div_readout;
+        scope.show_cursor_readout = self.show_cursor_readout;
+        scope.snapshot_on_pause = self.snapshot_on_pause;
+        scope.max_drawn_traces = self.max_drawn_traces;
+        scope.max_points = self.max_points;
+        scope.pinned_traces = self.pinned_traces.into_iter().map(TraceRef).collect();
     }
 
     /// Apply stored settings to a full scope panel, including UI-only state.
@@ -546,16 +742,71 @@ fn default_recompute_interval_ms() -> u64 {
     100
 }
 
+#[cfg(feature = "fft")]
+fn default_adaptive_size() -> bool {
+    true
+}
+
+#[cfg(feature = "fft")]
+fn default_dbfs_reference() -> f64 {
+    1.0
+}
+
+#[cfg(feature = "fft")]
+fn default_phase_mask_db() -> f64 {
+    crate::data::fft::DEFAULT_PHASE_MASK_DB
+}
+
+#[cfg(feature = "fft")]
+fn default_peak_count() -> usize {
+    5
+}
+
+#[cfg(feature = "fft")]
+fn default_spectrogram_frames() -> usize {
+    crate::data::fft::DEFAULT_SPECTROGRAM_FRAMES
+}
+
+#[cfg(feature = "fft")]
+fn default_spectrogram_db() -> bool {
+    true
+}
+
 #[cfg(feature = "fft")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FftPanelStateSerde {
     pub fft_size: usize,
     pub fft_window: String,
+    /// Shape parameter of Kaiser/Tukey windows.
+    #[serde(default)]
+    pub fft_window_param: Option<f64>,
     pub fft_db: bool,
     #[serde(default = "default_zero_pad_factor")]
     pub zero_pad_factor: usize,
     #[serde(default = "default_recompute_interval_ms")]
     pub recompute_interval_ms: u64,
+    #[serde(default = "default_adaptive_size")]
+    pub adaptive_size: bool,
+    #[serde(default)]
+    pub magnitude_scale: String,
+    #[serde(default = "default_dbfs_reference")]
+    pub dbfs_reference: f64,
+    #[serde(default)]
+    pub phase: bool,
+    #[serde(default = "default_phase_mask_db")]
+    pub phase_mask_db: f64,
+    #[serde(default)]
+    pub mark_peaks: bool,
+    #[serde(default = "default_peak_count")]
+    pub peak_count: usize,
+    #[serde(default)]
+    pub peak_floor: Option<f64>,
+    #[serde(default)]
+    pub view: String,
+    #[serde(default = "default_spectrogram_frames")]
+    pub spectrogram_frames: usize,
+    #[serde(default = "default_spectrogram_db")]
+    pub spectrogram_db: bool,
     pub scope: ScopeStateSerde,
 }
 
@@ -565,24 +816,54 @@ impl FftPanelStateSerde {
         Self {
             fft_size: panel.fft_data.fft_size,
             fft_window: panel.fft_data.fft_window.label().to_string(),
+            fft_window_param: panel.fft_data.fft_window.param(),
             fft_db: panel.fft_db,
             zero_pad_factor: panel.fft_data.zero_pad_factor,
             recompute_interval_ms: panel.fft_data.recompute_interval_ms,
+            adaptive_size: panel.fft_data.adaptive_size,
+            magnitude_scale: panel.fft_data.magnitude_scale.label().to_string(),
+            dbfs_reference: panel.fft_data.dbfs_reference,
+            phase: panel.fft_data.phase,
+            phase_mask_db: panel.fft_data.phase_mask_db,
+            mark_peaks: panel.mark_peaks,
+            peak_count: panel.peak_count,
+            peak_floor: panel.peak_floor,
+            view: panel.view.label().to_string(),
+            spectrogram_frames: panel.spectrogram_frames,
+            spectrogram_db: panel.spectrogram_db,
             scope: ScopeStateSerde::from(&panel.scope_ui),
         }
     }
 
     pub fn apply_to_panel(&self, panel: &mut crate::panels::fft_ui::FftPanel) {
         panel.fft_data.fft_size = self.fft_size;
-        panel.fft_data.fft_window = match self.fft_window.as_str() {
-            "Rect" => FFTWindow::Rect,
-            "Hamming" => FFTWindow::Hamming,
-            "Blackman" => FFTWindow::Blackman,
-            _ => FFTWindow::Hann,
+        let window = FFTWindow::from_label(&self.fft_window).unwrap_or_default();
+        panel.fft_data.fft_window = match self.fft_window_param {
+            Some(p) => window.with_param(p),
+            None => window,
         };
         panel.fft_db = self.fft_db;
         panel.fft_data.zero_pad_factor = self.zero_pad_factor;
         panel.fft_data.recompute_interval_ms = self.recompute_interval_ms;
+        panel.fft_data.adaptive_size = self.adaptive_size;
+        panel.fft_data.magnitude_scale = FftScale::ALL
+            .iter()
+            .copied()
+            .find(|s| s.label() == self.magnitude_scale)
+            .unwrap_or_default();
+        panel.fft_data.dbfs_reference = self.dbfs_reference;
+        panel.fft_data.phase = self.phase;
+        panel.fft_data.phase_mask_db = self.phase_mask_db.max(0.0);
+        panel.mark_peaks = self.mark_peaks;
+        panel.peak_count = self.peak_count.max(1);
+        panel.peak_floor = self.peak_floor;
+        panel.view = FftView::ALL
+            .iter()
+            .copied()
+            .find(|v| v.label() == self.view)
+            .unwrap_or_default();
+        panel.spectrogram_frames = self.spectrogram_frames.max(2);
+        panel.spectrogram_db = self.spectrogram_db;
         self.scope.clone().apply_to_panel(&mut panel.scope_ui);
         // FFT panel always uses RightTop legend position
         panel.scope_ui.get_data_mut().legend_position =
@@ -727,8 +1008,31 @@ impl Default for AppStateSerde {
                 scope_is_xy: false,
                 show_legend: true,
                 show_info_in_legend: false,
+                legend_stats_secs: 0.0,
                 legend_position: crate::data::scope::LegendPosition::default(),
+                legend_click: crate::data::scope::LegendClick::default(),
                 pause_on_click: false,
+                hover_scroll_lock: false,
+                x_smoothing_secs: 0.0,
+                resume_behavior: crate::data::scope::ResumeBehavior::default(),
+                initial_x: crate::data::scope::InitialXRange::default(),
+                initial_y: None,
+                decimation: crate::data::traces::DecimationMethod::default(),
+                x_source: None,
+                xy_tolerance: crate::data::scope::DEFAULT_XY_TOLERANCE,
+                persistence_secs: 0.0,
+                y_axis2: None,
+                minimal_axes: false,
+                show_zero_line: false,
+                zero_line_y: 0.0,
+                y_margin: crate::data::scope::DEFAULT_Y_MARGIN,
+                threshold_backdrop: false,
+                show_div_readout: false,
+                show_cursor_readout: false,
+                snapshot_on_pause: true,
+                max_drawn_traces: 0,
+                max_points: 0,
+                pinned_traces: Vec::new(),
                 controls_in_toolbar: true,
                 zoom_mode: crate::panels::scope_ui::ZoomMode::default(),
                 id: Some(0),
@@ -781,22 +1085,24 @@ pub fn load_state_from_path(path: &Path) -> Result<AppStateSerde, String> {
 /// Helper to capture trace styles from a traces collection.
 pub fn capture_trace_styles<'a>(
     trace_order: impl Iterator<Item = &'a TraceRef>,
-    get_trace: impl Fn(&TraceRef) -> Option<(&TraceLook, f64)>,
+    get_trace: impl Fn(&TraceRef) -> Option<&'a TraceData>,
 ) -> Vec<TraceStyleSerde> {
     trace_order
-        .filter_map(|name| {
-            get_trace(name).map(|(look, offset)| TraceStyleSerde {
-                name: name.0.clone(),
-                look: TraceLookSerde::from(look),
-                offset,
-            })
-        })
+        .filter_map(|name| get_trace(name).map(|tr| TraceStyleSerde::from_trace(name, tr)))
         .collect()
 }
 
 /// Helper to apply trace styles to a traces collection.
-pub fn apply_trace_styles(styles: &[TraceStyleSerde], mut apply: impl FnMut(&str, TraceLook, f64)) {
+pub fn apply_trace_styles(
+    styles: &[TraceStyleSerde],
+    mut apply: impl FnMut(&str, TraceLook, f64, TraceDisplayOptions),
+) {
     for s in styles {
-        apply(&s.name, s.look.clone().into_look(), s.offset);
+        apply(
+            &s.name,
+            s.look.clone().into_look(),
+            s.offset,
+            s.display_options(),
+        );
     }
 }
@@ -4,8 +4,9 @@
 //! - First create a `Trace` (with name and optional info). The library assigns a numeric ID.
 //! - Send `PlotPoint { x, y }` to a given trace, either singly or in chunks for efficiency.
 
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::mpsc::{Receiver, Sender};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::mpsc::{Receiver, SendError, Sender, SyncSender, TrySendError};
+use std::sync::Arc;
 
 /// Numeric identifier for a trace, assigned by the library when creating a `Trace`.
 pub type TraceId = u32;
@@ -40,6 +41,22 @@ pub enum PlotCommand {
         trace_id: TraceId,
         points: Vec<PlotPoint>,
     },
+    /// Append a batch of points from a high-rate source.
+    ///
+    /// Handled like [`Points`](Self::Points): the buffer is extended in one go
+    /// when the batch is in time order, and pruned once at the end.  Samples
+    /// that would be evicted by the same batch are never stored.
+    AppendBatch {
+        trace_id: TraceId,
+        points: Vec<PlotPoint>,
+    },
+    /// Append a chunk of points with a per-point Y uncertainty (`y_err[i]`
+    /// belongs to `points[i]`; missing or non-finite entries mean "no error").
+    PointsWithError {
+        trace_id: TraceId,
+        points: Vec<PlotPoint>,
+        y_err: Vec<f64>,
+    },
     /// Set the Y value for specific points identified by their exact X coordinates.
     SetPointsY {
         trace_id: TraceId,
@@ -71,20 +88,112 @@ pub enum PlotCommand {
     SetTraceInfo { trace_id: TraceId, info: String },
     /// Remove all data points for the given trace (resulting trace is empty).
     ClearData { trace_id: TraceId },
+    /// Delete the trace entirely: its buffers, its style and its place in every
+    /// scope.  Later commands for the same id are ignored.
+    RemoveTrace { trace_id: TraceId },
     /// Replace the entire data vector for the given trace with the provided points.
     ///
     /// This is intended as an efficient overwrite operation: any existing points
     /// for the trace are discarded and replaced atomically with `points`.
+    /// Suits sources that recompute an entire waveform per update (e.g. a
+    /// swept measurement) instead of streaming samples: the buffer is swapped
+    /// in one step during `TracesCollection::update`, so no append/prune pass
+    /// runs; math traces and thresholds see the new buffer on the next frame.
     SetData {
         trace_id: TraceId,
         points: Vec<PlotPoint>,
     },
 }
 
+impl PlotCommand {
+    /// Id of the trace this command addresses.
+    pub fn trace_id(&self) -> TraceId {
+        match self {
+            PlotCommand::RegisterTrace { id, .. } => *id,
+            PlotCommand::Point { trace_id, .. }
+            | PlotCommand::Points { trace_id, .. }
+            | PlotCommand::AppendBatch { trace_id, .. }
+            | PlotCommand::PointsWithError { trace_id, .. }
+            | PlotCommand::SetPointsY { trace_id, .. }
+            | PlotCommand::DeletePointsX { trace_id, .. }
+            | PlotCommand::DeleteXRange { trace_id, .. }
+            | PlotCommand::ApplyYFnAtX { trace_id, .. }
+            | PlotCommand::ApplyYFnInXRange { trace_id, .. }
+            | PlotCommand::SetTraceInfo { trace_id, .. }
+            | PlotCommand::RemoveTrace { trace_id }
+            | PlotCommand::ClearData { trace_id }
+            | PlotCommand::SetData { trace_id, .. } => *trace_id,
+        }
+    }
+
+    /// Mutable access to the id this command addresses.
+    fn trace_id_mut(&mut self) -> &mut TraceId {
+        match self {
+            PlotCommand::RegisterTrace { id, .. } => id,
+            PlotCommand::Point { trace_id, .. }
+            | PlotCommand::Points { trace_id, .. }
+            | PlotCommand::AppendBatch { trace_id, .. }
+            | PlotCommand::PointsWithError { trace_id, .. }
+            | PlotCommand::SetPointsY { trace_id, .. }
+            | PlotCommand::DeletePointsX { trace_id, .. }
+            | PlotCommand::DeleteXRange { trace_id, .. }
+            | PlotCommand::ApplyYFnAtX { trace_id, .. }
+            | PlotCommand::ApplyYFnInXRange { trace_id, .. }
+            | PlotCommand::SetTraceInfo { trace_id, .. }
+            | PlotCommand::RemoveTrace { trace_id }
+            | PlotCommand::ClearData { trace_id }
+            | PlotCommand::SetData { trace_id, .. } => trace_id,
+        }
+    }
+
+    /// Number of samples this command carries (`0` for edits and metadata).
+    pub fn sample_count(&self) -> usize {
+        match self {
+            PlotCommand::Point { .. } => 1,
+            PlotCommand::Points { points, .. }
+            | PlotCommand::AppendBatch { points, .. }
+            | PlotCommand::PointsWithError { points, .. }
+            | PlotCommand::SetData { points, .. } => points.len(),
+            _ => 0,
+        }
+    }
+}
+
 /// Convenience sender for feeding points into the multi-trace plotter.
+///
+/// Created by [`channel_plot`] (unbounded queue) or [`channel_plot_bounded`].
+/// On a bounded sink [`send`](Self::send) and [`send_batch`](Self::send_batch)
+/// report a full queue instead of waiting; the other methods block until
+/// there is room.
 #[derive(Clone)]
 pub struct PlotSink {
-    tx: Sender<PlotCommand>,
+    tx: PlotTx,
+    /// Samples rejected with [`TrySendError::Full`], shared by all clones.
+    rejected: Arc<AtomicU64>,
+}
+
+#[derive(Clone)]
+enum PlotTx {
+    Unbounded(Sender<PlotCommand>),
+    Bounded(SyncSender<PlotCommand>),
+}
+
+impl PlotTx {
+    fn send(&self, cmd: PlotCommand) -> Result<(), SendError<PlotCommand>> {
+        match self {
+            PlotTx::Unbounded(tx) => tx.send(cmd),
+            PlotTx::Bounded(tx) => tx.send(cmd),
+        }
+    }
+
+    fn try_send(&self, cmd: PlotCommand) -> Result<(), TrySendError<PlotCommand>> {
+        match self {
+            PlotTx::Unbounded(tx) => tx
+                .send(cmd)
+                .map_err(|SendError(cmd)| TrySendError::Disconnected(cmd)),
+            PlotTx::Bounded(tx) => tx.try_send(cmd),
+        }
+    }
 }
 
 /// A function that transforms a point's Y value.
@@ -93,6 +202,38 @@ pub struct PlotSink {
 pub type YTransform = Box<dyn Fn(f64) -> f64 + Send + 'static>;
 
 impl PlotSink {
+    /// Whether the queue behind this sink has a fixed capacity
+    /// (see [`channel_plot_bounded`]).
+    pub fn is_bounded(&self) -> bool {
+        matches!(self.tx, PlotTx::Bounded(_))
+    }
+
+    /// Queue any command without blocking.
+    ///
+    /// On a bounded sink a full queue returns [`TrySendError::Full`] with the
+    /// command, so the caller can drop or coalesce it.  An unbounded sink only
+    /// fails with [`TrySendError::Disconnected`] once the UI is gone.
+    pub fn send(&self, cmd: PlotCommand) -> Result<(), TrySendError<PlotCommand>> {
+        self.tx.try_send(cmd).inspect_err(|e| {
+            if let TrySendError::Full(cmd) = e {
+                self.rejected
+                    .fetch_add(cmd.sample_count() as u64, Ordering::Relaxed);
+            }
+        })
+    }
+
+    /// Samples this sink (or any clone of it) had to reject because the
+    /// bounded queue was full.  Pass the sink to
+    /// [`TracesCollection::count_rejected_sends`](crate::data::traces::TracesCollection::count_rejected_sends)
+    /// to include them in [`IngestStats::dropped`](crate::IngestStats::dropped).
+    pub fn rejected_samples(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn rejected_counter(&self) -> Arc<AtomicU64> {
+        self.rejected.clone()
+    }
+
     /// Create and register a new `Trace` with a unique numeric ID.
     pub fn create_trace<S: Into<String>>(&self, name: S, info: Option<S>) -> Trace {
         static NEXT_ID: AtomicU32 = AtomicU32::new(1);
@@ -171,6 +312,62 @@ impl PlotSink {
         })
     }
 
+    /// Send a batch of points for a given trace ID as a single
+    /// [`PlotCommand::AppendBatch`], for sources producing many samples per
+    /// call (e.g. a DAQ card).  One channel send per batch instead of one per
+    /// sample.  Never blocks; see [`send`](Self::send) for the errors.
+    pub fn send_batch(
+        &self,
+        trace: TraceId,
+        points: &[PlotPoint],
+    ) -> Result<(), TrySendError<PlotCommand>> {
+        self.send(PlotCommand::AppendBatch {
+            trace_id: trace,
+            points: points.to_vec(),
+        })
+    }
+
+    /// Send a batch of points for a given `Trace` (see [`send_batch`](Self::send_batch)).
+    pub fn send_batch_named(
+        &self,
+        trace: &Trace,
+        points: &[PlotPoint],
+    ) -> Result<(), TrySendError<PlotCommand>> {
+        self.send_batch(trace.id, points)
+    }
+
+    /// Send a chunk of points with per-point Y uncertainties for a given `Trace`.
+    pub fn send_points_with_error<I, E>(
+        &self,
+        trace: &Trace,
+        points: I,
+        y_err: E,
+    ) -> Result<(), std::sync::mpsc::SendError<PlotCommand>>
+    where
+        I: Into<Vec<PlotPoint>>,
+        E: Into<Vec<f64>>,
+    {
+        self.send_points_with_error_by_id(trace.id, points, y_err)
+    }
+
+    /// Send a chunk of points with per-point Y uncertainties for a given trace ID.
+    pub fn send_points_with_error_by_id<I, E>(
+        &self,
+        trace_id: TraceId,
+        points: I,
+        y_err: E,
+    ) -> Result<(), std::sync::mpsc::SendError<PlotCommand>>
+    where
+        I: Into<Vec<PlotPoint>>,
+        E: Into<Vec<f64>>,
+    {
+        self.tx.send(PlotCommand::PointsWithError {
+            trace_id,
+            points: points.into(),
+            y_err: y_err.into(),
+        })
+    }
+
     /// Set the Y value for a specific point (by exact X) on a given `Trace`.
     #[inline]
     pub fn set_point_y(
@@ -446,6 +643,16 @@ impl PlotSink {
         self.tx.send(PlotCommand::ClearData { trace_id })
     }
 
+    /// Delete a given `Trace` from the plot, including its scope membership.
+    #[inline]
+    pub fn remove_trace(
+        &self,
+        trace: &Trace,
+    ) -> Result<(), std::sync::mpsc::SendError<PlotCommand>> {
+        self.tx
+            .send(PlotCommand::RemoveTrace { trace_id: trace.id })
+    }
+
     /// Replace the entire data vector for a given `Trace` with the provided points.
     /// This discards any existing points for the trace.
     pub fn set_data<I>(
@@ -481,5 +688,79 @@ impl PlotSink {
 /// Create a new channel pair for plotting: `(PlotSink, Receiver<PlotCommand>)`.
 pub fn channel_plot() -> (PlotSink, Receiver<PlotCommand>) {
     let (tx, rx) = std::sync::mpsc::channel();
-    (PlotSink { tx }, rx)
+    (
+        PlotSink {
+            tx: PlotTx::Unbounded(tx),
+            rejected: Arc::default(),
+        },
+        rx,
+    )
+}
+
+/// Like [`channel_plot`], but the queue holds at most `capacity` commands, so
+/// a producer outrunning the UI cannot grow memory without limit.
+///
+/// [`PlotSink::send`] and [`PlotSink::send_batch`] return
+/// [`TrySendError::Full`] instead of blocking.  The other sink methods,
+/// including [`PlotSink::create_trace`], wait for room.  The receiver is drained
+/// each frame exactly as with [`channel_plot`].
+pub fn channel_plot_bounded(capacity: usize) -> (PlotSink, Receiver<PlotCommand>) {
+    let (tx, rx) = std::sync::mpsc::sync_channel(capacity);
+    (
+        PlotSink {
+            tx: PlotTx::Bounded(tx),
+            rejected: Arc::default(),
+        },
+        rx,
+    )
+}
+
+/// Multiplex several plot command streams into one receiver for a single
+/// plot, e.g. one [`PlotSink`] per subsystem.
+///
+/// Each input is forwarded by its own background thread, so commands keep
+/// their order per source but interleave freely between sources.  Trace ids
+/// are remapped per source, so two sources using the same raw id (e.g. via
+/// `send_point_by_id`) still feed separate traces; traces registered under
+/// the same name share one trace as usual.  See [`merge_sinks_prefixed`] to
+/// keep names apart as well.  A thread ends when its source is disconnected
+/// or the returned receiver is dropped.
+pub fn merge_sinks(receivers: Vec<Receiver<PlotCommand>>) -> Receiver<PlotCommand> {
+    merge_sinks_prefixed(
+        receivers
+            .into_iter()
+            .map(|rx| (String::new(), rx))
+            .collect(),
+    )
+}
+
+/// Like [`merge_sinks`], but every trace name registered by a source is
+/// prefixed with that source's string, e.g. `"motor/"` turns `current` into
+/// `motor/current`.
+pub fn merge_sinks_prefixed(
+    sources: Vec<(String, Receiver<PlotCommand>)>,
+) -> Receiver<PlotCommand> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let next_id = std::sync::Arc::new(AtomicU32::new(1));
+    for (prefix, source) in sources {
+        let tx = tx.clone();
+        let next_id = next_id.clone();
+        std::thread::spawn(move || {
+            let mut ids: std::collections::HashMap<TraceId, TraceId> =
+                std::collections::HashMap::new();
+            for mut cmd in source {
+                let id = cmd.trace_id_mut();
+                *id = *ids
+                    .entry(*id)
+                    .or_insert_with(|| next_id.fetch_add(1, Ordering::Relaxed));
+                if let PlotCommand::RegisterTrace { name, .. } = &mut cmd {
+                    name.insert_str(0, &prefix);
+                }
+                if tx.send(cmd).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    rx
 }
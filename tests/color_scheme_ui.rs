@@ -90,3 +90,18 @@ fn scheme_labels_include_builtins_and_custom() {
     assert_eq!(labels.len(), builtin_count + 1);
     assert_eq!(labels[builtin_count], "Custom1");
 }
+
+#[test]
+fn trace_palette_builtins_and_custom() {
+    use liveplot::TracePalette;
+
+    assert_eq!(TracePalette::Scheme.colors(), None);
+    assert_eq!(TracePalette::OkabeIto.colors().unwrap().len(), 8);
+    assert_eq!(TracePalette::Tableau10.colors().unwrap().len(), 10);
+    assert_eq!(
+        TracePalette::Custom(vec![[10, 20, 30]]).colors(),
+        Some(vec![Color32::from_rgb(10, 20, 30)])
+    );
+    // An empty custom palette falls back to the scheme.
+    assert_eq!(TracePalette::Custom(Vec::new()).colors(), None);
+}
@@ -0,0 +1,553 @@
+use liveplot::data::traces::TraceRef;
+use liveplot::sink::{PlotCommand, PlotPoint};
+use liveplot::{
+    LivePlotPanel, LiveplotController, MeasurementController, MeasurementKind, ScopesController,
+    StateIoOp, Trigger, TriggerController, UiActionController,
+};
+
+fn run_frames(panel: &mut LivePlotPanel, ctx: &egui::Context, n: usize) {
+    for _ in 0..n {
+        let _ = ctx.run_ui(egui::RawInput::default(), |ui| panel.update_embedded(ui));
+    }
+}
+
+#[test]
+fn save_and_load_state_report_completion() {
+    let (_tx, rx) = std::sync::mpsc::channel();
+    let mut panel = LivePlotPanel::new(rx);
+    let ctrl = LiveplotController::new();
+    panel.set_controllers(None, None, None, None, Some(ctrl.clone()), None, None);
+    let events = ctrl.subscribe_state_io();
+    let ctx = egui::Context::default();
+
+    let path = std::env::temp_dir().join(format!("liveplot_state_{}.json", std::process::id()));
+    ctrl.save_state_to(&path);
+    run_frames(&mut panel, &ctx, 2);
+    let evt = events.try_recv().expect("save completion event");
+    assert_eq!(evt.op, StateIoOp::Save);
+    assert_eq!(evt.path, path);
+    assert!(evt.result.is_ok(), "{:?}", evt.result);
+    assert!(path.exists());
+
+    ctrl.load_state_from(&path);
+    run_frames(&mut panel, &ctx, 2);
+    let evt = events.try_recv().expect("load completion event");
+    assert_eq!(evt.op, StateIoOp::Load);
+    assert!(evt.result.is_ok(), "{:?}", evt.result);
+    let _ = std::fs::remove_file(&path);
+
+    ctrl.load_state_from(path.with_extension("missing"));
+    run_frames(&mut panel, &ctx, 2);
+    let evt = events.try_recv().expect("failed load still reports");
+    assert!(evt.result.is_err());
+}
+
+#[test]
+fn panels_can_be_toggled_by_title() {
+    let (_tx, rx) = std::sync::mpsc::channel();
+    let mut panel = LivePlotPanel::new(rx);
+    let ctrl = UiActionController::new();
+    panel.set_controllers(None, Some(ctrl.clone()), None, None, None, None, None);
+    let updates = ctrl.subscribe_panels();
+    let ctx = egui::Context::default();
+
+    ctrl.set_panel_visible("math", true);
+    ctrl.detach_panel("Thresholds", true);
+    run_frames(&mut panel, &ctx, 1);
+    let panels = ctrl.get_panels().expect("panel list published");
+    let math = panels.iter().find(|p| p.title == "Math").unwrap();
+    assert!(math.visible);
+    let thr = panels.iter().find(|p| p.title == "Thresholds").unwrap();
+    assert!(thr.visible && thr.detached);
+    assert!(updates.try_recv().is_ok());
+
+    ctrl.set_panel_visible("Math", false);
+    run_frames(&mut panel, &ctx, 1);
+    let panels = updates.try_iter().last().expect("change published");
+    assert!(!panels.iter().find(|p| p.title == "Math").unwrap().visible);
+}
+
+#[test]
+fn repaint_pacing_follows_unfocused_behavior() {
+    use liveplot::UnfocusedBehavior;
+    use std::time::Duration;
+
+    let (_tx, rx) = std::sync::mpsc::channel();
+    let mut panel = LivePlotPanel::new(rx);
+    let ctx = egui::Context::default();
+    let normal = Duration::from_millis(16);
+
+    let _ = ctx.run_ui(egui::RawInput::default(), |_| {});
+    panel.unfocused_behavior = UnfocusedBehavior::Freeze;
+    assert_eq!(panel.repaint_after(&ctx), normal);
+    assert!(!panel.skips_drawing(&ctx));
+
+    let unfocused = egui::RawInput {
+        focused: false,
+        ..Default::default()
+    };
+    let _ = ctx.run_ui(unfocused, |_| {});
+    assert!(LivePlotPanel::is_backgrounded(&ctx));
+    assert_eq!(
+        panel.repaint_after(&ctx),
+        liveplot::config::FROZEN_DRAIN_INTERVAL
+    );
+    assert!(!panel.skips_drawing(&ctx));
+    panel.unfocused_behavior = UnfocusedBehavior::Throttle(Duration::from_millis(500));
+    assert_eq!(panel.repaint_after(&ctx), Duration::from_millis(500));
+    panel.unfocused_behavior = UnfocusedBehavior::KeepRunning;
+    assert_eq!(panel.repaint_after(&ctx), normal);
+}
+
+#[test]
+fn frozen_panel_keeps_ingesting_while_hidden() {
+    use liveplot::UnfocusedBehavior;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut panel = LivePlotPanel::new(rx);
+    panel.unfocused_behavior = UnfocusedBehavior::Freeze;
+    let ctrl = LiveplotController::new();
+    panel.set_controllers(None, None, None, None, Some(ctrl.clone()), None, None);
+    let events = ctrl.subscribe_state_io();
+    let ctx = egui::Context::default();
+    let mut hidden = egui::RawInput {
+        focused: false,
+        ..Default::default()
+    };
+    hidden
+        .viewports
+        .entry(egui::ViewportId::ROOT)
+        .or_default()
+        .minimized = Some(true);
+
+    tx.send(PlotCommand::RegisterTrace {
+        id: 1,
+        name: "sig".into(),
+        info: None,
+    })
+    .unwrap();
+    tx.send(PlotCommand::Points {
+        trace_id: 1,
+        points: vec![PlotPoint { x: 0.0, y: 1.0 }, PlotPoint { x: 1.0, y: 2.0 }],
+    })
+    .unwrap();
+    let _ = ctx.run_ui(hidden.clone(), |ui| {
+        assert!(panel.skips_drawing(ui.ctx()));
+        panel.update_embedded(ui);
+    });
+    let trace = panel
+        .traces_data
+        .get_trace(&TraceRef::from("sig"))
+        .expect("trace ingested while hidden");
+    assert_eq!(trace.live.len(), 2);
+
+    // Controller requests are still served while nothing is drawn.
+    let path = std::env::temp_dir().join(format!("liveplot_frozen_{}.json", std::process::id()));
+    ctrl.save_state_to(&path);
+    for _ in 0..2 {
+        let _ = ctx.run_ui(hidden.clone(), |ui| panel.update_embedded(ui));
+    }
+    let evt = events.try_recv().expect("save completes while hidden");
+    assert_eq!(evt.op, StateIoOp::Save);
+    assert!(evt.result.is_ok(), "{:?}", evt.result);
+    assert!(path.exists());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn traces_are_sampled_at_requested_x_every_frame() {
+    use liveplot::data::export::SampleMode;
+    use liveplot::{channel_plot, PlotPoint, TracesController};
+
+    let (sink, rx) = channel_plot();
+    let mut panel = LivePlotPanel::new(rx);
+    let ctrl = TracesController::new();
+    panel.set_controllers(None, None, Some(ctrl.clone()), None, None, None, None);
+    let samples = ctrl.subscribe_samples();
+    let ctx = egui::Context::default();
+
+    let a = sink.create_trace("a", None);
+    let points: Vec<PlotPoint> = [(0.0, 0.0), (1.0, 10.0), (2.0, 20.0)]
+        .into_iter()
+        .map(|(x, y)| PlotPoint { x, y })
+        .collect();
+    sink.send_points(&a, points).unwrap();
+
+    ctrl.sample_traces_at(1.5, SampleMode::Linear);
+    run_frames(&mut panel, &ctx, 2);
+    let got = samples.try_recv().expect("samples published");
+    assert_eq!(got.x, 1.5);
+    assert_eq!(got.values, vec![("a".to_string(), Some(15.0))]);
+
+    ctrl.sample_traces_at(5.0, SampleMode::Nearest);
+    run_frames(&mut panel, &ctx, 1);
+    let last = ctrl.get_last_samples().unwrap();
+    assert_eq!(last.values, vec![("a".to_string(), None)]);
+
+    ctrl.stop_sampling();
+    run_frames(&mut panel, &ctx, 1);
+    assert!(ctrl.get_last_samples().is_none());
+}
+
+#[test]
+fn clear_all_leaves_every_trace_empty_in_the_next_frame() {
+    use liveplot::{channel_plot, PlotPoint};
+
+    let (sink, rx) = channel_plot();
+    let mut panel = LivePlotPanel::new(rx);
+    let ctrl = LiveplotController::new();
+    panel.set_controllers(None, None, None, None, Some(ctrl.clone()), None, None);
+    let ctx = egui::Context::default();
+
+    for name in ["a", "b"] {
+        let t = sink.create_trace(name, None);
+        let points: Vec<PlotPoint> = (0..10)
+            .map(|i| PlotPoint {
+                x: i as f64,
+                y: i as f64,
+            })
+            .collect();
+        sink.send_points(&t, points).unwrap();
+    }
+    run_frames(&mut panel, &ctx, 1);
+    assert!(panel
+        .traces_data
+        .traces_iter()
+        .all(|(_, tr)| tr.live.len() == 10));
+
+    ctrl.request_clear_all();
+    run_frames(&mut panel, &ctx, 2);
+    assert!(panel
+        .traces_data
+        .traces_iter()
+        .all(|(_, tr)| tr.live.is_empty()));
+}
+
+#[test]
+fn forced_interaction_lock_covers_scopes_added_later() {
+    let (_sink, rx) = liveplot::channel_plot();
+    let mut panel = LivePlotPanel::new(rx);
+    let ctx = egui::Context::default();
+
+    panel.liveplot_panel.interaction_locked = true;
+    panel.liveplot_panel.add_scope();
+    run_frames(&mut panel, &ctx, 1);
+    let scopes = panel.liveplot_panel.get_data();
+    assert_eq!(scopes.len(), 2);
+    assert!(scopes.iter().all(|s| s.interaction_locked));
+}
+
+#[test]
+fn maximized_panel_is_cleared_once_hidden() {
+    let (_tx, rx) = std::sync::mpsc::channel();
+    let mut panel = LivePlotPanel::new(rx);
+    let ctrl = UiActionController::new();
+    panel.set_controllers(None, Some(ctrl.clone()), None, None, None, None, None);
+    let ctx = egui::Context::default();
+    let mut fonts = egui::FontDefinitions::default();
+    egui_phosphor_icons::add_fonts(&mut fonts);
+    ctx.set_fonts(fonts);
+
+    ctrl.set_panel_visible("Traces", true);
+    run_frames(&mut panel, &ctx, 1);
+    panel.maximized_panel = Some("Traces".to_string());
+    run_frames(&mut panel, &ctx, 2);
+    assert_eq!(panel.maximized_panel.as_deref(), Some("Traces"));
+
+    ctrl.set_panel_visible("Traces", false);
+    run_frames(&mut panel, &ctx, 2);
+    assert_eq!(panel.maximized_panel, None);
+
+    panel.maximized_panel = Some("No such panel".to_string());
+    run_frames(&mut panel, &ctx, 1);
+    assert_eq!(panel.maximized_panel, None);
+}
+
+#[test]
+fn scopes_can_be_reordered_by_id() {
+    let (_tx, rx) = std::sync::mpsc::channel();
+    let mut panel = LivePlotPanel::new(rx);
+    let scopes = ScopesController::new();
+    let liveplot = LiveplotController::new();
+    panel.set_controllers(
+        None,
+        None,
+        None,
+        Some(scopes.clone()),
+        Some(liveplot.clone()),
+        None,
+        None,
+    );
+    let ctx = egui::Context::default();
+    let ids = |scopes: &ScopesController| -> Vec<usize> {
+        let state = scopes.get_last_state().expect("scopes state");
+        state.scopes.iter().map(|s| s.id).collect()
+    };
+
+    scopes.request_add_scope();
+    run_frames(&mut panel, &ctx, 1);
+    scopes.request_add_scope();
+    run_frames(&mut panel, &ctx, 1);
+    assert_eq!(ids(&scopes), vec![0, 1, 2]);
+
+    // Unknown ids are ignored; unlisted scopes keep their relative order.
+    scopes.request_reorder(vec![2, 99, 0]);
+    run_frames(&mut panel, &ctx, 1);
+    assert_eq!(ids(&scopes), vec![2, 0, 1]);
+
+    // Handled after the scopes state is published, so it shows a frame later.
+    liveplot.request_reorder_scopes(vec![1]);
+    run_frames(&mut panel, &ctx, 2);
+    assert_eq!(ids(&scopes), vec![1, 2, 0]);
+}
+
+#[test]
+fn triggers_can_be_added_and_removed_through_controller() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let _ = tx.send(PlotCommand::RegisterTrace {
+        id: 0,
+        name: "sig".to_string(),
+        info: None,
+    });
+    let _ = tx.send(PlotCommand::Point {
+        trace_id: 0,
+        point: PlotPoint { x: 0.0, y: 0.0 },
+    });
+    let mut panel = LivePlotPanel::new(rx);
+    let ctrl = TriggerController::new();
+    panel.set_trigger_controller(Some(ctrl.clone()));
+    let states = ctrl.subscribe_state();
+    let ctx = egui::Context::default();
+
+    let trig = || {
+        let mut trig = Trigger::default();
+        trig.name = "T".to_string();
+        trig.target = TraceRef::new("sig");
+        trig.level = 0.5;
+        trig
+    };
+    ctrl.add_trigger(trig());
+    ctrl.arm("T");
+    run_frames(&mut panel, &ctx, 2);
+    let state = ctrl.get_last_state().expect("trigger state published");
+    let t = state.triggers.iter().find(|t| t.name == "T").unwrap();
+    assert_eq!(t.level, 0.5);
+    assert!(t.armed);
+    assert!(states.try_iter().last().is_some());
+
+    ctrl.remove_trigger("T");
+    run_frames(&mut panel, &ctx, 1);
+    let state = ctrl.get_last_state().unwrap();
+    assert!(state.triggers.iter().all(|t| t.name != "T"));
+    // A late subscriber gets the last state straight away.
+    assert!(ctrl.subscribe_state().try_recv().is_ok());
+
+    // Additions and removals apply in request order.
+    ctrl.add_trigger(trig());
+    ctrl.remove_trigger("T");
+    run_frames(&mut panel, &ctx, 1);
+    let state = ctrl.get_last_state().unwrap();
+    assert!(state.triggers.iter().all(|t| t.name != "T"));
+
+    // Requests wait until there is a triggers panel to apply them.
+    panel.right_side_panels.retain(|p| p.title() != "Triggers");
+    ctrl.add_trigger(trig());
+    run_frames(&mut panel, &ctx, 1);
+    panel
+        .right_side_panels
+        .push(Box::new(liveplot::panels::TriggersPanel::default()));
+    run_frames(&mut panel, &ctx, 1);
+    let state = ctrl.get_last_state().unwrap();
+    assert!(state.triggers.iter().any(|t| t.name == "T"));
+}
+
+#[test]
+fn measurement_snapshot_reports_selected_traces_and_kinds() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    for (id, name) in ["sig", "other"].iter().enumerate() {
+        let _ = tx.send(PlotCommand::RegisterTrace {
+            id: id as u32,
+            name: name.to_string(),
+            info: None,
+        });
+    }
+    // Square wave between -1 and 1 with a 0.1 s period.
+    for i in 0..100 {
+        let y = if (i / 5) % 2 == 0 { 1.0 } else { -1.0 };
+        for id in 0..2 {
+            let _ = tx.send(PlotCommand::Point {
+                trace_id: id,
+                point: PlotPoint {
+                    x: i as f64 * 0.01,
+                    y,
+                },
+            });
+        }
+    }
+    let mut panel = LivePlotPanel::new(rx);
+    let ctrl = MeasurementController::new();
+    panel.set_measurement_controller(Some(ctrl.clone()));
+    ctrl.request_set_traces(Some(vec![TraceRef::new("sig")]));
+    ctrl.request_set_kinds(vec![
+        MeasurementKind::PeakToPeak,
+        MeasurementKind::Frequency,
+    ]);
+    let snapshots = ctrl.subscribe_snapshots();
+    let ctx = egui::Context::default();
+
+    run_frames(&mut panel, &ctx, 2);
+    let snap = ctrl.get_last_snapshot().expect("snapshot published");
+    assert_eq!(snap.per_trace.len(), 1);
+    let sig = snap.trace("sig").unwrap();
+    assert_eq!(sig.samples, 100);
+    assert_eq!(sig.get(MeasurementKind::PeakToPeak), Some(2.0));
+    let f = sig.get(MeasurementKind::Frequency).expect("frequency");
+    assert!((f - 10.0).abs() < 0.5, "{f}");
+    assert_eq!(sig.get(MeasurementKind::Mean), None);
+    assert!(snapshots.try_recv().is_ok());
+
+    // X bounds are in data units on a log axis too.
+    {
+        let scope = &mut panel.liveplot_panel.get_data_mut()[0];
+        scope.x_axis.log_scale = true;
+        scope.paused = true;
+        scope.x_axis.bounds = (0.5, 0.995);
+        scope.time_window = 0.495;
+    }
+    run_frames(&mut panel, &ctx, 2);
+    let snap = ctrl.get_last_snapshot().unwrap();
+    assert_eq!(snap.trace("sig").unwrap().samples, 50);
+}
+
+#[test]
+fn visible_data_is_copied_as_tsv() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    for (id, name) in ["a", "b"].iter().enumerate() {
+        let _ = tx.send(PlotCommand::RegisterTrace {
+            id: id as u32,
+            name: name.to_string(),
+            info: None,
+        });
+        for i in 0..3 {
+            let _ = tx.send(PlotCommand::Point {
+                trace_id: id as u32,
+                point: PlotPoint {
+                    x: i as f64,
+                    y: (10 * id + i) as f64,
+                },
+            });
+        }
+    }
+    let mut panel = LivePlotPanel::new(rx);
+    let ui_ctrl = UiActionController::new();
+    panel.set_controllers(None, Some(ui_ctrl.clone()), None, None, None, None, None);
+    let ctx = egui::Context::default();
+    run_frames(&mut panel, &ctx, 1);
+
+    ui_ctrl.copy_visible_to_clipboard();
+    let out = ctx.run_ui(egui::RawInput::default(), |ui| panel.update_embedded(ui));
+    let copied: Vec<&String> = out
+        .platform_output
+        .commands
+        .iter()
+        .filter_map(|c| match c {
+            egui::OutputCommand::CopyText(s) => Some(s),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(copied.len(), 1);
+    let lines: Vec<&str> = copied[0].lines().collect();
+    assert_eq!(lines[0], "timestamp_seconds\ta\tb");
+    assert_eq!(lines[1], "0.000000000\t0\t10");
+    assert_eq!(lines.len(), 4);
+}
+
+fn frame_with(panel: &mut LivePlotPanel, ctx: &egui::Context, events: Vec<egui::Event>) {
+    let input = egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(1000.0, 700.0),
+        )),
+        events,
+        ..Default::default()
+    };
+    let _ = ctx.run_ui(input, |ui| panel.update_embedded(ui));
+}
+
+fn press(pos: egui::Pos2, pressed: bool) -> egui::Event {
+    egui::Event::PointerButton {
+        pos,
+        button: egui::PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    }
+}
+
+/// Clicks, drags and scrolls the middle of the plot; returns the scope's
+/// paused state and X bounds before and after.
+fn poke_plot(locked: bool) -> (bool, (f64, f64), (f64, f64)) {
+    let (sink, rx) = liveplot::channel_plot();
+    let mut panel = LivePlotPanel::new(rx);
+    let ctx = egui::Context::default();
+    let tr = sink.create_trace("sig", None);
+    let points: Vec<PlotPoint> = (0..100)
+        .map(|i| PlotPoint {
+            x: i as f64 * 0.1,
+            y: (i as f64 * 0.1).sin(),
+        })
+        .collect();
+    sink.send_points(&tr, points).unwrap();
+    frame_with(&mut panel, &ctx, vec![]);
+    {
+        let mut scopes = panel.liveplot_panel.get_data_mut();
+        scopes[0].pause_on_click = true;
+        scopes[0].interaction_locked = locked;
+    }
+    frame_with(&mut panel, &ctx, vec![]);
+    let before = panel.liveplot_panel.get_data()[0].x_axis.bounds;
+
+    let p = egui::pos2(500.0, 350.0);
+    frame_with(&mut panel, &ctx, vec![egui::Event::PointerMoved(p)]);
+    frame_with(&mut panel, &ctx, vec![press(p, true)]);
+    frame_with(&mut panel, &ctx, vec![press(p, false)]);
+    let paused = panel.liveplot_panel.get_data()[0].paused;
+
+    frame_with(&mut panel, &ctx, vec![press(p, true)]);
+    for dx in 1..=10 {
+        let q = p + egui::vec2(dx as f32 * 15.0, 0.0);
+        frame_with(&mut panel, &ctx, vec![egui::Event::PointerMoved(q)]);
+    }
+    frame_with(
+        &mut panel,
+        &ctx,
+        vec![press(p + egui::vec2(150.0, 0.0), false)],
+    );
+    for _ in 0..5 {
+        frame_with(
+            &mut panel,
+            &ctx,
+            vec![egui::Event::MouseWheel {
+                unit: egui::MouseWheelUnit::Point,
+                delta: egui::vec2(0.0, 50.0),
+                phase: egui::TouchPhase::Move,
+                modifiers: Default::default(),
+            }],
+        );
+    }
+    (
+        paused,
+        before,
+        panel.liveplot_panel.get_data()[0].x_axis.bounds,
+    )
+}
+
+#[test]
+fn locked_plot_ignores_clicks_pan_and_zoom() {
+    let (paused, before, after) = poke_plot(false);
+    assert!(paused, "an unlocked plot pauses on click");
+    assert_ne!(before, after, "an unlocked plot pans and zooms");
+
+    let (paused, before, after) = poke_plot(true);
+    assert!(!paused);
+    assert_eq!(before, after);
+}
@@ -0,0 +1,34 @@
+use liveplot::data::traces::{TraceRef, TracesCollection};
+use liveplot::panels::data_inspector_ui::{recent_samples, rows_to_tsv};
+
+#[test]
+fn recent_samples_lists_the_tail_with_flags() {
+    let mut traces = TracesCollection::default();
+    let tr = traces.get_trace_or_new(&TraceRef::new("a"));
+    tr.live = [
+        [0.0, 1.0],
+        [1.0, 2.0],
+        [1.0, 3.0],
+        [0.5, f64::NAN],
+        [2.0, 5.0],
+    ]
+    .into_iter()
+    .collect();
+    tr.y_err.insert(2.0f64.to_bits(), 0.25);
+
+    let rows = recent_samples(tr, false, 4);
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[0].index, 1);
+    assert_eq!(rows[0].flags, "");
+    assert_eq!(rows[1].flags, "dup-t");
+    assert_eq!(rows[2].flags, "out-of-order non-finite");
+    assert_eq!(rows[3].flags, "±0.25");
+
+    // A snapshot is only read when asked for and present.
+    tr.snap = Some([[9.0, 9.0]].into_iter().collect());
+    assert_eq!(recent_samples(tr, true, 10).len(), 1);
+    assert_eq!(recent_samples(tr, false, 10).len(), 5);
+
+    let tsv = rows_to_tsv(&rows[..1]);
+    assert_eq!(tsv, "index\tt\tvalue\tflags\n1\t1\t2\t\n");
+}
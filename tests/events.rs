@@ -135,6 +135,7 @@ fn event_kinds_do_not_overlap() {
         EventKind::TRACE_OFFSET_CHANGED,
         EventKind::Y_LOG_CHANGED,
         EventKind::Y_UNIT_CHANGED,
+        EventKind::THRESHOLD_ALARM,
     ];
     for (i, a) in all_kinds.iter().enumerate() {
         for (j, b) in all_kinds.iter().enumerate() {
@@ -197,3 +198,16 @@ fn plot_event_carries_metadata() {
     assert!(evt.measurement.is_some());
     assert_eq!(evt.click.as_ref().unwrap().plot_pos.unwrap().x, 1.5);
 }
+
+#[test]
+fn alarm_subscription_only_receives_alarms() {
+    let ctrl = EventController::new();
+    let alarms = ctrl.subscribe_alarms();
+    ctrl.emit_filtered(PlotEvent::new(EventKind::THRESHOLD_EXCEEDED));
+    ctrl.emit_filtered(PlotEvent::new(EventKind::THRESHOLD_ALARM));
+
+    let evt = alarms.try_recv().unwrap();
+    assert_eq!(evt.kinds, EventKind::THRESHOLD_ALARM);
+    assert!(alarms.try_recv().is_err());
+    assert_eq!(format!("{}", EventKind::THRESHOLD_ALARM), "THRESHOLD_ALARM");
+}
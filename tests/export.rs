@@ -40,3 +40,284 @@ fn writes_expected_csv() {
     let lines: Vec<&str> = s.trim().split('\n').collect();
     assert_eq!(lines[0], "timestamp_seconds,sine,cos");
 }
+
+#[test]
+fn samples_cursor_values_and_writes_csv() {
+    let (order, series) = mk_series(&[
+        ("a", &[(0.0, 0.0), (1.0, 10.0), (2.0, 20.0)]),
+        ("b", &[(1.0, 5.0), (3.0, 7.0)]),
+    ]);
+    assert_eq!(
+        sample_at(&series[&order[0]], 0.5, SampleMode::Linear),
+        Some(5.0)
+    );
+    assert_eq!(
+        sample_at(&series[&order[0]], 0.4, SampleMode::Nearest),
+        Some(0.0)
+    );
+    assert_eq!(sample_at(&series[&order[0]], 2.5, SampleMode::Linear), None);
+
+    let rows = sample_series_at(&order, &series, 0.5, 2.0, SampleMode::Linear, 1e-9);
+    let mut buf = Vec::new();
+    write_cursor_values_csv(&mut buf, &rows).unwrap();
+    let s = String::from_utf8(buf).unwrap();
+    let lines: Vec<&str> = s.trim().split('\n').collect();
+    assert_eq!(lines[0], "trace,value_at_p1,value_at_p2,delta");
+    assert_eq!(lines[1], "a,5,20,15");
+    assert_eq!(lines[2], "b,,6,");
+}
+
+#[test]
+fn nearest_cursor_values_match_export_rows() {
+    let (order, series) = mk_series(&[
+        ("a", &[(0.0, 0.0), (1.0, 10.0), (2.0, 20.0)]),
+        ("b", &[(1.0005, 5.0), (3.0, 7.0)]),
+    ]);
+    // Joined into one row, both traces read at the cursor.
+    let rows = sample_series_at(&order, &series, 1.0004, 5.0, SampleMode::Nearest, 1e-3);
+    assert_eq!(rows[0], (order[0].clone(), Some(10.0), None));
+    assert_eq!(rows[1], (order[1].clone(), Some(5.0), None));
+
+    // With a tight tolerance the export has separate rows; the cursor reads
+    // the nearest one, which only holds `b`.
+    let rows = sample_series_at(&order, &series, 1.0004, 2.9, SampleMode::Nearest, 1e-9);
+    assert_eq!(rows[0], (order[0].clone(), None, None));
+    assert_eq!(rows[1], (order[1].clone(), Some(5.0), Some(7.0)));
+}
+
+#[cfg(feature = "fft")]
+#[test]
+fn spectrum_csv_has_parameter_header_and_column_pairs() {
+    use liveplot::data::fft::{FFTWindow, FftScale, SpectrumMeta};
+
+    let meta = SpectrumMeta {
+        window: FFTWindow::Hann,
+        fft_size: 256,
+        padded_size: 512,
+        sample_rate: 1000.0,
+        scale: FftScale::Linear,
+        interval_cv: 0.0,
+    };
+    let spectra = vec![
+        (
+            TraceRef::from("a"),
+            Some(meta),
+            vec![[0.0, 1.0], [2.0, 0.5]],
+        ),
+        (TraceRef::from("b"), None, vec![[0.0, 3.0]]),
+    ];
+    let mut buf = Vec::new();
+    write_spectrum_csv(&mut buf, "Magnitude", &spectra).unwrap();
+    let csv = String::from_utf8(buf).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "# FFT spectrum, values: Magnitude");
+    assert_eq!(
+        lines[1],
+        "# a: window=Hann, N=256, padded=512, sample_rate_hz=1000, scale=Linear"
+    );
+    assert_eq!(lines[2], "# b: parameters unknown");
+    assert_eq!(lines[3], "a_hz,a,b_hz,b");
+    assert_eq!(lines[4], "0,1,0,3");
+    assert_eq!(lines[5], "2,0.5,,");
+}
+
+#[cfg(feature = "npz")]
+#[test]
+fn npy_header_is_aligned_and_data_is_row_major() {
+    let pts = [[0.0, 1.5], [1.0, -2.0], [2.0, 4.0]];
+    let mut buf = Vec::new();
+    write_npy(&mut buf, &pts).unwrap();
+    assert_eq!(&buf[..8], b"\x93NUMPY\x01\x00");
+    let header_len = u16::from_le_bytes([buf[8], buf[9]]) as usize;
+    assert_eq!((10 + header_len) % 64, 0);
+    let header = std::str::from_utf8(&buf[10..10 + header_len]).unwrap();
+    assert!(header.contains("'shape': (2, 3)"));
+    assert!(header.ends_with('\n'));
+    let values: Vec<f64> = buf[10 + header_len..]
+        .chunks(8)
+        .map(|c| f64::from_le_bytes(c.try_into().unwrap()))
+        .collect();
+    assert_eq!(values, vec![0.0, 1.0, 2.0, 1.5, -2.0, 4.0]);
+}
+
+#[cfg(feature = "npz")]
+#[test]
+fn npz_archive_lists_every_trace() {
+    let (order, series) = mk_series(&[("a", &[(0.0, 1.0)]), ("b", &[(0.0, 2.0), (1.0, 3.0)])]);
+    let mut buf = Vec::new();
+    write_npz(&mut buf, &order, &series).unwrap();
+    // End-of-central-directory record: two entries.
+    let eocd = buf.len() - 22;
+    assert_eq!(&buf[eocd..eocd + 4], &0x0605_4b50u32.to_le_bytes());
+    assert_eq!(u16::from_le_bytes([buf[eocd + 10], buf[eocd + 11]]), 2);
+    let text = String::from_utf8_lossy(&buf);
+    assert!(text.contains("a.npy") && text.contains("b.npy"));
+}
+
+#[test]
+fn filename_template_expands_tokens() {
+    use chrono::TimeZone;
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 3, 5, 14, 7, 9)
+        .unwrap();
+    assert_eq!(
+        expand_filename_template("run_{scope}_{trace_count}_{timestamp}", "Scope 1", 3, now),
+        "run_Scope 1_3_20240305-140709"
+    );
+    // Path separators in substituted values are neutralised; unknown tokens stay.
+    assert_eq!(
+        expand_filename_template("{scope}-{other}", "a/b", 0, now),
+        "a_b-{other}"
+    );
+}
+
+#[test]
+fn aligned_csv_reads_back_per_column() {
+    let (order, series) = mk_series(&[("a", &[(0.0, 1.0), (1.0, 2.0)]), ("b", &[(1.0, 5.0)])]);
+    let rows = align_series(&order, &series, 1e-9);
+    let mut buf = Vec::new();
+    write_aligned_rows_csv(&mut buf, &order, &rows).unwrap();
+
+    let read = read_aligned_csv(std::io::Cursor::new(buf)).unwrap();
+    assert_eq!(
+        read,
+        vec![
+            ("a".to_string(), vec![[0.0, 1.0], [1.0, 2.0]]),
+            ("b".to_string(), vec![[1.0, 5.0]]),
+        ]
+    );
+    assert!(read_aligned_csv(std::io::Cursor::new("")).is_err());
+}
+
+#[test]
+fn writes_integer_micros_and_reads_them_back() {
+    let t0 = 1_700_000_000.123_456;
+    let (order, series) = mk_series(&[("a", &[(t0, 1.0), (t0 + 0.000_001, 2.0)])]);
+    let rows = align_series(&order, &series, 1e-9);
+
+    let mut buf = Vec::new();
+    write_aligned_rows_csv_with(&mut buf, &order, &rows, CsvTimestamp::Micros).unwrap();
+    let s = String::from_utf8(buf).unwrap();
+    let lines: Vec<&str> = s.trim().split('\n').collect();
+    assert_eq!(lines[0], "timestamp_micros,a");
+    assert_eq!(lines[1], "1700000000123456,1");
+    assert_eq!(lines[2], "1700000000123457,2");
+
+    let mut buf = Vec::new();
+    write_aligned_rows_csv_with(&mut buf, &order, &rows, CsvTimestamp::Both).unwrap();
+    let s = String::from_utf8(buf).unwrap();
+    assert!(s.starts_with("timestamp_seconds,timestamp_micros,a\n"));
+    let back = read_aligned_csv(s.as_bytes()).unwrap();
+    assert_eq!(back.len(), 1);
+    assert_eq!(back[0].0, "a");
+    assert!((back[0].1[0][0] - t0).abs() < 1e-6);
+}
+
+#[test]
+fn larger_join_tolerance_merges_jittery_rows() {
+    let (order, series) = mk_series(&[
+        ("a", &[(0.0, 1.0), (1.0, 2.0)]),
+        ("b", &[(0.000_3, 10.0), (0.999_8, 20.0)]),
+    ]);
+    let path = std::env::temp_dir().join(format!("liveplot_join_tol_{}.csv", std::process::id()));
+    let rows = |tol: f64| {
+        write_csv_aligned_path(&path, &order, &series, tol, CsvTimestamp::Seconds).unwrap();
+        std::fs::read_to_string(&path).unwrap().lines().count() - 1
+    };
+    assert_eq!(rows(DEFAULT_JOIN_TOLERANCE), 4);
+    assert_eq!(rows(1e-3), 2);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn json_export_has_metadata_and_aligned_rows() {
+    use chrono::TimeZone;
+
+    let (order, series) = mk_series(&[
+        ("sine", &[(0.0, 0.1), (1.0, 0.2)]),
+        ("cos", &[(0.0 + 5e-10, 1.1), (2.0, f64::NAN)]),
+    ]);
+    let rows = align_series(&order, &series, 1e-9);
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 3, 5, 14, 7, 9)
+        .unwrap();
+    let mut buf = Vec::new();
+    write_aligned_rows_json(&mut buf, &order, &[Some("V".to_string())], &rows, now).unwrap();
+    let doc: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+
+    let meta = &doc["metadata"];
+    assert_eq!(meta["traces"], serde_json::json!(["sine", "cos"]));
+    // Missing units are padded with null.
+    assert_eq!(meta["units"], serde_json::json!(["V", null]));
+    assert_eq!(meta["exported_at"], serde_json::json!(now.to_rfc3339()));
+    assert_eq!(
+        meta["columns"],
+        serde_json::json!(["timestamp_seconds", "sine", "cos"])
+    );
+    // Same rows as the CSV writer; gaps and NaN become null.
+    assert_eq!(
+        doc["rows"],
+        serde_json::json!([[0.0, 0.1, 1.1], [1.0, 0.2, null], [2.0, null, null]])
+    );
+}
+
+#[test]
+fn aligned_rows_as_tsv_leave_gaps_empty() {
+    let (order, series) = mk_series(&[("a", &[(0.0, 1.5), (1.0, 2.0)]), ("b", &[(1.0, -3.0)])]);
+    let rows = align_series(&order, &series, 1e-9);
+    assert_eq!(
+        aligned_rows_tsv(&order, &rows),
+        "timestamp_seconds\ta\tb\n0.000000000\t1.5\t\n1.000000000\t2\t-3\n"
+    );
+}
+
+#[test]
+fn region_export_clips_series_to_range() {
+    let (order, mut series) = mk_series(&[
+        ("a", &[(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)]),
+        ("b", &[(1.5, 10.0), (3.0, 20.0)]),
+    ]);
+    clip_series(&mut series, (1.0, 2.0));
+    let rows = align_series(&order, &series, 1e-9);
+    let ts: Vec<f64> = rows.iter().map(|r| r.0).collect();
+    assert_eq!(ts, vec![1.0, 1.5, 2.0]);
+}
+
+#[test]
+fn export_range_uses_view_only_when_enabled_and_non_empty() {
+    use liveplot::panels::ExportPanel;
+
+    let mut panel = ExportPanel::default();
+    assert_eq!(panel.export_range(Some((1.0, 2.0))), None);
+    panel.region_view = true;
+    assert_eq!(panel.export_range(Some((1.0, 2.0))), Some((1.0, 2.0)));
+    // Empty ranges fall back to the full buffer.
+    assert_eq!(panel.export_range(Some((2.0, 2.0))), None);
+    // No P1–P2 selection: the view is used instead.
+    panel.region_selection = true;
+    assert_eq!(panel.export_range(Some((1.0, 2.0))), Some((1.0, 2.0)));
+}
+
+#[test]
+fn export_range_prefers_non_empty_selection_over_view() {
+    use liveplot::panels::ExportPanel;
+
+    let mut panel = ExportPanel::default();
+    panel.region_view = true;
+    panel.region_selection = true;
+    panel.set_selection(Some((3.0, 4.0)));
+    let view = Some((1.0, 5.0));
+    assert_eq!(panel.export_range(view), Some((3.0, 4.0)));
+    // An empty selection falls back to the view...
+    panel.set_selection(Some((4.0, 4.0)));
+    assert_eq!(panel.export_range(view), view);
+    // ...and a disabled view then exports the full buffer.
+    panel.region_view = false;
+    assert_eq!(panel.export_range(view), None);
+    // A disabled selection is ignored even when set.
+    panel.set_selection(Some((3.0, 4.0)));
+    panel.region_selection = false;
+    panel.region_view = true;
+    assert_eq!(panel.export_range(view), view);
+}
@@ -0,0 +1,47 @@
+use liveplot::data::expr::ParsedExpr;
+
+fn eval(src: &str, values: &[f64]) -> f64 {
+    ParsedExpr::parse(src).unwrap().eval(values)
+}
+
+#[test]
+fn precedence_and_associativity() {
+    assert_eq!(eval("1 + 2 * 3", &[]), 7.0);
+    assert_eq!(eval("(1 + 2) * 3", &[]), 9.0);
+    assert_eq!(eval("10 - 4 - 3", &[]), 3.0);
+    assert_eq!(eval("2 ^ 3 ^ 2", &[]), 512.0);
+    assert_eq!(eval("-2 ^ 2", &[]), -4.0);
+    assert_eq!(eval("7 % 4 + 1.5e1", &[]), 18.0);
+    assert_eq!(eval("max(2, min(5, 3)) + abs(-1)", &[]), 4.0);
+    assert!((eval("2 * pi", &[]) - std::f64::consts::TAU).abs() < 1e-12);
+}
+
+#[test]
+fn variables_are_bound_in_first_use_order() {
+    let p = ParsedExpr::parse("(a + 2*b) / c + a").unwrap();
+    assert_eq!(p.variables(), ["a", "b", "c"]);
+    assert_eq!(p.eval(&[1.0, 2.0, 5.0]), 2.0);
+
+    let q = ParsedExpr::parse("\"trace-1\" * sensor.x").unwrap();
+    assert_eq!(q.variables(), ["trace-1", "sensor.x"]);
+    assert_eq!(q.eval(&[3.0, 4.0]), 12.0);
+}
+
+#[test]
+fn malformed_input_is_reported_with_a_position() {
+    for src in [
+        "",
+        "a +",
+        "(a",
+        "a b",
+        "foo(1)",
+        "sqrt(1, 2)",
+        "a $ b",
+        "\"open",
+    ] {
+        assert!(ParsedExpr::parse(src).is_err(), "{src:?} should not parse");
+    }
+    let err = ParsedExpr::parse("a * (b + )").unwrap_err();
+    assert_eq!(err.pos, 9);
+    assert_eq!(err.to_string(), "expected a value at column 10");
+}
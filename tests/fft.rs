@@ -0,0 +1,347 @@
+#![cfg(feature = "fft")]
+
+use liveplot::data::fft::{effective_fft_size, MIN_ADAPTIVE_FFT_SIZE};
+
+#[test]
+fn effective_fft_size_adapts_to_short_buffers() {
+    assert_eq!(effective_fft_size(1024, 5000, true), Some(1024));
+    assert_eq!(effective_fft_size(1024, 1024, false), Some(1024));
+    assert_eq!(effective_fft_size(1024, 700, true), Some(512));
+    assert_eq!(effective_fft_size(1024, 700, false), None);
+    assert_eq!(
+        effective_fft_size(1024, MIN_ADAPTIVE_FFT_SIZE, true),
+        Some(MIN_ADAPTIVE_FFT_SIZE)
+    );
+    assert_eq!(
+        effective_fft_size(1024, MIN_ADAPTIVE_FFT_SIZE - 1, true),
+        None
+    );
+}
+
+#[test]
+fn scaled_spectrum_is_window_corrected() {
+    use liveplot::data::fft::{FFTWindow, FftData, FftScale};
+    use std::collections::VecDeque;
+
+    // 2.0-amplitude sine exactly on bin 64 (fs = 1024 Hz, N = 1024).
+    let buf: VecDeque<[f64; 2]> = (0..1024)
+        .map(|i| {
+            let t = i as f64 / 1024.0;
+            [t, 2.0 * (2.0 * std::f64::consts::PI * 64.0 * t).sin()]
+        })
+        .collect();
+    let peak = |fft: &mut FftData, window| {
+        let out = fft.compute_fft(&buf, false, &None, 1024, window).unwrap();
+        out.iter().map(|p| p[1]).fold(f64::MIN, f64::max)
+    };
+
+    let mut fft = FftData::default();
+    for w in [FFTWindow::Rect, FFTWindow::Hann, FFTWindow::Blackman] {
+        assert!((peak(&mut fft, w) - 2.0).abs() < 1e-2, "{:?}", w);
+    }
+
+    fft.magnitude_scale = FftScale::Dbfs;
+    fft.dbfs_reference = 2.0;
+    assert!(peak(&mut fft, FFTWindow::Hann).abs() < 0.1);
+
+    // Raw bins grow with N (N·A/2 for Rect); normalised ones do not.
+    fft.magnitude_scale = FftScale::Raw;
+    assert!((peak(&mut fft, FFTWindow::Rect) - 1024.0).abs() < 1e-6);
+    fft.magnitude_scale = FftScale::Linear;
+    let half = fft
+        .compute_fft(&buf, false, &None, 512, FFTWindow::Hann)
+        .unwrap();
+    let half_peak = half.iter().map(|p| p[1]).fold(f64::MIN, f64::max);
+    assert!((half_peak - 2.0).abs() < 1e-2);
+}
+
+#[test]
+fn fft_size_validation_requires_power_of_two_in_range() {
+    use liveplot::data::fft::is_valid_fft_size;
+
+    assert!(is_valid_fft_size(256));
+    assert!(is_valid_fft_size(4096));
+    assert!(is_valid_fft_size(1 << 20));
+    assert!(!is_valid_fft_size(1000));
+    assert!(!is_valid_fft_size(128));
+    assert!(!is_valid_fft_size(1 << 21));
+    assert!(!is_valid_fft_size(0));
+}
+
+#[test]
+fn zero_padding_densifies_bins_without_moving_the_peak() {
+    use liveplot::data::fft::{FFTWindow, FftData};
+    use std::collections::VecDeque;
+
+    // 1.0-amplitude sine at 100 Hz, fs = 1024 Hz, N = 1024.
+    let buf: VecDeque<[f64; 2]> = (0..1024)
+        .map(|i| {
+            let t = i as f64 / 1024.0;
+            [t, (2.0 * std::f64::consts::PI * 100.0 * t).sin()]
+        })
+        .collect();
+    let spectrum = |pad: usize| {
+        let mut fft = FftData::default();
+        fft.zero_pad_factor = pad;
+        fft.compute_fft(&buf, false, &None, 1024, FFTWindow::Hann)
+            .unwrap()
+    };
+
+    let plain = spectrum(1);
+    let padded = spectrum(4);
+    assert_eq!(padded.len(), 4 * plain.len());
+    let spacing = |s: &[[f64; 2]]| s[1][0] - s[0][0];
+    assert!((spacing(&plain) / spacing(&padded) - 4.0).abs() < 1e-9);
+
+    let peak = |s: &[[f64; 2]]| *s.iter().max_by(|a, b| a[1].total_cmp(&b[1])).unwrap();
+    let (p1, p4) = (peak(&plain), peak(&padded));
+    assert!((p1[0] - 100.0).abs() < 1.0 && (p4[0] - 100.0).abs() < 0.25);
+    // Amplitude stays window-corrected against the unpadded length.
+    assert!((p4[1] - 1.0).abs() < 0.05, "{}", p4[1]);
+}
+
+#[test]
+fn interval_cv_flags_irregular_timestamps() {
+    use liveplot::data::fft::{interval_cv, FFTWindow, FftData, IRREGULAR_SAMPLING_CV};
+    use std::collections::VecDeque;
+
+    let uniform: Vec<f64> = (0..100).map(|i| i as f64 * 0.01).collect();
+    assert!(interval_cv(uniform.iter().copied()).unwrap() < 1e-9);
+    assert_eq!(interval_cv([0.0, 1.0]), None);
+    assert_eq!(interval_cv([1.0, 1.0, 1.0]), None);
+
+    // Alternating 1 ms / 3 ms intervals: mean 2 ms, std 1 ms.
+    let jittered: VecDeque<[f64; 2]> = (0..512)
+        .map(|i| [(i / 2) as f64 * 0.004 + (i % 2) as f64 * 0.001, 0.0])
+        .collect();
+    let cv = interval_cv(jittered.iter().map(|p| p[0])).unwrap();
+    assert!((cv - 0.5).abs() < 1e-3, "{}", cv);
+    assert!(cv > IRREGULAR_SAMPLING_CV);
+
+    let mut fft = FftData::default();
+    fft.compute_fft(&jittered, false, &None, 256, FFTWindow::Hann)
+        .unwrap();
+    assert!(fft.last_interval_cv > IRREGULAR_SAMPLING_CV);
+}
+
+#[test]
+fn band_power_integrates_a_tone_in_every_scale() {
+    use liveplot::data::fft::{band_power, FFTWindow, FftData, FftScale, SpectrumMeta};
+    use std::collections::VecDeque;
+
+    // 2.0-amplitude sine on bin 64 (fs = 1024 Hz, N = 1024): power A²/2 = 2.
+    let buf: VecDeque<[f64; 2]> = (0..1024)
+        .map(|i| {
+            let t = i as f64 / 1024.0;
+            [t, 2.0 * (2.0 * std::f64::consts::PI * 64.0 * t).sin()]
+        })
+        .collect();
+    for scale in FftScale::ALL.iter().copied() {
+        let mut fft = FftData::default();
+        fft.magnitude_scale = scale;
+        let spectrum = fft
+            .compute_fft(&buf, false, &None, 1024, FFTWindow::Hann)
+            .unwrap();
+        let meta = SpectrumMeta {
+            window: FFTWindow::Hann,
+            fft_size: 1024,
+            padded_size: 1024,
+            sample_rate: 1024.0,
+            scale,
+            interval_cv: 0.0,
+        };
+        let bp = band_power(&spectrum, &meta, 1.0, 70.0, 58.0).unwrap();
+        assert_eq!((bp.f_lo, bp.f_hi, bp.bins), (58.0, 70.0, 13));
+        assert!((bp.power - 2.0).abs() < 1e-3, "{:?}: {}", scale, bp.power);
+        assert!((bp.rms - 2f64.sqrt()).abs() < 1e-3);
+
+        // A band away from the tone holds (almost) nothing.
+        let quiet = band_power(&spectrum, &meta, 1.0, 200.0, 300.0).unwrap();
+        assert!(quiet.power < 1e-6, "{:?}: {}", scale, quiet.power);
+        assert!(band_power(&spectrum, &meta, 1.0, 600.0, 700.0).is_none());
+    }
+}
+
+#[test]
+fn spectrogram_keeps_capped_history_and_maps_levels() {
+    use liveplot::data::fft::{Spectrogram, SPECTROGRAM_DB_SPAN};
+
+    let spectrum = |peak_bin: usize, bins: usize| -> Vec<[f64; 2]> {
+        (0..bins)
+            .map(|i| [i as f64 * 10.0, if i == peak_bin { 1.0 } else { 1e-9 }])
+            .collect()
+    };
+    let mut sg = Spectrogram::default();
+    for k in 0..8 {
+        sg.push(k as f64 * 0.5, spectrum(k, 8), 4);
+    }
+    assert_eq!(sg.len(), 4);
+    // Same timestamp replaces the newest frame instead of adding one.
+    sg.push(3.5, spectrum(0, 8), 4);
+    assert_eq!(sg.len(), 4);
+
+    // Two bins per column: 4 columns × 4 rows, oldest row first.
+    let g = sg.grid(None, 4).unwrap();
+    assert_eq!((g.cols, g.rows), (4, 4));
+    assert_eq!(g.col_width, 20.0);
+    assert_eq!((g.t_start, g.row_height), (-1.5, 0.5));
+    assert_eq!(&g.values[..4], &[1e-9, 1e-9, 1.0, 1e-9]); // peak at bin 4
+    assert_eq!(g.range, (1e-9, 1.0));
+
+    // In dB the range stops a fixed span below the strongest bin.
+    let db = sg.grid(Some(20.0), 8).unwrap();
+    assert_eq!(db.range, (-SPECTROGRAM_DB_SPAN, 0.0));
+
+    // A new FFT size starts the history over.
+    sg.push(4.0, spectrum(0, 16), 4);
+    assert_eq!(sg.len(), 1);
+}
+
+#[test]
+fn phase_of_a_delay_unwraps_to_a_straight_line() {
+    use liveplot::data::fft::{mask_phase, unwrap_phase, FFTWindow, FftData, FftScale};
+    use std::collections::VecDeque;
+    use std::f64::consts::PI;
+
+    // A unit impulse 10 samples into the window: phase = -2π·k·10/N.
+    let n = 1024;
+    let buf: VecDeque<[f64; 2]> = (0..n)
+        .map(|i| [i as f64 / n as f64, if i == 10 { 1.0 } else { 0.0 }])
+        .collect();
+    let mut fft = FftData::default();
+    let mut bins = fft
+        .compute_fft_complex(&buf, false, &None, n, FFTWindow::Rect)
+        .unwrap();
+    assert!(bins.iter().all(|b| b[2].abs() <= PI + 1e-9));
+    unwrap_phase(&mut bins);
+    for (k, b) in bins.iter().enumerate() {
+        let expected = -2.0 * PI * k as f64 * 10.0 / n as f64;
+        assert!(
+            (b[2] - expected).abs() < 1e-6,
+            "bin {k}: {} vs {expected}",
+            b[2]
+        );
+    }
+
+    // A pure tone leaves every other bin at rounding-noise level: masked.
+    let tone: VecDeque<[f64; 2]> = (0..n)
+        .map(|i| {
+            let t = i as f64 / n as f64;
+            [t, (2.0 * PI * 64.0 * t).cos()]
+        })
+        .collect();
+    let mut bins = fft
+        .compute_fft_complex(&tone, false, &None, n, FFTWindow::Rect)
+        .unwrap();
+    mask_phase(&mut bins, FftScale::Linear, 60.0);
+    assert!(bins[64][2].abs() < 1e-6);
+    assert_eq!(bins.iter().filter(|b| !b[2].is_nan()).count(), 1);
+    // Masked bins are skipped, not unwrapped through.
+    unwrap_phase(&mut bins);
+    assert!(bins[64][2].abs() < 1e-6);
+}
+
+#[test]
+fn flat_top_reads_amplitude_between_bins_and_shapes_reduce() {
+    use liveplot::data::fft::{FFTWindow, FftData};
+    use std::collections::VecDeque;
+
+    // Amplitude-1 tone halfway between bins 64 and 65: the worst case for
+    // scalloping loss.
+    let buf: VecDeque<[f64; 2]> = (0..1024)
+        .map(|i| {
+            let t = i as f64 / 1024.0;
+            [t, (2.0 * std::f64::consts::PI * 64.5 * t).sin()]
+        })
+        .collect();
+    let mut fft = FftData::default();
+    let mut peak = |window| {
+        let out = fft.compute_fft(&buf, false, &None, 1024, window).unwrap();
+        out.iter().map(|p| p[1]).fold(f64::MIN, f64::max)
+    };
+    let flat = peak(FFTWindow::FlatTop);
+    assert!((20.0 * flat.log10()).abs() < 0.02, "flat-top {flat}");
+    let hann = peak(FFTWindow::Hann);
+    assert!(20.0 * hann.log10() < -1.0, "Hann {hann}");
+
+    // Limiting shape parameters reduce to the simpler windows.
+    let n = 64;
+    for i in 0..n {
+        let w = |win: FFTWindow| win.weight(i, n);
+        assert!((w(FFTWindow::Kaiser { beta: 0.0 }) - 1.0).abs() < 1e-12);
+        assert!((w(FFTWindow::Tukey { alpha: 0.0 }) - 1.0).abs() < 1e-12);
+        assert!((w(FFTWindow::Tukey { alpha: 1.0 }) - w(FFTWindow::Hann)).abs() < 1e-12);
+    }
+    let kaiser = FFTWindow::Kaiser { beta: 8.6 };
+    assert!((kaiser.weight(n / 2, n) - 1.0).abs() < 1e-12);
+    assert!(kaiser.weight(0, n) < 2e-3);
+    assert_eq!(kaiser.description(), "Kaiser β=8.6");
+    assert_eq!(
+        FFTWindow::from_label("Tukey").map(|w| w.with_param(2.0)),
+        Some(FFTWindow::Tukey { alpha: 1.0 })
+    );
+}
+
+#[test]
+fn find_peaks_picks_strongest_local_maxima_above_floor() {
+    use liveplot::data::fft::{find_peaks, format_hz};
+
+    let ys = [0.0, 5.0, 5.0, 1.0, 3.0, 0.5, 9.0, 2.0, 0.2, 0.4];
+    let spectrum: Vec<[f64; 2]> = ys
+        .iter()
+        .enumerate()
+        .map(|(i, &y)| [i as f64 * 100.0, y])
+        .collect();
+    // The 5.0 plateau counts once; the rising edge into the last bin counts.
+    assert_eq!(
+        find_peaks(&spectrum, 10, None),
+        vec![[600.0, 9.0], [100.0, 5.0], [400.0, 3.0], [900.0, 0.4]]
+    );
+    assert_eq!(
+        find_peaks(&spectrum, 2, None),
+        vec![[600.0, 9.0], [100.0, 5.0]]
+    );
+    assert_eq!(find_peaks(&spectrum, 10, Some(3.0)).len(), 2);
+
+    assert_eq!(format_hz(1234.5), "1.234 kHz");
+    assert_eq!(format_hz(50.0), "50.00 Hz");
+    assert_eq!(format_hz(0.05), "50.00 mHz");
+    assert_eq!(format_hz(2.5e6), "2.500 MHz");
+}
+
+#[test]
+fn iq_spectrum_puts_a_clockwise_tone_at_a_negative_frequency() {
+    use liveplot::data::fft::{FFTWindow, FftData};
+    use std::collections::VecDeque;
+    use std::f64::consts::PI;
+
+    // e^{-j·2π·64·t}: I = cos, Q = −sin (fs = 1024 Hz, N = 1024).
+    let n = 1024;
+    let (i, q): (VecDeque<[f64; 2]>, VecDeque<[f64; 2]>) = (0..n)
+        .map(|k| {
+            let t = k as f64 / n as f64;
+            let ph = -2.0 * PI * 64.0 * t;
+            ([t, ph.cos()], [t, ph.sin()])
+        })
+        .unzip();
+    let mut fft = FftData::default();
+    let bins = fft.compute_fft_iq(&i, &q, n, FFTWindow::Hann).unwrap();
+
+    // fft-shifted: one bin per Hz from −fs/2 up to just below +fs/2.
+    assert_eq!(bins.len(), n);
+    assert_eq!(bins[0][0], -512.0);
+    assert_eq!(bins[n - 1][0], 511.0);
+    let peak = bins.iter().max_by(|a, b| a[1].total_cmp(&b[1])).unwrap();
+    assert_eq!(peak[0], -64.0);
+    assert!((peak[1] - 1.0).abs() < 1e-2);
+    let mirror = bins.iter().find(|b| b[0] == 64.0).unwrap();
+    assert!(mirror[1] < 1e-6);
+
+    // Without Q the same I trace is a real cosine: both sides, half each.
+    let zeros: VecDeque<[f64; 2]> = i.iter().map(|p| [p[0], 0.0]).collect();
+    let bins = fft.compute_fft_iq(&i, &zeros, n, FFTWindow::Hann).unwrap();
+    for f in [-64.0, 64.0] {
+        let b = bins.iter().find(|b| b[0] == f).unwrap();
+        assert!((b[1] - 0.5).abs() < 1e-2, "{f}: {}", b[1]);
+    }
+}
@@ -33,3 +33,26 @@ fn get_hotkey_traces_default() {
     assert_eq!(result.key, 'T');
     assert_eq!(result.modifier, Modifier::None);
 }
+
+#[test]
+fn trace_cycling_hotkeys_default() {
+    let hk = Hotkeys::default();
+    let next = get_hotkey_for_name(&hk, HotkeyName::NextTrace).unwrap();
+    let prev = get_hotkey_for_name(&hk, HotkeyName::PrevTrace).unwrap();
+    assert_eq!((next.key, next.modifier), ('N', Modifier::None));
+    assert_eq!((prev.key, prev.modifier), ('N', Modifier::Shift));
+}
+
+#[test]
+fn fft_db_toggle_hotkey_default() {
+    let hk = Hotkeys::default();
+    let db = get_hotkey_for_name(&hk, HotkeyName::FftDb).unwrap();
+    assert_eq!((db.key, db.modifier), ('D', Modifier::Ctrl));
+}
+
+#[test]
+fn fit_all_data_hotkey_default() {
+    let hk = Hotkeys::default();
+    let fit = get_hotkey_for_name(&hk, HotkeyName::FitAllData).unwrap();
+    assert_eq!((fit.key, fit.modifier), ('F', Modifier::Shift));
+}
@@ -226,6 +226,40 @@ fn test_divide_incremental() {
     assert_eq!(out2[2], [2.0, 5.0]);
 }
 
+#[test]
+fn test_magnitude_and_phase_iq() {
+    let sources = make_sources(&[
+        ("i", vec![[0.0, 3.0], [1.0, 0.0], [2.0, -1.0]]),
+        ("q", vec![[0.0, 4.0], [1.0, 2.0], [2.0, 0.0]]),
+        ("mag", vec![]),
+        ("phase", vec![]),
+    ]);
+
+    let mut mag = MathTrace::new(
+        TraceRef::new("mag"),
+        MathKind::Magnitude {
+            i: TraceRef::new("i"),
+            q: TraceRef::new("q"),
+        },
+    );
+    let out = mag.compute_math_trace(&sources);
+    assert_eq!(out, vec![[0.0, 5.0], [1.0, 2.0], [2.0, 1.0]]);
+
+    let mut phase = MathTrace::new(
+        TraceRef::new("phase"),
+        MathKind::Phase {
+            i: TraceRef::new("i"),
+            q: TraceRef::new("q"),
+        },
+    );
+    let out = phase.compute_math_trace(&sources);
+    assert_eq!(out.len(), 3);
+    assert!((out[0][1] - (4.0f64).atan2(3.0)).abs() < 1e-12);
+    assert!((out[1][1] - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    assert!((out[2][1] - std::f64::consts::PI).abs() < 1e-12);
+    assert_eq!(phase.input_trace_names().len(), 2);
+}
+
 #[test]
 fn test_no_math_traces_no_crash() {
     // Verify that compute_math_trace with empty inputs doesn't panic
@@ -248,3 +282,586 @@ fn test_input_trace_names() {
     assert!(names.contains(&&TraceRef::new("a")));
     assert!(names.contains(&&TraceRef::new("b")));
 }
+
+#[test]
+fn paused_math_outputs_stay_frozen_until_resume() {
+    use liveplot::data::data::{LivePlotData, LivePlotRequests};
+    use liveplot::data::math::FilterKind;
+    use liveplot::data::scope::ScopeData;
+    use liveplot::data::traces::TracesCollection;
+    use liveplot::panels::{MathPanel, Panel};
+
+    let (x, lp) = (TraceRef::new("x"), TraceRef::new("lp"));
+    let mut panel = MathPanel::default();
+    panel.set_math_traces(vec![MathTrace::new(
+        lp.clone(),
+        MathKind::Filter {
+            input: x.clone(),
+            kind: FilterKind::Lowpass { cutoff_hz: 1.0 },
+        },
+    )]);
+    let mut scope = ScopeData::default();
+    let mut traces = TracesCollection::default();
+    let mut requests = LivePlotRequests::default();
+    traces.get_trace_or_new(&x).live = (0..50).map(|i| [i as f64 * 0.1, 1.0]).collect();
+
+    let mut step = |scope: &mut ScopeData, traces: &mut TracesCollection| {
+        let mut data = LivePlotData {
+            scope_data: vec![scope],
+            traces,
+            pending_requests: &mut requests,
+            event_ctrl: None,
+            settings_changed: false,
+        };
+        panel.update_data(&mut data);
+    };
+
+    step(&mut scope, &mut traces);
+    scope.paused = true;
+    traces.take_snapshot();
+    step(&mut scope, &mut traces);
+    let out = |traces: &TracesCollection| {
+        let tr = traces.get_trace(&lp).unwrap();
+        (tr.live.clone(), tr.snap.clone())
+    };
+    let frozen = out(&traces);
+
+    // New input while paused changes nothing.
+    traces.get_trace_or_new(&x).live.push_back([5.0, 2.0]);
+    for _ in 0..3 {
+        step(&mut scope, &mut traces);
+        assert_eq!(out(&traces), frozen);
+    }
+
+    // On resume the live output catches up with the new sample.
+    scope.paused = false;
+    traces.clear_snapshot();
+    step(&mut scope, &mut traces);
+    assert_eq!(traces.get_trace(&lp).unwrap().live.back().unwrap()[0], 5.0);
+}
+
+#[test]
+fn math_trace_with_removed_input_is_disabled() {
+    use liveplot::data::data::{LivePlotData, LivePlotRequests};
+    use liveplot::data::scope::ScopeData;
+    use liveplot::data::traces::TracesCollection;
+    use liveplot::panels::{MathPanel, Panel};
+
+    let (a, b, sum) = (TraceRef::new("a"), TraceRef::new("b"), TraceRef::new("sum"));
+    let mut panel = MathPanel::default();
+    panel.set_math_traces(vec![MathTrace::new(
+        sum.clone(),
+        MathKind::Add {
+            inputs: vec![(a.clone(), 1.0), (b.clone(), 1.0)],
+        },
+    )]);
+    let mut scope = ScopeData::default();
+    scope.trace_order = vec![a.clone(), b.clone()];
+    let mut traces = TracesCollection::default();
+    let mut requests = LivePlotRequests::default();
+    traces.get_trace_or_new(&a).live = (0..5).map(|i| [i as f64, 1.0]).collect();
+    traces.get_trace_or_new(&b).live = (0..5).map(|i| [i as f64, 2.0]).collect();
+
+    let mut data = LivePlotData {
+        scope_data: vec![&mut scope],
+        traces: &mut traces,
+        pending_requests: &mut requests,
+        event_ctrl: None,
+        settings_changed: false,
+    };
+    panel.update_data(&mut data);
+    let before = data.traces.get_trace(&sum).unwrap().live.clone();
+    assert_eq!(before.back().unwrap()[1], 3.0);
+
+    data.remove_trace(&b);
+    data.traces.get_trace_or_new(&a).live.push_back([5.0, 1.0]);
+    panel.update_data(&mut data);
+
+    let out = data.traces.get_trace(&sum).unwrap();
+    assert_eq!(out.live, before);
+    assert!(out.info.contains("'b' was removed"), "{}", out.info);
+    assert!(!scope.trace_order.contains(&b));
+}
+
+#[test]
+fn math_outputs_are_flagged_for_export_filtering() {
+    use liveplot::data::data::{LivePlotData, LivePlotRequests};
+    use liveplot::data::scope::ScopeData;
+    use liveplot::data::traces::TracesCollection;
+    use liveplot::panels::{MathPanel, Panel};
+
+    let (a, d) = (TraceRef::new("a"), TraceRef::new("d"));
+    let mut panel = MathPanel::default();
+    panel.set_math_traces(vec![MathTrace::new(
+        d.clone(),
+        MathKind::Differentiate { input: a.clone() },
+    )]);
+    let mut scope = ScopeData::default();
+    let mut traces = TracesCollection::default();
+    let mut requests = LivePlotRequests::default();
+    traces.get_trace_or_new(&a).live = (0..5).map(|i| [i as f64, i as f64]).collect();
+    panel.update_data(&mut LivePlotData {
+        scope_data: vec![&mut scope],
+        traces: &mut traces,
+        pending_requests: &mut requests,
+        event_ctrl: None,
+        settings_changed: false,
+    });
+
+    assert!(traces.is_math(&d));
+    assert!(!traces.is_math(&a));
+    assert!(!traces.is_math(&TraceRef::new("missing")));
+}
+
+#[test]
+fn test_expression_on_aligned_inputs() {
+    let mut trace = MathTrace::new(
+        TraceRef::new("expr"),
+        MathKind::Expr {
+            expr: "(a + 2*b) / c".to_string(),
+            vars: vec![],
+        },
+    );
+    let mut input_names = trace.input_traces();
+    input_names.sort();
+    assert_eq!(
+        input_names,
+        vec![TraceRef::new("a"), TraceRef::new("b"), TraceRef::new("c")]
+    );
+
+    // `c` is sampled at half rate; the formula sees it interpolated.
+    let sources = make_sources(&[
+        ("a", vec![[0.0, 1.0], [1.0, 2.0], [2.0, 3.0]]),
+        ("b", vec![[0.0, 1.0], [1.0, 1.0], [2.0, 1.0]]),
+        ("c", vec![[0.0, 1.0], [2.0, 5.0]]),
+        ("expr", vec![]),
+    ]);
+    assert_eq!(trace.math_error(&sources), None);
+    let out = trace.compute_math_trace(&sources);
+    assert_eq!(out, vec![[0.0, 3.0], [1.0, 4.0 / 3.0], [2.0, 1.0]]);
+
+    // Incremental: only the new timestamp is appended.
+    let sources = make_sources(&[
+        ("a", vec![[0.0, 1.0], [1.0, 2.0], [2.0, 3.0], [3.0, 4.0]]),
+        ("b", vec![[0.0, 1.0], [1.0, 1.0], [2.0, 1.0], [3.0, 1.0]]),
+        ("c", vec![[0.0, 1.0], [2.0, 5.0], [3.0, 2.0]]),
+        ("expr", out),
+    ]);
+    let out = trace.compute_math_trace(&sources);
+    assert_eq!(out.len(), 4);
+    assert_eq!(out[3], [3.0, 3.0]);
+
+    // Editing the formula is picked up without rebuilding the definition.
+    trace.kind = MathKind::Expr {
+        expr: "a - b".to_string(),
+        vars: vec![],
+    };
+    assert_eq!(
+        trace.input_traces(),
+        vec![TraceRef::new("a"), TraceRef::new("b")]
+    );
+    let sources = make_sources(&[
+        ("a", vec![[0.0, 1.0], [1.0, 2.0]]),
+        ("b", vec![[0.0, 1.0], [1.0, 1.0]]),
+        ("expr", vec![]),
+    ]);
+    let out = trace.compute_math_trace(&sources);
+    assert_eq!(out, vec![[0.0, 0.0], [1.0, 1.0]]);
+    assert_eq!(
+        trace.input_trace_names(),
+        vec![&TraceRef::new("a"), &TraceRef::new("b")]
+    );
+}
+
+#[test]
+fn test_expression_reports_non_finite_results() {
+    let mut trace = MathTrace::new(
+        TraceRef::new("ratio"),
+        MathKind::Expr {
+            expr: "a/b".to_string(),
+            vars: vec![],
+        },
+    );
+    let sources = make_sources(&[
+        ("a", vec![[0.0, 1.0], [1.0, 1.0], [2.0, 1.0], [3.0, 1.0]]),
+        ("b", vec![[0.0, 2.0], [1.0, 0.0], [2.0, 4.0], [3.0, 0.0]]),
+        ("ratio", vec![]),
+    ]);
+    assert_eq!(trace.math_error(&sources), None);
+    let out = trace.compute_math_trace(&sources);
+    assert_eq!(out, vec![[0.0, 0.5], [2.0, 0.25]]);
+    assert_eq!(
+        trace.math_error(&sources).as_deref(),
+        Some("non-finite result at t = 1")
+    );
+
+    // Cleared by a reset or an edit of the formula.
+    trace.reset_state();
+    assert_eq!(trace.math_error(&sources), None);
+    trace.kind = MathKind::Expr {
+        expr: "a/(b + 1)".to_string(),
+        vars: vec![],
+    };
+    trace.compute_math_trace(&sources);
+    assert_eq!(trace.math_error(&sources), None);
+}
+
+#[test]
+fn test_expression_errors_are_reported() {
+    let sources = make_sources(&[("a", vec![[0.0, 1.0]])]);
+
+    let mut bad = MathTrace::new(
+        TraceRef::new("bad"),
+        MathKind::Expr {
+            expr: "a * (".to_string(),
+            vars: vec![],
+        },
+    );
+    assert!(bad.math_error(&sources).is_some());
+    assert!(bad.input_trace_names().is_empty());
+    assert!(bad.compute_math_trace(&sources).is_empty());
+
+    let mut unknown = MathTrace::new(
+        TraceRef::new("unknown"),
+        MathKind::Expr {
+            expr: "a + nope".to_string(),
+            vars: vec![],
+        },
+    );
+    assert_eq!(
+        unknown.math_error(&sources).as_deref(),
+        Some("unknown trace 'nope'")
+    );
+    assert!(unknown.compute_math_trace(&sources).is_empty());
+}
+
+#[test]
+fn test_rms_weights_by_dt_and_slides_incrementally() {
+    let mut trace = MathTrace::new(
+        TraceRef::new("rms"),
+        MathKind::Rms {
+            input: TraceRef::new("sig"),
+            window_secs: 2.0,
+        },
+    );
+    assert!(trace.is_stateful());
+
+    // First sample: window not yet filled, RMS is |v|.
+    let sources = make_sources(&[("sig", vec![[0.0, -3.0]]), ("rms", vec![])]);
+    let out1 = trace.compute_math_trace(&sources);
+    assert_eq!(out1, vec![[0.0, 3.0]]);
+
+    // Constant magnitude gives the same RMS regardless of irregular spacing.
+    let sig: Vec<[f64; 2]> = vec![[0.0, -3.0], [0.1, 3.0], [0.5, -3.0], [1.7, 3.0]];
+    let sources = make_sources(&[("sig", sig.clone()), ("rms", out1.clone())]);
+    let out2 = trace.compute_math_trace(&sources);
+    assert_eq!(out2.len(), 4);
+    assert_eq!(out2[0], out1[0]);
+    for p in &out2 {
+        assert!((p[1] - 3.0).abs() < 1e-9, "{p:?}");
+    }
+
+    // A step from 0 to 2 on a long segment: only the last 2 s count, and the
+    // sparse old sample is weighted by its spacing, not its count.
+    let mut sig = vec![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]];
+    sig.extend((1..=10).map(|k| [2.0 + k as f64 * 0.1, 2.0]));
+    sig.push([5.0, 2.0]);
+    let mut trace = MathTrace::new(
+        TraceRef::new("rms"),
+        MathKind::Rms {
+            input: TraceRef::new("sig"),
+            window_secs: 2.0,
+        },
+    );
+    let sources = make_sources(&[("sig", sig), ("rms", vec![])]);
+    let out = trace.compute_math_trace(&sources);
+    // At t=3: window [1,3], v² integral = 0 on [1,2], trapezoid 0..4 on [2,2.1] = 0.2,
+    // and 4 on [2.1,3] = 3.6 → sqrt(3.8 / 2).
+    let at3 = out.iter().find(|p| (p[0] - 3.0).abs() < 1e-9).unwrap();
+    assert!((at3[1] - (3.8f64 / 2.0).sqrt()).abs() < 1e-9, "{at3:?}");
+    // At t=5 the window holds only the 2.0 level.
+    assert!((out.last().unwrap()[1] - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_moving_average_boxcar_incremental_and_identity() {
+    let mut trace = MathTrace::new(
+        TraceRef::new("avg"),
+        MathKind::MovingAverage {
+            input: TraceRef::new("sig"),
+            window_secs: 2.5,
+        },
+    );
+    let sig: Vec<[f64; 2]> = (0..6).map(|i| [i as f64, i as f64]).collect();
+    let sources = make_sources(&[("sig", sig[..4].to_vec()), ("avg", vec![])]);
+    let out1 = trace.compute_math_trace(&sources);
+    // Window holds up to three samples (t - 2.5, t].
+    assert_eq!(out1, vec![[0.0, 0.0], [1.0, 0.5], [2.0, 1.0], [3.0, 2.0]]);
+
+    let sources = make_sources(&[("sig", sig.clone()), ("avg", out1.clone())]);
+    let out2 = trace.compute_math_trace(&sources);
+    assert_eq!(&out2[..4], &out1[..]);
+    assert_eq!(&out2[4..], &[[4.0, 3.0], [5.0, 4.0]]);
+
+    // Same result from scratch (e.g. a snapshot pass) as incrementally.
+    let mut fresh = trace.clone();
+    let sources = make_sources(&[("sig", sig.clone()), ("avg", vec![])]);
+    assert_eq!(fresh.compute_math_trace(&sources), out2);
+    // Resuming from an output the ring buffer does not match refills it.
+    let sources = make_sources(&[("sig", sig.clone()), ("avg", out2[..3].to_vec())]);
+    assert_eq!(trace.compute_math_trace(&sources), out2);
+
+    // A window shorter than the sample spacing passes the input through.
+    let mut ident = MathTrace::new(
+        TraceRef::new("avg"),
+        MathKind::MovingAverage {
+            input: TraceRef::new("sig"),
+            window_secs: 0.5,
+        },
+    );
+    let sources = make_sources(&[("sig", sig.clone()), ("avg", vec![])]);
+    assert_eq!(ident.compute_math_trace(&sources), sig);
+}
+
+#[test]
+fn reset_state_request_recomputes_from_current_inputs() {
+    use liveplot::data::data::{LivePlotData, LivePlotRequests};
+    use liveplot::data::scope::ScopeData;
+    use liveplot::data::traces::TracesCollection;
+    use liveplot::panels::{MathPanel, Panel};
+
+    let (x, int) = (TraceRef::new("x"), TraceRef::new("int"));
+    let mut panel = MathPanel::default();
+    panel.set_math_traces(vec![MathTrace::new(
+        int.clone(),
+        MathKind::Integrate {
+            input: x.clone(),
+            y0: 0.0,
+        },
+    )]);
+    let mut scope = ScopeData::default();
+    let mut traces = TracesCollection::default();
+    let mut requests = LivePlotRequests::default();
+    traces.get_trace_or_new(&x).live = (0..10).map(|i| [i as f64, 1.0]).collect();
+
+    let mut step = |traces: &mut TracesCollection, reset: Option<&TraceRef>| {
+        requests.reset_math_state.extend(reset.cloned());
+        let mut data = LivePlotData {
+            scope_data: vec![&mut scope],
+            traces,
+            pending_requests: &mut requests,
+            event_ctrl: None,
+            settings_changed: false,
+        };
+        panel.update_data(&mut data);
+    };
+    let last = |traces: &TracesCollection| *traces.get_trace(&int).unwrap().live.back().unwrap();
+
+    step(&mut traces, None);
+    assert_eq!(last(&traces), [9.0, 9.0]);
+
+    // The input buffer drops its oldest samples; the integral keeps its sum.
+    traces.get_trace_or_new(&x).live.drain(..5);
+    step(&mut traces, None);
+    assert_eq!(last(&traces), [9.0, 9.0]);
+
+    // A reset re-zeroes it against what is still there.
+    step(&mut traces, Some(&int));
+    assert_eq!(last(&traces), [9.0, 4.0]);
+    assert!(requests.reset_math_state.is_empty());
+}
+
+#[test]
+fn test_expression_with_bound_variables() {
+    let sources = make_sources(&[
+        ("motor current", vec![[0.0, 1.0], [1.0, 2.0]]),
+        (
+            "angle",
+            vec![[0.0, 0.0], [1.0, std::f64::consts::FRAC_PI_2]],
+        ),
+    ]);
+    let mut trace = MathTrace::new(
+        TraceRef::new("expr"),
+        MathKind::Expr {
+            expr: "a*sin(b) + 2".to_string(),
+            vars: vec![
+                ("a".to_string(), TraceRef::new("motor current")),
+                ("b".to_string(), TraceRef::new("angle")),
+            ],
+        },
+    );
+    assert_eq!(
+        trace.input_traces(),
+        vec![TraceRef::new("motor current"), TraceRef::new("angle")]
+    );
+    assert_eq!(trace.math_error(&sources), None);
+    let out = trace.compute_math_trace(&sources);
+    assert_eq!(out[0], [0.0, 2.0]);
+    assert!((out[1][1] - 4.0).abs() < 1e-12);
+
+    let dangling = MathTrace::new(
+        TraceRef::new("expr"),
+        MathKind::Expr {
+            expr: "log(a)".to_string(),
+            vars: vec![("a".to_string(), TraceRef::new("gone"))],
+        },
+    );
+    assert_eq!(
+        dangling.math_error(&sources).as_deref(),
+        Some("variable 'a' is bound to unknown trace 'gone'")
+    );
+
+    // Definitions saved before variable bindings existed still load.
+    let old: MathKind = serde_json::from_str(r#"{"Expr":{"expr":"a"}}"#).unwrap();
+    assert!(matches!(old, MathKind::Expr { vars, .. } if vars.is_empty()));
+}
+
+#[test]
+fn test_abs_incremental() {
+    let mut trace = MathTrace::new(
+        TraceRef::new("rect"),
+        MathKind::Abs {
+            input: TraceRef::new("sig"),
+        },
+    );
+    let sources = make_sources(&[("sig", vec![[0.0, -1.5], [1.0, 2.0]]), ("rect", vec![])]);
+    let out1 = trace.compute_math_trace(&sources);
+    assert_eq!(out1, vec![[0.0, 1.5], [1.0, 2.0]]);
+
+    let sources = make_sources(&[
+        ("sig", vec![[0.0, -1.5], [1.0, 2.0], [2.0, -0.25]]),
+        ("rect", out1),
+    ]);
+    let out2 = trace.compute_math_trace(&sources);
+    assert_eq!(out2, vec![[0.0, 1.5], [1.0, 2.0], [2.0, 0.25]]);
+    assert_eq!(trace.math_formula_string(), "|sig|");
+}
+
+#[test]
+fn test_biquad_notch_removes_mains_hum() {
+    use liveplot::data::math::FilterKind;
+    use std::f64::consts::TAU;
+
+    let mut trace = MathTrace::new(
+        TraceRef::new("clean"),
+        MathKind::Filter {
+            input: TraceRef::new("sig"),
+            kind: FilterKind::BiquadNotch {
+                center_hz: 50.0,
+                q: 5.0,
+            },
+        },
+    );
+    // 2 s at 1 kHz: slow 2 Hz signal plus 50 Hz hum.
+    let sig: Vec<[f64; 2]> = (0..2000)
+        .map(|i| {
+            let t = i as f64 * 1e-3;
+            [t, (TAU * 2.0 * t).sin() + 0.5 * (TAU * 50.0 * t).sin()]
+        })
+        .collect();
+    let sources = make_sources(&[("sig", sig), ("clean", vec![])]);
+    let out = trace.compute_math_trace(&sources);
+    assert_eq!(out.len(), 2000);
+    // Once settled, only the 2 Hz component remains.
+    let max_err = out[1000..]
+        .iter()
+        .map(|[t, y]| (y - (TAU * 2.0 * t).sin()).abs())
+        .fold(0.0, f64::max);
+    assert!(max_err < 0.03, "residual {max_err}");
+    assert_eq!(
+        trace.math_formula_string(),
+        "sig -> BQ-Notch f0=50.000 Q=5.000"
+    );
+}
+
+#[test]
+fn test_butterworth_order_and_incremental_state() {
+    use liveplot::data::math::{FilterBand, FilterKind};
+    use std::f64::consts::TAU;
+
+    let butter = |order: u8| {
+        MathTrace::new(
+            TraceRef::new("lp"),
+            MathKind::Filter {
+                input: TraceRef::new("sig"),
+                kind: FilterKind::Butterworth {
+                    kind: FilterBand::Lowpass,
+                    cutoff_hz: 10.0,
+                    order,
+                },
+            },
+        )
+    };
+    // 2 s at 1 kHz of a 100 Hz tone, one decade above the cutoff.
+    let sig: Vec<[f64; 2]> = (0..2000)
+        .map(|i| {
+            let t = i as f64 * 1e-3;
+            [t, (TAU * 100.0 * t).sin()]
+        })
+        .collect();
+    let peak = |out: &[[f64; 2]]| out[1000..].iter().map(|p| p[1].abs()).fold(0.0, f64::max);
+
+    let full2 =
+        butter(2).compute_math_trace(&make_sources(&[("sig", sig.clone()), ("lp", vec![])]));
+    let full4 =
+        butter(4).compute_math_trace(&make_sources(&[("sig", sig.clone()), ("lp", vec![])]));
+    // About -40 dB per decade per section pair: 2nd order ≈ 0.01, 4th ≈ 1e-4.
+    assert!(peak(&full2) < 0.015, "order 2 peak {}", peak(&full2));
+    assert!(peak(&full4) < 2e-4, "order 4 peak {}", peak(&full4));
+
+    // Feeding the samples in two chunks gives the same output as one pass.
+    let mut trace = butter(4);
+    let first = trace.compute_math_trace(&make_sources(&[
+        ("sig", sig[..700].to_vec()),
+        ("lp", vec![]),
+    ]));
+    let chunked = trace.compute_math_trace(&make_sources(&[
+        ("sig", sig.clone()),
+        ("lp", first.clone()),
+    ]));
+    assert_eq!(chunked, full4);
+
+    // Without carried-over state (e.g. a fresh trace) the sections are warmed
+    // up on the earlier input, so the continuation still matches.
+    let rebuilt =
+        butter(4).compute_math_trace(&make_sources(&[("sig", sig.clone()), ("lp", first)]));
+    assert_eq!(rebuilt, full4);
+}
+
+#[test]
+fn test_butterworth_bandpass_passes_only_the_band() {
+    use liveplot::data::math::{FilterBand, FilterKind};
+    use std::f64::consts::TAU;
+
+    let bandpass = || {
+        MathTrace::new(
+            TraceRef::new("bp"),
+            MathKind::Filter {
+                input: TraceRef::new("sig"),
+                kind: FilterKind::Butterworth {
+                    kind: FilterBand::Bandpass { high_cut_hz: 60.0 },
+                    cutoff_hz: 15.0,
+                    order: 4,
+                },
+            },
+        )
+    };
+    // Peak output over the second half of 4 s at 1 kHz of a tone at `f`.
+    let gain = |f: f64| {
+        let sig: Vec<[f64; 2]> = (0..4000)
+            .map(|i| {
+                let t = i as f64 * 1e-3;
+                [t, (TAU * f * t).sin()]
+            })
+            .collect();
+        let out = bandpass().compute_math_trace(&make_sources(&[("sig", sig), ("bp", vec![])]));
+        out[2000..].iter().map(|p| p[1].abs()).fold(0.0, f64::max)
+    };
+    assert!(gain(30.0) > 0.9, "in band {}", gain(30.0));
+    assert!(gain(1.5) < 0.01, "below band {}", gain(1.5));
+    assert!(gain(300.0) < 0.01, "above band {}", gain(300.0));
+    assert_eq!(
+        bandpass().math_formula_string(),
+        "sig -> BW4-BP 15.000-60.000 Hz"
+    );
+}
@@ -27,3 +27,172 @@ fn default_measurement_panel_has_no_measurements() {
     panel.update_data(&mut live);
     assert!(!live.scope_by_id(0).unwrap().measurement_active);
 }
+
+#[test]
+fn completed_measurement_is_logged() {
+    use liveplot::data::measurement::Measurement;
+
+    let mut panel = MeasurementPanel::default();
+    let mut m = Measurement::new("M1");
+    m.set_point1([1.0, 2.0]);
+    panel.restore_measurements(vec![m], Some(0));
+
+    let mut scope = ScopeData::default();
+    scope.clicked_point = Some([3.0, 6.0]);
+    let mut traces = TracesCollection::default();
+    let mut requests = LivePlotRequests::default();
+
+    let scope_refs: Vec<&mut ScopeData> = vec![&mut scope];
+    let mut live = LivePlotData {
+        scope_data: scope_refs,
+        traces: &mut traces,
+        pending_requests: &mut requests,
+        event_ctrl: None,
+        settings_changed: false,
+    };
+    panel.update_data(&mut live);
+
+    let history = panel.history();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].measurement, "M1");
+    assert_eq!(history[0].delta_x, 2.0);
+    assert_eq!(history[0].delta_y, 4.0);
+    assert_eq!(history[0].slope, Some(2.0));
+
+    let mut buf = Vec::new();
+    liveplot::data::measurement::write_measurement_log_csv(history, &mut buf).unwrap();
+    let csv = String::from_utf8(buf).unwrap();
+    let mut lines = csv.lines();
+    assert!(lines.next().unwrap().starts_with("timestamp,measurement,trace"));
+    assert!(lines.next().unwrap().contains(",M1,,0,"));
+}
+
+#[test]
+fn cycling_trace_resnaps_points_at_same_x() {
+    use liveplot::data::measurement::Measurement;
+    use liveplot::data::traces::TraceRef;
+
+    let (a, b) = (TraceRef::from("a"), TraceRef::from("b"));
+    let mut traces = TracesCollection::default();
+    traces.get_trace_or_new(&a).live = (0..5).map(|i| [i as f64, 1.0]).collect();
+    traces.get_trace_or_new(&b).live = (0..5).map(|i| [i as f64, 10.0 * i as f64]).collect();
+
+    let mut scope = ScopeData::default();
+    scope.trace_order = vec![a.clone(), b.clone()];
+
+    let mut m = Measurement::new("M1");
+    m.catch_trace = Some(a.clone());
+    m.set_point1([2.1, 1.0]);
+    m.set_point2([3.0, 1.0]);
+    let mut panel = MeasurementPanel::default();
+    panel.restore_measurements(vec![m], Some(0));
+
+    let mut requests = LivePlotRequests::default();
+    requests.cycle_measurement_trace = 1;
+    let mut live = LivePlotData {
+        scope_data: vec![&mut scope],
+        traces: &mut traces,
+        pending_requests: &mut requests,
+        event_ctrl: None,
+        settings_changed: false,
+    };
+    panel.update_data(&mut live);
+    assert_eq!(live.pending_requests.cycle_measurement_trace, 0);
+
+    let m = &panel.measurements()[0];
+    assert_eq!(m.catch_trace.as_ref(), Some(&b));
+    assert_eq!(m.p1, Some([2.0, 20.0]));
+    assert_eq!(m.p2, Some([3.0, 30.0]));
+
+    // Stepping backwards past the first trace wraps around.
+    live.pending_requests.cycle_measurement_trace = -1;
+    panel.update_data(&mut live);
+    live.pending_requests.cycle_measurement_trace = -1;
+    panel.update_data(&mut live);
+    assert_eq!(panel.measurements()[0].catch_trace.as_ref(), Some(&b));
+}
+
+#[test]
+fn crossing_frequency_ignores_noise_within_hysteresis() {
+    use liveplot::data::measurement::crossing_frequency;
+
+    // 5 Hz sine sampled at 1 kHz for one second, with ±0.05 dither.
+    let pts: Vec<[f64; 2]> = (0..1000)
+        .map(|i| {
+            let t = i as f64 / 1000.0;
+            let dither = if i % 2 == 0 { 0.05 } else { -0.05 };
+            [t, (2.0 * std::f64::consts::PI * 5.0 * t).sin() + dither]
+        })
+        .collect();
+
+    let f = crossing_frequency(pts.iter().copied(), 0.0, 0.2).unwrap();
+    assert!((f - 5.0).abs() < 0.1, "estimated {f} Hz");
+
+    // Without hysteresis the dither produces spurious crossings.
+    let noisy = crossing_frequency(pts.iter().copied(), 0.0, 0.0).unwrap();
+    assert!(noisy > 6.0, "expected spurious crossings, got {noisy} Hz");
+
+    assert_eq!(crossing_frequency([[0.0, 1.0], [1.0, 2.0]], 0.0, 0.0), None);
+}
+
+#[test]
+fn requested_points_produce_current_result() {
+    use liveplot::data::measurement::PointDelta;
+
+    let mut panel = MeasurementPanel::default();
+    let mut scope = ScopeData::default();
+    scope.y_axis.log_scale = true;
+    let mut traces = TracesCollection::default();
+    let mut requests = LivePlotRequests::default();
+    requests.set_measurement_points = Some(([1.0, 10.0], [3.0, 1000.0]));
+    let mut live = LivePlotData {
+        scope_data: vec![&mut scope],
+        traces: &mut traces,
+        pending_requests: &mut requests,
+        event_ctrl: None,
+        settings_changed: false,
+    };
+    panel.update_data(&mut live);
+    assert!(live.pending_requests.set_measurement_points.is_none());
+
+    // Stored in plot coordinates, reported back in axis units.
+    let m = &panel.measurements()[0];
+    assert_eq!(m.name, "M1");
+    assert_eq!(m.p1, Some([1.0, 1.0]));
+    assert_eq!(m.p2, Some([3.0, 3.0]));
+
+    let result = panel.current_result().unwrap();
+    assert_eq!(result.scope_id, Some(0));
+    assert_eq!(result.p1, Some([1.0, 10.0]));
+    assert_eq!(result.p2, Some([3.0, 1000.0]));
+    let delta = result.delta.unwrap();
+    assert_eq!(delta, PointDelta::between([1.0, 10.0], [3.0, 1000.0]));
+    assert_eq!(delta.slope, Some(495.0));
+    assert!(PointDelta::between([2.0, 0.0], [2.0, 5.0]).slope.is_none());
+}
+
+#[test]
+fn signal_stats_measure_offset_sine() {
+    use liveplot::data::measurement::SignalStats;
+
+    // 10 Hz sine of amplitude 2 on a DC offset of 5, one second at 1 kHz.
+    let pts: Vec<[f64; 2]> = (0..1000)
+        .map(|i| {
+            let t = i as f64 / 1000.0;
+            [t, 5.0 + 2.0 * (2.0 * std::f64::consts::PI * 10.0 * t).sin()]
+        })
+        .collect();
+    let s = SignalStats::compute(pts, 0.0).unwrap();
+    assert!((s.peak_to_peak - 4.0).abs() < 1e-3);
+    assert!((s.min - 3.0).abs() < 1e-3 && (s.max - 7.0).abs() < 1e-3);
+    assert!((s.mean - 5.0).abs() < 1e-9);
+    // sqrt(dc² + amplitude²/2)
+    assert!((s.rms - (25.0f64 + 2.0).sqrt()).abs() < 1e-9);
+    let f = s.frequency.unwrap();
+    assert!((f - 10.0).abs() < 0.1, "estimated {f} Hz");
+
+    // A ramp never crosses its mean twice.
+    let ramp = SignalStats::compute([[0.0, 0.0], [1.0, 1.0], [2.0, 2.0]], 0.0).unwrap();
+    assert_eq!(ramp.frequency, None);
+    assert_eq!(SignalStats::compute(Vec::new(), 0.0), None);
+}
@@ -32,3 +32,646 @@ fn scope_panel_api_controls_pause_click() {
     panel.set_pause_on_click(false);
     assert!(!panel.pause_on_click());
 }
+
+#[test]
+fn pause_pins_x_bounds_and_resume_restores_them() {
+    use liveplot::data::scope::{ResumeBehavior, ScopeType};
+    use liveplot::data::traces::TracesCollection;
+
+    let traces = TracesCollection::default();
+    let mut data = ScopeData::default();
+    data.scope_type = ScopeType::XYScope;
+    data.x_axis.auto_fit = false;
+    data.x_axis.bounds = (0.0, 10.0);
+    data.resume_behavior = ResumeBehavior::RestorePausedView;
+    data.update(&traces);
+
+    data.paused = true;
+    data.update(&traces);
+    assert_eq!(data.paused_x_bounds, Some((0.0, 10.0)));
+
+    // Pan while paused, then resume: the pinned view comes back.
+    data.x_axis.bounds = (5.0, 6.0);
+    data.paused = false;
+    data.update(&traces);
+    assert_eq!(data.x_axis.bounds, (0.0, 10.0));
+    assert_eq!(data.paused_x_bounds, None);
+
+    // With JumpToLatest the current view is kept.
+    data.resume_behavior = ResumeBehavior::JumpToLatest;
+    data.paused = true;
+    data.update(&traces);
+    data.x_axis.bounds = (5.0, 6.0);
+    data.paused = false;
+    data.update(&traces);
+    assert_eq!(data.x_axis.bounds, (5.0, 6.0));
+}
+
+#[test]
+fn time_scope_resume_restores_paused_position() {
+    use liveplot::data::scope::{ResumeBehavior, ScopeType};
+    use liveplot::data::traces::{TraceRef, TracesCollection};
+
+    let mut traces = TracesCollection::default();
+    let name = TraceRef::new("sig");
+    let push = |traces: &mut TracesCollection, upto: usize| {
+        let tr = traces.get_trace_or_new(&name);
+        let start = tr.live.len();
+        tr.live.extend((start..=upto).map(|i| [i as f64, 0.0]));
+    };
+    push(&mut traces, 100);
+
+    let mut data = ScopeData::default();
+    data.scope_type = ScopeType::TimeScope;
+    data.time_window = 10.0;
+    data.snapshot_on_pause = false;
+    data.resume_behavior = ResumeBehavior::RestorePausedView;
+    data.trace_order.push(name.clone());
+    data.update(&traces);
+    assert_eq!(data.x_axis.bounds, (90.0, 100.0));
+
+    data.paused = true;
+    data.update(&traces);
+    push(&mut traces, 200);
+    data.x_axis.bounds = (40.0, 50.0);
+    data.update(&traces);
+
+    // Resume: the pinned position is shown, not just its width.
+    data.paused = false;
+    data.update(&traces);
+    assert_eq!(data.x_axis.bounds, (90.0, 100.0));
+
+    // Following continues afterwards.
+    data.update(&traces);
+    assert_eq!(data.x_axis.bounds, (190.0, 200.0));
+}
+
+#[test]
+fn reference_line_round_trips() {
+    let mut panel = liveplot::panels::ScopePanel::new(0);
+    assert!(!panel.get_data().show_zero_line);
+    panel.set_reference_line(Some(2.5));
+
+    let serde: ScopeStateSerde = panel.get_data().into();
+    let mut restored = ScopeData::default();
+    serde.apply_to(&mut restored);
+    assert!(restored.show_zero_line);
+    assert_eq!(restored.zero_line_y, 2.5);
+
+    panel.set_reference_line(None);
+    assert!(!panel.get_data().show_zero_line);
+}
+
+#[test]
+fn pause_without_snapshot_keeps_live_buffers() {
+    use liveplot::data::data::{LivePlotData, LivePlotRequests};
+    use liveplot::data::traces::TracesCollection;
+
+    let mut scope = ScopeData::default();
+    scope.snapshot_on_pause = false;
+    let mut traces = TracesCollection::default();
+    let mut requests = LivePlotRequests::default();
+    let mut live = LivePlotData {
+        scope_data: vec![&mut scope],
+        traces: &mut traces,
+        pending_requests: &mut requests,
+        event_ctrl: None,
+        settings_changed: false,
+    };
+
+    live.pause(0);
+    assert!(live.are_all_paused());
+    assert!(!live.traces.has_snapshot());
+    assert!(!live.scope_by_id(0).unwrap().shows_snapshot());
+
+    let serde: ScopeStateSerde = (&*live.scope_by_id(0).unwrap()).into();
+    let mut restored = ScopeData::default();
+    serde.apply_to(&mut restored);
+    assert!(!restored.snapshot_on_pause);
+}
+
+#[test]
+fn draw_limit_keeps_pinned_then_most_recent() {
+    use liveplot::data::traces::{TraceRef, TracesCollection};
+    use liveplot::sink::{PlotCommand, PlotPoint};
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut traces = TracesCollection::new(rx);
+    // "a" is oldest, "c" newest; each gets one sample at x = its index.
+    for (id, name) in ["a", "b", "c"].iter().enumerate() {
+        let id = id as u32;
+        let _ = tx.send(PlotCommand::RegisterTrace {
+            id,
+            name: name.to_string(),
+            info: None,
+        });
+        let _ = tx.send(PlotCommand::Point {
+            trace_id: id,
+            point: PlotPoint {
+                x: id as f64,
+                y: 0.0,
+            },
+        });
+    }
+    traces.update();
+
+    let mut scope = ScopeData::default();
+    scope.trace_order = ["a", "b", "c"].map(TraceRef::new).to_vec();
+    assert_eq!(scope.drawn_traces(&traces).1, 0);
+
+    scope.max_drawn_traces = 2;
+    scope.pinned_traces = vec![TraceRef::new("a")];
+    let (drawn, hidden) = scope.drawn_traces(&traces);
+    assert_eq!(drawn, vec![TraceRef::new("a"), TraceRef::new("c")]);
+    assert_eq!(hidden, 1);
+}
+
+#[test]
+fn fit_all_data_covers_full_buffer_and_disables_auto_fit() {
+    use liveplot::data::traces::{TraceRef, TracesCollection};
+
+    let a = TraceRef::new("a");
+    let mut traces = TracesCollection::default();
+    traces.get_trace_or_new(&a).live = [[0.0, 5.0], [1.0, -2.0], [10.0, 3.0]].into();
+
+    let mut scope = ScopeData::default();
+    scope.trace_order = vec![a];
+    scope.x_axis.auto_fit = true;
+    scope.y_axis.auto_fit = true;
+    scope.x_axis.bounds = (9.0, 10.0);
+
+    assert_eq!(scope.data_extent(&traces), Some(((0.0, 10.0), (-2.0, 5.0))));
+    scope.fit_all_data(&traces);
+    assert_eq!(scope.x_axis.bounds, (0.0, 10.0));
+    assert_eq!(scope.y_axis.bounds, (-2.0, 5.0));
+    assert_eq!(scope.time_window, 10.0);
+    assert!(!scope.x_axis.auto_fit && !scope.y_axis.auto_fit);
+}
+
+#[test]
+fn division_readout_uses_major_step_or_nice_fallback() {
+    use liveplot::data::scope::{division_step, AxisSettings};
+
+    assert_eq!(division_step(10.0, Some(2.0)), Some((2.0, 5.0)));
+    // Without a known grid step, a 1-2-5 step for ~10 divisions is chosen.
+    assert_eq!(division_step(0.8, None), Some((0.1, 8.0)));
+    assert_eq!(division_step(0.0, None), None);
+
+    let time = AxisSettings::new_time_axis();
+    assert_eq!(time.format_per_div(0.002), "2 ms/div");
+    let mut value = AxisSettings::default();
+    value.set_unit(Some("V".into()));
+    value.value_decimals = 1;
+    assert_eq!(value.format_per_div(0.5), "0.5 V/div");
+}
+
+#[test]
+fn x_window_smoothing_tracks_and_snaps() {
+    use liveplot::data::scope::smooth_toward;
+
+    // One time constant covers ~63% of the gap.
+    let v = smooth_toward(0.0, 1.0, 0.1, 0.1, 10.0);
+    assert!((v - (1.0 - (-1.0f64).exp())).abs() < 1e-12);
+    // Disabled, backwards or far jumps snap to the target.
+    assert_eq!(smooth_toward(0.0, 1.0, 0.1, 0.0, 10.0), 1.0);
+    assert_eq!(smooth_toward(5.0, 1.0, 0.1, 0.1, 10.0), 1.0);
+    assert_eq!(smooth_toward(0.0, 50.0, 0.1, 0.1, 10.0), 50.0);
+    assert_eq!(ScopeData::default().x_smoothing_secs, 0.0);
+}
+
+#[test]
+fn reference_overlay_joins_autofit_only_when_enabled() {
+    use liveplot::data::scope::ReferenceTrace;
+    use liveplot::data::traces::{TraceRef, TracesCollection};
+
+    let a = TraceRef::new("a");
+    let mut traces = TracesCollection::default();
+    traces.get_trace_or_new(&a).live = [[10.0, 0.0], [11.0, 1.0]].into();
+
+    let mut scope = ScopeData::default();
+    scope.trace_order = vec![a];
+    scope.reference_traces = vec![ReferenceTrace {
+        name: "a".into(),
+        points: vec![[0.0, -5.0], [1.0, 5.0]],
+    }];
+    scope.fit_bounds(&traces, false);
+    assert_eq!(scope.x_axis.bounds, (10.0, 11.0));
+    assert_eq!(scope.y_axis.bounds, (0.0, 1.0));
+
+    scope.x_axis.bounds = (10.0, 11.0);
+    scope.align_reference_to_window();
+    assert_eq!(scope.reference_x_offset, 10.0);
+    scope.reference_in_autofit = true;
+    scope.fit_bounds(&traces, false);
+    assert_eq!(scope.y_axis.bounds, (-5.0, 5.0));
+}
+
+#[test]
+fn x_source_pairs_traces_by_timestamp() {
+    use liveplot::data::scope::{pair_by_time, AxisType};
+    use liveplot::data::traces::{TraceRef, TracesCollection};
+
+    let x = [[0.0, 10.0], [1.0, 11.0], [2.0, 12.0], [3.0, 13.0]];
+    let y = [[1.0, 5.0], [1.5, 9.0], [3.0, 7.0]];
+    assert_eq!(pair_by_time(&x, &y, 1e-9), vec![[11.0, 5.0], [13.0, 7.0]]);
+
+    let mut traces = TracesCollection::default();
+    let (pos, sig) = (TraceRef::new("pos"), TraceRef::new("sig"));
+    traces.get_trace_or_new(&pos).live = [[0.0, 1.0], [1.0, 2.0], [2.0, 4.0]].into();
+    traces.get_trace_or_new(&sig).live = [[0.0, 10.0], [1.0, 20.0], [2.0, 40.0]].into();
+
+    let mut scope = ScopeData::default();
+    scope.trace_order = vec![pos.clone(), sig.clone()];
+    scope.y_axis.auto_fit = true;
+    scope.set_x_source(Some(pos.clone()));
+    assert!(scope.uses_x_source() && scope.x_axis.auto_fit);
+    assert!(matches!(scope.x_axis.axis_type, AxisType::Value(_)));
+    assert_eq!(scope.paired_with_x_source(&pos, &traces), None);
+    assert_eq!(
+        scope.paired_with_x_source(&sig, &traces),
+        Some(vec![[1.0, 10.0], [2.0, 20.0], [4.0, 40.0]])
+    );
+
+    scope.update(&traces);
+    assert_eq!(scope.x_axis.bounds, (1.0, 4.0));
+    assert_eq!(scope.y_axis.bounds, (10.0, 40.0));
+
+    scope.set_x_source(None);
+    assert!(matches!(scope.x_axis.axis_type, AxisType::Time(_)));
+}
+
+#[test]
+fn auto_attach_picks_target_scope_or_falls_back_to_primary() {
+    use liveplot::data::data::{LivePlotData, LivePlotRequests};
+    use liveplot::data::scope::AutoAttach;
+    use liveplot::data::traces::TracesCollection;
+
+    let mut first = ScopeData::default();
+    first.id = 0;
+    let mut second = ScopeData::default();
+    second.id = 7;
+    second.name = "Currents".into();
+    let mut traces = TracesCollection::default();
+    let mut requests = LivePlotRequests::default();
+    let mut live = LivePlotData {
+        scope_data: vec![&mut first, &mut second],
+        traces: &mut traces,
+        pending_requests: &mut requests,
+        event_ctrl: None,
+        settings_changed: false,
+    };
+
+    let id = |live: &mut LivePlotData, t: AutoAttach| live.auto_attach_scope_mut(&t).map(|s| s.id);
+    assert_eq!(id(&mut live, AutoAttach::Primary), Some(0));
+    assert_eq!(id(&mut live, AutoAttach::ScopeId(7)), Some(7));
+    assert_eq!(
+        id(&mut live, AutoAttach::ScopeName("Currents".into())),
+        Some(7)
+    );
+    assert_eq!(id(&mut live, AutoAttach::ScopeName("gone".into())), Some(0));
+    assert_eq!(id(&mut live, AutoAttach::Disabled), None);
+}
+
+#[test]
+fn cursor_readout_round_trips_and_defaults_off() {
+    let mut data = ScopeData::default();
+    assert!(!data.show_cursor_readout);
+    data.show_cursor_readout = true;
+
+    let serde: ScopeStateSerde = (&data).into();
+    let mut restored = ScopeData::default();
+    serde.apply_to(&mut restored);
+    assert!(restored.show_cursor_readout);
+}
+
+#[test]
+fn scope_point_caps_resolve_to_the_largest_per_trace() {
+    use liveplot::data::scope::trace_point_caps;
+    use liveplot::data::traces::{TraceRef, TracesCollection};
+
+    let (a, b, c) = (
+        TraceRef::from("a"),
+        TraceRef::from("b"),
+        TraceRef::from("c"),
+    );
+    let mut small = ScopeData::default();
+    small.trace_order = vec![a.clone(), b.clone()];
+    let mut plain = ScopeData::default();
+    plain.trace_order = vec![b.clone(), c.clone()];
+
+    // No scope sets a cap: every trace uses the global one.
+    assert!(trace_point_caps([&small, &plain], 1000).is_empty());
+
+    small.max_points = 100;
+    let caps = trace_point_caps([&small, &plain], 1000);
+    assert_eq!(caps.get(&a), Some(&100));
+    // `b` is also in an uncapped scope, which needs the global 1000.
+    assert_eq!(caps.get(&b), None);
+    assert_eq!(caps.get(&c), None);
+
+    small.max_points = 5000;
+    let caps = trace_point_caps([&small, &plain], 1000);
+    assert_eq!(caps.get(&b), Some(&5000));
+
+    let mut traces = TracesCollection::default();
+    traces.max_points = 1000;
+    traces.point_caps = caps;
+    assert_eq!(traces.max_points_for(&a), 5000);
+    assert_eq!(traces.max_points_for(&c), 1000);
+}
+
+#[test]
+fn legend_click_modes_update_hidden_entries() {
+    use liveplot::data::scope::LegendClick;
+    use std::collections::HashSet;
+
+    let (a, b, c) = (egui::Id::new("a"), egui::Id::new("b"), egui::Id::new("c"));
+    let all = [a, b, c];
+    let none = HashSet::new();
+
+    let hidden = LegendClick::ToggleVisible.apply(a, &none, &all);
+    assert_eq!(hidden, HashSet::from([a]));
+    assert!(LegendClick::ToggleVisible
+        .apply(a, &hidden, &all)
+        .is_empty());
+
+    // Solo hides the others; soloing the only visible entry shows all again.
+    let solo = LegendClick::Solo.apply(b, &none, &all);
+    assert_eq!(solo, HashSet::from([a, c]));
+    assert!(LegendClick::Solo.apply(b, &solo, &all).is_empty());
+    // Soloing a hidden entry shows just that one.
+    assert_eq!(
+        LegendClick::Solo.apply(a, &solo, &all),
+        HashSet::from([b, c])
+    );
+
+    assert_eq!(LegendClick::Nothing.apply(a, &solo, &all), solo);
+
+    let mut scope = ScopeData::default();
+    assert_eq!(scope.legend_click, LegendClick::ToggleVisible);
+    scope.legend_click = LegendClick::Solo;
+    let serde = ScopeStateSerde::from(&scope);
+    let mut restored = ScopeData::default();
+    serde.apply_to(&mut restored);
+    assert_eq!(restored.legend_click, LegendClick::Solo);
+}
+
+#[test]
+fn y_margin_round_trips_and_defaults_for_old_files() {
+    use liveplot::data::scope::DEFAULT_Y_MARGIN;
+
+    let mut data = ScopeData::default();
+    assert_eq!(data.y_margin, DEFAULT_Y_MARGIN);
+    data.y_margin = 0.2;
+
+    let serde: ScopeStateSerde = (&data).into();
+    let mut json = serde_json::to_value(&serde).unwrap();
+    let mut restored = ScopeData::default();
+    serde.apply_to(&mut restored);
+    assert_eq!(restored.y_margin, 0.2);
+
+    json.as_object_mut().unwrap().remove("y_margin");
+    let old: ScopeStateSerde = serde_json::from_value(json).unwrap();
+    assert_eq!(old.y_margin, DEFAULT_Y_MARGIN);
+}
+
+#[test]
+fn idle_traces_with_auto_hide_are_not_drawn() {
+    use liveplot::data::traces::{ActivityRule, TraceRef, TracesCollection};
+
+    let (flat, busy, plain) = (
+        TraceRef::new("flat"),
+        TraceRef::new("busy"),
+        TraceRef::new("plain"),
+    );
+    let rule = ActivityRule {
+        window_secs: 2.0,
+        min_peak_to_peak: 0.5,
+    };
+    let mut traces = TracesCollection::default();
+    for name in [&flat, &busy, &plain] {
+        traces.get_trace_or_new(name).live = [[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]].into();
+    }
+    traces.get_trace_or_new(&flat).auto_hide = Some(rule);
+    let tr = traces.get_trace_or_new(&busy);
+    tr.auto_hide = Some(rule);
+    // Only the step inside the trailing window counts.
+    tr.live = [[0.0, 9.0], [5.0, 0.0], [6.0, 1.0], [7.0, 0.0]].into();
+
+    let mut scope = ScopeData::default();
+    scope.trace_order = vec![flat.clone(), busy.clone(), plain.clone()];
+    assert_eq!(
+        scope.drawn_traces(&traces),
+        (vec![busy.clone(), plain.clone()], 0)
+    );
+    assert!(traces.get_trace(&flat).unwrap().look.visible);
+
+    traces.get_trace_or_new(&busy).live = [[0.0, 9.0], [5.0, 0.0], [7.0, 0.2]].into();
+    assert_eq!(scope.drawn_traces(&traces).0, vec![plain]);
+}
+
+#[test]
+fn initial_view_applies_until_data_arrives() {
+    use liveplot::data::scope::InitialXRange;
+    use liveplot::data::traces::{TraceRef, TracesCollection};
+
+    let a = TraceRef::new("a");
+    let mut traces = TracesCollection::default();
+    traces.get_trace_or_new(&a);
+    let mut scope = ScopeData::default();
+    scope.trace_order = vec![a.clone()];
+    scope.time_window = 10.0;
+    scope.y_axis.auto_fit = true;
+    scope.initial_x = InitialXRange::WallClock;
+    scope.initial_y = Some((-5.0, 5.0));
+
+    scope.update(&traces);
+    let now = chrono::Utc::now().timestamp_micros() as f64 * 1e-6;
+    let (lo, hi) = scope.x_axis.bounds;
+    assert!((hi - now).abs() < 5.0, "{hi} vs {now}");
+    assert!((hi - lo - 10.0).abs() < 1e-9);
+    assert_eq!(scope.y_axis.bounds, (-5.0, 5.0));
+
+    scope.initial_x = InitialXRange::Fixed { min: 1.0, max: 3.0 };
+    scope.update(&traces);
+    assert_eq!(scope.x_axis.bounds, (1.0, 3.0));
+
+    // Real data takes over both axes.
+    traces.get_trace_or_new(&a).live = [[now, 1.0], [now + 1.0, 2.0]].into();
+    scope.update(&traces);
+    assert_eq!(scope.x_axis.bounds, (now + 1.0 - 10.0, now + 1.0));
+    assert_eq!(scope.y_axis.bounds, (1.0, 2.0));
+
+    let serde: ScopeStateSerde = (&scope).into();
+    let mut restored = ScopeData::default();
+    serde.apply_to(&mut restored);
+    assert_eq!(restored.initial_x, scope.initial_x);
+    assert_eq!(restored.initial_y, Some((-5.0, 5.0)));
+}
+
+#[test]
+fn xy_pairs_nearest_samples_within_tolerance() {
+    use liveplot::data::scope::{pair_by_time, ScopeType};
+    use liveplot::data::trace_look::TraceLook;
+    use liveplot::data::traces::{TraceRef, TracesCollection};
+
+    // X at 1 kHz, Y at 250 Hz with a 0.3 ms skew: each Y sample pairs with
+    // the X sample nearest in time, and no X sample is used twice.
+    let x: Vec<[f64; 2]> = (0..8).map(|i| [i as f64 * 1e-3, i as f64]).collect();
+    let y: Vec<[f64; 2]> = (0..2)
+        .map(|i| [i as f64 * 4e-3 + 0.3e-3, 100.0 + i as f64])
+        .collect();
+    assert!(pair_by_time(&x, &y, 1e-9).is_empty());
+    assert_eq!(pair_by_time(&x, &y, 1e-3), vec![[0.0, 100.0], [4.0, 101.0]]);
+    // An empty series pairs with nothing.
+    assert!(pair_by_time(&x, &[], 1.0).is_empty());
+
+    let mut traces = TracesCollection::default();
+    let (a, b) = (TraceRef::new("a"), TraceRef::new("b"));
+    traces.get_trace_or_new(&a).live = x.into();
+    traces.get_trace_or_new(&b).live = y.into();
+    let mut scope = ScopeData::default();
+    scope.scope_type = ScopeType::XYScope;
+    scope.xy_pairs = vec![(Some(a), Some(b), TraceLook::default())];
+    scope.xy_tolerance = 1e-3;
+    scope.fit_bounds(&traces, false);
+    assert_eq!(scope.x_axis.bounds, (0.0, 4.0));
+}
+
+#[test]
+fn afterglow_fades_and_stays_bounded() {
+    use liveplot::data::scope::{Afterglow, AFTERGLOW_MAX_FRAMES};
+
+    let mut glow = Afterglow::default();
+    // 60 fps for five seconds into a one-second window.
+    for i in 0..300 {
+        let t = i as f64 / 60.0;
+        glow.record(t, &[[t, 0.0]], 1.0);
+        assert!(glow.len() <= AFTERGLOW_MAX_FRAMES);
+    }
+    let now = 299.0 / 60.0;
+    let frames: Vec<(f32, f64)> = glow.frames(now, 1.0).map(|(f, p)| (f, p[0][0])).collect();
+    assert!(!frames.is_empty());
+    // Oldest first, all within the window, fading with age.
+    assert!(frames.iter().all(|&(_, t)| now - t < 1.0));
+    assert!(frames.windows(2).all(|w| w[0].0 < w[1].0));
+    // Frames are spaced by window / cap, so the newest is at most that old.
+    let newest = frames.last().unwrap().0 as f64;
+    assert!(newest >= 1.0 - 1.0 / AFTERGLOW_MAX_FRAMES as f64 - 1e-6);
+
+    // Switching persistence off drops everything.
+    glow.record(now, &[], 0.0);
+    assert!(glow.is_empty());
+}
+
+#[test]
+fn right_axis_traces_fit_and_map_separately() {
+    use liveplot::data::scope::{AxisSettings, YAxisSelect};
+    use liveplot::data::traces::{TraceRef, TracesCollection};
+
+    let (volts, amps) = (TraceRef::new("volts"), TraceRef::new("amps"));
+    let mut traces = TracesCollection::default();
+    traces.get_trace_or_new(&volts).live = [[0.0, 0.0], [1.0, 10.0]].into();
+    let tr = traces.get_trace_or_new(&amps);
+    tr.live = [[0.0, 0.1], [1.0, 0.3]].into();
+    tr.axis = YAxisSelect::Right;
+
+    let mut scope = ScopeData::default();
+    scope.trace_order = vec![volts.clone(), amps.clone()];
+    scope.x_axis.bounds = (0.0, 1.0);
+
+    // Without a right axis every trace shares the left one.
+    scope.fit_y_bounds(&traces, false);
+    assert_eq!(scope.y_axis.bounds, (0.0, 10.0));
+    assert_eq!(scope.y2_to_y1(0.3), 0.3);
+
+    scope.y_axis2 = Some(AxisSettings::default());
+    scope.fit_y_bounds(&traces, false);
+    scope.fit_y2_bounds(&traces, false);
+    assert_eq!(scope.y_axis.bounds, (0.0, 10.0));
+    assert_eq!(scope.y_axis2.as_ref().unwrap().bounds, (0.1, 0.3));
+    assert!((scope.y2_to_y1(0.2) - 5.0).abs() < 1e-9);
+    assert!((scope.y1_to_y2(10.0) - 0.3).abs() < 1e-9);
+
+    // Overlays and readouts of a right-axis trace go through the same mapping.
+    let (v, a) = (
+        traces.get_trace(&volts).unwrap(),
+        traces.get_trace(&amps).unwrap(),
+    );
+    assert!((scope.value_to_plot_y(a, 0.2) - 5.0).abs() < 1e-9);
+    assert!((scope.plot_y_to_value(a, 5.0) - 0.2).abs() < 1e-9);
+    assert_eq!(scope.value_to_plot_y(v, 7.0), 7.0);
+    assert_eq!(scope.value_axis(a).bounds, (0.1, 0.3));
+    assert_eq!(scope.value_axis(v).bounds, (0.0, 10.0));
+
+    // Panning the left axis by half its range moves the right one alongside.
+    let old = scope.y_axis.bounds;
+    scope.y_axis.bounds = (5.0, 15.0);
+    scope.follow_y_bounds(old);
+    let (lo, hi) = scope.y_axis2.as_ref().unwrap().bounds;
+    assert!((lo - 0.2).abs() < 1e-9 && (hi - 0.4).abs() < 1e-9);
+
+    let serde: ScopeStateSerde = (&scope).into();
+    let mut restored = ScopeData::default();
+    serde.apply_to(&mut restored);
+    assert_eq!(restored.y_axis2.map(|a| a.bounds), Some((lo, hi)));
+}
+
+#[test]
+fn log_x_ticks_are_numeric_and_bounds_clip_non_positive() {
+    use liveplot::data::scope::{log_bounds, AxisSettings};
+
+    // Time axes format log ticks as plain numbers, not timestamps.
+    let axis = AxisSettings::new_time_axis();
+    assert_eq!(axis.format_log_tick(2.0), "100");
+    assert_eq!(axis.format_log_tick(2f64.log10() + 3.0), "2000");
+    assert_eq!(axis.format_log_tick(-0.3), "0.5");
+    // 10^0.1 is not a 1-2-5 value, so that grid line stays unlabelled.
+    assert_eq!(axis.format_log_tick(0.1), "");
+    assert_eq!(axis.format_log_tick(f64::NAN), "");
+
+    assert_eq!(log_bounds((10.0, 1000.0)), (1.0, 3.0));
+    assert_eq!(log_bounds((-5.0, 1000.0)), (0.0, 3.0));
+    assert_eq!(log_bounds((-5.0, 0.0)), (0.0, 1.0));
+}
+
+#[test]
+fn visible_series_skips_hidden_traces_and_applies_sign() {
+    use liveplot::data::traces::{TraceRef, TracesCollection};
+    use liveplot::sink::{PlotCommand, PlotPoint};
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut traces = TracesCollection::new(rx);
+    for (id, name) in ["a", "b", "c"].iter().enumerate() {
+        let id = id as u32;
+        let _ = tx.send(PlotCommand::RegisterTrace {
+            id,
+            name: name.to_string(),
+            info: None,
+        });
+        for i in 0..3 {
+            let _ = tx.send(PlotCommand::Point {
+                trace_id: id,
+                point: PlotPoint {
+                    x: i as f64,
+                    y: 1.0,
+                },
+            });
+        }
+    }
+    traces.update();
+    traces
+        .get_trace_mut(&TraceRef::new("b"))
+        .unwrap()
+        .look
+        .visible = false;
+    traces.get_trace_mut(&TraceRef::new("c")).unwrap().invert = true;
+
+    let mut scope = ScopeData::default();
+    scope.trace_order = ["a", "b", "c"].map(TraceRef::new).to_vec();
+    scope.x_axis.bounds = (0.0, 2.0);
+    let (order, series) = scope.visible_series(&traces);
+    assert_eq!(order, vec![TraceRef::new("a"), TraceRef::new("c")]);
+    assert_eq!(series[&TraceRef::new("a")].len(), 3);
+    assert!(series[&TraceRef::new("c")].iter().all(|p| p[1] == -1.0));
+}
@@ -0,0 +1,306 @@
+use liveplot::data::thresholds::{ThresholdDef, ThresholdKind};
+use liveplot::data::traces::TraceRef;
+use std::collections::HashMap;
+
+#[test]
+fn runtime_state_reports_active_condition() {
+    let target = TraceRef::from("sig");
+    let mut def = ThresholdDef {
+        name: "high".into(),
+        target: target.clone(),
+        kind: ThresholdKind::GreaterThan { value: 1.0 },
+        ..Default::default()
+    };
+    assert!(!def.get_runtime_state().is_active());
+
+    let mut sources = HashMap::new();
+    sources.insert(target.clone(), vec![[0.0, 0.0], [1.0, 2.0]]);
+    def.process_threshold(sources.clone());
+    assert!(def.get_runtime_state().is_active());
+
+    sources.insert(target, vec![[0.0, 0.0], [1.0, 2.0], [2.0, 0.5]]);
+    def.process_threshold(sources);
+    assert!(!def.get_runtime_state().is_active());
+    assert_eq!(def.count_threshold_events(), 1);
+}
+
+#[test]
+fn debounce_collapses_a_burst_into_one_event() {
+    let target = TraceRef::from("sig");
+    // A burst of three short pulses, then one more well after it.
+    let mut pts = Vec::new();
+    for start in [0.0, 0.3, 0.6, 3.0] {
+        pts.extend([[start, 2.0], [start + 0.1, 2.0], [start + 0.15, 0.0]]);
+    }
+    let mut sources = HashMap::new();
+    sources.insert(target.clone(), pts);
+
+    let run = |debounce_secs: f64| {
+        let mut def = ThresholdDef {
+            name: "burst".into(),
+            target: target.clone(),
+            kind: ThresholdKind::GreaterThan { value: 1.0 },
+            debounce_secs,
+            ..Default::default()
+        };
+        def.process_threshold(sources.clone());
+        def.get_threshold_events()
+    };
+
+    assert_eq!(run(0.0).len(), 4);
+    let debounced = run(1.0);
+    assert_eq!(debounced.len(), 2);
+    assert_eq!(debounced[0].start_t, 0.0);
+    assert_eq!(debounced[1].start_t, 3.0);
+}
+
+#[test]
+fn notify_flag_round_trips_and_defaults_off() {
+    use liveplot::persistence::ThresholdSerde;
+
+    let mut def = ThresholdDef::default();
+    assert!(!def.notify);
+    def.notify = true;
+    let restored = ThresholdSerde::from_threshold(&def).into_threshold();
+    assert!(restored.notify);
+
+    // Older saved thresholds without the field load with notify off.
+    let mut json = serde_json::to_value(ThresholdSerde::from_threshold(&def)).unwrap();
+    json.as_object_mut().unwrap().remove("notify");
+    let old: ThresholdSerde = serde_json::from_value(json).unwrap();
+    assert!(!old.into_threshold().notify);
+}
+
+#[test]
+fn panel_clears_events_for_one_or_all_thresholds() {
+    use liveplot::ThresholdsPanel;
+
+    let target = TraceRef::from("sig");
+    let mut sources = HashMap::new();
+    sources.insert(target.clone(), vec![[0.0, 0.0], [1.0, 2.0], [2.0, 0.5]]);
+    let mut panel = ThresholdsPanel::default();
+    for name in ["a", "b"] {
+        let mut def = ThresholdDef {
+            name: name.into(),
+            target: target.clone(),
+            kind: ThresholdKind::GreaterThan { value: 1.0 },
+            ..Default::default()
+        };
+        def.process_threshold(sources.clone());
+        assert_eq!(def.count_threshold_events(), 1);
+        panel.thresholds.insert(name.into(), def);
+    }
+
+    assert_eq!(panel.clear_events(Some("a")), vec!["a".to_string()]);
+    assert_eq!(panel.thresholds["a"].count_threshold_events(), 0);
+    assert_eq!(panel.thresholds["b"].count_threshold_events(), 1);
+    assert!(panel.clear_events(Some("missing")).is_empty());
+
+    let mut cleared = panel.clear_events(None);
+    cleared.sort();
+    assert_eq!(cleared, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(panel.thresholds["b"].count_threshold_events(), 0);
+}
+
+#[test]
+fn flag_trace_marks_samples_while_exceeded() {
+    let target = TraceRef::from("sig");
+    let mut def = ThresholdDef {
+        name: "high".into(),
+        target: target.clone(),
+        kind: ThresholdKind::GreaterThan { value: 1.0 },
+        emit_flag_trace: true,
+        ..Default::default()
+    };
+    assert_eq!(def.flag_trace_name(), TraceRef::from("high exceeded"));
+
+    let mut sources = HashMap::new();
+    sources.insert(target.clone(), vec![[0.0, 0.0], [1.0, 2.0], [2.0, 3.0]]);
+    def.process_threshold(sources.clone());
+    assert_eq!(
+        def.get_runtime_state().flag_points,
+        vec![[0.0, 0.0], [1.0, 1.0], [2.0, 1.0]]
+    );
+
+    // Only samples new since the last call are flagged.
+    sources.insert(target, vec![[0.0, 0.0], [1.0, 2.0], [2.0, 3.0], [3.0, 0.0]]);
+    def.process_threshold(sources);
+    assert_eq!(def.get_runtime_state().flag_points, vec![[3.0, 0.0]]);
+
+    def.emit_flag_trace = false;
+    def.process_threshold(HashMap::new());
+    assert!(def.get_runtime_state().flag_points.is_empty());
+}
+
+#[test]
+fn hysteresis_keeps_event_open_until_release_level() {
+    let target = TraceRef::from("sig");
+    // Noisy signal dithering around 1.0 before finally dropping to 0.
+    let ys = [0.0, 1.1, 0.95, 1.05, 0.9, 1.2, 0.98, 0.2, 0.0];
+    let pts: Vec<[f64; 2]> = ys
+        .iter()
+        .enumerate()
+        .map(|(i, &y)| [i as f64 * 0.1, y])
+        .collect();
+    let mut sources = HashMap::new();
+    sources.insert(target.clone(), pts);
+
+    let run = |kind: ThresholdKind| {
+        let mut def = ThresholdDef {
+            name: "level".into(),
+            target: target.clone(),
+            kind,
+            min_duration_s: 0.0,
+            ..Default::default()
+        };
+        def.process_threshold(sources.clone());
+        def.get_threshold_events()
+    };
+
+    // Without a release level every dip below 1.0 closes the event.
+    assert_eq!(run(ThresholdKind::GreaterThan { value: 1.0 }).len(), 3);
+    let events = run(ThresholdKind::Hysteresis {
+        high: 1.0,
+        low: 0.5,
+    });
+    assert_eq!(events.len(), 1);
+    assert!((events[0].start_t - 0.1).abs() < 1e-12);
+    assert!((events[0].end_t - 0.7).abs() < 1e-12);
+}
+
+#[test]
+fn rate_threshold_fires_on_fast_changes_only() {
+    let target = TraceRef::from("sig");
+    // Slow ramp (1 unit/s), a fast step (20 units/s), then the slow ramp again.
+    let pts = vec![
+        [0.0, 0.0],
+        [0.1, 0.1],
+        [0.2, 0.2],
+        [0.3, 2.2],
+        [0.4, 2.3],
+        [0.5, 2.4],
+    ];
+    let mut sources = HashMap::new();
+    sources.insert(target.clone(), pts);
+    let mut def = ThresholdDef {
+        name: "spike".into(),
+        target: target.clone(),
+        kind: ThresholdKind::RateExceeds { units_per_sec: 5.0 },
+        min_duration_s: 0.0,
+        ..Default::default()
+    };
+    def.process_threshold(sources);
+
+    let events = def.get_threshold_events();
+    assert_eq!(events.len(), 1);
+    assert!((events[0].start_t - 0.3).abs() < 1e-12);
+    assert!((events[0].end_t - 0.4).abs() < 1e-12);
+    // Over-rate of 15 units/s at t=0.3, ramping in from and back out to 0 on
+    // the neighbouring intervals (trapezoids, as for level thresholds).
+    assert!(
+        (events[0].area - 1.5).abs() < 1e-9,
+        "area {}",
+        events[0].area
+    );
+}
+
+#[test]
+fn threshold_on_math_trace_fires_and_goes_with_it() {
+    use liveplot::data::data::{LivePlotData, LivePlotRequests};
+    use liveplot::data::math::{MathKind, MathTrace};
+    use liveplot::data::scope::ScopeData;
+    use liveplot::data::traces::TracesCollection;
+    use liveplot::panels::{MathPanel, Panel};
+    use liveplot::ThresholdsPanel;
+
+    let (x, rect) = (TraceRef::from("x"), TraceRef::from("rect"));
+    let mut math = MathPanel::default();
+    math.set_math_traces(vec![MathTrace::new(
+        rect.clone(),
+        MathKind::Abs { input: x.clone() },
+    )]);
+    let mut thresholds = ThresholdsPanel::default();
+    thresholds.thresholds.insert(
+        "dip".into(),
+        ThresholdDef {
+            name: "dip".into(),
+            target: rect.clone(),
+            kind: ThresholdKind::GreaterThan { value: 1.0 },
+            min_duration_s: 0.0,
+            ..Default::default()
+        },
+    );
+
+    let mut scope = ScopeData::default();
+    let mut traces = TracesCollection::default();
+    let mut requests = LivePlotRequests::default();
+    traces.get_trace_or_new(&x).live = [0.0, -2.0, -2.0, 0.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &y)| [i as f64, y])
+        .collect();
+    let mut data = LivePlotData {
+        scope_data: vec![&mut scope],
+        traces: &mut traces,
+        pending_requests: &mut requests,
+        event_ctrl: None,
+        settings_changed: false,
+    };
+    math.update_data(&mut data);
+    // Take the math trace off the plot; the threshold still sees its output.
+    data.scope_data[0].remove_trace(&rect);
+    thresholds.update_data(&mut data);
+    assert_eq!(thresholds.thresholds["dip"].count_threshold_events(), 1);
+
+    data.remove_trace(&rect);
+    thresholds.update_data(&mut data);
+    assert!(thresholds.thresholds.is_empty());
+}
+
+#[test]
+fn update_data_publishes_threshold_active_per_scope() {
+    use liveplot::data::data::{LivePlotData, LivePlotRequests};
+    use liveplot::data::scope::ScopeData;
+    use liveplot::data::traces::TracesCollection;
+    use liveplot::panels::Panel;
+    use liveplot::ThresholdsPanel;
+
+    let (sig, other) = (TraceRef::from("sig"), TraceRef::from("other"));
+    let mut panel = ThresholdsPanel::default();
+    panel.thresholds.insert(
+        "high".into(),
+        ThresholdDef {
+            name: "high".into(),
+            target: sig.clone(),
+            kind: ThresholdKind::GreaterThan { value: 1.0 },
+            ..Default::default()
+        },
+    );
+
+    let (mut watched, mut unrelated) = (ScopeData::default(), ScopeData::default());
+    watched.trace_order = vec![sig.clone()];
+    watched.x_axis.bounds = (0.0, 10.0);
+    unrelated.trace_order = vec![other.clone()];
+    let mut traces = TracesCollection::default();
+    let mut requests = LivePlotRequests::default();
+    traces.get_trace_or_new(&sig).live = [[0.0, 0.0], [1.0, 2.0]].into_iter().collect();
+    traces.get_trace_or_new(&other).live = [[0.0, 5.0], [1.0, 5.0]].into_iter().collect();
+    let mut data = LivePlotData {
+        scope_data: vec![&mut watched, &mut unrelated],
+        traces: &mut traces,
+        pending_requests: &mut requests,
+        event_ctrl: None,
+        settings_changed: false,
+    };
+
+    panel.update_data(&mut data);
+    assert!(data.scope_data[0].threshold_active);
+    assert!(!data.scope_data[1].threshold_active);
+
+    data.traces
+        .get_trace_or_new(&sig)
+        .live
+        .push_back([2.0, 0.5]);
+    panel.update_data(&mut data);
+    assert!(!data.scope_data[0].threshold_active);
+}
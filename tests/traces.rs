@@ -230,3 +230,631 @@ fn alloc_color_uses_global_palette() {
     assert_eq!(TraceLook::alloc_color(1), Color32::from_rgb(4, 5, 6));
     assert_eq!(TraceLook::alloc_color(2), Color32::from_rgb(1, 2, 3));
 }
+
+#[test]
+fn set_data_replaces_whole_buffer() {
+    use liveplot::sink::PlotPoint;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    let _ = tx.send(PlotCommand::RegisterTrace {
+        id: 7,
+        name: "sweep".to_string(),
+        info: None,
+    });
+    let _ = tx.send(PlotCommand::Points {
+        trace_id: 7,
+        points: (0..5)
+            .map(|i| PlotPoint {
+                x: i as f64,
+                y: 0.0,
+            })
+            .collect(),
+    });
+    col.update();
+    assert_eq!(
+        col.get_trace(&TraceRef::new("sweep")).unwrap().live.len(),
+        5
+    );
+
+    let _ = tx.send(PlotCommand::SetData {
+        trace_id: 7,
+        points: vec![PlotPoint { x: 10.0, y: 1.0 }, PlotPoint { x: 11.0, y: 2.0 }],
+    });
+    col.update();
+    let live = &col.get_trace(&TraceRef::new("sweep")).unwrap().live;
+    assert_eq!(
+        live.iter().copied().collect::<Vec<_>>(),
+        vec![[10.0, 1.0], [11.0, 2.0]]
+    );
+}
+
+#[test]
+fn moving_average_is_trailing_and_keeps_x() {
+    let pts = vec![[0.0, 1.0], [1.0, 3.0], [2.0, 5.0], [3.0, 7.0]];
+    let smoothed = TraceData::moving_average(&pts, 2);
+    assert_eq!(
+        smoothed,
+        vec![[0.0, 1.0], [1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]
+    );
+    assert_eq!(TraceData::moving_average(&pts, 1), pts);
+}
+
+#[test]
+fn recent_mean_std_uses_trailing_window() {
+    let pts: std::collections::VecDeque<[f64; 2]> =
+        vec![[0.0, 100.0], [1.0, 1.0], [2.0, 3.0], [3.0, f64::NAN]].into();
+    let (mean, sd) = TraceData::recent_mean_std(&pts, 2.0).unwrap();
+    assert_eq!(mean, 2.0);
+    assert_eq!(sd, 1.0);
+    assert!(TraceData::recent_mean_std(&Default::default(), 5.0).is_none());
+}
+
+#[test]
+fn invert_negates_before_offset() {
+    let tr = TraceData {
+        invert: true,
+        offset: 1.0,
+        ..Default::default()
+    };
+    assert_eq!(tr.signed(2.0), -2.0);
+    assert_eq!(tr.display_y(2.0), -1.0);
+}
+
+#[test]
+fn reserve_points_preallocates_live_buffers() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    col.max_points = 5000;
+    col.reserve_points = true;
+    let _ = tx.send(PlotCommand::RegisterTrace {
+        id: 1,
+        name: "a".to_string(),
+        info: None,
+    });
+    col.update();
+    let cap = col
+        .get_trace(&TraceRef("a".into()))
+        .unwrap()
+        .live
+        .capacity();
+    assert!(cap > 5000, "capacity {cap} should cover max_points + 1");
+
+    // Growing the limit extends the reservation on the next update.
+    col.max_points = 8000;
+    col.update();
+    let cap = col
+        .get_trace(&TraceRef("a".into()))
+        .unwrap()
+        .live
+        .capacity();
+    assert!(cap > 8000, "capacity {cap} should follow max_points");
+}
+
+#[test]
+fn points_with_error_store_uncertainty_by_x() {
+    use liveplot::sink::PlotPoint;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    col.max_points = 3;
+    let _ = tx.send(PlotCommand::RegisterTrace {
+        id: 1,
+        name: "meas".to_string(),
+        info: None,
+    });
+    let _ = tx.send(PlotCommand::PointsWithError {
+        trace_id: 1,
+        points: (0..4)
+            .map(|i| PlotPoint {
+                x: i as f64,
+                y: 1.0,
+            })
+            .collect(),
+        // Sample 2 has no uncertainty; the negative one is stored as magnitude.
+        y_err: vec![0.1, -0.2, f64::NAN, 0.4],
+    });
+    col.update();
+
+    let tr = col.get_trace(&TraceRef::new("meas")).unwrap();
+    assert_eq!(tr.live.len(), 3);
+    // Sample 0 was trimmed by max_points, so its uncertainty is pruned too.
+    assert_eq!(tr.y_err_at(0.0), None);
+    assert_eq!(tr.y_err_at(1.0), Some(0.2));
+    assert_eq!(tr.y_err_at(2.0), None);
+    assert_eq!(tr.y_err_at(3.0), Some(0.4));
+
+    let _ = tx.send(PlotCommand::ClearData { trace_id: 1 });
+    col.update();
+    assert!(col
+        .get_trace(&TraceRef::new("meas"))
+        .unwrap()
+        .y_err
+        .is_empty());
+}
+
+#[test]
+fn remove_trace_command_deletes_trace_and_ignores_later_data() {
+    use liveplot::sink::PlotPoint;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    let name = TraceRef::new("gone");
+    let _ = tx.send(PlotCommand::RegisterTrace {
+        id: 7,
+        name: "gone".to_string(),
+        info: None,
+    });
+    col.update();
+    let _ = tx.send(PlotCommand::RemoveTrace { trace_id: 7 });
+    let _ = tx.send(PlotCommand::Point {
+        trace_id: 7,
+        point: PlotPoint { x: 1.0, y: 1.0 },
+    });
+    col.update();
+
+    assert!(col.get_trace(&name).is_none());
+    assert!(col.get_trace(&TraceRef::new("trace-7")).is_none());
+    assert!(col.was_removed(&name));
+    assert_eq!(col.take_removed(), vec![name.clone()]);
+    assert!(col.take_removed().is_empty());
+
+    // Data arriving for the removed id in a later frame must not bring
+    // back an auto-registered trace.
+    let _ = tx.send(PlotCommand::Point {
+        trace_id: 7,
+        point: PlotPoint { x: 2.0, y: 2.0 },
+    });
+    col.update();
+    assert!(col.get_trace(&TraceRef::new("trace-7")).is_none());
+    assert!(col.is_empty());
+
+    // Recreating the trace clears the removed mark.
+    col.get_trace_or_new(&name);
+    assert!(!col.was_removed(&name));
+}
+
+#[test]
+fn ingest_stats_count_received_dropped_and_evicted() {
+    use liveplot::sink::PlotPoint;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    col.max_points = 3;
+    let pts = |n: usize| -> Vec<PlotPoint> {
+        (0..n)
+            .map(|i| PlotPoint {
+                x: i as f64,
+                y: 0.0,
+            })
+            .collect()
+    };
+    let _ = tx.send(PlotCommand::RegisterTrace {
+        id: 1,
+        name: "a".to_string(),
+        info: None,
+    });
+    let _ = tx.send(PlotCommand::Points {
+        trace_id: 1,
+        points: pts(5),
+    });
+    // Never registered: ignored.
+    let _ = tx.send(PlotCommand::Points {
+        trace_id: 9,
+        points: pts(4),
+    });
+    col.update();
+
+    let stats = col.ingest_stats();
+    assert_eq!(stats.received, 9);
+    assert_eq!(stats.dropped, 4);
+    assert_eq!(stats.evicted, 2);
+
+    col.reset_ingest_stats();
+    assert_eq!(col.ingest_stats(), Default::default());
+}
+
+#[test]
+fn min_max_and_lttb_decimation_keep_spike_and_endpoints() {
+    use liveplot::data::traces::DecimationMethod;
+
+    let mut pts: Vec<[f64; 2]> = (0..1000).map(|i| [i as f64, 0.0]).collect();
+    pts[437][1] = 10.0;
+    let bounds = (0.0, 999.0);
+
+    let mm = TraceData::decimate(&pts, bounds, 100, DecimationMethod::MinMax);
+    assert!(mm.len() <= 100);
+    assert!(mm.contains(&[437.0, 10.0]));
+    assert!(mm.windows(2).all(|w| w[0][0] < w[1][0]));
+
+    let lttb = TraceData::decimate(&pts, bounds, 100, DecimationMethod::Lttb);
+    assert_eq!(lttb.len(), 100);
+    assert_eq!(lttb.first(), Some(&[0.0, 0.0]));
+    assert_eq!(lttb.last(), Some(&[999.0, 0.0]));
+    assert!(lttb.contains(&[437.0, 10.0]));
+
+    // Small inputs pass through untouched, filtered to the bounds.
+    let few = TraceData::decimate(&pts[..10], (2.0, 5.0), 100, DecimationMethod::Lttb);
+    assert_eq!(few.len(), 4);
+}
+
+#[test]
+fn out_of_order_samples_follow_the_policy() {
+    use liveplot::data::traces::OutOfOrderPolicy;
+    use liveplot::sink::PlotPoint;
+
+    let xs = [0.0, 1.0, 3.0, 2.0, 4.0, 0.5];
+    let run = |policy: OutOfOrderPolicy| {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut col = TracesCollection::new(rx);
+        col.out_of_order = policy;
+        let _ = tx.send(PlotCommand::RegisterTrace {
+            id: 1,
+            name: "a".to_string(),
+            info: None,
+        });
+        let _ = tx.send(PlotCommand::Points {
+            trace_id: 1,
+            points: xs.iter().map(|&x| PlotPoint { x, y: x }).collect(),
+        });
+        col.update();
+        let live: Vec<f64> = col
+            .get_points(&TraceRef::new("a"), false)
+            .unwrap()
+            .iter()
+            .map(|p| p[0])
+            .collect();
+        (live, col.ingest_stats())
+    };
+
+    let (live, stats) = run(OutOfOrderPolicy::Reject);
+    assert_eq!(live, [0.0, 1.0, 3.0, 4.0]);
+    assert_eq!((stats.out_of_order, stats.dropped), (2, 2));
+
+    let (live, stats) = run(OutOfOrderPolicy::SortInsert);
+    assert_eq!(live, [0.0, 0.5, 1.0, 2.0, 3.0, 4.0]);
+    assert_eq!((stats.out_of_order, stats.dropped), (2, 0));
+
+    let (live, stats) = run(OutOfOrderPolicy::Accept);
+    assert_eq!(live, xs);
+    assert_eq!((stats.out_of_order, stats.dropped), (2, 0));
+}
+
+#[test]
+fn recolor_all_follows_scope_order_and_keeps_custom_colors() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    for (id, name) in [(1, "a"), (2, "b"), (3, "c"), (4, "z")] {
+        let _ = tx.send(PlotCommand::RegisterTrace {
+            id,
+            name: name.into(),
+            info: None,
+        });
+    }
+    let _ = col.update();
+    let pinned = Color32::from_rgb(1, 2, 3);
+    col.get_trace_mut(&TraceRef::new("b"))
+        .unwrap()
+        .look
+        .set_custom_color(pinned);
+
+    let scope_order = [TraceRef::new("c"), TraceRef::new("b"), TraceRef::new("a")];
+    col.recolor_all(&[&scope_order]);
+
+    let idx = |n: &str| col.get_trace(&TraceRef::new(n)).unwrap().creation_index;
+    assert_eq!((idx("c"), idx("b"), idx("a"), idx("z")), (0, 1, 2, 3));
+    let b = &col.get_trace(&TraceRef::new("b")).unwrap().look;
+    assert!(b.color_is_custom);
+    assert_eq!(b.color, pinned);
+
+    // The distinction survives a save/load round trip.
+    let saved = liveplot::persistence::TraceLookSerde::from(b);
+    let json = serde_json::to_string(&saved).unwrap();
+    let loaded: liveplot::persistence::TraceLookSerde = serde_json::from_str(&json).unwrap();
+    assert!(loaded.into_look().color_is_custom);
+}
+
+#[test]
+fn new_traces_use_defaults_unless_a_pending_style_exists() {
+    use egui_plot::LineStyle;
+    use liveplot::data::trace_look::TraceDefaults;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    col.trace_defaults = TraceDefaults {
+        visible: false,
+        width: 3.0,
+        style: LineStyle::dashed_dense(),
+    };
+    let mut saved = TraceLook::default();
+    saved.width = 0.5;
+    col.set_pending_style("saved", saved, 0.0);
+    for (id, name) in [(1, "fresh"), (2, "saved")] {
+        let _ = tx.send(PlotCommand::RegisterTrace {
+            id,
+            name: name.to_string(),
+            info: None,
+        });
+    }
+    col.update();
+
+    let fresh = &col.get_trace(&TraceRef::new("fresh")).unwrap().look;
+    assert!(!fresh.visible);
+    assert_eq!(fresh.width, 3.0);
+    assert_eq!(fresh.style, LineStyle::dashed_dense());
+    let saved = &col.get_trace(&TraceRef::new("saved")).unwrap().look;
+    assert!(saved.visible);
+    assert_eq!(saved.width, 0.5);
+
+    let direct = &col.get_trace_or_new(&TraceRef::new("direct")).look;
+    assert!(!direct.visible);
+}
+
+#[test]
+fn requested_clear_is_applied_before_new_samples() {
+    use liveplot::PlotPoint;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    let _ = tx.send(PlotCommand::RegisterTrace {
+        id: 1,
+        name: "a".to_string(),
+        info: None,
+    });
+    let _ = tx.send(PlotCommand::Point {
+        trace_id: 1,
+        point: PlotPoint { x: 0.0, y: 1.0 },
+    });
+    col.update();
+    let name = TraceRef::new("a");
+    assert_eq!(col.get_trace(&name).unwrap().live.len(), 1);
+
+    // Nothing changes until the next update.
+    col.request_clear();
+    assert!(col.clear_pending());
+    assert_eq!(col.get_trace(&name).unwrap().live.len(), 1);
+
+    let _ = tx.send(PlotCommand::Point {
+        trace_id: 1,
+        point: PlotPoint { x: 1.0, y: 2.0 },
+    });
+    col.update();
+    assert!(!col.clear_pending());
+    let live: Vec<_> = col.get_trace(&name).unwrap().live.iter().copied().collect();
+    assert_eq!(live, vec![[1.0, 2.0]]);
+}
+
+#[test]
+fn static_duplicate_keeps_its_data_while_the_original_moves_on() {
+    use liveplot::PlotPoint;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    col.max_points = 3;
+    let _ = tx.send(PlotCommand::RegisterTrace {
+        id: 1,
+        name: "orig".to_string(),
+        info: None,
+    });
+    let send = |x: f64| {
+        let _ = tx.send(PlotCommand::Point {
+            trace_id: 1,
+            point: PlotPoint { x, y: x },
+        });
+    };
+    send(0.0);
+    send(1.0);
+    col.update();
+
+    let orig = TraceRef::new("orig");
+    let copy = col.duplicate_as_static(&orig).unwrap();
+    assert!(copy.as_str().starts_with("orig (frozen "));
+    assert!(col.is_frozen(&copy));
+    assert!(!col.is_frozen(&orig));
+    assert_ne!(
+        col.get_trace(&copy).unwrap().look.color,
+        col.get_trace(&orig).unwrap().look.color
+    );
+
+    for x in 2..6 {
+        send(x as f64);
+    }
+    col.update();
+    col.clear_all();
+    let frozen: Vec<_> = col.get_trace(&copy).unwrap().live.iter().copied().collect();
+    assert_eq!(frozen, vec![[0.0, 0.0], [1.0, 1.0]]);
+    assert!(col.get_trace(&orig).unwrap().live.is_empty());
+
+    // A second copy in the same second gets a distinct id.
+    let again = col.duplicate_as_static(&orig).unwrap();
+    assert_ne!(again, copy);
+    col.remove_trace(&copy);
+    assert!(!col.is_frozen(&copy));
+}
+
+#[test]
+fn fill_baseline_survives_a_save_load_round_trip() {
+    let mut look = TraceLook::default();
+    assert_eq!(look.fill_baseline, None);
+    look.fill_baseline = Some(-2.5);
+    let saved = liveplot::persistence::TraceLookSerde::from(&look);
+    let json = serde_json::to_string(&saved).unwrap();
+    let loaded: liveplot::persistence::TraceLookSerde = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.into_look().fill_baseline, Some(-2.5));
+
+    // Older files without the field load with the fill off.
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value.as_object_mut().unwrap().remove("fill_baseline");
+    let old: liveplot::persistence::TraceLookSerde = serde_json::from_value(value).unwrap();
+    assert_eq!(old.into_look().fill_baseline, None);
+}
+
+#[test]
+fn batches_append_in_one_go_and_prune_once() {
+    use liveplot::channel_plot;
+    use liveplot::sink::PlotPoint;
+
+    let (sink, rx) = channel_plot();
+    let mut col = TracesCollection::new(rx);
+    col.max_points = 4;
+    let trace = sink.create_trace("daq", None);
+    let batch: Vec<PlotPoint> = (0..6)
+        .map(|i| PlotPoint {
+            x: i as f64,
+            y: i as f64 * 10.0,
+        })
+        .collect();
+    sink.send_batch(trace.id, &batch[..2]).unwrap();
+    sink.send_batch_named(&trace, &batch[2..]).unwrap();
+    col.update();
+
+    let name = TraceRef::new("daq");
+    let live: Vec<_> = col.get_trace(&name).unwrap().live.iter().copied().collect();
+    assert_eq!(
+        live,
+        vec![[2.0, 20.0], [3.0, 30.0], [4.0, 40.0], [5.0, 50.0]]
+    );
+    let stats = col.ingest_stats();
+    assert_eq!(stats.received, 6);
+    assert_eq!(stats.evicted, 2);
+
+    // A batch reaching back in time goes through the out-of-order policy.
+    let late = [PlotPoint { x: 1.0, y: 0.0 }, PlotPoint { x: 6.0, y: 60.0 }];
+    sink.send_batch(trace.id, &late).unwrap();
+    col.update();
+    let stats = col.ingest_stats();
+    assert_eq!(stats.out_of_order, 1);
+    assert_eq!(
+        col.get_trace(&name).unwrap().live.back().copied(),
+        Some([6.0, 60.0])
+    );
+}
+
+#[test]
+fn bounded_sink_reports_a_full_queue_without_blocking() {
+    use liveplot::channel_plot_bounded;
+    use liveplot::sink::PlotPoint;
+    use std::sync::mpsc::TrySendError;
+
+    let (sink, rx) = channel_plot_bounded(2);
+    assert!(sink.is_bounded());
+    let mut col = TracesCollection::new(rx);
+    col.count_rejected_sends(&sink);
+    let trace = sink.create_trace("logger", None);
+    let p = [PlotPoint { x: 0.0, y: 1.0 }];
+    sink.send_batch(trace.id, &p).unwrap();
+    match sink.send_batch(trace.id, &p) {
+        Err(TrySendError::Full(PlotCommand::AppendBatch { points, .. })) => {
+            assert_eq!(points.len(), 1)
+        }
+        _ => panic!("expected a full queue"),
+    }
+    assert_eq!(sink.rejected_samples(), 1);
+
+    col.update();
+    let name = TraceRef::new("logger");
+    assert_eq!(col.get_trace(&name).unwrap().live.len(), 1);
+    assert_eq!(col.ingest_stats().dropped, 1);
+    sink.send(PlotCommand::Point {
+        trace_id: trace.id,
+        point: PlotPoint { x: 1.0, y: 2.0 },
+    })
+    .unwrap();
+    col.update();
+    assert_eq!(col.get_trace(&name).unwrap().live.len(), 2);
+
+    drop(col);
+    assert!(matches!(
+        sink.send_batch(trace.id, &p),
+        Err(TrySendError::Disconnected(_))
+    ));
+}
+
+#[test]
+fn merged_sinks_keep_sources_apart() {
+    use liveplot::sink::{merge_sinks_prefixed, PlotPoint};
+
+    // Two producers that both use raw id 7 for their own trace.
+    let (tx_a, rx_a) = std::sync::mpsc::channel();
+    let (tx_b, rx_b) = std::sync::mpsc::channel();
+    for (tx, name, y) in [(&tx_a, "current", 1.0), (&tx_b, "current", 2.0)] {
+        tx.send(PlotCommand::RegisterTrace {
+            id: 7,
+            name: name.into(),
+            info: None,
+        })
+        .unwrap();
+        tx.send(PlotCommand::Points {
+            trace_id: 7,
+            points: vec![PlotPoint { x: 0.0, y }, PlotPoint { x: 1.0, y }],
+        })
+        .unwrap();
+    }
+    drop((tx_a, tx_b));
+    let merged = merge_sinks_prefixed(vec![("a/".into(), rx_a), ("b/".into(), rx_b)]);
+
+    // Wait for both forwarding threads to finish, then replay into one plot.
+    let cmds: Vec<PlotCommand> = merged.iter().collect();
+    assert_eq!(cmds.len(), 4);
+    let (tx, rx) = std::sync::mpsc::channel();
+    for cmd in cmds {
+        tx.send(cmd).unwrap();
+    }
+    let mut col = TracesCollection::new(rx);
+    col.update();
+    let ys = |name: &str| -> Vec<f64> {
+        col.get_trace(&TraceRef(name.into()))
+            .unwrap()
+            .live
+            .iter()
+            .map(|p| p[1])
+            .collect()
+    };
+    assert_eq!(ys("a/current"), vec![1.0, 1.0]);
+    assert_eq!(ys("b/current"), vec![2.0, 2.0]);
+}
+
+#[test]
+fn trace_styles_round_trip_display_options() {
+    use liveplot::data::traces::ActivityRule;
+    use liveplot::persistence::{apply_trace_styles, capture_trace_styles, TraceStyleSerde};
+
+    let (_tx, rx) = std::sync::mpsc::channel();
+    let mut col = TracesCollection::new(rx);
+    let name = TraceRef::new("sig");
+    {
+        let tr = col.get_trace_or_new(&name);
+        tr.offset = 1.5;
+        tr.display_name = Some("Signal".to_string());
+        tr.display_smooth_window = 8;
+        tr.invert = true;
+        tr.auto_hide = Some(ActivityRule {
+            window_secs: 2.0,
+            min_peak_to_peak: 0.1,
+        });
+    }
+
+    let order = [name.clone()];
+    let styles = capture_trace_styles(order.iter(), |n| col.get_trace(n));
+    let json = serde_json::to_string(&styles).unwrap();
+    let loaded: Vec<TraceStyleSerde> = serde_json::from_str(&json).unwrap();
+
+    let (_tx2, rx2) = std::sync::mpsc::channel();
+    let mut restored = TracesCollection::new(rx2);
+    apply_trace_styles(&loaded, |n, look, off, display| {
+        restored.set_pending_style(n, look, off);
+        restored.set_pending_display(n, display);
+    });
+    let tr = restored.get_trace_or_new(&name);
+    assert_eq!(tr.offset, 1.5);
+    assert_eq!(tr.display_name.as_deref(), Some("Signal"));
+    assert_eq!(tr.display_smooth_window, 8);
+    assert!(tr.invert);
+    assert_eq!(
+        tr.auto_hide,
+        Some(ActivityRule {
+            window_secs: 2.0,
+            min_peak_to_peak: 0.1,
+        })
+    );
+}
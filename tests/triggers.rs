@@ -0,0 +1,61 @@
+use liveplot::data::data::{LivePlotData, LivePlotRequests};
+use liveplot::data::scope::ScopeData;
+use liveplot::data::traces::{TraceRef, TracesCollection};
+use liveplot::panels::{Panel, TriggersPanel};
+use liveplot::{Trigger, TriggerCommand};
+
+#[test]
+fn commands_arm_force_and_disarm_named_triggers() {
+    let mut traces = TracesCollection::default();
+    let target = TraceRef::new("sig");
+    for i in 0..10 {
+        traces
+            .get_trace_or_new(&target)
+            .live
+            .push_back([i as f64, 0.0]);
+    }
+    let mut panel = TriggersPanel::default();
+    let mut trig = Trigger::default();
+    trig.name = "T".to_string();
+    trig.target = target.clone();
+    trig.enabled = false;
+    trig.trigger_position = 0.0;
+    panel.triggers.insert(trig.name.clone(), trig);
+
+    let mut scope = ScopeData::default();
+    let mut requests = LivePlotRequests::default();
+    let mut live = LivePlotData {
+        scope_data: vec![&mut scope],
+        traces: &mut traces,
+        pending_requests: &mut requests,
+        event_ctrl: None,
+        settings_changed: false,
+    };
+
+    live.pending_requests
+        .trigger_commands
+        .push(("T".to_string(), TriggerCommand::Arm));
+    panel.update_data(&mut live);
+    assert!(panel.triggers["T"].is_active());
+    // A flat signal never crosses, so nothing is captured.
+    assert!(panel.take_captures().is_empty());
+
+    live.pending_requests
+        .trigger_commands
+        .push(("T".to_string(), TriggerCommand::Disarm));
+    panel.update_data(&mut live);
+    assert!(!panel.triggers["T"].is_active());
+
+    // Forcing fires at the newest sample without a crossing.
+    live.pending_requests
+        .trigger_commands
+        .push(("T".to_string(), TriggerCommand::Force));
+    panel.update_data(&mut live);
+    let captures = panel.take_captures();
+    assert_eq!(captures.len(), 1);
+    assert_eq!(captures[0].trigger, "T");
+    assert_eq!(captures[0].target, target);
+    assert_eq!(captures[0].time, 9.0);
+    assert!(live.are_all_paused());
+    assert!(panel.take_captures().is_empty());
+}